@@ -417,3 +417,86 @@ pub(crate) fn resolve_region(
             )
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect_record_bufs_with_bed_records() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bed as bed;
+        use noodles_core::{
+            ops::{self, Located, ReferenceSequences},
+            Position,
+        };
+        use sam::alignment::record::cigar::{op::Kind, Op};
+
+        use sam::alignment::io::Write;
+
+        use crate::io::Writer;
+
+        let header: sam::Header = "@SQ\tSN:sq0\tLN:34\n@SQ\tSN:sq1\tLN:34\n".parse()?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (reference_sequence_id, alignment_start) in [(0, 1), (0, 13), (1, 5), (1, 21)] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(alignment_start)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(writer.get_ref().get_ref().as_slice());
+        let header = reader.read_header()?;
+
+        let records = reader.record_bufs(&header).map(|result| {
+            let record = result?;
+
+            let (name, _) = record
+                .reference_sequence(&header)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing reference"))??;
+
+            let start = record.alignment_start().unwrap_or(Position::MIN);
+            let end = record.alignment_end().unwrap_or(start);
+
+            Ok(Located::new(Region::new(name, start..=end), record))
+        });
+
+        let targets: Vec<bed::Record<3>> = ["sq0\t10\t20", "sq1\t0\t6"]
+            .into_iter()
+            .map(|s| s.parse())
+            .collect::<Result<_, _>>()?;
+
+        let reference_sequences = ReferenceSequences::from(&header);
+
+        let actual: Vec<_> = ops::intersect(&reference_sequences, records, targets.iter().map(Ok))
+            .map(|result| {
+                result.map(|(located, hits)| {
+                    (
+                        located.value().reference_sequence_id(),
+                        located.value().alignment_start().map(usize::from),
+                        hits.len(),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            actual,
+            [
+                (Some(0), Some(1), 0),
+                (Some(0), Some(13), 1),
+                (Some(1), Some(5), 1),
+                (Some(1), Some(21), 0),
+            ]
+        );
+
+        Ok(())
+    }
+}
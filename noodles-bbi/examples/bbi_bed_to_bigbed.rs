@@ -0,0 +1,66 @@
+//! Converts a BED file to a bigBed file.
+//!
+//! The reference sequences are read from a chromosome sizes file, i.e., a tab-delimited file of
+//! names and lengths. Records in the BED file must be sorted by reference sequence and start
+//! position.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter},
+};
+
+use noodles_bbi::{bigbed, header::ReferenceSequences};
+use noodles_bed as bed;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+
+    let sizes_src = args.next().expect("missing sizes_src");
+    let src = args.next().expect("missing src");
+    let dst = args.next().expect("missing dst");
+
+    let reference_sequences = File::open(sizes_src)
+        .map(BufReader::new)
+        .and_then(read_reference_sequences)?;
+
+    let mut reader = File::open(src)
+        .map(BufReader::new)
+        .map(bed::io::Reader::new)?;
+
+    let mut writer = File::create(dst)
+        .map(BufWriter::new)
+        .map(|inner| bigbed::io::Writer::new(inner, reference_sequences))?;
+
+    for result in reader.records::<3>() {
+        let record = result?;
+        writer.write_record(&record)?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
+
+fn read_reference_sequences<R>(reader: R) -> io::Result<ReferenceSequences>
+where
+    R: BufRead,
+{
+    let mut reference_sequences = ReferenceSequences::new();
+
+    for result in reader.lines() {
+        let line = result?;
+
+        let (name, raw_length) = line
+            .split_once('\t')
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid sizes line"))?;
+
+        let length = raw_length
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        reference_sequences.insert(name.into(), length);
+    }
+
+    Ok(reference_sequences)
+}
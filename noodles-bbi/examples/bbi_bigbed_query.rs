@@ -0,0 +1,25 @@
+//! Queries a bigBed file with a given region.
+//!
+//! The results are printed as BED records.
+
+use std::{env, fs::File};
+
+use noodles_bbi::bigbed;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let region = args.next().expect("missing region").parse()?;
+
+    let mut reader = File::open(src).map(bigbed::io::Reader::new)?;
+    let header = reader.read_header()?;
+
+    for result in reader.query(&header, &region)? {
+        let record = result?;
+        let bed_record: noodles_bed::Record<3> = record.try_into_bed_record(&header)?;
+        println!("{bed_record}");
+    }
+
+    Ok(())
+}
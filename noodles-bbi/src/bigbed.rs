@@ -0,0 +1,10 @@
+//! bigBed format.
+//!
+//! bigBed stores BED features, with any fields beyond the positions kept as text.
+
+pub mod io;
+mod record;
+
+pub use self::record::Record;
+
+pub(crate) const MAGIC_NUMBER: u32 = 0x8789f2eb;
@@ -0,0 +1,247 @@
+use std::io::{self, BufRead, Read, Seek};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use noodles_core::Region;
+
+use crate::{
+    bigbed::{Record, MAGIC_NUMBER},
+    io::{
+        reader::{intersects, read_header, resolve_region, to_positions, zoom_query},
+        Query,
+    },
+    zoom, Header,
+};
+
+/// A bigBed reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R> {
+    /// Creates a bigBed reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigbed;
+    /// let reader = bigbed::io::Reader::new(io::empty());
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigbed;
+    /// let reader = bigbed::io::Reader::new(io::empty());
+    /// let _inner = reader.get_ref();
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigbed;
+    /// let mut reader = bigbed::io::Reader::new(io::empty());
+    /// let _inner = reader.get_mut();
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigbed;
+    /// let reader = bigbed::io::Reader::new(io::empty());
+    /// let _inner = reader.into_inner();
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Reads the bigBed header.
+    ///
+    /// This includes the zoom levels, total summary, autoSql definition, and reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bbi::{bigbed, header::ReferenceSequences};
+    ///
+    /// # let reference_sequences: ReferenceSequences = [(String::from("sq0"), 8)].into_iter().collect();
+    /// # let writer = bigbed::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    /// # let data = writer.finish()?.into_inner();
+    /// let mut reader = bigbed::io::Reader::new(Cursor::new(data));
+    /// let header = reader.read_header()?;
+    /// assert_eq!(header.reference_sequences().len(), 1);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<Header> {
+        read_header(&mut self.inner, MAGIC_NUMBER)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bbi::bigbed;
+    ///
+    /// let mut reader = File::open("sample.bb").map(bigbed::io::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let region = "sq0:8-13".parse()?;
+    ///
+    /// for result in reader.query(&header, &region)? {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&mut self, header: &Header, region: &Region) -> io::Result<Query<'_, R, Record>> {
+        let (reference_sequence_id, start, end) = resolve_region(header, region)?;
+
+        Query::new(
+            &mut self.inner,
+            header.full_index_offset,
+            header.is_compressed(),
+            reference_sequence_id,
+            start,
+            end,
+            decode_records,
+        )
+    }
+
+    /// Returns an iterator over zoom records of the given zoom level that intersect the given
+    /// region.
+    ///
+    /// In bigBed zoom records, the value of each base is its coverage depth, i.e., the number of
+    /// features that overlap it. Bases that are not covered by any feature are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bbi::bigbed;
+    ///
+    /// let mut reader = File::open("sample.bb").map(bigbed::io::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// if let Some(zoom_level) = header.zoom_levels().first() {
+    ///     let region = "sq0:8-13".parse()?;
+    ///
+    ///     for result in reader.zoom_query(&header, zoom_level, &region)? {
+    ///         let record = result?;
+    ///         // ...
+    ///     }
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn zoom_query(
+        &mut self,
+        header: &Header,
+        zoom_level: &zoom::Level,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, zoom::Record>> {
+        zoom_query(&mut self.inner, header, zoom_level, region)
+    }
+}
+
+fn decode_records(
+    mut src: &[u8],
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+    records: &mut Vec<Record>,
+) -> io::Result<()> {
+    const NUL: u8 = 0x00;
+
+    while !src.is_empty() {
+        let id = src.read_u32::<LittleEndian>()?;
+        let record_start = src.read_u32::<LittleEndian>()?;
+        let record_end = src.read_u32::<LittleEndian>()?;
+
+        let mut buf = Vec::new();
+        src.read_until(NUL, &mut buf)?;
+
+        if buf.last() == Some(&NUL) {
+            buf.pop();
+        }
+
+        if id != reference_sequence_id || !intersects(record_start, record_end, start, end) {
+            continue;
+        }
+
+        let rest =
+            String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let (record_start, record_end) = to_positions(record_start, record_end)?;
+
+        records.push(Record::new(id as usize, record_start, record_end, rest));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_records_with_zero_length_features() -> Result<(), Box<dyn std::error::Error>> {
+        let mut src = Vec::new();
+
+        for (start, end, rest) in [(0u32, 0u32, "ndls0"), (5, 5, "ndls1"), (5, 13, "ndls2")] {
+            src.extend(0u32.to_le_bytes());
+            src.extend(start.to_le_bytes());
+            src.extend(end.to_le_bytes());
+            src.extend(rest.as_bytes());
+            src.push(0x00);
+        }
+
+        let mut records = Vec::new();
+        decode_records(&src, 0, 0, 6, &mut records)?;
+
+        let expected = [
+            Record::new(0, Position::MIN, None, String::from("ndls0")),
+            Record::new(
+                0,
+                Position::try_from(6)?,
+                Some(Position::try_from(5)?),
+                String::from("ndls1"),
+            ),
+            Record::new(
+                0,
+                Position::try_from(6)?,
+                Some(Position::try_from(13)?),
+                String::from("ndls2"),
+            ),
+        ];
+
+        assert_eq!(records, expected);
+
+        Ok(())
+    }
+}
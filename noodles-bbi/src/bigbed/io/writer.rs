@@ -0,0 +1,437 @@
+mod coverage;
+
+use std::{
+    fmt,
+    io::{self, Seek, Write},
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use noodles_bed::{self as bed, record::BedN};
+
+use self::coverage::Coverage;
+use crate::{
+    bigbed::MAGIC_NUMBER,
+    header::ReferenceSequences,
+    io::writer::{self, DataBlock, Fields, ITEMS_PER_SLOT},
+};
+
+// The autoSql field definitions of the standard BED fields.
+static STANDARD_FIELD_DEFINITIONS: [&str; 12] = [
+    r#"string chrom; "Reference sequence chromosome or scaffold""#,
+    r#"uint chromStart; "Start position in chromosome""#,
+    r#"uint chromEnd; "End position in chromosome""#,
+    r#"string name; "Name of item""#,
+    r#"uint score; "Score from 0-1000""#,
+    r#"char[1] strand; "+ or -""#,
+    r#"uint thickStart; "Start of where display should be thick (start codon)""#,
+    r#"uint thickEnd; "End of where display should be thick (stop codon)""#,
+    r#"uint reserved; "Used as itemRgb as of 2004-11-22""#,
+    r#"int blockCount; "Number of blocks""#,
+    r#"int[blockCount] blockSizes; "Comma separated list of block sizes""#,
+    r#"int[blockCount] chromStarts; "Start positions relative to chromStart""#,
+];
+
+/// A bigBed writer.
+///
+/// Records must be sorted by reference sequence ID and start position, and all records must have
+/// the same number of fields. Records are written in data blocks as the blocks fill. When the
+/// writer is finished, the index and zoom levels are written, and the header is updated.
+///
+/// The total summary and zoom levels summarize the per-base coverage depth of the features.
+pub struct Writer<W> {
+    inner: writer::Writer<W>,
+    field_count: Option<(u16, u16)>,
+    coverage: Coverage,
+    last_start: Option<(u32, u32)>,
+    record_count: u64,
+    block: Option<Block>,
+    buf: Vec<u8>,
+}
+
+// The bounds and item statistics of the current block.
+struct Block {
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+    item_count: u64,
+    item_length: u64,
+}
+
+impl<W> Writer<W>
+where
+    W: Write + Seek,
+{
+    /// Creates a bigBed writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bbi::{bigbed, header::ReferenceSequences};
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 8)].into_iter().collect();
+    /// let writer = bigbed::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    /// ```
+    pub fn new(inner: W, reference_sequences: ReferenceSequences) -> Self {
+        Self {
+            inner: writer::Writer::new(inner, MAGIC_NUMBER, reference_sequences),
+            field_count: None,
+            coverage: Coverage::default(),
+            last_start: None,
+            record_count: 0,
+            block: None,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bbi::{bigbed, header::ReferenceSequences};
+    /// let writer = bigbed::io::Writer::new(Cursor::new(Vec::new()), ReferenceSequences::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Writes a BED record.
+    ///
+    /// Zero-length features, i.e., where the start equals the end, are allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bbi::{bigbed, header::ReferenceSequences};
+    /// use noodles_bed as bed;
+    ///
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 21)].into_iter().collect();
+    /// let mut writer = bigbed::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    ///
+    /// let record: bed::Record<4> = "sq0\t7\t13\tndls1".parse()?;
+    /// writer.write_record(&record)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record<const N: u8>(&mut self, record: &bed::Record<N>) -> io::Result<()>
+    where
+        bed::Record<N>: BedN<3> + fmt::Display,
+    {
+        let reference_sequence_name = record.reference_sequence_name();
+
+        let (reference_sequence_id, _, length) = self
+            .inner
+            .reference_sequences()
+            .get_full(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence name: {reference_sequence_name}"),
+                )
+            })?;
+
+        let reference_sequence_id = u32::try_from(reference_sequence_id)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let start = to_u32(usize::from(record.start_position()) - 1)?;
+        let end = to_u32(usize::from(record.end_position()))?;
+
+        if start > end || end > *length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid record interval",
+            ));
+        }
+
+        let line = record.to_string();
+        let rest = line.splitn(4, '\t').nth(3).unwrap_or_default();
+
+        let field_count = u16::try_from(line.split('\t').count())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        match self.field_count {
+            Some((n, _)) if n != field_count => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("field count mismatch: expected {n}, got {field_count}"),
+                ));
+            }
+            Some(_) => {}
+            None => self.field_count = Some((field_count, u16::from(N))),
+        }
+
+        if self
+            .last_start
+            .is_some_and(|last_start| (reference_sequence_id, start) < last_start)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "records must be sorted",
+            ));
+        }
+
+        if self.block.as_ref().is_some_and(|block| {
+            block.reference_sequence_id != reference_sequence_id
+                || block.item_count >= ITEMS_PER_SLOT as u64
+        }) {
+            self.flush_block()?;
+        }
+
+        let inner = &mut self.inner;
+        self.coverage
+            .add(reference_sequence_id, start, end, &mut |interval| {
+                inner.add_interval(interval)
+            })?;
+
+        self.buf.write_u32::<LittleEndian>(reference_sequence_id)?;
+        self.buf.write_u32::<LittleEndian>(start)?;
+        self.buf.write_u32::<LittleEndian>(end)?;
+        self.buf.extend_from_slice(rest.as_bytes());
+        self.buf.push(0x00);
+
+        let block = self.block.get_or_insert(Block {
+            reference_sequence_id,
+            start,
+            end,
+            item_count: 0,
+            item_length: 0,
+        });
+
+        block.end = block.end.max(end);
+        block.item_count += 1;
+        block.item_length += u64::from(end - start);
+
+        self.last_start = Some((reference_sequence_id, start));
+        self.record_count += 1;
+
+        Ok(())
+    }
+
+    /// Writes the remaining records, the index, and the zoom levels and returns the underlying
+    /// writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bbi::{bigbed, header::ReferenceSequences};
+    /// let writer = bigbed::io::Writer::new(Cursor::new(Vec::new()), ReferenceSequences::new());
+    /// let data = writer.finish()?.into_inner();
+    /// assert!(!data.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_block()?;
+
+        let inner = &mut self.inner;
+        self.coverage
+            .finish(&mut |interval| inner.add_interval(interval))?;
+
+        let (field_count, defined_field_count) = self.field_count.unwrap_or((3, 3));
+        let auto_sql = build_auto_sql(field_count, defined_field_count);
+
+        let fields = Fields {
+            field_count,
+            defined_field_count,
+            auto_sql: Some(&auto_sql),
+        };
+
+        self.inner.finish(self.record_count, &fields)
+    }
+
+    fn flush_block(&mut self) -> io::Result<()> {
+        let Some(block) = self.block.take() else {
+            return Ok(());
+        };
+
+        self.inner.write_block(&DataBlock {
+            start: (block.reference_sequence_id, block.start),
+            end: (block.reference_sequence_id, block.end),
+            buf: &self.buf,
+            item_count: block.item_count,
+            item_length: block.item_length,
+        })?;
+
+        self.buf.clear();
+
+        Ok(())
+    }
+}
+
+fn to_u32(n: usize) -> io::Result<u32> {
+    u32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+fn build_auto_sql(field_count: u16, defined_field_count: u16) -> String {
+    let mut s = String::from("table bed\n\"Browser Extensible Data\"\n(\n");
+
+    for i in 0..usize::from(field_count) {
+        let definition = if i < usize::from(defined_field_count) {
+            STANDARD_FIELD_DEFINITIONS
+                .get(i)
+                .map(|definition| definition.to_string())
+        } else {
+            None
+        };
+
+        let definition =
+            definition.unwrap_or_else(|| format!(r#"string field{}; "Undocumented field""#, i + 1));
+
+        s.push_str("    ");
+        s.push_str(&definition);
+        s.push('\n');
+    }
+
+    s.push_str(")\n");
+
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::bigbed;
+
+    #[test]
+    fn test_write_and_read() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences: ReferenceSequences =
+            [(String::from("sq0"), 100_000), (String::from("sq1"), 8)]
+                .into_iter()
+                .collect();
+
+        let mut writer = Writer::new(Cursor::new(Vec::new()), reference_sequences.clone());
+
+        let mut expected = Vec::new();
+
+        for i in 0..3000 {
+            let record: bed::Record<6> =
+                format!("sq0\t{}\t{}\tndls{i}\t{}\t+", i * 30, i * 30 + 50, i % 1000).parse()?;
+
+            writer.write_record(&record)?;
+            expected.push(record);
+        }
+
+        let record: bed::Record<6> = "sq1\t0\t8\tndls\t0\t-".parse()?;
+        writer.write_record(&record)?;
+
+        let data = writer.finish()?.into_inner();
+
+        let mut reader = bigbed::io::Reader::new(Cursor::new(data));
+        let header = reader.read_header()?;
+
+        assert_eq!(header.reference_sequences(), &reference_sequences);
+        assert_eq!(header.field_count(), 6);
+        assert_eq!(header.defined_field_count(), 6);
+        assert!(header.auto_sql().is_some());
+
+        // The features on sq0 overlap and cover [0, 89_990 + 30).
+        let total_summary = header.total_summary().copied().unwrap_or_default();
+        assert_eq!(total_summary.bases_covered(), 90_020 + 8);
+        assert_eq!(total_summary.sum(), 3000.0 * 50.0 + 8.0);
+        assert_eq!(total_summary.max(), 2.0);
+
+        let zoom_level = header.zoom_levels()[0];
+        let region = "sq0".parse()?;
+        let zoom_records: Vec<_> = reader
+            .zoom_query(&header, &zoom_level, &region)?
+            .collect::<io::Result<_>>()?;
+        let bases_covered: u64 = zoom_records
+            .iter()
+            .map(|record| record.summary().bases_covered())
+            .sum();
+        let sum: f64 = zoom_records
+            .iter()
+            .map(|record| record.summary().sum())
+            .sum();
+        assert_eq!(bases_covered, 90_020);
+        assert_eq!(sum, 3000.0 * 50.0);
+
+        let region = "sq0:30001-30100".parse()?;
+        let actual = reader
+            .query(&header, &region)?
+            .map(|result| result.and_then(|record| record.try_into_bed_record(&header)))
+            .collect::<io::Result<Vec<bed::Record<6>>>>()?;
+        assert_eq!(actual, &expected[999..1004]);
+
+        let region = "sq1".parse()?;
+        let actual: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(
+            actual,
+            [bigbed::Record::new(
+                1,
+                Position::MIN,
+                Some(Position::try_from(8)?),
+                String::from("ndls\t0\t-")
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_zero_length_features() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences: ReferenceSequences =
+            [(String::from("sq0"), 21)].into_iter().collect();
+
+        let mut writer = Writer::new(Cursor::new(Vec::new()), reference_sequences);
+
+        let records: [bed::Record<4>; 3] = [
+            "sq0\t5\t5\tndls0".parse()?,
+            "sq0\t5\t13\tndls1".parse()?,
+            "sq0\t13\t13\tndls2".parse()?,
+        ];
+
+        for record in &records {
+            writer.write_record(record)?;
+        }
+
+        let data = writer.finish()?.into_inner();
+
+        let mut reader = bigbed::io::Reader::new(Cursor::new(data));
+        let header = reader.read_header()?;
+
+        let total_summary = header.total_summary().copied().unwrap_or_default();
+        assert_eq!(total_summary.bases_covered(), 8);
+
+        let region = "sq0:6-13".parse()?;
+        let actual = reader
+            .query(&header, &region)?
+            .map(|result| result.and_then(|record| record.try_into_bed_record(&header)))
+            .collect::<io::Result<Vec<bed::Record<4>>>>()?;
+        assert_eq!(actual, records);
+
+        let region = "sq0:14-21".parse()?;
+        let actual = reader
+            .query(&header, &region)?
+            .map(|result| result.and_then(|record| record.try_into_bed_record(&header)))
+            .collect::<io::Result<Vec<bed::Record<4>>>>()?;
+        assert_eq!(actual, &records[2..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_auto_sql() {
+        let auto_sql = build_auto_sql(5, 4);
+
+        assert_eq!(
+            auto_sql,
+            r#"table bed
+"Browser Extensible Data"
+(
+    string chrom; "Reference sequence chromosome or scaffold"
+    uint chromStart; "Start position in chromosome"
+    uint chromEnd; "End position in chromosome"
+    string name; "Name of item"
+    string field5; "Undocumented field"
+)
+"#
+        );
+    }
+}
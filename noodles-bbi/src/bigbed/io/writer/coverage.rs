@@ -0,0 +1,139 @@
+use std::{cmp::Reverse, collections::BinaryHeap, io};
+
+use crate::io::writer::Interval;
+
+/// A per-base coverage depth builder.
+///
+/// Features are added in coordinate order, and runs of bases with the same nonzero depth are
+/// emitted as intervals, where the value is the depth.
+#[derive(Default)]
+pub(super) struct Coverage {
+    reference_sequence_id: u32,
+    position: u32,
+    ends: BinaryHeap<Reverse<u32>>,
+}
+
+impl Coverage {
+    /// Adds a 0-based, half-open feature.
+    ///
+    /// Depth runs that end at or before the start of the feature are emitted.
+    pub(super) fn add<F>(
+        &mut self,
+        reference_sequence_id: u32,
+        start: u32,
+        end: u32,
+        emit: &mut F,
+    ) -> io::Result<()>
+    where
+        F: FnMut(Interval) -> io::Result<()>,
+    {
+        if reference_sequence_id != self.reference_sequence_id {
+            self.finish(emit)?;
+            self.reference_sequence_id = reference_sequence_id;
+            self.position = 0;
+        }
+
+        self.advance(start, emit)?;
+
+        if end > start {
+            self.emit_to(start, emit)?;
+            self.ends.push(Reverse(end));
+        }
+
+        Ok(())
+    }
+
+    /// Emits the remaining depth runs.
+    pub(super) fn finish<F>(&mut self, emit: &mut F) -> io::Result<()>
+    where
+        F: FnMut(Interval) -> io::Result<()>,
+    {
+        self.advance(u32::MAX, emit)
+    }
+
+    fn advance<F>(&mut self, position: u32, emit: &mut F) -> io::Result<()>
+    where
+        F: FnMut(Interval) -> io::Result<()>,
+    {
+        while let Some(&Reverse(end)) = self.ends.peek() {
+            if end > position {
+                break;
+            }
+
+            self.emit_to(end, emit)?;
+
+            while self.ends.peek() == Some(&Reverse(end)) {
+                self.ends.pop();
+            }
+        }
+
+        if self.ends.is_empty() {
+            self.position = self.position.max(position);
+        }
+
+        Ok(())
+    }
+
+    fn emit_to<F>(&mut self, end: u32, emit: &mut F) -> io::Result<()>
+    where
+        F: FnMut(Interval) -> io::Result<()>,
+    {
+        if end > self.position {
+            let depth = self.ends.len();
+
+            emit(Interval {
+                reference_sequence_id: self.reference_sequence_id,
+                start: self.position,
+                end,
+                value: depth as f64,
+            })?;
+
+            self.position = end;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() -> io::Result<()> {
+        let mut coverage = Coverage::default();
+        let mut intervals = Vec::new();
+
+        let mut emit = |interval: Interval| {
+            intervals.push((
+                interval.reference_sequence_id,
+                interval.start,
+                interval.end,
+                interval.value,
+            ));
+
+            Ok(())
+        };
+
+        coverage.add(0, 2, 8, &mut emit)?;
+        coverage.add(0, 5, 13, &mut emit)?;
+        coverage.add(0, 5, 8, &mut emit)?;
+        coverage.add(0, 9, 9, &mut emit)?;
+        coverage.add(0, 21, 34, &mut emit)?;
+        coverage.add(1, 0, 5, &mut emit)?;
+        coverage.finish(&mut emit)?;
+
+        assert_eq!(
+            intervals,
+            [
+                (0, 2, 5, 1.0),
+                (0, 5, 8, 3.0),
+                (0, 8, 13, 1.0),
+                (0, 21, 34, 1.0),
+                (1, 0, 5, 1.0),
+            ]
+        );
+
+        Ok(())
+    }
+}
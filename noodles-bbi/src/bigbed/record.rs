@@ -0,0 +1,162 @@
+use std::{error, io, str::FromStr};
+
+use noodles_bed as bed;
+use noodles_core::Position;
+
+use crate::Header;
+
+/// A bigBed record.
+///
+/// This is a feature on a reference sequence. The fields after the end position are kept as
+/// unparsed, tab-delimited text.
+///
+/// A zero-length feature has an end position one less than its start position. The end position
+/// of a zero-length feature at the start of a reference sequence is `None`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    reference_sequence_id: usize,
+    start: Position,
+    end: Option<Position>,
+    rest: String,
+}
+
+impl Record {
+    /// Creates a bigBed record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigbed;
+    /// use noodles_core::Position;
+    ///
+    /// let record = bigbed::Record::new(
+    ///     0,
+    ///     Position::try_from(8)?,
+    ///     Some(Position::try_from(13)?),
+    ///     String::from("ndls1"),
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new(
+        reference_sequence_id: usize,
+        start: Position,
+        end: Option<Position>,
+        rest: String,
+    ) -> Self {
+        Self {
+            reference_sequence_id,
+            start,
+            end,
+            rest,
+        }
+    }
+
+    /// Returns the reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigbed;
+    /// use noodles_core::Position;
+    /// let record = bigbed::Record::new(0, Position::MIN, Some(Position::MIN), String::new());
+    /// assert_eq!(record.reference_sequence_id(), 0);
+    /// ```
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigbed;
+    /// use noodles_core::Position;
+    /// let record = bigbed::Record::new(0, Position::MIN, Some(Position::MIN), String::new());
+    /// assert_eq!(record.start(), Position::MIN);
+    /// ```
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigbed;
+    /// use noodles_core::Position;
+    /// let record = bigbed::Record::new(0, Position::MIN, Some(Position::MIN), String::new());
+    /// assert_eq!(record.end(), Some(Position::MIN));
+    /// ```
+    pub fn end(&self) -> Option<Position> {
+        self.end
+    }
+
+    /// Returns the raw, tab-delimited fields after the end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigbed;
+    /// use noodles_core::Position;
+    /// let record = bigbed::Record::new(0, Position::MIN, Some(Position::MIN), String::from("ndls1"));
+    /// assert_eq!(record.rest(), "ndls1");
+    /// ```
+    pub fn rest(&self) -> &str {
+        &self.rest
+    }
+
+    /// Converts this record to a BED record.
+    ///
+    /// The reference sequence name is resolved using the given header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bbi::{bigbed, header::ReferenceSequences};
+    /// use noodles_bed as bed;
+    ///
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 21)].into_iter().collect();
+    /// let mut writer = bigbed::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    /// let record: bed::Record<4> = "sq0\t7\t13\tndls1".parse()?;
+    /// writer.write_record(&record)?;
+    /// let data = writer.finish()?.into_inner();
+    ///
+    /// let mut reader = bigbed::io::Reader::new(Cursor::new(data));
+    /// let header = reader.read_header()?;
+    ///
+    /// let region = "sq0".parse()?;
+    ///
+    /// for result in reader.query(&header, &region)? {
+    ///     let bed_record: bed::Record<4> = result?.try_into_bed_record(&header)?;
+    ///     assert_eq!(bed_record, record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_into_bed_record<const N: u8>(&self, header: &Header) -> io::Result<bed::Record<N>>
+    where
+        bed::Record<N>: FromStr,
+        <bed::Record<N> as FromStr>::Err: error::Error + Send + Sync + 'static,
+    {
+        let (reference_sequence_name, _) = header
+            .reference_sequences()
+            .get_index(self.reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+            })?;
+
+        let start = usize::from(self.start) - 1;
+        let end = self.end.map(usize::from).unwrap_or_default();
+
+        let s = if self.rest.is_empty() {
+            format!("{reference_sequence_name}\t{start}\t{end}")
+        } else {
+            format!("{reference_sequence_name}\t{start}\t{end}\t{}", self.rest)
+        };
+
+        s.parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
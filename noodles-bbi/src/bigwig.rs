@@ -0,0 +1,10 @@
+//! bigWig format.
+//!
+//! bigWig stores continuous numeric data over reference sequences, e.g., signal coverage.
+
+pub mod io;
+mod record;
+
+pub use self::record::Record;
+
+pub(crate) const MAGIC_NUMBER: u32 = 0x888ffc26;
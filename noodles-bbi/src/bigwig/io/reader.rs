@@ -0,0 +1,314 @@
+use std::io::{self, Read, Seek};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use noodles_core::Region;
+
+use crate::{
+    bigwig::{Record, MAGIC_NUMBER},
+    io::{
+        reader::{read_header, resolve_region, to_nonempty_positions, zoom_query},
+        Query,
+    },
+    zoom, Header, Summary,
+};
+
+const SECTION_HEADER_SIZE: usize = 24;
+
+const BED_GRAPH: u8 = 1;
+const VARIABLE_STEP: u8 = 2;
+const FIXED_STEP: u8 = 3;
+
+/// A bigWig reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R> {
+    /// Creates a bigWig reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigwig;
+    /// let reader = bigwig::io::Reader::new(io::empty());
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigwig;
+    /// let reader = bigwig::io::Reader::new(io::empty());
+    /// let _inner = reader.get_ref();
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigwig;
+    /// let mut reader = bigwig::io::Reader::new(io::empty());
+    /// let _inner = reader.get_mut();
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_bbi::bigwig;
+    /// let reader = bigwig::io::Reader::new(io::empty());
+    /// let _inner = reader.into_inner();
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Reads the bigWig header.
+    ///
+    /// This includes the zoom levels, total summary, and reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    ///
+    /// # let reference_sequences: ReferenceSequences = [(String::from("sq0"), 8)].into_iter().collect();
+    /// # let writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    /// # let data = writer.finish()?.into_inner();
+    /// let mut reader = bigwig::io::Reader::new(Cursor::new(data));
+    /// let header = reader.read_header()?;
+    /// assert_eq!(header.reference_sequences().len(), 1);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<Header> {
+        read_header(&mut self.inner, MAGIC_NUMBER)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    /// use noodles_core::Position;
+    ///
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 21)].into_iter().collect();
+    /// let mut writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    ///
+    /// let record = bigwig::Record::new(0, Position::try_from(8)?, Position::try_from(13)?, 0.5);
+    /// writer.write_record(&record)?;
+    /// let data = writer.finish()?.into_inner();
+    ///
+    /// let mut reader = bigwig::io::Reader::new(Cursor::new(data));
+    /// let header = reader.read_header()?;
+    ///
+    /// let region = "sq0:10-15".parse()?;
+    /// let records: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+    /// assert_eq!(records, [record]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&mut self, header: &Header, region: &Region) -> io::Result<Query<'_, R, Record>> {
+        let (reference_sequence_id, start, end) = resolve_region(header, region)?;
+
+        Query::new(
+            &mut self.inner,
+            header.full_index_offset,
+            header.is_compressed(),
+            reference_sequence_id,
+            start,
+            end,
+            decode_records,
+        )
+    }
+
+    /// Returns an iterator over zoom records of the given zoom level that intersect the given
+    /// region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bbi::bigwig;
+    ///
+    /// let mut reader = File::open("sample.bw").map(bigwig::io::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// if let Some(zoom_level) = header.zoom_levels().first() {
+    ///     let region = "sq0:8-13".parse()?;
+    ///
+    ///     for result in reader.zoom_query(&header, zoom_level, &region)? {
+    ///         let record = result?;
+    ///         // ...
+    ///     }
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn zoom_query(
+        &mut self,
+        header: &Header,
+        zoom_level: &zoom::Level,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, zoom::Record>> {
+        zoom_query(&mut self.inner, header, zoom_level, region)
+    }
+
+    /// Returns the summary statistics of the values in the given region.
+    ///
+    /// Values are weighted by the number of bases they cover within the region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    /// use noodles_core::Position;
+    ///
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 21)].into_iter().collect();
+    /// let mut writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    ///
+    /// let record = bigwig::Record::new(0, Position::try_from(8)?, Position::try_from(13)?, 0.5);
+    /// writer.write_record(&record)?;
+    /// let data = writer.finish()?.into_inner();
+    ///
+    /// let mut reader = bigwig::io::Reader::new(Cursor::new(data));
+    /// let header = reader.read_header()?;
+    ///
+    /// let region = "sq0:10-15".parse()?;
+    /// let summary = reader.summarize(&header, &region)?;
+    /// assert_eq!(summary.bases_covered(), 4);
+    /// assert_eq!(summary.mean(), Some(0.5));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn summarize(&mut self, header: &Header, region: &Region) -> io::Result<Summary> {
+        let (_, start, end) = resolve_region(header, region)?;
+
+        let mut summary = Summary::default();
+
+        for result in self.query(header, region)? {
+            let record = result?;
+
+            let record_start = (usize::from(record.start()) - 1) as u32;
+            let record_end = usize::from(record.end()) as u32;
+
+            let bases = record_end.min(end) - record_start.max(start);
+            summary.add(f64::from(record.value()), u64::from(bases));
+        }
+
+        Ok(summary)
+    }
+}
+
+fn decode_records(
+    mut src: &[u8],
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+    records: &mut Vec<Record>,
+) -> io::Result<()> {
+    while src.len() >= SECTION_HEADER_SIZE {
+        let id = src.read_u32::<LittleEndian>()?;
+        let section_start = src.read_u32::<LittleEndian>()?;
+        let _section_end = src.read_u32::<LittleEndian>()?;
+        let item_step = src.read_u32::<LittleEndian>()?;
+        let item_span = src.read_u32::<LittleEndian>()?;
+        let ty = src.read_u8()?;
+        let _reserved = src.read_u8()?;
+        let item_count = src.read_u16::<LittleEndian>()?;
+
+        for i in 0..u32::from(item_count) {
+            let (item_start, item_end, value) = match ty {
+                BED_GRAPH => {
+                    let item_start = src.read_u32::<LittleEndian>()?;
+                    let item_end = src.read_u32::<LittleEndian>()?;
+                    let value = src.read_f32::<LittleEndian>()?;
+                    (item_start, item_end, value)
+                }
+                VARIABLE_STEP => {
+                    let item_start = src.read_u32::<LittleEndian>()?;
+                    let value = src.read_f32::<LittleEndian>()?;
+                    (item_start, item_start.saturating_add(item_span), value)
+                }
+                FIXED_STEP => {
+                    let item_start = section_start.saturating_add(i.saturating_mul(item_step));
+                    let value = src.read_f32::<LittleEndian>()?;
+                    (item_start, item_start.saturating_add(item_span), value)
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid bigWig section type: {ty}"),
+                    ))
+                }
+            };
+
+            if id != reference_sequence_id || item_start >= end || item_end <= start {
+                continue;
+            }
+
+            let (item_start, item_end) = to_nonempty_positions(item_start, item_end)?;
+            records.push(Record::new(id as usize, item_start, item_end, value));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_records() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_core::Position;
+
+        let mut src = Vec::new();
+
+        // fixedStep
+        src.extend([0, 0, 0, 0, 10, 0, 0, 0, 40, 0, 0, 0]); // chromId, start, end
+        src.extend([10, 0, 0, 0, 5, 0, 0, 0, FIXED_STEP, 0, 3, 0]); // step, span, type, count
+        src.extend(1.0f32.to_le_bytes());
+        src.extend(2.0f32.to_le_bytes());
+        src.extend(3.0f32.to_le_bytes());
+
+        // variableStep
+        src.extend([0, 0, 0, 0, 50, 0, 0, 0, 52, 0, 0, 0]); // chromId, start, end
+        src.extend([0, 0, 0, 0, 2, 0, 0, 0, VARIABLE_STEP, 0, 1, 0]); // step, span, type, count
+        src.extend([50, 0, 0, 0]);
+        src.extend(4.0f32.to_le_bytes());
+
+        let mut records = Vec::new();
+        decode_records(&src, 0, 25, 60, &mut records)?;
+
+        let expected = [
+            Record::new(0, Position::try_from(31)?, Position::try_from(35)?, 3.0),
+            Record::new(0, Position::try_from(51)?, Position::try_from(52)?, 4.0),
+        ];
+
+        assert_eq!(records, expected);
+
+        Ok(())
+    }
+}
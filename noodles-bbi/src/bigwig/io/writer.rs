@@ -0,0 +1,337 @@
+use std::io::{self, Seek, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use noodles_core::Position;
+
+use crate::{
+    bigwig::{Record, MAGIC_NUMBER},
+    header::ReferenceSequences,
+    io::writer::{self, DataBlock, Fields, Interval, ITEMS_PER_SLOT},
+};
+
+const BED_GRAPH: u8 = 1;
+
+/// A bigWig writer.
+///
+/// Records must be sorted by reference sequence ID and start position and must not overlap.
+/// Sections of records are written as data blocks as they fill. When the writer is finished, the
+/// index and zoom levels are written, and the header is updated.
+pub struct Writer<W> {
+    inner: writer::Writer<W>,
+    section: Vec<Interval>,
+    last_interval: Option<Interval>,
+    section_count: u64,
+}
+
+impl<W> Writer<W>
+where
+    W: Write + Seek,
+{
+    /// Creates a bigWig writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 8)].into_iter().collect();
+    /// let writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    /// ```
+    pub fn new(inner: W, reference_sequences: ReferenceSequences) -> Self {
+        Self {
+            inner: writer::Writer::new(inner, MAGIC_NUMBER, reference_sequences),
+            section: Vec::new(),
+            last_interval: None,
+            section_count: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    /// let writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), ReferenceSequences::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Writes a bigWig record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    /// use noodles_core::Position;
+    ///
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 21)].into_iter().collect();
+    /// let mut writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    ///
+    /// let record = bigwig::Record::new(0, Position::try_from(8)?, Position::try_from(13)?, 0.5);
+    /// writer.write_record(&record)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let reference_sequence_id = u32::try_from(record.reference_sequence_id())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let interval = Interval {
+            reference_sequence_id,
+            start: to_u32(record.start())? - 1,
+            end: to_u32(record.end())?,
+            value: f64::from(record.value()),
+        };
+
+        self.push_interval(interval)
+    }
+
+    /// Writes a bedGraph record.
+    ///
+    /// The reference sequence name is resolved using the writer's reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    /// use noodles_core::Position;
+    ///
+    /// let reference_sequences: ReferenceSequences = [(String::from("sq0"), 21)].into_iter().collect();
+    /// let mut writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), reference_sequences);
+    ///
+    /// writer.write_bedgraph_record("sq0", Position::try_from(8)?, Position::try_from(13)?, 0.5)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_bedgraph_record(
+        &mut self,
+        reference_sequence_name: &str,
+        start: Position,
+        end: Position,
+        value: f32,
+    ) -> io::Result<()> {
+        let reference_sequence_id = self
+            .inner
+            .reference_sequences()
+            .get_index_of(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence name: {reference_sequence_name}"),
+                )
+            })?;
+
+        self.write_record(&Record::new(reference_sequence_id, start, end, value))
+    }
+
+    /// Writes the remaining records, the index, and the zoom levels and returns the underlying
+    /// writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bbi::{bigwig, header::ReferenceSequences};
+    /// let writer = bigwig::io::Writer::new(Cursor::new(Vec::new()), ReferenceSequences::new());
+    /// let data = writer.finish()?.into_inner();
+    /// assert!(!data.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_section()?;
+
+        let fields = Fields {
+            field_count: 0,
+            defined_field_count: 0,
+            auto_sql: None,
+        };
+
+        self.inner.finish(self.section_count, &fields)
+    }
+
+    fn push_interval(&mut self, interval: Interval) -> io::Result<()> {
+        let length = self
+            .inner
+            .reference_sequences()
+            .get_index(interval.reference_sequence_id as usize)
+            .map(|(_, length)| *length)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "invalid reference sequence ID")
+            })?;
+
+        if interval.start >= interval.end || interval.end > length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid record interval",
+            ));
+        }
+
+        if let Some(last) = self.last_interval {
+            if (interval.reference_sequence_id, interval.start)
+                < (last.reference_sequence_id, last.end)
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "records must be sorted and not overlap",
+                ));
+            }
+
+            if interval.reference_sequence_id != last.reference_sequence_id
+                || self.section.len() >= ITEMS_PER_SLOT
+            {
+                self.flush_section()?;
+            }
+        }
+
+        self.inner.add_interval(interval)?;
+        self.section.push(interval);
+        self.last_interval = Some(interval);
+
+        Ok(())
+    }
+
+    // Sections are bedGraph items of a single reference sequence.
+    fn flush_section(&mut self) -> io::Result<()> {
+        let items = &self.section;
+
+        let (Some(first), Some(last)) = (items.first(), items.last()) else {
+            return Ok(());
+        };
+
+        let item_count = u16::try_from(items.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let mut buf = Vec::new();
+
+        buf.write_u32::<LittleEndian>(first.reference_sequence_id)?;
+        buf.write_u32::<LittleEndian>(first.start)?;
+        buf.write_u32::<LittleEndian>(last.end)?;
+        buf.write_u32::<LittleEndian>(0)?; // item step
+        buf.write_u32::<LittleEndian>(0)?; // item span
+        buf.write_u8(BED_GRAPH)?;
+        buf.write_u8(0)?; // reserved
+        buf.write_u16::<LittleEndian>(item_count)?;
+
+        let mut item_length = 0;
+
+        for item in items {
+            buf.write_u32::<LittleEndian>(item.start)?;
+            buf.write_u32::<LittleEndian>(item.end)?;
+            buf.write_f32::<LittleEndian>(item.value as f32)?;
+            item_length += u64::from(item.end - item.start);
+        }
+
+        self.inner.write_block(&DataBlock {
+            start: (first.reference_sequence_id, first.start),
+            end: (last.reference_sequence_id, last.end),
+            buf: &buf,
+            item_count: items.len() as u64,
+            item_length,
+        })?;
+
+        self.section.clear();
+        self.section_count += 1;
+
+        Ok(())
+    }
+}
+
+fn to_u32(position: Position) -> io::Result<u32> {
+    u32::try_from(usize::from(position)).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::bigwig;
+
+    #[test]
+    fn test_write_and_read() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences: ReferenceSequences =
+            [(String::from("sq0"), 100_000), (String::from("sq1"), 8)]
+                .into_iter()
+                .collect();
+
+        let mut writer = Writer::new(Cursor::new(Vec::new()), reference_sequences.clone());
+
+        let mut expected = Vec::new();
+
+        for i in 0..5000 {
+            let start = Position::try_from(i * 20 + 1)?;
+            let end = Position::try_from(i * 20 + 10)?;
+            let record = Record::new(0, start, end, (i % 4) as f32);
+            writer.write_record(&record)?;
+            expected.push(record);
+        }
+
+        writer.write_bedgraph_record("sq1", Position::MIN, Position::try_from(8)?, 2.0)?;
+
+        let data = writer.finish()?.into_inner();
+
+        let mut reader = bigwig::io::Reader::new(Cursor::new(data));
+        let header = reader.read_header()?;
+
+        assert_eq!(header.reference_sequences(), &reference_sequences);
+        assert!(header.is_compressed());
+        assert!(!header.zoom_levels().is_empty());
+
+        let total_summary = header.total_summary().copied().unwrap_or_default();
+        assert_eq!(total_summary.bases_covered(), 5000 * 10 + 8);
+        assert_eq!(total_summary.max(), 3.0);
+
+        let region = "sq0:30001-30100".parse()?;
+        let actual: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(actual, &expected[1500..1505]);
+
+        let summary = reader.summarize(&header, &region)?;
+        assert_eq!(summary.bases_covered(), 50);
+        assert_eq!(summary.sum(), 10.0 * (0.0 + 1.0 + 2.0 + 3.0 + 0.0));
+
+        let region = "sq1".parse()?;
+        let actual: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+        assert_eq!(
+            actual,
+            [Record::new(1, Position::MIN, Position::try_from(8)?, 2.0)]
+        );
+
+        let zoom_level = header.zoom_levels()[0];
+        let region = "sq0".parse()?;
+        let zoom_records: Vec<_> = reader
+            .zoom_query(&header, &zoom_level, &region)?
+            .collect::<io::Result<_>>()?;
+        let bases_covered: u64 = zoom_records
+            .iter()
+            .map(|record| record.summary().bases_covered())
+            .sum();
+        assert_eq!(bases_covered, 5000 * 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences: ReferenceSequences =
+            [(String::from("sq0"), 21)].into_iter().collect();
+        let mut writer = Writer::new(Cursor::new(Vec::new()), reference_sequences);
+
+        writer.write_bedgraph_record(
+            "sq0",
+            Position::try_from(8)?,
+            Position::try_from(13)?,
+            0.0,
+        )?;
+
+        assert!(matches!(
+            writer.write_bedgraph_record("sq0", Position::try_from(5)?, Position::try_from(10)?, 0.0),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
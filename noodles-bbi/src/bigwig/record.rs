@@ -0,0 +1,95 @@
+use noodles_core::Position;
+
+/// A bigWig record.
+///
+/// This is a value over an interval of a reference sequence.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    reference_sequence_id: usize,
+    start: Position,
+    end: Position,
+    value: f32,
+}
+
+impl Record {
+    /// Creates a bigWig record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigwig;
+    /// use noodles_core::Position;
+    ///
+    /// let record = bigwig::Record::new(
+    ///     0,
+    ///     Position::try_from(8)?,
+    ///     Position::try_from(13)?,
+    ///     0.5,
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new(reference_sequence_id: usize, start: Position, end: Position, value: f32) -> Self {
+        Self {
+            reference_sequence_id,
+            start,
+            end,
+            value,
+        }
+    }
+
+    /// Returns the reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigwig;
+    /// use noodles_core::Position;
+    /// let record = bigwig::Record::new(0, Position::MIN, Position::MIN, 0.5);
+    /// assert_eq!(record.reference_sequence_id(), 0);
+    /// ```
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigwig;
+    /// use noodles_core::Position;
+    /// let record = bigwig::Record::new(0, Position::MIN, Position::MIN, 0.5);
+    /// assert_eq!(record.start(), Position::MIN);
+    /// ```
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigwig;
+    /// use noodles_core::Position;
+    /// let record = bigwig::Record::new(0, Position::MIN, Position::MIN, 0.5);
+    /// assert_eq!(record.end(), Position::MIN);
+    /// ```
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::bigwig;
+    /// use noodles_core::Position;
+    /// let record = bigwig::Record::new(0, Position::MIN, Position::MIN, 0.5);
+    /// assert_eq!(record.value(), 0.5);
+    /// ```
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
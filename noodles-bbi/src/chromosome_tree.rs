@@ -0,0 +1,217 @@
+//! BBI chromosome B+ tree.
+
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    ops::Range,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    header::ReferenceSequences,
+    io::writer::{build_node_offsets, build_tree_levels},
+};
+
+const MAGIC_NUMBER: u32 = 0x78ca8c91;
+const VALUE_SIZE: u32 = 8;
+const MAX_BLOCK_SIZE: usize = 256;
+
+pub(crate) fn read<R>(reader: &mut R, offset: u64) -> io::Result<ReferenceSequences>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(offset))?;
+
+    if reader.read_u32::<LittleEndian>()? != MAGIC_NUMBER {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid chromosome B+ tree magic number",
+        ));
+    }
+
+    let _block_size = reader.read_u32::<LittleEndian>()?;
+
+    let key_size = reader.read_u32::<LittleEndian>().and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    if reader.read_u32::<LittleEndian>()? != VALUE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid chromosome B+ tree value size",
+        ));
+    }
+
+    let _item_count = reader.read_u64::<LittleEndian>()?;
+    let _reserved = reader.read_u64::<LittleEndian>()?;
+
+    let mut entries = Vec::new();
+    read_node(reader, key_size, &mut entries)?;
+
+    entries.sort_unstable_by_key(|(id, _, _)| *id);
+
+    let mut reference_sequences = ReferenceSequences::with_capacity(entries.len());
+
+    for (i, (id, name, length)) in entries.into_iter().enumerate() {
+        if usize::try_from(id).ok() != Some(i) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid chromosome ID",
+            ));
+        }
+
+        reference_sequences.insert(name, length);
+    }
+
+    Ok(reference_sequences)
+}
+
+fn read_node<R>(
+    reader: &mut R,
+    key_size: usize,
+    entries: &mut Vec<(u32, String, u32)>,
+) -> io::Result<()>
+where
+    R: Read + Seek,
+{
+    let is_leaf = reader.read_u8()? != 0;
+    let _reserved = reader.read_u8()?;
+    let count = reader.read_u16::<LittleEndian>()?;
+
+    let mut key = vec![0; key_size];
+
+    if is_leaf {
+        for _ in 0..count {
+            reader.read_exact(&mut key)?;
+            let name = parse_key(&key)?;
+            let id = reader.read_u32::<LittleEndian>()?;
+            let length = reader.read_u32::<LittleEndian>()?;
+            entries.push((id, name, length));
+        }
+    } else {
+        let mut child_offsets = Vec::with_capacity(usize::from(count));
+
+        for _ in 0..count {
+            reader.read_exact(&mut key)?;
+            child_offsets.push(reader.read_u64::<LittleEndian>()?);
+        }
+
+        for offset in child_offsets {
+            reader.seek(SeekFrom::Start(offset))?;
+            read_node(reader, key_size, entries)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_key(buf: &[u8]) -> io::Result<String> {
+    const NUL: u8 = 0x00;
+
+    let len = buf.iter().position(|&b| b == NUL).unwrap_or(buf.len());
+
+    String::from_utf8(buf[..len].to_vec())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Nodes are written breadth-first, starting from the root. Keys are sorted by name.
+pub(crate) fn write(dst: &mut Vec<u8>, reference_sequences: &ReferenceSequences) -> io::Result<()> {
+    const NODE_HEADER_SIZE: u64 = 4;
+
+    let mut items = Vec::with_capacity(reference_sequences.len());
+
+    for (id, (name, length)) in reference_sequences.iter().enumerate() {
+        let id = u32::try_from(id).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        items.push((name.as_bytes(), id, *length));
+    }
+
+    items.sort_unstable();
+
+    let key_size = items
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(1);
+    let block_size = items.len().clamp(1, MAX_BLOCK_SIZE);
+    let item_size = (key_size as u64) + u64::from(VALUE_SIZE);
+
+    let levels = build_tree_levels(items.len(), block_size);
+
+    let invalid_input = |e| io::Error::new(io::ErrorKind::InvalidInput, e);
+
+    dst.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+    dst.write_u32::<LittleEndian>(u32::try_from(block_size).map_err(invalid_input)?)?;
+    dst.write_u32::<LittleEndian>(u32::try_from(key_size).map_err(invalid_input)?)?;
+    dst.write_u32::<LittleEndian>(VALUE_SIZE)?;
+    dst.write_u64::<LittleEndian>(items.len() as u64)?;
+    dst.write_u64::<LittleEndian>(0)?; // reserved
+
+    let node_size =
+        |_: usize, range: &Range<usize>| NODE_HEADER_SIZE + item_size * range.len() as u64;
+    let node_offsets = build_node_offsets(&levels, dst.len() as u64, node_size);
+
+    for (i, level) in levels.iter().enumerate().rev() {
+        let is_leaf = i == 0;
+
+        for range in level {
+            dst.write_u8(u8::from(is_leaf))?;
+            dst.write_u8(0)?; // reserved
+            dst.write_u16::<LittleEndian>(u16::try_from(range.len()).map_err(invalid_input)?)?;
+
+            if is_leaf {
+                for &(name, id, length) in &items[range.clone()] {
+                    write_key(dst, name, key_size);
+                    dst.write_u32::<LittleEndian>(id)?;
+                    dst.write_u32::<LittleEndian>(length)?;
+                }
+            } else {
+                for j in range.clone() {
+                    let (name, _, _) = items[first_item_index(&levels[..i], j)];
+                    write_key(dst, name, key_size);
+                    dst.write_u64::<LittleEndian>(node_offsets[i - 1][j])?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the index of the first item under node `j` of the last level in `levels`.
+fn first_item_index(levels: &[Vec<Range<usize>>], mut j: usize) -> usize {
+    for level in levels.iter().rev() {
+        j = level[j].start;
+    }
+
+    j
+}
+
+fn write_key(dst: &mut Vec<u8>, name: &[u8], key_size: usize) {
+    dst.extend_from_slice(name);
+    dst.resize(dst.len() + key_size - name.len(), 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_write_and_read() -> io::Result<()> {
+        for n in [0, 1, 3, 300, 70000] {
+            let reference_sequences: ReferenceSequences =
+                (0..n).map(|i| (format!("sq{i}"), i + 1)).collect();
+
+            let mut buf = vec![0; 5];
+            write(&mut buf, &reference_sequences)?;
+
+            let mut reader = Cursor::new(buf);
+            let actual = read(&mut reader, 5)?;
+
+            assert_eq!(actual, reference_sequences);
+        }
+
+        Ok(())
+    }
+}
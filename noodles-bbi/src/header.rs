@@ -0,0 +1,78 @@
+//! BBI header.
+
+use indexmap::IndexMap;
+
+use crate::{zoom, Summary};
+
+/// BBI reference sequences.
+///
+/// This maps reference sequence names to their lengths. The index of an entry is its reference
+/// sequence ID.
+pub type ReferenceSequences = IndexMap<String, u32>;
+
+/// A BBI header.
+///
+/// This includes the fixed header, the zoom level headers, the total summary, the autoSql
+/// definition, and the reference sequences from the chromosome B+ tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Header {
+    pub(crate) version: u16,
+    pub(crate) zoom_levels: Vec<zoom::Level>,
+    pub(crate) chromosome_tree_offset: u64,
+    pub(crate) full_data_offset: u64,
+    pub(crate) full_index_offset: u64,
+    pub(crate) field_count: u16,
+    pub(crate) defined_field_count: u16,
+    pub(crate) auto_sql_offset: u64,
+    pub(crate) total_summary_offset: u64,
+    pub(crate) uncompressed_buffer_size: u32,
+    pub(crate) total_summary: Option<Summary>,
+    pub(crate) auto_sql: Option<String>,
+    pub(crate) reference_sequences: ReferenceSequences,
+}
+
+impl Header {
+    /// Returns the format version.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Returns the zoom levels.
+    pub fn zoom_levels(&self) -> &[zoom::Level] {
+        &self.zoom_levels
+    }
+
+    /// Returns the number of fields in a bigBed record.
+    ///
+    /// This is 0 for bigWig.
+    pub fn field_count(&self) -> u16 {
+        self.field_count
+    }
+
+    /// Returns the number of standard BED fields in a bigBed record.
+    ///
+    /// This is 0 for bigWig.
+    pub fn defined_field_count(&self) -> u16 {
+        self.defined_field_count
+    }
+
+    /// Returns whether data blocks are compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.uncompressed_buffer_size > 0
+    }
+
+    /// Returns the summary statistics of the entire file.
+    pub fn total_summary(&self) -> Option<&Summary> {
+        self.total_summary.as_ref()
+    }
+
+    /// Returns the autoSql definition of bigBed records.
+    pub fn auto_sql(&self) -> Option<&str> {
+        self.auto_sql.as_deref()
+    }
+
+    /// Returns the reference sequences.
+    pub fn reference_sequences(&self) -> &ReferenceSequences {
+        &self.reference_sequences
+    }
+}
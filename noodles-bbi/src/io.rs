@@ -0,0 +1,7 @@
+//! BBI I/O.
+
+mod query;
+pub(crate) mod reader;
+pub(crate) mod writer;
+
+pub use self::query::Query;
@@ -0,0 +1,97 @@
+use std::{
+    io::{self, Read, Seek},
+    vec,
+};
+
+use super::reader::read_block;
+use crate::r_tree;
+
+type Decode<T> = fn(&[u8], u32, u32, u32, &mut Vec<T>) -> io::Result<()>;
+
+/// An iterator over records of a BBI reader that intersect a given region.
+///
+/// This is created by calling a bigWig or bigBed reader's `query` or `zoom_query` method.
+pub struct Query<'r, R, T> {
+    reader: &'r mut R,
+    is_compressed: bool,
+    blocks: vec::IntoIter<r_tree::Block>,
+    records: vec::IntoIter<T>,
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+    decode: Decode<T>,
+    buf: Vec<u8>,
+}
+
+impl<'r, R, T> Query<'r, R, T>
+where
+    R: Read + Seek,
+{
+    // `start` and `end` are a 0-based, half-open interval.
+    pub(crate) fn new(
+        reader: &'r mut R,
+        index_offset: u64,
+        is_compressed: bool,
+        reference_sequence_id: u32,
+        start: u32,
+        end: u32,
+        decode: Decode<T>,
+    ) -> io::Result<Self> {
+        let blocks = r_tree::query(reader, index_offset, reference_sequence_id, start, end)?;
+
+        Ok(Self {
+            reader,
+            is_compressed,
+            blocks: blocks.into_iter(),
+            records: Vec::new().into_iter(),
+            reference_sequence_id,
+            start,
+            end,
+            decode,
+            buf: Vec::new(),
+        })
+    }
+
+    fn read_next_block(&mut self) -> io::Result<Option<()>> {
+        let Some(block) = self.blocks.next() else {
+            return Ok(None);
+        };
+
+        read_block(self.reader, &block, self.is_compressed, &mut self.buf)?;
+
+        let mut records = Vec::new();
+
+        (self.decode)(
+            &self.buf,
+            self.reference_sequence_id,
+            self.start,
+            self.end,
+            &mut records,
+        )?;
+
+        self.records = records.into_iter();
+
+        Ok(Some(()))
+    }
+}
+
+impl<'r, R, T> Iterator for Query<'r, R, T>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(record) = self.records.next() {
+                return Some(Ok(record));
+            }
+
+            match self.read_next_block() {
+                Ok(Some(())) => {}
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
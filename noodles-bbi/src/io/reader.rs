@@ -0,0 +1,322 @@
+//! BBI reader.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use flate2::read::ZlibDecoder;
+use noodles_core::{region::Interval, Position, Region};
+
+use super::Query;
+use crate::{chromosome_tree, r_tree, zoom, Header, Summary};
+
+const ZOOM_RECORD_SIZE: usize = 32;
+
+/// Reads a BBI header with the given magic number.
+pub(crate) fn read_header<R>(reader: &mut R, magic_number: u32) -> io::Result<Header>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(0))?;
+
+    let actual_magic_number = reader.read_u32::<LittleEndian>()?;
+
+    if actual_magic_number == magic_number.swap_bytes() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "big-endian BBI files are not supported",
+        ));
+    } else if actual_magic_number != magic_number {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid magic number",
+        ));
+    }
+
+    let version = reader.read_u16::<LittleEndian>()?;
+    let zoom_level_count = reader.read_u16::<LittleEndian>()?;
+    let chromosome_tree_offset = reader.read_u64::<LittleEndian>()?;
+    let full_data_offset = reader.read_u64::<LittleEndian>()?;
+    let full_index_offset = reader.read_u64::<LittleEndian>()?;
+    let field_count = reader.read_u16::<LittleEndian>()?;
+    let defined_field_count = reader.read_u16::<LittleEndian>()?;
+    let auto_sql_offset = reader.read_u64::<LittleEndian>()?;
+    let total_summary_offset = reader.read_u64::<LittleEndian>()?;
+    let uncompressed_buffer_size = reader.read_u32::<LittleEndian>()?;
+    let _extension_offset = reader.read_u64::<LittleEndian>()?;
+
+    let mut zoom_levels = Vec::with_capacity(usize::from(zoom_level_count));
+
+    for _ in 0..zoom_level_count {
+        let reduction_level = reader.read_u32::<LittleEndian>()?;
+        let _reserved = reader.read_u32::<LittleEndian>()?;
+        let data_offset = reader.read_u64::<LittleEndian>()?;
+        let index_offset = reader.read_u64::<LittleEndian>()?;
+        zoom_levels.push(zoom::Level::new(reduction_level, data_offset, index_offset));
+    }
+
+    let total_summary = if total_summary_offset == 0 {
+        None
+    } else {
+        reader.seek(SeekFrom::Start(total_summary_offset))?;
+        read_summary(reader).map(Some)?
+    };
+
+    let auto_sql = if auto_sql_offset == 0 {
+        None
+    } else {
+        reader.seek(SeekFrom::Start(auto_sql_offset))?;
+        read_c_string(reader).map(Some)?
+    };
+
+    let reference_sequences = chromosome_tree::read(reader, chromosome_tree_offset)?;
+
+    Ok(Header {
+        version,
+        zoom_levels,
+        chromosome_tree_offset,
+        full_data_offset,
+        full_index_offset,
+        field_count,
+        defined_field_count,
+        auto_sql_offset,
+        total_summary_offset,
+        uncompressed_buffer_size,
+        total_summary,
+        auto_sql,
+        reference_sequences,
+    })
+}
+
+fn read_summary<R>(reader: &mut R) -> io::Result<Summary>
+where
+    R: Read,
+{
+    let bases_covered = reader.read_u64::<LittleEndian>()?;
+    let min = reader.read_f64::<LittleEndian>()?;
+    let max = reader.read_f64::<LittleEndian>()?;
+    let sum = reader.read_f64::<LittleEndian>()?;
+    let sum_squares = reader.read_f64::<LittleEndian>()?;
+    Ok(Summary::new(bases_covered, min, max, sum, sum_squares))
+}
+
+fn read_c_string<R>(reader: &mut R) -> io::Result<String>
+where
+    R: Read,
+{
+    const NUL: u8 = 0x00;
+
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_u8()? {
+            NUL => break,
+            b => buf.push(b),
+        }
+    }
+
+    String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Resolves a region to a reference sequence ID and a 0-based, half-open interval.
+pub(crate) fn resolve_region(header: &Header, region: &Region) -> io::Result<(u32, u32, u32)> {
+    let region_name = std::str::from_utf8(region.name())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let (id, _, length) = header
+        .reference_sequences()
+        .get_full(region_name)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in reference sequences: {region:?}"
+                ),
+            )
+        })?;
+
+    let id = u32::try_from(id).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let (start, end) = resolve_interval(region.interval(), *length)?;
+
+    Ok((id, start, end))
+}
+
+fn resolve_interval(interval: Interval, length: u32) -> io::Result<(u32, u32)> {
+    let to_u32 = |position: Position| {
+        u32::try_from(usize::from(position))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    };
+
+    let start = interval.start().map(to_u32).transpose()?.unwrap_or(1) - 1;
+    let end = interval.end().map(to_u32).transpose()?.unwrap_or(length);
+
+    Ok((start, end))
+}
+
+/// Returns an iterator over zoom records that intersect the given region.
+pub(crate) fn zoom_query<'r, R>(
+    reader: &'r mut R,
+    header: &Header,
+    zoom_level: &zoom::Level,
+    region: &Region,
+) -> io::Result<Query<'r, R, zoom::Record>>
+where
+    R: Read + Seek,
+{
+    let (reference_sequence_id, start, end) = resolve_region(header, region)?;
+
+    Query::new(
+        reader,
+        zoom_level.index_offset,
+        header.is_compressed(),
+        reference_sequence_id,
+        start,
+        end,
+        decode_zoom_records,
+    )
+}
+
+/// Reads and, if compressed, decompresses a data block.
+pub(crate) fn read_block<R>(
+    reader: &mut R,
+    block: &r_tree::Block,
+    is_compressed: bool,
+    dst: &mut Vec<u8>,
+) -> io::Result<()>
+where
+    R: Read + Seek,
+{
+    let size =
+        usize::try_from(block.size).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    reader.seek(SeekFrom::Start(block.offset))?;
+
+    let mut buf = vec![0; size];
+    reader.read_exact(&mut buf)?;
+
+    dst.clear();
+
+    if is_compressed {
+        let mut decoder = ZlibDecoder::new(&buf[..]);
+        decoder.read_to_end(dst)?;
+    } else {
+        *dst = buf;
+    }
+
+    Ok(())
+}
+
+// Decodes the zoom records in a block that intersect the given interval.
+fn decode_zoom_records(
+    mut src: &[u8],
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+    records: &mut Vec<zoom::Record>,
+) -> io::Result<()> {
+    while src.len() >= ZOOM_RECORD_SIZE {
+        let id = src.read_u32::<LittleEndian>()?;
+        let record_start = src.read_u32::<LittleEndian>()?;
+        let record_end = src.read_u32::<LittleEndian>()?;
+        let valid_count = src.read_u32::<LittleEndian>()?;
+        let min = src.read_f32::<LittleEndian>()?;
+        let max = src.read_f32::<LittleEndian>()?;
+        let sum = src.read_f32::<LittleEndian>()?;
+        let sum_squares = src.read_f32::<LittleEndian>()?;
+
+        if id != reference_sequence_id || record_start >= end || record_end <= start {
+            continue;
+        }
+
+        let (record_start, record_end) = to_nonempty_positions(record_start, record_end)?;
+
+        let summary = Summary::new(
+            u64::from(valid_count),
+            f64::from(min),
+            f64::from(max),
+            f64::from(sum),
+            f64::from(sum_squares),
+        );
+
+        records.push(zoom::Record::new(
+            id as usize,
+            record_start,
+            record_end,
+            summary,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns whether a 0-based, half-open item intersects a 0-based, half-open interval.
+///
+/// A zero-length item intersects when it is within the interval or at either of its edges.
+pub(crate) fn intersects(item_start: u32, item_end: u32, start: u32, end: u32) -> bool {
+    if item_start == item_end {
+        start <= item_start && item_start <= end
+    } else {
+        item_start < end && item_end > start
+    }
+}
+
+/// Converts a 0-based, half-open interval to 1-based positions.
+///
+/// The end position is `None` when the end is 0, i.e., for a zero-length interval at the start
+/// of a reference sequence.
+pub(crate) fn to_positions(start: u32, end: u32) -> io::Result<(Position, Option<Position>)> {
+    let start = Position::try_from(start as usize + 1)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let end = Position::new(end as usize);
+    Ok((start, end))
+}
+
+// Converts a 0-based, half-open interval that is not empty to 1-based positions.
+pub(crate) fn to_nonempty_positions(start: u32, end: u32) -> io::Result<(Position, Position)> {
+    match to_positions(start, end)? {
+        (start, Some(end)) if usize::from(start) <= usize::from(end) => Ok((start, end)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid interval",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(13)?;
+
+        assert_eq!(resolve_interval((start..=end).into(), 21)?, (7, 13));
+        assert_eq!(resolve_interval((start..).into(), 21)?, (7, 21));
+        assert_eq!(resolve_interval((..).into(), 21)?, (0, 21));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersects() {
+        assert!(intersects(5, 13, 8, 21));
+        assert!(!intersects(5, 8, 8, 21));
+        assert!(intersects(8, 8, 8, 21));
+        assert!(intersects(21, 21, 8, 21));
+        assert!(!intersects(5, 5, 8, 21));
+    }
+
+    #[test]
+    fn test_to_positions() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            to_positions(7, 13)?,
+            (Position::try_from(8)?, Some(Position::try_from(13)?))
+        );
+        assert_eq!(
+            to_positions(8, 8)?,
+            (Position::try_from(9)?, Some(Position::try_from(8)?))
+        );
+        assert_eq!(to_positions(0, 0)?, (Position::MIN, None));
+        Ok(())
+    }
+}
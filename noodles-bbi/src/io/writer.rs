@@ -0,0 +1,461 @@
+//! BBI writer.
+
+mod zoom;
+
+use std::{
+    io::{self, Seek, SeekFrom, Write},
+    iter, mem,
+    ops::Range,
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use flate2::{write::ZlibEncoder, Compression};
+
+use crate::{chromosome_tree, header::ReferenceSequences, r_tree, Summary};
+
+const VERSION: u16 = 4;
+const HEADER_SIZE: usize = 64;
+const ZOOM_HEADER_SIZE: usize = 24;
+const SUMMARY_SIZE: usize = 40;
+
+pub(crate) const ITEMS_PER_SLOT: usize = 1024;
+
+const MAX_ZOOM_LEVEL_COUNT: usize = 10;
+const ZOOM_INITIAL_SCALE: u64 = 10;
+const ZOOM_INCREMENT: u32 = 4;
+
+/// An uncompressed data block and its bounds.
+pub(crate) struct DataBlock<'a> {
+    pub(crate) start: (u32, u32),
+    pub(crate) end: (u32, u32),
+    pub(crate) buf: &'a [u8],
+    pub(crate) item_count: u64,
+    pub(crate) item_length: u64,
+}
+
+/// A 0-based, half-open interval with a value, used to build summaries.
+#[derive(Clone, Copy)]
+pub(crate) struct Interval {
+    pub(crate) reference_sequence_id: u32,
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+    pub(crate) value: f64,
+}
+
+/// The bigBed-specific header fields.
+pub(crate) struct Fields<'a> {
+    pub(crate) field_count: u16,
+    pub(crate) defined_field_count: u16,
+    pub(crate) auto_sql: Option<&'a str>,
+}
+
+#[derive(Clone, Copy)]
+struct Offsets {
+    base: u64,
+    chromosome_tree_offset: u64,
+    total_summary_offset: u64,
+    full_data_offset: u64,
+}
+
+enum ZoomLevels {
+    // Intervals are held until enough items are written to choose the reduction levels.
+    Pending(Vec<Interval>),
+    Building(Vec<zoom::Level>),
+}
+
+/// A BBI file writer.
+///
+/// This follows the layout written by the UCSC tools. Space for the header is reserved, and data
+/// blocks are compressed and written as they are added, keeping only their index entries.
+/// Intervals are summarized into the total summary and zoom levels as they are added. When the
+/// writer is finished, the R-tree index and zoom levels are appended, and the header is patched.
+pub(crate) struct Writer<W> {
+    inner: W,
+    magic_number: u32,
+    reference_sequences: ReferenceSequences,
+    offsets: Option<Offsets>,
+    position: u64,
+    index_blocks: Vec<r_tree::Block>,
+    uncompressed_buffer_size: usize,
+    item_count: u64,
+    item_length: u64,
+    total_summary: Summary,
+    zoom_levels: ZoomLevels,
+}
+
+impl<W> Writer<W>
+where
+    W: Write + Seek,
+{
+    pub(crate) fn new(
+        inner: W,
+        magic_number: u32,
+        reference_sequences: ReferenceSequences,
+    ) -> Self {
+        Self {
+            inner,
+            magic_number,
+            reference_sequences,
+            offsets: None,
+            position: 0,
+            index_blocks: Vec::new(),
+            uncompressed_buffer_size: 0,
+            item_count: 0,
+            item_length: 0,
+            total_summary: Summary::default(),
+            zoom_levels: ZoomLevels::Pending(Vec::new()),
+        }
+    }
+
+    pub(crate) fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub(crate) fn reference_sequences(&self) -> &ReferenceSequences {
+        &self.reference_sequences
+    }
+
+    /// Compresses and writes a data block.
+    pub(crate) fn write_block(&mut self, block: &DataBlock<'_>) -> io::Result<()> {
+        self.write_prefix()?;
+
+        let mut buf = Vec::new();
+        compress(&mut buf, block.buf)?;
+
+        let offset = self.position;
+        self.write_all(&buf)?;
+
+        self.index_blocks.push(r_tree::Block {
+            start: block.start,
+            end: block.end,
+            offset,
+            size: buf.len() as u64,
+        });
+
+        self.uncompressed_buffer_size = self.uncompressed_buffer_size.max(block.buf.len());
+        self.item_count += block.item_count;
+        self.item_length += block.item_length;
+
+        if self.item_count >= ITEMS_PER_SLOT as u64 {
+            self.build_zoom_levels()?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds an interval to the summaries.
+    ///
+    /// Intervals must be sorted and not overlap.
+    pub(crate) fn add_interval(&mut self, interval: Interval) -> io::Result<()> {
+        self.total_summary
+            .add(interval.value, u64::from(interval.end - interval.start));
+
+        match &mut self.zoom_levels {
+            ZoomLevels::Pending(intervals) => intervals.push(interval),
+            ZoomLevels::Building(levels) => {
+                for level in levels {
+                    level.add(&self.reference_sequences, &interval)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the index and zoom levels, patches the header, and returns the underlying writer.
+    pub(crate) fn finish(mut self, data_count: u64, fields: &Fields<'_>) -> io::Result<W> {
+        let offsets = self.write_prefix()?;
+        self.build_zoom_levels()?;
+
+        let full_index_offset = self.position;
+        let mut buf = Vec::new();
+        r_tree::write(
+            &mut buf,
+            full_index_offset,
+            &self.index_blocks,
+            ITEMS_PER_SLOT as u32,
+        )?;
+        self.write_all(&buf)?;
+
+        let levels = match mem::replace(&mut self.zoom_levels, ZoomLevels::Building(Vec::new())) {
+            ZoomLevels::Pending(_) => Vec::new(),
+            ZoomLevels::Building(levels) => levels,
+        };
+
+        let mut zoom_headers = Vec::new();
+
+        for level in select_zoom_levels(levels, self.item_count)? {
+            let data_offset = self.position;
+            let (index_offset, end_offset) = level.write(&mut self.inner, data_offset)?;
+            self.position = end_offset;
+
+            self.uncompressed_buffer_size = self
+                .uncompressed_buffer_size
+                .max(level.uncompressed_buffer_size());
+
+            zoom_headers.push((level.reduction_level(), data_offset, index_offset));
+        }
+
+        let auto_sql_offset = if let Some(auto_sql) = fields.auto_sql {
+            let offset = self.position;
+            self.write_all(auto_sql.as_bytes())?;
+            self.write_all(&[0x00])?;
+            offset
+        } else {
+            0
+        };
+
+        let invalid_input = |e| io::Error::new(io::ErrorKind::InvalidInput, e);
+
+        let mut header = Vec::with_capacity(HEADER_SIZE + ZOOM_HEADER_SIZE * zoom_headers.len());
+        header.write_u32::<LittleEndian>(self.magic_number)?;
+        header.write_u16::<LittleEndian>(VERSION)?;
+        header
+            .write_u16::<LittleEndian>(u16::try_from(zoom_headers.len()).map_err(invalid_input)?)?;
+        header.write_u64::<LittleEndian>(offsets.chromosome_tree_offset)?;
+        header.write_u64::<LittleEndian>(offsets.full_data_offset)?;
+        header.write_u64::<LittleEndian>(full_index_offset)?;
+        header.write_u16::<LittleEndian>(fields.field_count)?;
+        header.write_u16::<LittleEndian>(fields.defined_field_count)?;
+        header.write_u64::<LittleEndian>(auto_sql_offset)?;
+        header.write_u64::<LittleEndian>(offsets.total_summary_offset)?;
+        header.write_u32::<LittleEndian>(
+            u32::try_from(self.uncompressed_buffer_size).map_err(invalid_input)?,
+        )?;
+        header.write_u64::<LittleEndian>(0)?; // extension offset
+
+        for (reduction_level, data_offset, index_offset) in zoom_headers {
+            header.write_u32::<LittleEndian>(reduction_level)?;
+            header.write_u32::<LittleEndian>(0)?; // reserved
+            header.write_u64::<LittleEndian>(data_offset)?;
+            header.write_u64::<LittleEndian>(index_offset)?;
+        }
+
+        self.inner.seek(SeekFrom::Start(offsets.base))?;
+        self.inner.write_all(&header)?;
+
+        self.inner
+            .seek(SeekFrom::Start(offsets.base + offsets.total_summary_offset))?;
+        write_summary(&mut self.inner, &self.total_summary)?;
+
+        self.inner
+            .seek(SeekFrom::Start(offsets.base + offsets.full_data_offset))?;
+        self.inner.write_u64::<LittleEndian>(data_count)?;
+
+        self.inner
+            .seek(SeekFrom::Start(offsets.base + self.position))?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+
+    // Reserves space for the header, zoom headers, and total summary and writes the chromosome
+    // B+ tree.
+    fn write_prefix(&mut self) -> io::Result<Offsets> {
+        if let Some(offsets) = self.offsets {
+            return Ok(offsets);
+        }
+
+        let base = self.inner.stream_position()?;
+
+        let mut dst = vec![0; HEADER_SIZE + ZOOM_HEADER_SIZE * MAX_ZOOM_LEVEL_COUNT];
+
+        let total_summary_offset = dst.len() as u64;
+        dst.resize(dst.len() + SUMMARY_SIZE, 0);
+
+        let chromosome_tree_offset = dst.len() as u64;
+        chromosome_tree::write(&mut dst, &self.reference_sequences)?;
+
+        let full_data_offset = dst.len() as u64;
+        dst.write_u64::<LittleEndian>(0)?; // data count
+
+        self.inner.write_all(&dst)?;
+        self.position = dst.len() as u64;
+
+        let offsets = Offsets {
+            base,
+            chromosome_tree_offset,
+            total_summary_offset,
+            full_data_offset,
+        };
+
+        self.offsets = Some(offsets);
+
+        Ok(offsets)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.inner.write_all(buf)?;
+        self.position += buf.len() as u64;
+        Ok(())
+    }
+
+    // Each zoom level is 4x coarser than the previous, starting at 10x the mean item length.
+    fn build_zoom_levels(&mut self) -> io::Result<()> {
+        let ZoomLevels::Pending(intervals) = &mut self.zoom_levels else {
+            return Ok(());
+        };
+
+        let intervals = mem::take(intervals);
+
+        let mut levels: Vec<_> = match self.item_length.checked_div(self.item_count) {
+            Some(mean_length) => {
+                let initial_reduction_level =
+                    (mean_length * ZOOM_INITIAL_SCALE).clamp(1, u64::from(u32::MAX)) as u32;
+
+                iter::successors(Some(initial_reduction_level), |n| {
+                    n.checked_mul(ZOOM_INCREMENT)
+                })
+                .take(MAX_ZOOM_LEVEL_COUNT)
+                .map(zoom::Level::new)
+                .collect()
+            }
+            None => Vec::new(),
+        };
+
+        for interval in &intervals {
+            for level in &mut levels {
+                level.add(&self.reference_sequences, interval)?;
+            }
+        }
+
+        self.zoom_levels = ZoomLevels::Building(levels);
+
+        Ok(())
+    }
+}
+
+// Levels stop being added when they no longer halve the number of records.
+fn select_zoom_levels(levels: Vec<zoom::Level>, item_count: u64) -> io::Result<Vec<zoom::Level>> {
+    let mut selected_levels = Vec::new();
+    let mut previous_count = item_count;
+
+    for mut level in levels {
+        level.finish()?;
+
+        let record_count = level.record_count();
+
+        if record_count == 0 || record_count * 2 > previous_count {
+            break;
+        }
+
+        previous_count = record_count;
+        selected_levels.push(level);
+    }
+
+    Ok(selected_levels)
+}
+
+fn write_summary<W>(writer: &mut W, summary: &Summary) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u64::<LittleEndian>(summary.bases_covered())?;
+    writer.write_f64::<LittleEndian>(summary.min())?;
+    writer.write_f64::<LittleEndian>(summary.max())?;
+    writer.write_f64::<LittleEndian>(summary.sum())?;
+    writer.write_f64::<LittleEndian>(summary.sum_squares())?;
+    Ok(())
+}
+
+fn compress(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let mut encoder = ZlibEncoder::new(dst, Compression::default());
+    encoder.write_all(src)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+/// Partitions items into tree nodes.
+///
+/// The first level groups items into leaf nodes of at most `block_size` items. Each subsequent
+/// level groups the nodes of the previous level, until a single root node remains.
+pub(crate) fn build_tree_levels(item_count: usize, block_size: usize) -> Vec<Vec<Range<usize>>> {
+    let chunk = |n: usize| -> Vec<Range<usize>> {
+        (0..n)
+            .step_by(block_size)
+            .map(|start| start..(start + block_size).min(n))
+            .collect()
+    };
+
+    if item_count == 0 {
+        return vec![vec![0..0]];
+    }
+
+    let mut levels = vec![chunk(item_count)];
+
+    while let Some(level) = levels.last() {
+        if level.len() <= 1 {
+            break;
+        }
+
+        let next_level = chunk(level.len());
+        levels.push(next_level);
+    }
+
+    levels
+}
+
+/// Computes the offset of each node when levels are written from the root down.
+pub(crate) fn build_node_offsets<F>(
+    levels: &[Vec<Range<usize>>],
+    start_offset: u64,
+    node_size: F,
+) -> Vec<Vec<u64>>
+where
+    F: Fn(usize, &Range<usize>) -> u64,
+{
+    let mut node_offsets = vec![Vec::new(); levels.len()];
+    let mut offset = start_offset;
+
+    for (i, level) in levels.iter().enumerate().rev() {
+        for range in level {
+            node_offsets[i].push(offset);
+            offset += node_size(i, range);
+        }
+    }
+
+    node_offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_build_tree_levels() {
+        assert_eq!(build_tree_levels(0, 4), [vec![0..0]]);
+        assert_eq!(build_tree_levels(3, 4), [vec![0..3]]);
+        assert_eq!(
+            build_tree_levels(9, 4),
+            [vec![0..4, 4..8, 8..9], vec![0..3]]
+        );
+        assert_eq!(
+            build_tree_levels(17, 2),
+            [
+                vec![
+                    0..2,
+                    2..4,
+                    4..6,
+                    6..8,
+                    8..10,
+                    10..12,
+                    12..14,
+                    14..16,
+                    16..17
+                ],
+                vec![0..2, 2..4, 4..6, 6..8, 8..9],
+                vec![0..2, 2..4, 4..5],
+                vec![0..2, 2..3],
+                vec![0..2],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_node_offsets() {
+        let levels = build_tree_levels(9, 4);
+        let node_offsets = build_node_offsets(&levels, 8, |_, range| range.len() as u64);
+        assert_eq!(node_offsets, [vec![11, 15, 19], vec![8]]);
+    }
+}
@@ -0,0 +1,269 @@
+//! BBI zoom level building.
+
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::{compress, Interval, ITEMS_PER_SLOT};
+use crate::{header::ReferenceSequences, r_tree, Summary};
+
+const RECORD_SIZE: usize = 32;
+
+struct Record {
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+    summary: Summary,
+}
+
+/// A zoom level that is built as intervals are added.
+///
+/// Records of completed bins are encoded into compressed blocks. Block offsets are relative to
+/// the start of the block data.
+pub(super) struct Level {
+    reduction_level: u32,
+    record: Option<Record>,
+    records: Vec<Record>,
+    record_count: u64,
+    buf: Vec<u8>,
+    index_blocks: Vec<r_tree::Block>,
+    uncompressed_buffer_size: usize,
+}
+
+impl Level {
+    pub(super) fn new(reduction_level: u32) -> Self {
+        Self {
+            reduction_level,
+            record: None,
+            records: Vec::new(),
+            record_count: 0,
+            buf: Vec::new(),
+            index_blocks: Vec::new(),
+            uncompressed_buffer_size: 0,
+        }
+    }
+
+    pub(super) fn reduction_level(&self) -> u32 {
+        self.reduction_level
+    }
+
+    pub(super) fn record_count(&self) -> u64 {
+        self.record_count
+    }
+
+    pub(super) fn uncompressed_buffer_size(&self) -> usize {
+        self.uncompressed_buffer_size
+    }
+
+    /// Adds an interval.
+    ///
+    /// Intervals must be sorted and not overlap.
+    pub(super) fn add(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+        interval: &Interval,
+    ) -> io::Result<()> {
+        let mut bin_start = interval.start - interval.start % self.reduction_level;
+
+        while bin_start < interval.end {
+            let bin_end = bin_start.saturating_add(self.reduction_level);
+
+            let is_open = self.record.as_ref().is_some_and(|record| {
+                (record.reference_sequence_id, record.start)
+                    == (interval.reference_sequence_id, bin_start)
+            });
+
+            if !is_open {
+                self.close_bin()?;
+
+                let length = reference_sequences
+                    .get_index(interval.reference_sequence_id as usize)
+                    .map(|(_, length)| *length)
+                    .unwrap_or(u32::MAX);
+
+                self.record = Some(Record {
+                    reference_sequence_id: interval.reference_sequence_id,
+                    start: bin_start,
+                    end: bin_end.min(length),
+                    summary: Summary::default(),
+                });
+            }
+
+            if let Some(record) = self.record.as_mut() {
+                let start = interval.start.max(bin_start);
+                let end = interval.end.min(bin_end);
+                record.summary.add(interval.value, u64::from(end - start));
+            }
+
+            bin_start = bin_end;
+        }
+
+        Ok(())
+    }
+
+    /// Encodes the remaining records.
+    pub(super) fn finish(&mut self) -> io::Result<()> {
+        self.close_bin()?;
+        self.flush_block()
+    }
+
+    /// Writes the record count, blocks, and R-tree index of this zoom level at the given offset.
+    ///
+    /// This returns the offset of the index and the offset after the index.
+    pub(super) fn write<W>(&self, writer: &mut W, offset: u64) -> io::Result<(u64, u64)>
+    where
+        W: Write,
+    {
+        let record_count = u32::try_from(self.record_count)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        writer.write_u32::<LittleEndian>(record_count)?;
+
+        writer.write_all(&self.buf)?;
+
+        let data_offset = offset + 4;
+
+        let index_blocks: Vec<_> = self
+            .index_blocks
+            .iter()
+            .map(|block| r_tree::Block {
+                offset: data_offset + block.offset,
+                ..*block
+            })
+            .collect();
+
+        let index_offset = data_offset + self.buf.len() as u64;
+
+        let mut buf = Vec::new();
+        r_tree::write(&mut buf, index_offset, &index_blocks, ITEMS_PER_SLOT as u32)?;
+        writer.write_all(&buf)?;
+
+        Ok((index_offset, index_offset + buf.len() as u64))
+    }
+
+    fn close_bin(&mut self) -> io::Result<()> {
+        let Some(record) = self.record.take() else {
+            return Ok(());
+        };
+
+        // Blocks hold records of a single reference sequence.
+        let is_new_reference_sequence = self
+            .records
+            .first()
+            .is_some_and(|first| first.reference_sequence_id != record.reference_sequence_id);
+
+        if is_new_reference_sequence || self.records.len() >= ITEMS_PER_SLOT {
+            self.flush_block()?;
+        }
+
+        self.records.push(record);
+        self.record_count += 1;
+
+        Ok(())
+    }
+
+    fn flush_block(&mut self) -> io::Result<()> {
+        let (Some(first), Some(last)) = (self.records.first(), self.records.last()) else {
+            return Ok(());
+        };
+
+        let mut buf = Vec::with_capacity(RECORD_SIZE * self.records.len());
+
+        for record in &self.records {
+            let valid_count = u32::try_from(record.summary.bases_covered())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            buf.write_u32::<LittleEndian>(record.reference_sequence_id)?;
+            buf.write_u32::<LittleEndian>(record.start)?;
+            buf.write_u32::<LittleEndian>(record.end)?;
+            buf.write_u32::<LittleEndian>(valid_count)?;
+            buf.write_f32::<LittleEndian>(record.summary.min() as f32)?;
+            buf.write_f32::<LittleEndian>(record.summary.max() as f32)?;
+            buf.write_f32::<LittleEndian>(record.summary.sum() as f32)?;
+            buf.write_f32::<LittleEndian>(record.summary.sum_squares() as f32)?;
+        }
+
+        let offset = self.buf.len() as u64;
+        compress(&mut self.buf, &buf)?;
+
+        self.index_blocks.push(r_tree::Block {
+            start: (first.reference_sequence_id, first.start),
+            end: (last.reference_sequence_id, last.end),
+            offset,
+            size: self.buf.len() as u64 - offset,
+        });
+
+        self.uncompressed_buffer_size = self.uncompressed_buffer_size.max(buf.len());
+        self.records.clear();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() -> io::Result<()> {
+        let reference_sequences: ReferenceSequences =
+            [(String::from("sq0"), 25), (String::from("sq1"), 8)]
+                .into_iter()
+                .collect();
+
+        let intervals = [
+            Interval {
+                reference_sequence_id: 0,
+                start: 2,
+                end: 8,
+                value: 1.0,
+            },
+            Interval {
+                reference_sequence_id: 0,
+                start: 8,
+                end: 23,
+                value: 2.0,
+            },
+            Interval {
+                reference_sequence_id: 1,
+                start: 0,
+                end: 5,
+                value: 3.0,
+            },
+        ];
+
+        let mut level = Level::new(10);
+
+        for interval in &intervals[..2] {
+            level.add(&reference_sequences, interval)?;
+        }
+
+        level.close_bin()?;
+
+        let actual: Vec<_> = level
+            .records
+            .iter()
+            .map(|record| {
+                (
+                    record.start,
+                    record.end,
+                    record.summary.bases_covered(),
+                    record.summary.sum(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            actual,
+            [(0, 10, 8, 10.0), (10, 20, 10, 20.0), (20, 25, 3, 6.0)]
+        );
+
+        level.add(&reference_sequences, &intervals[2])?;
+        level.finish()?;
+
+        assert_eq!(level.record_count(), 4);
+        assert_eq!(level.index_blocks.len(), 2);
+        assert_eq!(level.uncompressed_buffer_size(), 3 * RECORD_SIZE);
+
+        Ok(())
+    }
+}
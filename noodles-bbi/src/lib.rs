@@ -0,0 +1,19 @@
+#![warn(missing_docs)]
+
+//! **noodles-bbi** handles the reading and writing of the UCSC big binary indexed (BBI) formats,
+//! bigWig and bigBed.
+//!
+//! Both formats share a common layout: a header, a chromosome B+ tree that maps reference
+//! sequence names to IDs and lengths, (compressed) data blocks indexed by an R-tree, and zoom
+//! levels of summarized data, each with their own R-tree index.
+
+pub mod bigbed;
+pub mod bigwig;
+mod chromosome_tree;
+pub mod header;
+pub mod io;
+mod r_tree;
+pub mod summary;
+pub mod zoom;
+
+pub use self::{header::Header, summary::Summary};
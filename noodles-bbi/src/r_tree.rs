@@ -0,0 +1,294 @@
+//! BBI R-tree index.
+
+use std::{
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::Range,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::io::writer::{build_node_offsets, build_tree_levels};
+
+const MAGIC_NUMBER: u32 = 0x2468ace0;
+const HEADER_SIZE: u64 = 48;
+const BLOCK_SIZE: usize = 256;
+
+// A pair of (start, end) bounds, each as (reference sequence ID, position).
+type Bounds = ((u32, u32), (u32, u32));
+
+/// An indexed data block.
+///
+/// Bounds are 0-based pairs of (reference sequence ID, position), with an exclusive end.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Block {
+    pub(crate) start: (u32, u32),
+    pub(crate) end: (u32, u32),
+    pub(crate) offset: u64,
+    pub(crate) size: u64,
+}
+
+impl Block {
+    fn intersects(&self, reference_sequence_id: u32, start: u32, end: u32) -> bool {
+        intersects(self.start, self.end, reference_sequence_id, start, end)
+    }
+}
+
+// Bounds are compared inclusively so that zero-length items at the interval edges are found.
+fn intersects(
+    block_start: (u32, u32),
+    block_end: (u32, u32),
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+) -> bool {
+    block_start <= (reference_sequence_id, end) && block_end >= (reference_sequence_id, start)
+}
+
+/// Returns the data blocks that intersect the given interval.
+pub(crate) fn query<R>(
+    reader: &mut R,
+    index_offset: u64,
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+) -> io::Result<Vec<Block>>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(index_offset))?;
+
+    if reader.read_u32::<LittleEndian>()? != MAGIC_NUMBER {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid R-tree index magic number",
+        ));
+    }
+
+    let mut blocks = Vec::new();
+
+    query_node(
+        reader,
+        index_offset + HEADER_SIZE,
+        reference_sequence_id,
+        start,
+        end,
+        &mut blocks,
+    )?;
+
+    Ok(blocks)
+}
+
+fn query_node<R>(
+    reader: &mut R,
+    offset: u64,
+    reference_sequence_id: u32,
+    start: u32,
+    end: u32,
+    blocks: &mut Vec<Block>,
+) -> io::Result<()>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(offset))?;
+
+    let is_leaf = reader.read_u8()? != 0;
+    let _reserved = reader.read_u8()?;
+    let count = reader.read_u16::<LittleEndian>()?;
+
+    if is_leaf {
+        for _ in 0..count {
+            let block = Block {
+                start: read_bound(reader)?,
+                end: read_bound(reader)?,
+                offset: reader.read_u64::<LittleEndian>()?,
+                size: reader.read_u64::<LittleEndian>()?,
+            };
+
+            if block.intersects(reference_sequence_id, start, end) {
+                blocks.push(block);
+            }
+        }
+    } else {
+        let mut child_offsets = Vec::new();
+
+        for _ in 0..count {
+            let node_start = read_bound(reader)?;
+            let node_end = read_bound(reader)?;
+            let child_offset = reader.read_u64::<LittleEndian>()?;
+
+            if intersects(node_start, node_end, reference_sequence_id, start, end) {
+                child_offsets.push(child_offset);
+            }
+        }
+
+        for child_offset in child_offsets {
+            query_node(
+                reader,
+                child_offset,
+                reference_sequence_id,
+                start,
+                end,
+                blocks,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_bound<R>(reader: &mut R) -> io::Result<(u32, u32)>
+where
+    R: Read,
+{
+    let reference_sequence_id = reader.read_u32::<LittleEndian>()?;
+    let position = reader.read_u32::<LittleEndian>()?;
+    Ok((reference_sequence_id, position))
+}
+
+/// Writes an R-tree index of the given blocks at the given offset.
+///
+/// The blocks are expected to be sorted and directly precede the index.
+pub(crate) fn write<W>(
+    writer: &mut W,
+    offset: u64,
+    blocks: &[Block],
+    items_per_slot: u32,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    const NODE_HEADER_SIZE: u64 = 4;
+    const LEAF_ITEM_SIZE: u64 = 32;
+    const NON_LEAF_ITEM_SIZE: u64 = 24;
+
+    let levels = build_tree_levels(blocks.len(), BLOCK_SIZE);
+
+    let (start, end) = merge_bounds(blocks.iter().map(|b| (b.start, b.end)));
+
+    let mut dst = Vec::new();
+
+    dst.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+    dst.write_u32::<LittleEndian>(BLOCK_SIZE as u32)?;
+    dst.write_u64::<LittleEndian>(blocks.len() as u64)?;
+    write_bound(&mut dst, start)?;
+    write_bound(&mut dst, end)?;
+    dst.write_u64::<LittleEndian>(offset)?; // end file offset
+    dst.write_u32::<LittleEndian>(items_per_slot)?;
+    dst.write_u32::<LittleEndian>(0)?; // reserved
+
+    // Each level holds the bounds of its nodes.
+    let mut bounds: Vec<Vec<Bounds>> = Vec::with_capacity(levels.len());
+
+    for (i, level) in levels.iter().enumerate() {
+        let level_bounds = level
+            .iter()
+            .map(|range| {
+                if i == 0 {
+                    merge_bounds(blocks[range.clone()].iter().map(|b| (b.start, b.end)))
+                } else {
+                    merge_bounds(bounds[i - 1][range.clone()].iter().copied())
+                }
+            })
+            .collect();
+
+        bounds.push(level_bounds);
+    }
+
+    let node_size = |i: usize, range: &Range<usize>| {
+        let item_size = if i == 0 {
+            LEAF_ITEM_SIZE
+        } else {
+            NON_LEAF_ITEM_SIZE
+        };
+
+        NODE_HEADER_SIZE + item_size * range.len() as u64
+    };
+
+    let node_offsets = build_node_offsets(&levels, offset + HEADER_SIZE, node_size);
+
+    for (i, level) in levels.iter().enumerate().rev() {
+        let is_leaf = i == 0;
+
+        for range in level {
+            let count = u16::try_from(range.len())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            dst.write_u8(u8::from(is_leaf))?;
+            dst.write_u8(0)?; // reserved
+            dst.write_u16::<LittleEndian>(count)?;
+
+            if is_leaf {
+                for block in &blocks[range.clone()] {
+                    write_bound(&mut dst, block.start)?;
+                    write_bound(&mut dst, block.end)?;
+                    dst.write_u64::<LittleEndian>(block.offset)?;
+                    dst.write_u64::<LittleEndian>(block.size)?;
+                }
+            } else {
+                for j in range.clone() {
+                    let (child_start, child_end) = bounds[i - 1][j];
+                    write_bound(&mut dst, child_start)?;
+                    write_bound(&mut dst, child_end)?;
+                    dst.write_u64::<LittleEndian>(node_offsets[i - 1][j])?;
+                }
+            }
+        }
+    }
+
+    writer.write_all(&dst)
+}
+
+fn merge_bounds<I>(mut bounds: I) -> Bounds
+where
+    I: Iterator<Item = Bounds>,
+{
+    let Some(first) = bounds.next() else {
+        return ((0, 0), (0, 0));
+    };
+
+    bounds.fold(first, |(start, end), (s, e)| (start.min(s), end.max(e)))
+}
+
+fn write_bound(dst: &mut Vec<u8>, (reference_sequence_id, position): (u32, u32)) -> io::Result<()> {
+    dst.write_u32::<LittleEndian>(reference_sequence_id)?;
+    dst.write_u32::<LittleEndian>(position)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_write_and_query() -> io::Result<()> {
+        let blocks: Vec<_> = (0..1000)
+            .map(|i| Block {
+                start: (i / 500, (i % 500) * 10),
+                end: (i / 500, (i % 500) * 10 + 10),
+                offset: u64::from(i),
+                size: 1,
+            })
+            .collect();
+
+        let mut buf = vec![0; 1000];
+        write(&mut buf, 1000, &blocks, 1)?;
+
+        let mut reader = Cursor::new(buf);
+
+        let actual = query(&mut reader, 1000, 1, 15, 31)?;
+        assert_eq!(actual, &blocks[501..504]);
+
+        let actual = query(&mut reader, 1000, 1, 16, 30)?;
+        assert_eq!(actual, &blocks[501..504]);
+
+        let actual = query(&mut reader, 1000, 0, 4995, 8000)?;
+        assert_eq!(actual, &blocks[499..500]);
+
+        let actual = query(&mut reader, 1000, 2, 0, 8)?;
+        assert!(actual.is_empty());
+
+        Ok(())
+    }
+}
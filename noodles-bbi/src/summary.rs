@@ -0,0 +1,186 @@
+//! BBI summary statistics.
+
+/// BBI summary statistics.
+///
+/// Statistics are weighted by the number of bases covered by each value.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+    bases_covered: u64,
+    min: f64,
+    max: f64,
+    sum: f64,
+    sum_squares: f64,
+}
+
+impl Summary {
+    /// Creates summary statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// ```
+    pub fn new(bases_covered: u64, min: f64, max: f64, sum: f64, sum_squares: f64) -> Self {
+        Self {
+            bases_covered,
+            min,
+            max,
+            sum,
+            sum_squares,
+        }
+    }
+
+    /// Returns the number of bases covered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// assert_eq!(summary.bases_covered(), 8);
+    /// ```
+    pub fn bases_covered(&self) -> u64 {
+        self.bases_covered
+    }
+
+    /// Returns the minimum value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// assert_eq!(summary.min(), 0.0);
+    /// ```
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Returns the maximum value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// assert_eq!(summary.max(), 2.0);
+    /// ```
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns the sum of values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// assert_eq!(summary.sum(), 8.0);
+    /// ```
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Returns the sum of squared values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// assert_eq!(summary.sum_squares(), 13.0);
+    /// ```
+    pub fn sum_squares(&self) -> f64 {
+        self.sum_squares
+    }
+
+    /// Returns the mean value.
+    ///
+    /// This returns `None` if no bases are covered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// assert_eq!(summary.mean(), Some(1.0));
+    /// ```
+    pub fn mean(&self) -> Option<f64> {
+        if self.bases_covered == 0 {
+            None
+        } else {
+            Some(self.sum / self.bases_covered as f64)
+        }
+    }
+
+    /// Returns the sample standard deviation.
+    ///
+    /// This returns `None` if fewer than two bases are covered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::Summary;
+    /// let summary = Summary::new(8, 0.0, 2.0, 8.0, 13.0);
+    /// assert_eq!(summary.std_dev(), Some((5.0f64 / 7.0).sqrt()));
+    /// ```
+    pub fn std_dev(&self) -> Option<f64> {
+        if self.bases_covered < 2 {
+            return None;
+        }
+
+        let n = self.bases_covered as f64;
+        let variance = (self.sum_squares - self.sum * self.sum / n) / (n - 1.0);
+
+        Some(variance.max(0.0).sqrt())
+    }
+
+    pub(crate) fn add(&mut self, value: f64, bases: u64) {
+        if bases == 0 {
+            return;
+        }
+
+        self.merge(&Self::new(
+            bases,
+            value,
+            value,
+            value * bases as f64,
+            value * value * bases as f64,
+        ));
+    }
+
+    pub(crate) fn merge(&mut self, other: &Self) {
+        if other.bases_covered == 0 {
+            return;
+        }
+
+        if self.bases_covered == 0 {
+            *self = *other;
+            return;
+        }
+
+        self.bases_covered += other.bases_covered;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.sum_squares += other.sum_squares;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        let mut summary = Summary::default();
+
+        summary.add(2.0, 3);
+        summary.add(0.5, 2);
+        summary.add(8.0, 0);
+
+        assert_eq!(summary, Summary::new(5, 0.5, 2.0, 7.0, 12.5));
+    }
+}
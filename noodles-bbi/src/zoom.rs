@@ -0,0 +1,95 @@
+//! BBI zoom levels and records.
+
+use noodles_core::Position;
+
+use crate::Summary;
+
+/// A BBI zoom level.
+///
+/// A zoom level holds summaries of the data over fixed-size bins.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Level {
+    reduction_level: u32,
+    pub(crate) data_offset: u64,
+    pub(crate) index_offset: u64,
+}
+
+impl Level {
+    pub(crate) fn new(reduction_level: u32, data_offset: u64, index_offset: u64) -> Self {
+        Self {
+            reduction_level,
+            data_offset,
+            index_offset,
+        }
+    }
+
+    /// Returns the reduction level.
+    ///
+    /// This is the bin size, in bases, of the summaries in this zoom level.
+    pub fn reduction_level(&self) -> u32 {
+        self.reduction_level
+    }
+}
+
+/// A BBI zoom record.
+///
+/// This is a summary of the data in a bin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    reference_sequence_id: usize,
+    start: Position,
+    end: Position,
+    summary: Summary,
+}
+
+impl Record {
+    /// Creates a zoom record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bbi::{zoom, Summary};
+    /// use noodles_core::Position;
+    ///
+    /// let record = zoom::Record::new(
+    ///     0,
+    ///     Position::MIN,
+    ///     Position::try_from(8)?,
+    ///     Summary::default(),
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new(
+        reference_sequence_id: usize,
+        start: Position,
+        end: Position,
+        summary: Summary,
+    ) -> Self {
+        Self {
+            reference_sequence_id,
+            start,
+            end,
+            summary,
+        }
+    }
+
+    /// Returns the reference sequence ID.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the start position.
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    /// Returns the end position.
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Returns the summary statistics of the bin.
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+}
@@ -0,0 +1,70 @@
+//! Prints the records in a BED3+ file that intersect the records in another BED3+ file.
+//!
+//! The genome file lists the reference sequence names and lengths in the sort order of the BED
+//! files, one per line, e.g., `sq0\t13`.
+//!
+//! The results match the output of `bedtools intersect -u -sorted -g <genome> -a <src> -b <dst>`.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+use noodles_bed as bed;
+use noodles_core::ops::{self, ReferenceSequences};
+
+fn read_genome(src: &str) -> io::Result<ReferenceSequences> {
+    let reader = File::open(src).map(BufReader::new)?;
+    let mut reference_sequences = ReferenceSequences::default();
+
+    for result in reader.lines() {
+        let line = result?;
+
+        let (name, length) = line
+            .split_once('\t')
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid genome line"))?;
+
+        let length = length
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        reference_sequences.extend([(name, length)]);
+    }
+
+    Ok(reference_sequences)
+}
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let genome_src = args.next().expect("missing genome");
+    let a_src = args.next().expect("missing a");
+    let b_src = args.next().expect("missing b");
+
+    let reference_sequences = read_genome(&genome_src)?;
+
+    let mut a_reader = File::open(a_src)
+        .map(BufReader::new)
+        .map(bed::io::Reader::new)?;
+
+    let mut b_reader = File::open(b_src)
+        .map(BufReader::new)
+        .map(bed::io::Reader::new)?;
+
+    let mut writer = bed::io::Writer::new(io::stdout().lock());
+
+    for result in ops::intersect(
+        &reference_sequences,
+        a_reader.records::<3>(),
+        b_reader.records::<3>(),
+    ) {
+        let (record, hits) = result?;
+
+        if !hits.is_empty() {
+            writer.write_record(&record)?;
+        }
+    }
+
+    Ok(())
+}
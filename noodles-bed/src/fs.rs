@@ -0,0 +1,5 @@
+//! BED filesystem operations.
+
+mod index;
+
+pub use self::index::index;
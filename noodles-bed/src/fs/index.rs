@@ -0,0 +1,123 @@
+use std::{
+    fs::File,
+    io::{self, BufRead},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, binning_index::index::reference_sequence::bin::Chunk};
+use noodles_tabix as tabix;
+
+use crate::{header::is_header_line, Record};
+
+/// Indexes a bgzipped-compressed BED file.
+///
+/// The records are expected to be coordinate-sorted. Comment (`#`), browser, and track lines are
+/// skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_bed as bed;
+/// let index = bed::fs::index("sample.bed.gz")?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<tabix::Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(bgzf::Reader::new)?;
+    index_inner(&mut reader)
+}
+
+fn index_inner<R>(reader: &mut bgzf::Reader<R>) -> io::Result<tabix::Index>
+where
+    R: io::Read,
+{
+    let mut indexer = tabix::index::Indexer::default();
+    indexer.set_header(csi::binning_index::index::header::Builder::bed().build());
+
+    let mut buf = String::new();
+    let mut start_position = reader.virtual_position();
+
+    loop {
+        buf.clear();
+
+        if reader.read_line(&mut buf)? == 0 {
+            break;
+        }
+
+        let end_position = reader.virtual_position();
+
+        let line = buf.trim_end_matches(['\n', '\r']);
+
+        if !is_header_line(line) {
+            let record: Record<3> = line
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            let chunk = Chunk::new(start_position, end_position);
+
+            indexer.add_record(
+                record.reference_sequence_name(),
+                record.start_position(),
+                record.end_position(),
+                chunk,
+            )?;
+        }
+
+        start_position = end_position;
+    }
+
+    Ok(indexer.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_csi::BinningIndex;
+
+    use super::*;
+
+    #[test]
+    fn test_index_inner() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(b"# noodles\nsq0\t7\t13\nsq0\t20\t34\nsq1\t0\t5\n")?;
+        let data = writer.finish()?;
+
+        let mut reader = bgzf::Reader::new(&data[..]);
+        let index = index_inner(&mut reader)?;
+
+        let header = index.header().expect("missing header");
+        let names: Vec<_> = header
+            .reference_sequence_names()
+            .iter()
+            .map(|name| name.as_str())
+            .collect();
+        assert_eq!(names, ["sq0", "sq1"]);
+
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(13)?;
+        assert!(!index.query(0, (start..=end).into())?.is_empty());
+
+        let mut reader = crate::io::IndexedReader::new(io::Cursor::new(data), index);
+        let region = "sq0:10-25".parse()?;
+
+        let records: Vec<Record<3>> = reader.query(&region)?.collect::<io::Result<_>>()?;
+        let positions: Vec<_> = records
+            .iter()
+            .map(|record| (record.start_position(), record.end_position()))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                (start, end),
+                (Position::try_from(21)?, Position::try_from(34)?)
+            ]
+        );
+
+        Ok(())
+    }
+}
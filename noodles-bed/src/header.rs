@@ -0,0 +1,120 @@
+//! BED header.
+
+pub mod track;
+
+pub use self::track::Track;
+
+const BROWSER_PREFIX: &str = "browser";
+pub(crate) const COMMENT_PREFIX: char = '#';
+
+/// A BED header.
+///
+/// A BED header is the optional set of `browser` and `track` lines that precede the records.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Header {
+    browser_lines: Vec<String>,
+    track: Option<Track>,
+}
+
+impl Header {
+    /// Returns the browser lines.
+    ///
+    /// Each line is the raw value after the `browser` prefix, e.g., `position sq0:8-13`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let header = bed::Header::default();
+    /// assert!(header.browser_lines().is_empty());
+    /// ```
+    pub fn browser_lines(&self) -> &[String] {
+        &self.browser_lines
+    }
+
+    /// Returns a mutable reference to the browser lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    ///
+    /// let mut header = bed::Header::default();
+    /// header.browser_lines_mut().push(String::from("position sq0:8-13"));
+    ///
+    /// assert_eq!(header.browser_lines(), [String::from("position sq0:8-13")]);
+    /// ```
+    pub fn browser_lines_mut(&mut self) -> &mut Vec<String> {
+        &mut self.browser_lines
+    }
+
+    /// Returns the track definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let header = bed::Header::default();
+    /// assert!(header.track().is_none());
+    /// ```
+    pub fn track(&self) -> Option<&Track> {
+        self.track.as_ref()
+    }
+
+    /// Returns a mutable reference to the track definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, header::Track};
+    ///
+    /// let mut header = bed::Header::default();
+    /// *header.track_mut() = Some(Track::default());
+    ///
+    /// assert!(header.track().is_some());
+    /// ```
+    pub fn track_mut(&mut self) -> &mut Option<Track> {
+        &mut self.track
+    }
+}
+
+/// Returns the value of a browser line, if the given line is one.
+pub(crate) fn parse_browser_line(s: &str) -> Option<&str> {
+    strip_keyword(s, BROWSER_PREFIX)
+}
+
+// Returns whether the given line is a comment, browser, or track line.
+pub(crate) fn is_header_line(s: &str) -> bool {
+    s.starts_with(COMMENT_PREFIX)
+        || parse_browser_line(s).is_some()
+        || strip_keyword(s, track::PREFIX).is_some()
+}
+
+fn strip_keyword<'a>(s: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = s.strip_prefix(keyword)?;
+
+    if rest.is_empty() {
+        Some(rest)
+    } else if rest.starts_with([' ', '\t']) {
+        Some(rest.trim_start_matches([' ', '\t']))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_header_line() {
+        assert!(is_header_line("# comment"));
+        assert!(is_header_line("browser position sq0:8-13"));
+        assert!(is_header_line("track name=ndls"));
+        assert!(is_header_line("track"));
+
+        assert!(!is_header_line("sq0\t7\t13"));
+        assert!(!is_header_line("tracks\t7\t13"));
+        assert!(!is_header_line("browser0\t7\t13"));
+    }
+}
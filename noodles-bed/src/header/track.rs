@@ -0,0 +1,242 @@
+//! BED header track definition.
+
+use std::{error, fmt, str::FromStr};
+
+use indexmap::IndexMap;
+
+pub(super) const PREFIX: &str = "track";
+
+const SEPARATOR: char = '=';
+const QUOTATION_MARK: char = '"';
+
+/// A BED header track definition.
+///
+/// This is the `track` line that describes how the records are displayed, e.g.,
+/// `track name=ndls description="noodles track" type=bedGraph`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Track {
+    attributes: IndexMap<String, String>,
+}
+
+impl Track {
+    /// Returns the track name.
+    ///
+    /// This is the value of the `name` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::header::Track;
+    /// let track: Track = "track name=ndls".parse()?;
+    /// assert_eq!(track.name(), Some("ndls"));
+    /// # Ok::<_, noodles_bed::header::track::ParseError>(())
+    /// ```
+    pub fn name(&self) -> Option<&str> {
+        self.attributes.get("name").map(|s| s.as_str())
+    }
+
+    /// Returns the track type.
+    ///
+    /// This is the value of the `type` attribute, e.g., `bedGraph` or `bedDetail`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::header::Track;
+    /// let track: Track = "track type=bedGraph".parse()?;
+    /// assert_eq!(track.ty(), Some("bedGraph"));
+    /// # Ok::<_, noodles_bed::header::track::ParseError>(())
+    /// ```
+    pub fn ty(&self) -> Option<&str> {
+        self.attributes.get("type").map(|s| s.as_str())
+    }
+
+    /// Returns the attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::header::Track;
+    /// let track: Track = r#"track name=ndls description="noodles track""#.parse()?;
+    /// assert_eq!(
+    ///     track.attributes().get("description").map(|s| s.as_str()),
+    ///     Some("noodles track")
+    /// );
+    /// # Ok::<_, noodles_bed::header::track::ParseError>(())
+    /// ```
+    pub fn attributes(&self) -> &IndexMap<String, String> {
+        &self.attributes
+    }
+
+    /// Returns a mutable reference to the attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::header::Track;
+    ///
+    /// let mut track = Track::default();
+    /// track
+    ///     .attributes_mut()
+    ///     .insert(String::from("name"), String::from("ndls"));
+    ///
+    /// assert_eq!(track.name(), Some("ndls"));
+    /// ```
+    pub fn attributes_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.attributes
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(PREFIX)?;
+
+        for (key, value) in &self.attributes {
+            write!(f, " {key}{SEPARATOR}")?;
+
+            if value.is_empty() || value.contains(char::is_whitespace) {
+                write!(f, "{QUOTATION_MARK}{value}{QUOTATION_MARK}")?;
+            } else {
+                f.write_str(value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw BED header track definition fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The `track` prefix is missing.
+    MissingPrefix,
+    /// An attribute is missing its value.
+    MissingValue(String),
+    /// A quoted value is not terminated.
+    UnterminatedValue(String),
+    /// An attribute key is duplicated.
+    DuplicateKey(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPrefix => f.write_str("missing prefix"),
+            Self::MissingValue(key) => write!(f, "missing value: {key}"),
+            Self::UnterminatedValue(key) => write!(f, "unterminated value: {key}"),
+            Self::DuplicateKey(key) => write!(f, "duplicate key: {key}"),
+        }
+    }
+}
+
+impl FromStr for Track {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rest = super::strip_keyword(s, PREFIX).ok_or(ParseError::MissingPrefix)?;
+        let mut attributes = IndexMap::new();
+
+        loop {
+            rest = rest.trim_start();
+
+            if rest.is_empty() {
+                break;
+            }
+
+            let (key, value, remaining) = parse_attribute(rest)?;
+
+            if attributes
+                .insert(key.to_string(), value.to_string())
+                .is_some()
+            {
+                return Err(ParseError::DuplicateKey(key.into()));
+            }
+
+            rest = remaining;
+        }
+
+        Ok(Self { attributes })
+    }
+}
+
+fn parse_attribute(s: &str) -> Result<(&str, &str, &str), ParseError> {
+    let i = s
+        .find(|c: char| c == SEPARATOR || c.is_whitespace())
+        .unwrap_or(s.len());
+
+    let (key, rest) = s.split_at(i);
+
+    let rest = rest
+        .strip_prefix(SEPARATOR)
+        .ok_or_else(|| ParseError::MissingValue(key.into()))?;
+
+    if let Some(rest) = rest.strip_prefix(QUOTATION_MARK) {
+        let j = rest
+            .find(QUOTATION_MARK)
+            .ok_or_else(|| ParseError::UnterminatedValue(key.into()))?;
+
+        Ok((key, &rest[..j], &rest[j + 1..]))
+    } else {
+        let j = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        Ok((key, &rest[..j], &rest[j..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let mut track = Track::default();
+        assert_eq!(track.to_string(), "track");
+
+        track
+            .attributes_mut()
+            .insert(String::from("name"), String::from("ndls"));
+        track
+            .attributes_mut()
+            .insert(String::from("description"), String::from("noodles track"));
+
+        assert_eq!(
+            track.to_string(),
+            r#"track name=ndls description="noodles track""#
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let actual = r#"track name=ndls  description="noodles track" visibility=2"#.parse();
+
+        let expected = Track {
+            attributes: [
+                (String::from("name"), String::from("ndls")),
+                (String::from("description"), String::from("noodles track")),
+                (String::from("visibility"), String::from("2")),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(actual, Ok(expected));
+
+        assert_eq!("track".parse(), Ok(Track::default()));
+
+        assert_eq!("".parse::<Track>(), Err(ParseError::MissingPrefix));
+        assert_eq!("tracks".parse::<Track>(), Err(ParseError::MissingPrefix));
+        assert_eq!(
+            "track name".parse::<Track>(),
+            Err(ParseError::MissingValue(String::from("name")))
+        );
+        assert_eq!(
+            r#"track name="ndls"#.parse::<Track>(),
+            Err(ParseError::UnterminatedValue(String::from("name")))
+        );
+        assert_eq!(
+            "track name=n name=d".parse::<Track>(),
+            Err(ParseError::DuplicateKey(String::from("name")))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! BED I/O.
 
-mod reader;
+pub mod indexed_reader;
+pub mod reader;
 mod writer;
 
-pub use self::{reader::Reader, writer::Writer};
+pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
@@ -0,0 +1,87 @@
+//! Indexed BED reader.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    io::{self, BufRead, Read},
+    str::FromStr,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+
+use super::{reader::Query, Reader};
+use crate::Record;
+
+/// An indexed BED reader.
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: Box<dyn BinningIndex>,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: BufRead,
+{
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Reads a raw BED line.
+    pub fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.inner.read_line(buf)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    pub fn records<const N: u8>(&mut self) -> impl Iterator<Item = io::Result<Record<N>>> + '_
+    where
+        Record<N>: FromStr<Err = crate::record::ParseError>,
+    {
+        self.inner.records()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &dyn BinningIndex {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    /// Returns an iterator over records that intersect the given region.
+    pub fn query<const N: u8>(&mut self, region: &Region) -> io::Result<Query<'_, R, N>> {
+        self.inner.query(&self.index, region)
+    }
+}
+
+impl<R> IndexedReader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Creates an indexed BED reader.
+    pub fn new<I>(inner: R, index: I) -> Self
+    where
+        I: BinningIndex + 'static,
+    {
+        Self {
+            inner: Reader::new(bgzf::Reader::new(inner)),
+            index: Box::new(index),
+        }
+    }
+}
@@ -1,14 +1,30 @@
+//! BED reader.
+
+mod query;
+
+pub use self::query::Query;
+
 use std::{
-    io::{self, BufRead},
-    iter,
+    error,
+    io::{self, BufRead, Read},
+    iter, mem,
     str::FromStr,
 };
 
-use crate::Record;
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+
+use crate::{
+    header,
+    record::{BedGraph, BroadPeak, Detail, NarrowPeak},
+    Header, Record,
+};
 
 /// A BED reader.
 pub struct Reader<R> {
     inner: R,
+    line: String,
 }
 
 impl<R> Reader<R>
@@ -67,7 +83,10 @@ where
     /// let reader = bed::io::Reader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            line: String::new(),
+        }
     }
 
     /// Reads a raw BED line.
@@ -88,7 +107,39 @@ where
     /// # Ok::<_, io::Error>(())
     /// ```
     pub fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
-        read_line(&mut self.inner, buf)
+        if self.line.is_empty() {
+            read_line(&mut self.inner, buf)
+        } else {
+            let line = mem::take(&mut self.line);
+            read_line(&mut line.as_bytes(), buf)
+        }
+    }
+
+    /// Reads the BED header.
+    ///
+    /// This reads the leading `browser`, `track`, and comment lines. The stream is expected to be
+    /// at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"browser position sq0:8-13\ntrack name=ndls\nsq0\t7\t13\n";
+    /// let mut reader = bed::io::Reader::new(&data[..]);
+    ///
+    /// let header = reader.read_header()?;
+    /// assert_eq!(header.browser_lines(), [String::from("position sq0:8-13")]);
+    /// assert_eq!(header.track().and_then(|track| track.name()), Some("ndls"));
+    ///
+    /// let mut records = reader.records::<3>();
+    /// assert!(records.next().transpose()?.is_some());
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<Header> {
+        read_header(&mut self.inner, &mut self.line)
     }
 
     /// Returns an iterator over records starting from the current stream position.
@@ -116,31 +167,274 @@ where
     where
         Record<N>: FromStr<Err = crate::record::ParseError>,
     {
-        const COMMENT_PREFIX: char = '#';
+        let mut buf = String::new();
+        iter::from_fn(move || self.read_record(&mut buf).transpose())
+    }
 
+    /// Returns an iterator over bedGraph records starting from the current stream position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"track type=bedGraph\nsq0\t7\t13\t0.5\n";
+    /// let mut reader = bed::io::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut records = reader.bed_graph_records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.map(|r| r.value()), Some(0.5));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn bed_graph_records(&mut self) -> impl Iterator<Item = io::Result<BedGraph>> + '_ {
         let mut buf = String::new();
+        iter::from_fn(move || self.read_record(&mut buf).transpose())
+    }
 
-        iter::from_fn(move || loop {
-            buf.clear();
+    /// Returns an iterator over ENCODE narrowPeak records starting from the current stream
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"sq0\t7\t13\t.\t0\t.\t5\t8\t-1\t2\n";
+    /// let mut reader = bed::io::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.narrow_peak_records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.and_then(|r| r.peak()), Some(2));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn narrow_peak_records(&mut self) -> impl Iterator<Item = io::Result<NarrowPeak>> + '_ {
+        let mut buf = String::new();
+        iter::from_fn(move || self.read_record(&mut buf).transpose())
+    }
 
-            match self.read_line(&mut buf) {
-                Ok(0) => return None,
-                Ok(_) => {
-                    if buf.starts_with(COMMENT_PREFIX) {
-                        continue;
-                    } else {
-                        return Some(
-                            buf.parse()
-                                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
-                        );
-                    }
-                }
-                Err(e) => return Some(Err(e)),
+    /// Returns an iterator over ENCODE broadPeak records starting from the current stream
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"sq0\t7\t13\t.\t0\t.\t5\t8\t-1\n";
+    /// let mut reader = bed::io::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.broad_peak_records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.map(|r| r.signal_value()), Some(5.0));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn broad_peak_records(&mut self) -> impl Iterator<Item = io::Result<BroadPeak>> + '_ {
+        let mut buf = String::new();
+        iter::from_fn(move || self.read_record(&mut buf).transpose())
+    }
+
+    /// Returns an iterator over BED detail records starting from the current stream position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"track type=bedDetail\nsq0\t7\t13\tndls1\tn1\tnoodles\n";
+    /// let mut reader = bed::io::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut records = reader.detail_records::<4>();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.as_ref().map(|r| r.id()), Some("n1"));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn detail_records<const N: u8>(
+        &mut self,
+    ) -> impl Iterator<Item = io::Result<Detail<N>>> + '_
+    where
+        Detail<N>: FromStr<Err = crate::record::detail::ParseError>,
+    {
+        let mut buf = String::new();
+        iter::from_fn(move || self.read_record(&mut buf).transpose())
+    }
+
+    fn read_record<T>(&mut self, buf: &mut String) -> io::Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: error::Error + Send + Sync + 'static,
+    {
+        // The first line after the header is read ahead when reading the header.
+        let line = mem::take(&mut self.line);
+
+        if line.is_empty() {
+            read_record(&mut self.inner, buf)
+        } else {
+            read_record(&mut line.as_bytes().chain(&mut self.inner), buf)
+        }
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("sample.bed.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(bed::io::Reader::new)?;
+    ///
+    /// let index = tabix::read("sample.bed.gz.tbi")?;
+    /// let region = "sq0:8-13".parse()?;
+    /// let query = reader.query(&index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record: bed::Record<3> = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<I, const N: u8>(
+        &mut self,
+        index: &I,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, N>>
+    where
+        I: BinningIndex,
+    {
+        let reference_sequence_id = resolve_region(index, region)?;
+        let chunks = index.query(reference_sequence_id, region.interval())?;
+
+        self.line.clear();
+
+        Ok(Query::new(
+            &mut self.inner,
+            chunks,
+            region.name().to_vec(),
+            region.interval(),
+        ))
+    }
+}
+
+// Reads the leading header lines.
+//
+// Header lines are only known to end once a full line is read, so the first line that is not a
+// header line is left in `line`.
+fn read_header<R>(reader: &mut R, line: &mut String) -> io::Result<Header>
+where
+    R: BufRead,
+{
+    const LINE_FEED: char = '\n';
+    const CARRIAGE_RETURN: char = '\r';
+
+    let mut header = Header::default();
+
+    loop {
+        line.clear();
+
+        if reader.read_line(line)? == 0 {
+            break;
+        }
+
+        let buf = line
+            .strip_suffix(LINE_FEED)
+            .map(|s| s.strip_suffix(CARRIAGE_RETURN).unwrap_or(s))
+            .unwrap_or(line);
+
+        if !header::is_header_line(buf) {
+            break;
+        } else if let Some(value) = header::parse_browser_line(buf) {
+            header.browser_lines_mut().push(value.into());
+        } else if !buf.starts_with(header::COMMENT_PREFIX) {
+            let track = buf
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            if header.track().is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "duplicate track line",
+                ));
             }
-        })
+
+            *header.track_mut() = Some(track);
+        }
+    }
+
+    Ok(header)
+}
+
+// Reads and parses the next line that is not a comment, browser, or track line.
+fn read_record<R, T>(reader: &mut R, buf: &mut String) -> io::Result<Option<T>>
+where
+    R: BufRead,
+    T: FromStr,
+    T::Err: error::Error + Send + Sync + 'static,
+{
+    loop {
+        buf.clear();
+
+        if read_line(reader, buf)? == 0 {
+            return Ok(None);
+        } else if !header::is_header_line(buf) {
+            return buf
+                .parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+        }
     }
 }
 
+fn resolve_region<I>(index: &I, region: &Region) -> io::Result<usize>
+where
+    I: BinningIndex,
+{
+    let header = index
+        .header()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing tabix header"))?;
+
+    let region_name = std::str::from_utf8(region.name())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    header
+        .reference_sequence_names()
+        .get_index_of(region_name)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in reference sequences: {region:?}"
+                ),
+            )
+        })
+}
+
 fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
 where
     R: BufRead,
@@ -186,4 +480,64 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_header() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"#noodles\nbrowser hide all\ntrack name=ndls\nsq0\t7\t13\n";
+        let mut reader = &data[..];
+        let mut line = String::new();
+
+        let actual = read_header(&mut reader, &mut line)?;
+
+        let mut expected = Header::default();
+        expected.browser_lines_mut().push(String::from("hide all"));
+        *expected.track_mut() = Some("track name=ndls".parse()?);
+
+        assert_eq!(actual, expected);
+        assert_eq!(line, "sq0\t7\t13\n");
+        assert!(reader.is_empty());
+
+        let data = b"track name=n\ntrack name=d\n";
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_header(&mut reader, &mut line),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_header_with_lines_spanning_buffers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::BufReader;
+
+        let data = b"browser hide all\ntrack name=ndls\ntrack1\t7\t13\n";
+        let mut reader = Reader::new(BufReader::with_capacity(4, &data[..]));
+
+        let actual = reader.read_header()?;
+
+        let mut expected = Header::default();
+        expected.browser_lines_mut().push(String::from("hide all"));
+        *expected.track_mut() = Some("track name=ndls".parse()?);
+
+        assert_eq!(actual, expected);
+
+        let mut records = reader.records::<3>();
+        let record = records.next().transpose()?;
+        assert_eq!(
+            record.as_ref().map(|r| r.reference_sequence_name()),
+            Some("track1")
+        );
+        assert!(records.next().is_none());
+
+        let data = b"browser hide all\nsq0\t7\t13\n";
+        let mut reader = Reader::new(BufReader::with_capacity(4, &data[..]));
+        reader.read_header()?;
+
+        let mut buf = String::new();
+        reader.read_line(&mut buf)?;
+        assert_eq!(buf, "sq0\t7\t13");
+
+        Ok(())
+    }
 }
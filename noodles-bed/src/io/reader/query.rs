@@ -0,0 +1,73 @@
+use std::{io, str::FromStr};
+
+use noodles_bgzf as bgzf;
+use noodles_core::region::Interval;
+use noodles_csi::{self as csi, binning_index::index::reference_sequence::bin::Chunk};
+
+use super::read_record;
+use crate::{record::BedN, Record};
+
+/// An iterator over records of a BED reader that intersect a given region.
+///
+/// This is created by calling [`super::Reader::query`].
+pub struct Query<'r, R, const N: u8> {
+    reader: csi::io::Query<'r, R>,
+    reference_sequence_name: Vec<u8>,
+    interval: Interval,
+    buf: String,
+}
+
+impl<'r, R, const N: u8> Query<'r, R, N>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+{
+    pub(super) fn new(
+        reader: &'r mut R,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: Vec<u8>,
+        interval: Interval,
+    ) -> Self {
+        Self {
+            reader: csi::io::Query::new(reader, chunks),
+            reference_sequence_name,
+            interval,
+            buf: String::new(),
+        }
+    }
+}
+
+impl<'r, R, const N: u8> Iterator for Query<'r, R, N>
+where
+    R: bgzf::io::BufRead + bgzf::io::Seek,
+    Record<N>: BedN<3> + FromStr<Err = crate::record::ParseError>,
+{
+    type Item = io::Result<Record<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match read_record(&mut self.reader, &mut self.buf) {
+                Ok(Some(record)) => {
+                    if intersects(&record, &self.reference_sequence_name, self.interval) {
+                        return Some(Ok(record));
+                    }
+                }
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+fn intersects<const N: u8>(
+    record: &Record<N>,
+    reference_sequence_name: &[u8],
+    region_interval: Interval,
+) -> bool
+where
+    Record<N>: BedN<3>,
+{
+    let record_interval = Interval::from(record.start_position()..=record.end_position());
+
+    record.reference_sequence_name().as_bytes() == reference_sequence_name
+        && record_interval.intersects(region_interval)
+}
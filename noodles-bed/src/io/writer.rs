@@ -3,7 +3,10 @@ use std::{
     io::{self, Write},
 };
 
-use crate::Record;
+use crate::{
+    record::{BedGraph, BroadPeak, Detail, NarrowPeak},
+    Header, Record,
+};
 
 /// A BED writer.
 pub struct Writer<W> {
@@ -65,6 +68,29 @@ where
         self.inner
     }
 
+    /// Writes a BED header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let mut writer = bed::io::Writer::new(Vec::new());
+    ///
+    /// let mut header = bed::Header::default();
+    /// header.browser_lines_mut().push(String::from("position sq0:8-13"));
+    /// *header.track_mut() = Some("track name=ndls".parse().unwrap());
+    ///
+    /// writer.write_header(&header)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"browser position sq0:8-13\ntrack name=ndls\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        write_header(&mut self.inner, header)
+    }
+
     /// Writes a BED record.
     ///
     /// # Examples
@@ -92,12 +118,107 @@ where
     {
         write_record(&mut self.inner, record)
     }
+
+    /// Writes a bedGraph record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BedGraph};
+    ///
+    /// let mut writer = bed::io::Writer::new(Vec::new());
+    ///
+    /// let record = BedGraph::new("sq0\t7\t13".parse()?, 0.5);
+    /// writer.write_bed_graph_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t7\t13\t0.5\n");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_bed_graph_record(&mut self, record: &BedGraph) -> io::Result<()> {
+        write_record(&mut self.inner, record)
+    }
+
+    /// Writes an ENCODE narrowPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::NarrowPeak};
+    ///
+    /// let mut writer = bed::io::Writer::new(Vec::new());
+    ///
+    /// let record = NarrowPeak::new("sq0\t7\t13\t.\t0\t.".parse()?, 5.0, Some(8.0), None, Some(2));
+    /// writer.write_narrow_peak_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t7\t13\t.\t0\t.\t5\t8\t-1\t2\n");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_narrow_peak_record(&mut self, record: &NarrowPeak) -> io::Result<()> {
+        write_record(&mut self.inner, record)
+    }
+
+    /// Writes an ENCODE broadPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BroadPeak};
+    ///
+    /// let mut writer = bed::io::Writer::new(Vec::new());
+    ///
+    /// let record = BroadPeak::new("sq0\t7\t13\t.\t0\t.".parse()?, 5.0, Some(8.0), None);
+    /// writer.write_broad_peak_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t7\t13\t.\t0\t.\t5\t8\t-1\n");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_broad_peak_record(&mut self, record: &BroadPeak) -> io::Result<()> {
+        write_record(&mut self.inner, record)
+    }
+
+    /// Writes a BED detail record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::Detail};
+    ///
+    /// let mut writer = bed::io::Writer::new(Vec::new());
+    ///
+    /// let record: bed::Record<4> = "sq0\t7\t13\tndls1".parse()?;
+    /// let detail = Detail::new(record, String::from("n1"), String::from("noodles"));
+    /// writer.write_detail_record(&detail)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t7\t13\tndls1\tn1\tnoodles\n");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_detail_record<const N: u8>(&mut self, record: &Detail<N>) -> io::Result<()>
+    where
+        Detail<N>: fmt::Display,
+    {
+        write_record(&mut self.inner, record)
+    }
 }
 
-fn write_record<W, const N: u8>(writer: &mut W, record: &Record<N>) -> io::Result<()>
+fn write_header<W>(writer: &mut W, header: &Header) -> io::Result<()>
 where
     W: Write,
-    Record<N>: fmt::Display,
+{
+    for browser_line in header.browser_lines() {
+        writeln!(writer, "browser {browser_line}")?;
+    }
+
+    if let Some(track) = header.track() {
+        writeln!(writer, "{track}")?;
+    }
+
+    Ok(())
+}
+
+fn write_record<W, T>(writer: &mut W, record: &T) -> io::Result<()>
+where
+    W: Write,
+    T: fmt::Display,
 {
     writeln!(writer, "{record}")
 }
@@ -114,4 +235,23 @@ mod tests {
         assert_eq!(buf, b"sq0\t8\t13\n");
         Ok(())
     }
+
+    #[test]
+    fn test_write_header() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        write_header(&mut buf, &Header::default())?;
+        assert!(buf.is_empty());
+
+        let mut header = Header::default();
+        header.browser_lines_mut().push(String::from("hide all"));
+        *header.track_mut() = Some(r#"track name=ndls description="noodles track""#.parse()?);
+
+        buf.clear();
+        write_header(&mut buf, &header)?;
+
+        let expected = b"browser hide all\ntrack name=ndls description=\"noodles track\"\n";
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
 }
@@ -2,10 +2,12 @@
 
 //! **noodles-bed** handles the reading and writing of the BED (Browser Extensible Data) format.
 
+pub mod fs;
+pub mod header;
 pub mod io;
 pub mod record;
 
-pub use self::record::Record;
+pub use self::{header::Header, record::Record};
 
 #[deprecated(since = "0.14.0", note = "Use `noodles_bed::io::Reader` instead.")]
 pub use self::io::Reader;
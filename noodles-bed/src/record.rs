@@ -1,12 +1,24 @@
 //! BED record and fields.
 
+pub mod bed_graph;
 pub mod builder;
 pub mod color;
+pub mod detail;
 pub mod name;
+pub mod peak;
 pub mod score;
 pub mod strand;
 
-pub use self::{builder::Builder, color::Color, name::Name, score::Score, strand::Strand};
+pub use self::{
+    bed_graph::BedGraph,
+    builder::Builder,
+    color::Color,
+    detail::Detail,
+    name::Name,
+    peak::{BroadPeak, NarrowPeak},
+    score::Score,
+    strand::Strand,
+};
 
 use std::{
     error,
@@ -16,7 +28,8 @@ use std::{
     str::FromStr,
 };
 
-use noodles_core::Position;
+use bstr::{BStr, ByteSlice};
+use noodles_core::{ops, region::Interval, Position};
 
 const DELIMITER: char = '\t';
 const MISSING_STRING: &str = ".";
@@ -465,6 +478,19 @@ where
     }
 }
 
+impl<const N: u8> ops::Feature for Record<N>
+where
+    Self: BedN<3>,
+{
+    fn reference_sequence_name(&self) -> &BStr {
+        self.reference_sequence_name().as_bytes().as_bstr()
+    }
+
+    fn interval(&self) -> Interval {
+        Interval::from(self.start_position()..=self.end_position())
+    }
+}
+
 impl fmt::Display for Record<3> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         format_bed_3_fields(f, self)?;
@@ -1072,6 +1098,14 @@ where
     Ok(blocks)
 }
 
+// Splits a raw record into its first `n` fields and the remaining fields, if any.
+fn split_fields(s: &str, n: usize) -> (&str, Option<&str>) {
+    match s.match_indices(DELIMITER).nth(n - 1) {
+        Some((i, _)) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    }
+}
+
 fn parse_optional_fields<'a, I>(fields: &mut I) -> OptionalFields
 where
     I: Iterator<Item = &'a str>,
@@ -0,0 +1,153 @@
+//! bedGraph record.
+
+use std::{error, fmt, num, str::FromStr};
+
+use super::{split_fields, Record, DELIMITER};
+
+/// A bedGraph record.
+///
+/// A bedGraph record is a BED3 record followed by a data value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BedGraph {
+    record: Record<3>,
+    value: f64,
+}
+
+impl BedGraph {
+    /// Creates a bedGraph record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BedGraph};
+    /// let record: bed::Record<3> = "sq0\t7\t13".parse()?;
+    /// let bed_graph = BedGraph::new(record, 0.5);
+    /// # Ok::<_, bed::record::ParseError>(())
+    /// ```
+    pub fn new(record: Record<3>, value: f64) -> Self {
+        Self { record, value }
+    }
+
+    /// Returns the BED3 record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BedGraph;
+    /// use noodles_core::Position;
+    ///
+    /// let bed_graph: BedGraph = "sq0\t7\t13\t0.5".parse()?;
+    /// assert_eq!(bed_graph.record().reference_sequence_name(), "sq0");
+    /// assert_eq!(bed_graph.record().start_position(), Position::try_from(8)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn record(&self) -> &Record<3> {
+        &self.record
+    }
+
+    /// Returns the data value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BedGraph;
+    /// let bed_graph: BedGraph = "sq0\t7\t13\t0.5".parse()?;
+    /// assert_eq!(bed_graph.value(), 0.5);
+    /// # Ok::<_, noodles_bed::record::bed_graph::ParseError>(())
+    /// ```
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl fmt::Display for BedGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{DELIMITER}{}", self.record, self.value)
+    }
+}
+
+/// An error returned when a raw bedGraph record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The BED3 record is invalid.
+    InvalidRecord(super::ParseError),
+    /// The data value is missing.
+    MissingValue,
+    /// The data value is invalid.
+    InvalidValue(num::ParseFloatError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidRecord(e) => Some(e),
+            Self::InvalidValue(e) => Some(e),
+            Self::MissingValue => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(_) => f.write_str("invalid record"),
+            Self::MissingValue => f.write_str("missing value"),
+            Self::InvalidValue(_) => f.write_str("invalid value"),
+        }
+    }
+}
+
+impl FromStr for BedGraph {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (raw_record, raw_value) = split_fields(s, 3);
+
+        let record = raw_record.parse().map_err(ParseError::InvalidRecord)?;
+
+        let value = raw_value
+            .ok_or(ParseError::MissingValue)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidValue))?;
+
+        Ok(Self::new(record, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), super::super::ParseError> {
+        let bed_graph = BedGraph::new("sq0\t7\t13".parse()?, 0.5);
+        assert_eq!(bed_graph.to_string(), "sq0\t7\t13\t0.5");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), super::super::ParseError> {
+        assert_eq!(
+            "sq0\t7\t13\t0.5".parse(),
+            Ok(BedGraph::new("sq0\t7\t13".parse()?, 0.5))
+        );
+
+        assert_eq!(
+            "sq0\t7\t13".parse::<BedGraph>(),
+            Err(ParseError::MissingValue)
+        );
+        assert!(matches!(
+            "sq0\t7\t13\tndls".parse::<BedGraph>(),
+            Err(ParseError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            "sq0\t7\t13\t0.5\t8".parse::<BedGraph>(),
+            Err(ParseError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            "sq0\tndls\t13\t0.5".parse::<BedGraph>(),
+            Err(ParseError::InvalidRecord(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+//! BED detail record.
+
+use std::{error, fmt, str::FromStr};
+
+use super::{split_fields, BedN, Record, DELIMITER};
+
+/// A BED detail record.
+///
+/// A BED detail record is a BED record with 4 to 12 standard fields followed by an ID and a
+/// description.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Detail<const N: u8> {
+    record: Record<N>,
+    id: String,
+    description: String,
+}
+
+impl<const N: u8> Detail<N>
+where
+    Record<N>: BedN<4>,
+{
+    /// Creates a BED detail record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::Detail};
+    ///
+    /// let record: bed::Record<4> = "sq0\t7\t13\tndls1".parse()?;
+    /// let detail = Detail::new(record, String::from("n1"), String::from("noodles"));
+    /// # Ok::<_, bed::record::ParseError>(())
+    /// ```
+    pub fn new(record: Record<N>, id: String, description: String) -> Self {
+        Self {
+            record,
+            id,
+            description,
+        }
+    }
+
+    /// Returns the BED record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::Detail;
+    /// let detail: Detail<4> = "sq0\t7\t13\tndls1\tn1\tnoodles".parse()?;
+    /// assert_eq!(detail.record().name().map(|name| &**name), Some("ndls1"));
+    /// # Ok::<_, noodles_bed::record::detail::ParseError>(())
+    /// ```
+    pub fn record(&self) -> &Record<N> {
+        &self.record
+    }
+
+    /// Returns the ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::Detail;
+    /// let detail: Detail<4> = "sq0\t7\t13\tndls1\tn1\tnoodles".parse()?;
+    /// assert_eq!(detail.id(), "n1");
+    /// # Ok::<_, noodles_bed::record::detail::ParseError>(())
+    /// ```
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::Detail;
+    /// let detail: Detail<4> = "sq0\t7\t13\tndls1\tn1\tnoodles".parse()?;
+    /// assert_eq!(detail.description(), "noodles");
+    /// # Ok::<_, noodles_bed::record::detail::ParseError>(())
+    /// ```
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl<const N: u8> fmt::Display for Detail<N>
+where
+    Record<N>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{DELIMITER}{}{DELIMITER}{}",
+            self.record, self.id, self.description
+        )
+    }
+}
+
+/// An error returned when a raw BED detail record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The BED record is invalid.
+    InvalidRecord(super::ParseError),
+    /// The ID is missing.
+    MissingId,
+    /// The description is missing.
+    MissingDescription,
+    /// The record has more fields than expected.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidRecord(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(_) => f.write_str("invalid record"),
+            Self::MissingId => f.write_str("missing ID"),
+            Self::MissingDescription => f.write_str("missing description"),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl<const N: u8> FromStr for Detail<N>
+where
+    Record<N>: BedN<4> + FromStr<Err = super::ParseError>,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (raw_record, rest) = split_fields(s, usize::from(N));
+        let record = raw_record.parse().map_err(ParseError::InvalidRecord)?;
+
+        let mut fields = rest.into_iter().flat_map(|t| t.split(DELIMITER));
+
+        let id = fields.next().ok_or(ParseError::MissingId)?;
+        let description = fields.next().ok_or(ParseError::MissingDescription)?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(record, id.into(), description.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), super::super::ParseError> {
+        let record: Record<6> = "sq0\t7\t13\tndls1\t0\t+".parse()?;
+        let detail = Detail::new(record, String::from("n1"), String::from("<b>noodles</b>"));
+        assert_eq!(
+            detail.to_string(),
+            "sq0\t7\t13\tndls1\t0\t+\tn1\t<b>noodles</b>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), super::super::ParseError> {
+        let record: Record<4> = "sq0\t7\t13\tndls1".parse()?;
+
+        assert_eq!(
+            "sq0\t7\t13\tndls1\tn1\tnoodles".parse(),
+            Ok(Detail::new(
+                record,
+                String::from("n1"),
+                String::from("noodles")
+            ))
+        );
+
+        assert_eq!(
+            "sq0\t7\t13\tndls1".parse::<Detail<4>>(),
+            Err(ParseError::MissingId)
+        );
+        assert_eq!(
+            "sq0\t7\t13\tndls1\tn1".parse::<Detail<4>>(),
+            Err(ParseError::MissingDescription)
+        );
+        assert_eq!(
+            "sq0\t7\t13\tndls1\tn1\tnoodles\t0".parse::<Detail<4>>(),
+            Err(ParseError::UnexpectedField)
+        );
+        assert!(matches!(
+            "sq0\t7\t13\tndls1\tn1\tnoodles".parse::<Detail<5>>(),
+            Err(ParseError::InvalidRecord(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,465 @@
+//! ENCODE narrowPeak and broadPeak records.
+
+use std::{
+    error,
+    fmt::{self, Write},
+    num,
+    str::FromStr,
+};
+
+use super::{split_fields, Record, DELIMITER};
+
+const MISSING: &str = "-1";
+
+/// An ENCODE narrowPeak record.
+///
+/// A narrowPeak record is a BED6 record followed by the signal value, p-value, q-value, and peak
+/// offset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NarrowPeak {
+    record: Record<6>,
+    signal_value: f64,
+    p_value: Option<f64>,
+    q_value: Option<f64>,
+    peak: Option<usize>,
+}
+
+impl NarrowPeak {
+    /// Creates a narrowPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::NarrowPeak};
+    /// let record: bed::Record<6> = "sq0\t7\t13\t.\t0\t.".parse()?;
+    /// let narrow_peak = NarrowPeak::new(record, 5.0, Some(8.0), None, Some(2));
+    /// # Ok::<_, bed::record::ParseError>(())
+    /// ```
+    pub fn new(
+        record: Record<6>,
+        signal_value: f64,
+        p_value: Option<f64>,
+        q_value: Option<f64>,
+        peak: Option<usize>,
+    ) -> Self {
+        Self {
+            record,
+            signal_value,
+            p_value,
+            q_value,
+            peak,
+        }
+    }
+
+    /// Returns the BED6 record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let narrow_peak: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1\t2".parse()?;
+    /// assert_eq!(narrow_peak.record().reference_sequence_name(), "sq0");
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn record(&self) -> &Record<6> {
+        &self.record
+    }
+
+    /// Returns the signal value (`signalValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let narrow_peak: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1\t2".parse()?;
+    /// assert_eq!(narrow_peak.signal_value(), 5.0);
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn signal_value(&self) -> f64 {
+        self.signal_value
+    }
+
+    /// Returns the -log10 p-value (`pValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let narrow_peak: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1\t2".parse()?;
+    /// assert_eq!(narrow_peak.p_value(), Some(8.0));
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn p_value(&self) -> Option<f64> {
+        self.p_value
+    }
+
+    /// Returns the -log10 q-value (`qValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let narrow_peak: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1\t2".parse()?;
+    /// assert!(narrow_peak.q_value().is_none());
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn q_value(&self) -> Option<f64> {
+        self.q_value
+    }
+
+    /// Returns the 0-based offset of the peak summit from the start position (`peak`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let narrow_peak: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1\t2".parse()?;
+    /// assert_eq!(narrow_peak.peak(), Some(2));
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn peak(&self) -> Option<usize> {
+        self.peak
+    }
+}
+
+impl fmt::Display for NarrowPeak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.record)?;
+        format_values(f, self.signal_value, self.p_value, self.q_value)?;
+
+        f.write_char(DELIMITER)?;
+
+        if let Some(peak) = self.peak {
+            write!(f, "{peak}")
+        } else {
+            f.write_str(MISSING)
+        }
+    }
+}
+
+impl FromStr for NarrowPeak {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (raw_record, rest) = split_fields(s, 6);
+        let record = raw_record.parse().map_err(ParseError::InvalidRecord)?;
+
+        let mut fields = rest.into_iter().flat_map(|t| t.split(DELIMITER));
+        let (signal_value, p_value, q_value) = parse_values(&mut fields)?;
+
+        let peak = fields
+            .next()
+            .ok_or(ParseError::MissingPeak)
+            .and_then(|t| match t {
+                MISSING => Ok(None),
+                _ => t.parse().map(Some).map_err(ParseError::InvalidPeak),
+            })?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(record, signal_value, p_value, q_value, peak))
+    }
+}
+
+/// An ENCODE broadPeak record.
+///
+/// A broadPeak record is a BED6 record followed by the signal value, p-value, and q-value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BroadPeak {
+    record: Record<6>,
+    signal_value: f64,
+    p_value: Option<f64>,
+    q_value: Option<f64>,
+}
+
+impl BroadPeak {
+    /// Creates a broadPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BroadPeak};
+    /// let record: bed::Record<6> = "sq0\t7\t13\t.\t0\t.".parse()?;
+    /// let broad_peak = BroadPeak::new(record, 5.0, Some(8.0), None);
+    /// # Ok::<_, bed::record::ParseError>(())
+    /// ```
+    pub fn new(
+        record: Record<6>,
+        signal_value: f64,
+        p_value: Option<f64>,
+        q_value: Option<f64>,
+    ) -> Self {
+        Self {
+            record,
+            signal_value,
+            p_value,
+            q_value,
+        }
+    }
+
+    /// Returns the BED6 record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BroadPeak;
+    /// let broad_peak: BroadPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1".parse()?;
+    /// assert_eq!(broad_peak.record().reference_sequence_name(), "sq0");
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn record(&self) -> &Record<6> {
+        &self.record
+    }
+
+    /// Returns the signal value (`signalValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BroadPeak;
+    /// let broad_peak: BroadPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1".parse()?;
+    /// assert_eq!(broad_peak.signal_value(), 5.0);
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn signal_value(&self) -> f64 {
+        self.signal_value
+    }
+
+    /// Returns the -log10 p-value (`pValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BroadPeak;
+    /// let broad_peak: BroadPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1".parse()?;
+    /// assert_eq!(broad_peak.p_value(), Some(8.0));
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn p_value(&self) -> Option<f64> {
+        self.p_value
+    }
+
+    /// Returns the -log10 q-value (`qValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BroadPeak;
+    /// let broad_peak: BroadPeak = "sq0\t7\t13\t.\t0\t.\t5\t8\t-1".parse()?;
+    /// assert!(broad_peak.q_value().is_none());
+    /// # Ok::<_, noodles_bed::record::peak::ParseError>(())
+    /// ```
+    pub fn q_value(&self) -> Option<f64> {
+        self.q_value
+    }
+}
+
+impl fmt::Display for BroadPeak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.record)?;
+        format_values(f, self.signal_value, self.p_value, self.q_value)
+    }
+}
+
+impl FromStr for BroadPeak {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (raw_record, rest) = split_fields(s, 6);
+        let record = raw_record.parse().map_err(ParseError::InvalidRecord)?;
+
+        let mut fields = rest.into_iter().flat_map(|t| t.split(DELIMITER));
+        let (signal_value, p_value, q_value) = parse_values(&mut fields)?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(record, signal_value, p_value, q_value))
+    }
+}
+
+/// An error returned when a raw narrowPeak or broadPeak record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The BED6 record is invalid.
+    InvalidRecord(super::ParseError),
+    /// The signal value is missing.
+    MissingSignalValue,
+    /// The signal value is invalid.
+    InvalidSignalValue(num::ParseFloatError),
+    /// The p-value is missing.
+    MissingPValue,
+    /// The p-value is invalid.
+    InvalidPValue(num::ParseFloatError),
+    /// The q-value is missing.
+    MissingQValue,
+    /// The q-value is invalid.
+    InvalidQValue(num::ParseFloatError),
+    /// The peak is missing.
+    MissingPeak,
+    /// The peak is invalid.
+    InvalidPeak(num::ParseIntError),
+    /// The record has more fields than expected.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidRecord(e) => Some(e),
+            Self::InvalidSignalValue(e) | Self::InvalidPValue(e) | Self::InvalidQValue(e) => {
+                Some(e)
+            }
+            Self::InvalidPeak(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(_) => f.write_str("invalid record"),
+            Self::MissingSignalValue => f.write_str("missing signal value"),
+            Self::InvalidSignalValue(_) => f.write_str("invalid signal value"),
+            Self::MissingPValue => f.write_str("missing p-value"),
+            Self::InvalidPValue(_) => f.write_str("invalid p-value"),
+            Self::MissingQValue => f.write_str("missing q-value"),
+            Self::InvalidQValue(_) => f.write_str("invalid q-value"),
+            Self::MissingPeak => f.write_str("missing peak"),
+            Self::InvalidPeak(_) => f.write_str("invalid peak"),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+fn format_values(
+    f: &mut fmt::Formatter<'_>,
+    signal_value: f64,
+    p_value: Option<f64>,
+    q_value: Option<f64>,
+) -> fmt::Result {
+    write!(f, "{DELIMITER}{signal_value}")?;
+
+    for value in [p_value, q_value] {
+        f.write_char(DELIMITER)?;
+
+        if let Some(n) = value {
+            write!(f, "{n}")?;
+        } else {
+            f.write_str(MISSING)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_values<'a, I>(fields: &mut I) -> Result<(f64, Option<f64>, Option<f64>), ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let signal_value = fields
+        .next()
+        .ok_or(ParseError::MissingSignalValue)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidSignalValue))?;
+
+    let p_value = fields
+        .next()
+        .ok_or(ParseError::MissingPValue)
+        .and_then(|s| parse_optional_value(s).map_err(ParseError::InvalidPValue))?;
+
+    let q_value = fields
+        .next()
+        .ok_or(ParseError::MissingQValue)
+        .and_then(|s| parse_optional_value(s).map_err(ParseError::InvalidQValue))?;
+
+    Ok((signal_value, p_value, q_value))
+}
+
+fn parse_optional_value(s: &str) -> Result<Option<f64>, num::ParseFloatError> {
+    match s {
+        MISSING => Ok(None),
+        _ => s.parse().map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt_for_narrow_peak() -> Result<(), super::super::ParseError> {
+        let record = "sq0\t7\t13\tndls\t8\t+".parse()?;
+        let narrow_peak = NarrowPeak::new(record, 5.5, Some(8.0), None, Some(2));
+        assert_eq!(
+            narrow_peak.to_string(),
+            "sq0\t7\t13\tndls\t8\t+\t5.5\t8\t-1\t2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_for_narrow_peak() -> Result<(), super::super::ParseError> {
+        let record = "sq0\t7\t13\t.\t0\t.".parse()?;
+
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t13\t-1".parse(),
+            Ok(NarrowPeak::new(record, 5.5, None, Some(13.0), None))
+        );
+
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t13".parse::<NarrowPeak>(),
+            Err(ParseError::MissingPeak)
+        );
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t13\t-1\t0".parse::<NarrowPeak>(),
+            Err(ParseError::UnexpectedField)
+        );
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.".parse::<NarrowPeak>(),
+            Err(ParseError::MissingSignalValue)
+        );
+        assert!(matches!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\tndls\t13\t-1".parse::<NarrowPeak>(),
+            Err(ParseError::InvalidPValue(_))
+        ));
+        assert!(matches!(
+            "sq0\t7\t13".parse::<NarrowPeak>(),
+            Err(ParseError::InvalidRecord(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt_for_broad_peak() -> Result<(), super::super::ParseError> {
+        let record = "sq0\t7\t13\t.\t0\t.".parse()?;
+        let broad_peak = BroadPeak::new(record, 5.5, None, Some(13.0));
+        assert_eq!(broad_peak.to_string(), "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t13");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_for_broad_peak() -> Result<(), super::super::ParseError> {
+        let record = "sq0\t7\t13\t.\t0\t.".parse()?;
+
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t8\t-1".parse(),
+            Ok(BroadPeak::new(record, 5.5, Some(8.0), None))
+        );
+
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t8".parse::<BroadPeak>(),
+            Err(ParseError::MissingQValue)
+        );
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t8\t-1\t2".parse::<BroadPeak>(),
+            Err(ParseError::UnexpectedField)
+        );
+
+        Ok(())
+    }
+}
@@ -7,16 +7,18 @@
 #[cfg(feature = "async")]
 pub mod r#async;
 
+mod indexer;
 mod reader;
+mod writer;
 
-pub use self::reader::Reader;
+pub use self::{indexer::Indexer, reader::Reader, writer::Writer};
 
 #[cfg(feature = "async")]
 pub use self::r#async::Reader as AsyncReader;
 
 use std::{
     fs::File,
-    io::{self, BufReader},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
     path::Path,
 };
 
@@ -43,3 +45,81 @@ where
     let mut reader = File::open(src).map(BufReader::new).map(Reader::new)?;
     reader.read_index()
 }
+
+/// Writes a gzip index to a file.
+///
+/// This is a convenience function and is equivalent to creating a file at the given path and
+/// writing the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = vec![(0, 0)];
+/// gzi::write("in.gz.gzi", &index)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn write<P>(dst: P, index: &Index) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut writer = File::create(dst).map(BufWriter::new).map(Writer::new)?;
+    writer.write_index(index)?;
+    writer.into_inner().flush()
+}
+
+/// Builds a gzip index from a BGZF reader.
+///
+/// The position of the stream is expected to be at the start. The entire stream is read.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf::{self as bgzf, gzi};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// writer.flush()?;
+/// writer.write_all(b"bgzf")?;
+/// let data = writer.finish()?;
+///
+/// let mut reader = bgzf::Reader::new(&data[..]);
+/// let index = gzi::index(&mut reader)?;
+/// assert_eq!(index.len(), 2);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn index<R>(reader: &mut crate::Reader<R>) -> io::Result<Index>
+where
+    R: Read,
+{
+    let mut indexer = Indexer::default();
+    let mut block: Option<(u64, u64)> = None;
+
+    loop {
+        let buf = reader.fill_buf()?;
+
+        if buf.is_empty() {
+            break;
+        }
+
+        let len = buf.len();
+        let compressed_position = reader.virtual_position().compressed();
+
+        if let Some((prev_compressed_position, prev_len)) = block.take() {
+            indexer.add_block(compressed_position - prev_compressed_position, prev_len);
+        }
+
+        block = Some((compressed_position, len as u64));
+
+        reader.consume(len);
+    }
+
+    if let Some((_, len)) = block {
+        // The compressed size of the last block does not contribute to any entry.
+        indexer.add_block(0, len);
+    }
+
+    Ok(indexer.build())
+}
@@ -0,0 +1,86 @@
+use super::Index;
+
+/// A gzip index (GZI) indexer.
+///
+/// The indexer builds a gzip index from a sequence of block sizes. Empty blocks, e.g., the BGZF
+/// EOF block, are not indexed.
+#[derive(Debug)]
+pub struct Indexer {
+    index: Index,
+    compressed_position: u64,
+    uncompressed_position: u64,
+}
+
+impl Indexer {
+    /// Adds a block to the index.
+    ///
+    /// `compressed_size` is the size of the entire block, including its header and trailer, and
+    /// `uncompressed_size` is the size of its uncompressed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let mut indexer = gzi::Indexer::default();
+    /// indexer.add_block(21, 8);
+    /// ```
+    pub fn add_block(&mut self, compressed_size: u64, uncompressed_size: u64) {
+        if uncompressed_size == 0 {
+            self.compressed_position += compressed_size;
+            return;
+        }
+
+        if self.uncompressed_position > 0 {
+            self.index
+                .push((self.compressed_position, self.uncompressed_position));
+        }
+
+        self.compressed_position += compressed_size;
+        self.uncompressed_position += uncompressed_size;
+    }
+
+    /// Builds a gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let mut indexer = gzi::Indexer::default();
+    /// indexer.add_block(21, 8);
+    /// indexer.add_block(34, 13);
+    ///
+    /// let index = indexer.build();
+    /// assert_eq!(index, [(0, 0), (21, 8)]);
+    /// ```
+    pub fn build(self) -> Index {
+        self.index
+    }
+}
+
+impl Default for Indexer {
+    fn default() -> Self {
+        Self {
+            index: vec![(0, 0)],
+            compressed_position: 0,
+            uncompressed_position: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_block() {
+        let mut indexer = Indexer::default();
+        indexer.add_block(21, 8);
+        indexer.add_block(28, 0);
+        indexer.add_block(34, 13);
+        indexer.add_block(55, 89);
+        indexer.add_block(28, 0);
+
+        assert_eq!(indexer.build(), [(0, 0), (49, 8), (83, 21)]);
+    }
+}
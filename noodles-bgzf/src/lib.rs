@@ -160,4 +160,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_self_with_gzi_index() -> io::Result<()> {
+        let data: Vec<u8> = (0..=u8::MAX).cycle().take(1 << 18).collect();
+
+        let mut writer = writer::Builder::default()
+            .set_gzi_indexing(true)
+            .build_with_writer(Vec::new());
+        writer.write_all(&data)?;
+        let (compressed_data, index) = writer.finish_with_gzi_index()?;
+        let index = index.unwrap();
+
+        assert_eq!(index.len(), 5);
+
+        let mut writer = multithreaded_writer::Builder::default()
+            .set_gzi_indexing(true)
+            .build_from_writer(Vec::new());
+        writer.write_all(&data)?;
+        let (_, multithreaded_index) = writer.finish_with_gzi_index()?;
+        assert_eq!(multithreaded_index, Some(index.clone()));
+
+        let mut reader = Reader::new(&compressed_data[..]);
+        assert_eq!(gzi::index(&mut reader)?, index);
+
+        let mut buf = Vec::new();
+        gzi::Writer::new(&mut buf).write_index(&index)?;
+        assert_eq!(gzi::Reader::new(&buf[..]).read_index()?, index);
+
+        let mut reader = Reader::new(Cursor::new(compressed_data));
+        reader.seek_by_uncompressed_position(&index, 200000)?;
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(buf, data[200000..200004]);
+
+        Ok(())
+    }
 }
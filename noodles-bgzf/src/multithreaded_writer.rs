@@ -13,7 +13,10 @@ use bytes::{Bytes, BytesMut};
 use crossbeam_channel::{Receiver, Sender};
 
 pub use self::builder::Builder;
-use super::writer::{CompressionLevelImpl, MAX_BUF_SIZE};
+use super::{
+    gzi,
+    writer::{CompressionLevelImpl, MAX_BUF_SIZE},
+};
 
 type FrameParts = (Vec<u8>, u32, usize);
 type BufferedTx = Sender<io::Result<FrameParts>>;
@@ -25,7 +28,7 @@ type WriteRx = Receiver<BufferedRx>;
 
 enum State<W> {
     Running {
-        writer_handle: JoinHandle<io::Result<W>>,
+        writer_handle: JoinHandle<io::Result<(W, Option<gzi::Index>)>>,
         deflater_handles: Vec<JoinHandle<()>>,
         write_tx: WriteTx,
         deflate_tx: DeflateTx,
@@ -91,6 +94,31 @@ where
     /// # Ok::<_, io::Error>(())
     /// ```
     pub fn finish(&mut self) -> io::Result<W> {
+        self.finish_with_gzi_index().map(|(inner, _)| inner)
+    }
+
+    /// Finishes the output stream and returns the underlying writer and gzip index.
+    ///
+    /// The gzip index is only built when enabled using [`Builder::set_gzi_indexing`];
+    /// otherwise, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, multithreaded_writer};
+    ///
+    /// let mut writer = multithreaded_writer::Builder::default()
+    ///     .set_gzi_indexing(true)
+    ///     .build_from_writer(Vec::new());
+    ///
+    /// writer.write_all(b"noodles-bgzf")?;
+    ///
+    /// let (_data, index) = writer.finish_with_gzi_index()?;
+    /// assert_eq!(index, Some(vec![(0, 0)]));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish_with_gzi_index(&mut self) -> io::Result<(W, Option<gzi::Index>)> {
         self.flush()?;
 
         let state = mem::replace(&mut self.state, State::Done);
@@ -116,7 +144,29 @@ where
         }
     }
 
-    fn remaining(&self) -> usize {
+    /// Returns the number of uncompressed bytes that can be written to the current block before it
+    /// is sent to be compressed.
+    ///
+    /// This can be used to align data boundaries to blocks by calling [`Write::flush`] when the
+    /// next write does not fit in the current block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::MultithreadedWriter::new(io::sink());
+    /// let max = writer.remaining_block_len();
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// assert_eq!(writer.remaining_block_len(), max - 7);
+    ///
+    /// writer.flush()?;
+    /// assert_eq!(writer.remaining_block_len(), max);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn remaining_block_len(&self) -> usize {
         MAX_BUF_SIZE - self.buf.len()
     }
 
@@ -162,7 +212,7 @@ where
     W: Write + Send + 'static,
 {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let amt = self.remaining().min(buf.len());
+        let amt = self.remaining_block_len().min(buf.len());
         self.buf.extend_from_slice(&buf[..amt]);
 
         if !self.has_remaining() {
@@ -181,7 +231,11 @@ where
     }
 }
 
-fn spawn_writer<W>(mut writer: W, write_rx: WriteRx) -> JoinHandle<io::Result<W>>
+fn spawn_writer<W>(
+    mut writer: W,
+    mut gzi_indexer: Option<gzi::Indexer>,
+    write_rx: WriteRx,
+) -> JoinHandle<io::Result<(W, Option<gzi::Index>)>>
 where
     W: Write + Send + 'static,
 {
@@ -191,13 +245,18 @@ where
         while let Ok(buffered_rx) = write_rx.recv() {
             if let Ok(result) = buffered_rx.recv() {
                 let (compressed_data, crc32, uncompressed_len) = result?;
-                write_frame(&mut writer, &compressed_data, crc32, uncompressed_len)?;
+                let block_size =
+                    write_frame(&mut writer, &compressed_data, crc32, uncompressed_len)?;
+
+                if let Some(indexer) = gzi_indexer.as_mut() {
+                    indexer.add_block(block_size as u64, uncompressed_len as u64);
+                }
             }
         }
 
         writer.write_all(BGZF_EOF)?;
 
-        Ok(writer)
+        Ok((writer, gzi_indexer.map(|indexer| indexer.build())))
     })
 }
 
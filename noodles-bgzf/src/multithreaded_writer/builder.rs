@@ -3,12 +3,13 @@ use std::{io::Write, num::NonZeroUsize};
 use bytes::BytesMut;
 
 use super::MultithreadedWriter;
-use crate::writer::CompressionLevel;
+use crate::{gzi, writer::CompressionLevel};
 
 /// A multithreaded BGZF writer builder.
 pub struct Builder {
     compression_level: CompressionLevel,
     worker_count: NonZeroUsize,
+    gzi_indexing: bool,
 }
 
 impl Builder {
@@ -39,6 +40,23 @@ impl Builder {
         self
     }
 
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// The index can be retrieved using [`MultithreadedWriter::finish_with_gzi_index`].
+    ///
+    /// By default, GZI indexing is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::multithreaded_writer::Builder;
+    /// let builder = Builder::default().set_gzi_indexing(true);
+    /// ```
+    pub fn set_gzi_indexing(mut self, gzi_indexing: bool) -> Self {
+        self.gzi_indexing = gzi_indexing;
+        self
+    }
+
     /// Builds a multithreaded BGZF writer from a writer.
     ///
     /// # Examples
@@ -59,7 +77,8 @@ impl Builder {
         let (write_tx, write_rx) = crossbeam_channel::bounded(worker_count);
         let (deflate_tx, deflate_rx) = crossbeam_channel::bounded(worker_count);
 
-        let writer_handle = spawn_writer(writer, write_rx);
+        let gzi_indexer = self.gzi_indexing.then(gzi::Indexer::default);
+        let writer_handle = spawn_writer(writer, gzi_indexer, write_rx);
         let deflater_handles =
             spawn_deflaters(self.compression_level, self.worker_count, deflate_rx);
 
@@ -80,6 +99,7 @@ impl Default for Builder {
         Self {
             compression_level: CompressionLevel::default(),
             worker_count: NonZeroUsize::MIN,
+            gzi_indexing: false,
         }
     }
 }
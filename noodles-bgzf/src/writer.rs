@@ -9,7 +9,7 @@ pub use self::{builder::Builder, compression_level::CompressionLevel};
 use std::io::{self, Write};
 
 pub(crate) use self::frame::write_frame;
-use super::{gz, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
+use super::{gz, gzi, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
 
 // The max DEFLATE overhead for 65536 bytes of data at compression level 0.
 //
@@ -72,6 +72,7 @@ where
     staging_buf: Vec<u8>,
     compression_buf: Vec<u8>,
     compression_level: CompressionLevelImpl,
+    gzi_indexer: Option<gzi::Indexer>,
 }
 
 impl<W> Writer<W>
@@ -165,6 +166,10 @@ where
 
         self.position += block_size as u64;
 
+        if let Some(indexer) = self.gzi_indexer.as_mut() {
+            indexer.add_block(block_size as u64, uncompressed_len as u64);
+        }
+
         self.staging_buf.clear();
 
         Ok(())
@@ -219,6 +224,38 @@ where
         Ok(inner)
     }
 
+    /// Returns the underlying writer and gzip index after finishing the output stream.
+    ///
+    /// The gzip index is only built when enabled using
+    /// [`Builder::set_gzi_indexing`]; otherwise, `None` is returned.
+    ///
+    /// This method can only be called once. Any further usage of the writer may result in a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::writer::Builder::default()
+    ///     .set_gzi_indexing(true)
+    ///     .build_with_writer(Vec::new());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"bgzf")?;
+    ///
+    /// let (_data, index) = writer.finish_with_gzi_index()?;
+    /// assert_eq!(index.map(|index| index.len()), Some(2));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn finish_with_gzi_index(mut self) -> io::Result<(W, Option<gzi::Index>)> {
+        self.try_finish()?;
+        let inner = self.inner.take().unwrap();
+        let index = self.gzi_indexer.take().map(|indexer| indexer.build());
+        Ok((inner, index))
+    }
+
     fn remaining(&self) -> usize {
         MAX_BUF_SIZE - self.staging_buf.len()
     }
@@ -302,4 +339,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_finish_with_gzi_index() -> io::Result<()> {
+        let mut writer = Builder::default()
+            .set_gzi_indexing(true)
+            .build_with_writer(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let position = writer.position();
+        writer.write_all(b"-bgzf")?;
+
+        let (_, index) = writer.finish_with_gzi_index()?;
+        assert_eq!(index, Some(vec![(0, 0), (position, 7)]));
+
+        let writer = Writer::new(Vec::new());
+        let (_, index) = writer.finish_with_gzi_index()?;
+        assert!(index.is_none());
+
+        Ok(())
+    }
 }
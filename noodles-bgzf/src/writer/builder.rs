@@ -1,11 +1,13 @@
 use std::io::Write;
 
 use super::{CompressionLevel, Writer, MAX_BUF_SIZE};
+use crate::gzi;
 
 /// A BGZF writer builder.
 #[derive(Debug, Default)]
 pub struct Builder {
     compression_level: CompressionLevel,
+    gzi_indexing: bool,
 }
 
 impl Builder {
@@ -26,6 +28,24 @@ impl Builder {
         self
     }
 
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// When enabled, the compressed and uncompressed offsets of each written block are recorded.
+    /// The index can be retrieved using [`Writer::finish_with_gzi_index`].
+    ///
+    /// By default, GZI indexing is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::writer::Builder::default().set_gzi_indexing(true);
+    /// ```
+    pub fn set_gzi_indexing(mut self, gzi_indexing: bool) -> Self {
+        self.gzi_indexing = gzi_indexing;
+        self
+    }
+
     /// Builds a BGZF writer from a writer.
     ///
     /// # Examples
@@ -45,6 +65,7 @@ impl Builder {
             staging_buf: Vec::with_capacity(MAX_BUF_SIZE),
             compression_buf: Vec::new(),
             compression_level: self.compression_level.into(),
+            gzi_indexer: self.gzi_indexing.then(gzi::Indexer::default),
         }
     }
 }
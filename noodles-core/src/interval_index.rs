@@ -0,0 +1,320 @@
+//! In-memory interval index.
+
+mod builder;
+mod query;
+
+pub use self::{builder::Builder, query::Query};
+
+use std::collections::HashMap;
+
+use bstr::BString;
+
+use crate::{ops::Feature, region::Interval, Position, Region};
+
+/// An in-memory interval index.
+///
+/// This is an implicit interval tree (as in cgranges) per reference sequence. The intervals are
+/// stored in a flat list sorted by start position, where each node additionally holds the maximum
+/// end position of its subtree. Overlap queries take O(log n + k) time, where k is the number of
+/// overlapping intervals.
+///
+/// An index can be collected from a list of [`Feature`]s, e.g., the records of a BED, GFF, or GTF
+/// reader (`reader.records::<3>().collect::<io::Result<IntervalIndex<_>>>()`), or created with a
+/// [`Builder`] to attach arbitrary values.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{interval_index::IntervalIndex, Position, Region};
+///
+/// let index: IntervalIndex<_> = [
+///     Region::new("sq0", Position::try_from(5)?..=Position::try_from(13)?),
+///     Region::new("sq0", Position::try_from(8)?..=Position::try_from(21)?),
+///     Region::new("sq1", Position::try_from(1)?..=Position::try_from(8)?),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let region = "sq0:1-5".parse()?;
+/// let features: Vec<_> = index.query(&region).map(|(_, feature)| feature).collect();
+/// assert_eq!(
+///     features,
+///     [&Region::new("sq0", Position::try_from(5)?..=Position::try_from(13)?)]
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntervalIndex<T> {
+    trees: HashMap<BString, Tree<T>>,
+}
+
+impl<T> IntervalIndex<T> {
+    /// Returns a builder to create an interval index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_index::IntervalIndex;
+    /// let builder = IntervalIndex::<()>::builder();
+    /// ```
+    pub fn builder() -> Builder<T> {
+        Builder::default()
+    }
+
+    /// Returns the number of intervals in the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_index::IntervalIndex;
+    /// let index = IntervalIndex::<()>::builder().build();
+    /// assert_eq!(index.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.trees.values().map(|tree| tree.nodes.len()).sum()
+    }
+
+    /// Returns whether the index has no intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_index::IntervalIndex;
+    /// let index = IntervalIndex::<()>::builder().build();
+    /// assert!(index.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.trees.values().all(|tree| tree.nodes.is_empty())
+    }
+
+    /// Returns an iterator over the intervals and values that intersect the given region.
+    ///
+    /// The results are sorted by start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_index::IntervalIndex, region::Interval, Position};
+    ///
+    /// let interval = Interval::from(Position::try_from(8)?..=Position::try_from(13)?);
+    ///
+    /// let index = IntervalIndex::builder()
+    ///     .add_interval("sq0", interval, "ndls")
+    ///     .build();
+    ///
+    /// let region = "sq0:13-21".parse()?;
+    /// let mut query = index.query(&region);
+    /// assert_eq!(query.next(), Some((interval, &"ndls")));
+    /// assert!(query.next().is_none());
+    ///
+    /// let region = "sq1".parse()?;
+    /// assert!(index.query(&region).next().is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&self, region: &Region) -> Query<'_, T> {
+        let interval = region.interval();
+        let start = interval.start().unwrap_or(Position::MIN);
+        let end = interval.end().unwrap_or(Position::MAX);
+
+        let tree = self.trees.get(region.name().as_ref() as &[u8]);
+
+        Query::new(tree, usize::from(start), usize::from(end))
+    }
+}
+
+impl<T> FromIterator<T> for IntervalIndex<T>
+where
+    T: Feature,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut builder = Builder::default();
+
+        for feature in iter {
+            let name = feature.reference_sequence_name().to_owned();
+            let interval = feature.interval();
+            builder = builder.add_interval(name, interval, feature);
+        }
+
+        builder.build()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Node {
+    start: usize,
+    end: usize,
+    // The maximum end position of the subtree rooted at this node.
+    max_end: usize,
+}
+
+impl Node {
+    fn interval(&self) -> Interval {
+        // SAFETY: Node positions are created from `Position`s.
+        let start = Position::new(self.start).unwrap();
+        let end = Position::new(self.end).unwrap();
+        Interval::from(start..=end)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Tree<T> {
+    nodes: Vec<Node>,
+    values: Vec<T>,
+    max_level: usize,
+}
+
+impl<T> Tree<T> {
+    fn new(mut entries: Vec<(usize, usize, T)>) -> Self {
+        entries.sort_by_key(|(start, end, _)| (*start, *end));
+
+        let (nodes, values) = entries
+            .into_iter()
+            .map(|(start, end, value)| {
+                let node = Node {
+                    start,
+                    end,
+                    max_end: end,
+                };
+
+                (node, value)
+            })
+            .unzip();
+
+        let mut tree = Self {
+            nodes,
+            values,
+            max_level: 0,
+        };
+
+        tree.index();
+
+        tree
+    }
+
+    // Sets the maximum end positions of the internal nodes.
+    //
+    // A node at index i is at level k, where k is the number of trailing 1 bits of i. Leaves are at
+    // even indices.
+    fn index(&mut self) {
+        let nodes = &mut self.nodes;
+        let n = nodes.len();
+
+        if n == 0 {
+            return;
+        }
+
+        // The last node at each level and the maximum end position of its subtree. This stands in
+        // for the right child of nodes whose right subtree is out of bounds.
+        let mut last_i = 0;
+        let mut last_max_end = 0;
+
+        for i in (0..n).step_by(2) {
+            last_i = i;
+            last_max_end = nodes[i].end;
+        }
+
+        let mut k = 1;
+
+        while 1 << k <= n {
+            let x = 1 << (k - 1);
+            let i0 = (x << 1) - 1;
+            let step = x << 2;
+
+            for i in (i0..n).step_by(step) {
+                let left_max_end = nodes[i - x].max_end;
+
+                let right_max_end = if i + x < n {
+                    nodes[i + x].max_end
+                } else {
+                    last_max_end
+                };
+
+                nodes[i].max_end = nodes[i].end.max(left_max_end).max(right_max_end);
+            }
+
+            last_i = if (last_i >> k) & 1 == 1 {
+                last_i - x
+            } else {
+                last_i + x
+            };
+
+            if last_i < n {
+                last_max_end = last_max_end.max(nodes[last_i].max_end);
+            }
+
+            k += 1;
+        }
+
+        self.max_level = k - 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        // Compare against a linear scan for a range of sizes, including ones that are not powers
+        // of two.
+        for n in [0, 1, 2, 3, 7, 8, 9, 31, 100, 257] {
+            let intervals: Vec<_> = (0..n)
+                .map(|i| {
+                    let start = (i * 7919) % 1000 + 1;
+                    let end = start + (i * 104_729) % 50;
+                    (start, end)
+                })
+                .collect();
+
+            let mut builder = IntervalIndex::builder();
+
+            for (i, &(start, end)) in intervals.iter().enumerate() {
+                let start = Position::new(start).unwrap();
+                let end = Position::new(end).unwrap();
+                builder = builder.add_interval("sq0", start..=end, i);
+            }
+
+            let index = builder.build();
+            assert_eq!(index.len(), n);
+
+            for (query_start, query_end) in [(1, 1), (1, 1000), (100, 150), (500, 500), (990, 2000)]
+            {
+                let region = Region::new(
+                    "sq0",
+                    Position::new(query_start).unwrap()..=Position::new(query_end).unwrap(),
+                );
+
+                let mut actual: Vec<_> = index.query(&region).map(|(_, &i)| i).collect();
+                actual.sort_unstable();
+
+                let expected: Vec<_> = intervals
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &(start, end))| start <= query_end && query_start <= end)
+                    .map(|(i, _)| i)
+                    .collect();
+
+                assert_eq!(
+                    actual, expected,
+                    "n = {n}, query = {query_start}-{query_end}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_query_order() -> Result<(), crate::position::TryFromIntError> {
+        let index = IntervalIndex::builder()
+            .add_interval("sq0", Position::try_from(21)?..=Position::try_from(34)?, 2)
+            .add_interval("sq0", Position::try_from(5)?..=Position::try_from(55)?, 0)
+            .add_interval("sq0", Position::try_from(8)?..=Position::try_from(13)?, 1)
+            .add_interval("sq1", Position::try_from(8)?..=Position::try_from(13)?, 3)
+            .build();
+
+        let region = Region::new("sq0", ..);
+        let actual: Vec<_> = index.query(&region).map(|(_, &i)| i).collect();
+        assert_eq!(actual, [0, 1, 2]);
+
+        Ok(())
+    }
+}
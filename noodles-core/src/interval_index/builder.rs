@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use bstr::BString;
+
+use super::{IntervalIndex, Tree};
+use crate::{region::Interval, Position};
+
+/// An interval index builder.
+#[derive(Debug)]
+pub struct Builder<T> {
+    entries: HashMap<BString, Vec<(usize, usize, T)>>,
+}
+
+impl<T> Builder<T> {
+    /// Adds an interval with a value on the given reference sequence.
+    ///
+    /// An unbounded start or end is treated as the start or end of the reference sequence,
+    /// respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{interval_index::IntervalIndex, Position};
+    ///
+    /// let index = IntervalIndex::builder()
+    ///     .add_interval("sq0", Position::try_from(8)?..=Position::try_from(13)?, "ndls")
+    ///     .build();
+    ///
+    /// assert_eq!(index.len(), 1);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn add_interval<N, I>(mut self, name: N, interval: I, value: T) -> Self
+    where
+        N: Into<BString>,
+        I: Into<Interval>,
+    {
+        let interval = interval.into();
+        let start = interval.start().unwrap_or(Position::MIN);
+        let end = interval.end().unwrap_or(Position::MAX);
+
+        self.entries.entry(name.into()).or_default().push((
+            usize::from(start),
+            usize::from(end),
+            value,
+        ));
+
+        self
+    }
+
+    /// Builds an interval index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::interval_index::IntervalIndex;
+    /// let index = IntervalIndex::<()>::builder().build();
+    /// ```
+    pub fn build(self) -> IntervalIndex<T> {
+        let trees = self
+            .entries
+            .into_iter()
+            .map(|(name, entries)| (name, Tree::new(entries)))
+            .collect();
+
+        IntervalIndex { trees }
+    }
+}
+
+impl<T> Default for Builder<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
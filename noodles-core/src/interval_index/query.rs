@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use super::Tree;
+use crate::region::Interval;
+
+// Subtrees at or below this level are scanned linearly.
+const MAX_SCAN_LEVEL: usize = 3;
+
+struct StackFrame {
+    level: usize,
+    i: usize,
+    is_left_visited: bool,
+}
+
+/// An iterator over the intervals and values of an interval index that intersect a region.
+///
+/// This is created by calling [`super::IntervalIndex::query`].
+pub struct Query<'a, T> {
+    tree: Option<&'a Tree<T>>,
+    start: usize,
+    end: usize,
+    stack: Vec<StackFrame>,
+    scan: Range<usize>,
+}
+
+impl<'a, T> Query<'a, T> {
+    pub(super) fn new(tree: Option<&'a Tree<T>>, start: usize, end: usize) -> Self {
+        let stack = match tree {
+            Some(tree) if !tree.nodes.is_empty() => {
+                let level = tree.max_level;
+
+                vec![StackFrame {
+                    level,
+                    i: (1 << level) - 1,
+                    is_left_visited: false,
+                }]
+            }
+            _ => Vec::new(),
+        };
+
+        Self {
+            tree,
+            start,
+            end,
+            stack,
+            scan: 0..0,
+        }
+    }
+
+    fn get(&self, tree: &'a Tree<T>, i: usize) -> (Interval, &'a T) {
+        (tree.nodes[i].interval(), &tree.values[i])
+    }
+}
+
+impl<'a, T> Iterator for Query<'a, T> {
+    type Item = (Interval, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.tree?;
+        let nodes = &tree.nodes;
+        let n = nodes.len();
+
+        loop {
+            while let Some(i) = self.scan.next() {
+                let node = &nodes[i];
+
+                if node.start > self.end {
+                    self.scan = 0..0;
+                    break;
+                }
+
+                if self.start <= node.end {
+                    return Some(self.get(tree, i));
+                }
+            }
+
+            let frame = self.stack.pop()?;
+
+            if frame.level <= MAX_SCAN_LEVEL {
+                let i0 = (frame.i >> frame.level) << frame.level;
+                let i1 = (i0 + (1 << (frame.level + 1)) - 1).min(n);
+                self.scan = i0..i1;
+            } else if !frame.is_left_visited {
+                let left_i = frame.i - (1 << (frame.level - 1));
+
+                self.stack.push(StackFrame {
+                    is_left_visited: true,
+                    ..frame
+                });
+
+                // The left child may be out of bounds, in which case its subtree is still
+                // visited for in-bounds descendants.
+                if left_i >= n || nodes[left_i].max_end >= self.start {
+                    self.stack.push(StackFrame {
+                        level: frame.level - 1,
+                        i: left_i,
+                        is_left_visited: false,
+                    });
+                }
+            } else if frame.i < n && nodes[frame.i].start <= self.end {
+                self.stack.push(StackFrame {
+                    level: frame.level - 1,
+                    i: frame.i + (1 << (frame.level - 1)),
+                    is_left_visited: false,
+                });
+
+                if self.start <= nodes[frame.i].end {
+                    return Some(self.get(tree, frame.i));
+                }
+            }
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 //! **noodles-core** contains shared structures and behavior among noodles libraries.
 
+pub mod interval_index;
+pub mod ops;
 pub mod position;
 pub mod region;
 
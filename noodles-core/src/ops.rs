@@ -0,0 +1,45 @@
+//! Interval arithmetic on sorted feature streams.
+//!
+//! These are bedtools-style operations (intersect, window, subtract, merge, complement, closest,
+//! and coverage) over streams of [`Feature`]s. Each operation is a sweep over its inputs, reading
+//! each one once, and only holds the features that can still overlap the current position.
+//!
+//! Inputs are iterators of `io::Result<T>`, e.g., the record iterators of the format readers.
+//! Features must be sorted by reference sequence and start position. For operations on two
+//! streams, both must use the reference sequence order given by [`ReferenceSequences`], e.g., the
+//! `@SQ` order of a SAM header or the `##contig` order of a VCF header. An unsorted stream or an
+//! unknown reference sequence name is an [`io::ErrorKind::InvalidData`] error.
+//!
+//! [`io::ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+
+mod closest;
+mod complement;
+mod coverage;
+mod feature;
+mod intersect;
+mod merge;
+mod reference_sequences;
+mod stream;
+mod subtract;
+
+pub use self::{
+    closest::{closest, Closest},
+    complement::{complement, Complement},
+    coverage::{coverage, Coverage, CoverageSummary},
+    feature::{distance, Feature, Located},
+    intersect::{intersect, window, Intersect},
+    merge::{merge, Merge},
+    reference_sequences::ReferenceSequences,
+    subtract::{subtract, Subtract},
+};
+
+#[cfg(test)]
+mod tests {
+    use crate::{Position, Region};
+
+    pub(super) fn region(name: &str, start: usize, end: usize) -> Region {
+        let start = Position::new(start).unwrap();
+        let end = Position::new(end).unwrap();
+        Region::new(name, start..=end)
+    }
+}
@@ -0,0 +1,188 @@
+use std::io;
+
+use super::{
+    feature::span_distance,
+    stream::{Entry, Stream},
+    Feature, ReferenceSequences,
+};
+
+/// An iterator over features and their closest features.
+///
+/// This is created by calling [`closest`].
+pub struct Closest<'r, A, T, B, U> {
+    a: Stream<'r, A, T>,
+    b: Stream<'r, B, U>,
+    buf: Vec<Entry<U>>,
+}
+
+impl<'r, A, T, B, U> Closest<'r, A, T, B, U>
+where
+    A: Iterator<Item = io::Result<T>>,
+    T: Feature,
+    B: Iterator<Item = io::Result<U>>,
+    U: Feature + Clone,
+{
+    fn next_closest(&mut self) -> io::Result<Option<(T, Vec<U>)>> {
+        let Some(a) = self.a.next_entry()? else {
+            return Ok(None);
+        };
+
+        let id = a.reference_sequence_id;
+
+        self.buf.retain(|b| b.reference_sequence_id == id);
+
+        // Read the features that start at or before the end of `a` and the first features that
+        // start after it.
+        let mut downstream_start = None;
+
+        while let Some(b) = self.b.peek()? {
+            if b.reference_sequence_id > id {
+                break;
+            } else if b.reference_sequence_id == id && b.start > a.end {
+                match downstream_start {
+                    Some(start) if b.start > start => break,
+                    _ => downstream_start = Some(b.start),
+                }
+            }
+
+            let b = self.b.next_entry()?.expect("missing peeked entry");
+
+            if b.reference_sequence_id == id {
+                self.buf.push(b);
+            }
+        }
+
+        let distance = |b: &Entry<U>| span_distance(a.start, a.end, b.start, b.end);
+
+        let min_distance = self.buf.iter().map(distance).min();
+
+        let hits = self
+            .buf
+            .iter()
+            .filter(|b| Some(distance(b)) == min_distance)
+            .map(|b| b.feature.clone())
+            .collect();
+
+        // Of the features that end before `a`, only the ones that end last can be the closest to
+        // the remaining features in `a`.
+        let max_upstream_end = self
+            .buf
+            .iter()
+            .filter(|b| b.end < a.start)
+            .map(|b| b.end)
+            .max();
+
+        if let Some(max_upstream_end) = max_upstream_end {
+            self.buf
+                .retain(|b| b.end >= a.start || b.end == max_upstream_end);
+        }
+
+        Ok(Some((a.feature, hits)))
+    }
+}
+
+impl<'r, A, T, B, U> Iterator for Closest<'r, A, T, B, U>
+where
+    A: Iterator<Item = io::Result<T>>,
+    T: Feature,
+    B: Iterator<Item = io::Result<U>>,
+    U: Feature + Clone,
+{
+    type Item = io::Result<(T, Vec<U>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_closest().transpose()
+    }
+}
+
+/// Returns an iterator over each feature in `a` and the closest features in `b`.
+///
+/// Overlapping features are the closest. All features tied for the closest distance are returned
+/// (`bedtools closest -t all`), and the list is empty when there are no features in `b` on the
+/// same reference sequence. Use [`super::distance`] to get the distance.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::{ops::{self, ReferenceSequences}, Position, Region};
+///
+/// let reference_sequences: ReferenceSequences = [("sq0", 55)].into_iter().collect();
+/// let a = [Region::new("sq0", Position::try_from(13)?..=Position::try_from(21)?)];
+/// let b = [
+///     Region::new("sq0", Position::try_from(1)?..=Position::try_from(5)?),
+///     Region::new("sq0", Position::try_from(34)?..=Position::try_from(55)?),
+/// ];
+///
+/// let mut iter = ops::closest(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok));
+/// assert_eq!(iter.next().transpose()?, Some((&a[0], vec![&b[0]])));
+/// assert!(iter.next().is_none());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn closest<A, T, B, U>(
+    reference_sequences: &ReferenceSequences,
+    a: A,
+    b: B,
+) -> Closest<'_, A::IntoIter, T, B::IntoIter, U>
+where
+    A: IntoIterator<Item = io::Result<T>>,
+    T: Feature,
+    B: IntoIterator<Item = io::Result<U>>,
+    U: Feature + Clone,
+{
+    Closest {
+        a: Stream::new(a.into_iter(), reference_sequences),
+        b: Stream::new(b.into_iter(), reference_sequences),
+        buf: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::tests::region;
+
+    #[test]
+    fn test_closest() -> io::Result<()> {
+        let reference_sequences: ReferenceSequences = [("sq0", 89), ("sq1", 89), ("sq2", 89)]
+            .into_iter()
+            .collect();
+
+        let a = [
+            region("sq0", 8, 13),
+            region("sq0", 21, 21),
+            region("sq0", 34, 55),
+            region("sq0", 89, 89),
+            region("sq1", 1, 8),
+            region("sq2", 5, 8),
+        ];
+
+        let b = [
+            region("sq0", 1, 5),
+            region("sq0", 2, 5),
+            region("sq0", 16, 18),
+            region("sq0", 24, 24),
+            region("sq0", 34, 34),
+            region("sq0", 55, 89),
+            region("sq2", 13, 21),
+            region("sq2", 13, 34),
+            region("sq2", 21, 34),
+        ];
+
+        let actual: Vec<_> = closest(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (&a[0], vec![&b[0], &b[1], &b[2]]),
+            (&a[1], vec![&b[2], &b[3]]),
+            (&a[2], vec![&b[4], &b[5]]),
+            (&a[3], vec![&b[5]]),
+            (&a[4], Vec::new()),
+            (&a[5], vec![&b[6], &b[7]]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use std::io;
+
+use super::{feature::to_region, stream::Stream, Feature, ReferenceSequences};
+use crate::{Position, Region};
+
+/// An iterator over regions not covered by features.
+///
+/// This is created by calling [`complement`].
+pub struct Complement<'r, I, T> {
+    reference_sequences: &'r ReferenceSequences,
+    features: Stream<'r, I, T>,
+    reference_sequence_id: usize,
+    position: Option<Position>,
+}
+
+impl<'r, I, T> Complement<'r, I, T>
+where
+    I: Iterator<Item = io::Result<T>>,
+    T: Feature,
+{
+    fn next_region(&mut self) -> io::Result<Option<Region>> {
+        while let Some((name, length)) = self
+            .reference_sequences
+            .get_index(self.reference_sequence_id)
+        {
+            let entry = match self.features.peek()? {
+                Some(entry) if entry.reference_sequence_id == self.reference_sequence_id => {
+                    self.features.next_entry()?.expect("missing peeked entry")
+                }
+                _ => {
+                    let start = self.position.take();
+
+                    self.reference_sequence_id += 1;
+                    self.position = Some(Position::MIN);
+
+                    match (start, Position::new(length)) {
+                        (Some(start), Some(end)) if start <= end => {
+                            return Ok(Some(to_region(name, start, end)));
+                        }
+                        _ => continue,
+                    }
+                }
+            };
+
+            let Some(start) = self.position else {
+                continue;
+            };
+
+            if entry.start > start {
+                // SAFETY: `entry.start` > `start` >= 1.
+                let end = Position::new(usize::from(entry.start) - 1).unwrap();
+                let end = Position::new(length).map(|n| n.min(end));
+
+                if entry.end >= start {
+                    self.position = entry.end.checked_add(1);
+                }
+
+                if let Some(end) = end.filter(|&end| start <= end) {
+                    return Ok(Some(to_region(name, start, end)));
+                }
+            } else if entry.end >= start {
+                self.position = entry.end.checked_add(1);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<'r, I, T> Iterator for Complement<'r, I, T>
+where
+    I: Iterator<Item = io::Result<T>>,
+    T: Feature,
+{
+    type Item = io::Result<Region>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_region().transpose()
+    }
+}
+
+/// Returns an iterator over regions of the reference sequences not covered by features.
+///
+/// Reference sequences with no features are returned whole (`bedtools complement`).
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::{ops::{self, ReferenceSequences}, Position, Region};
+///
+/// let reference_sequences: ReferenceSequences = [("sq0", 21), ("sq1", 8)].into_iter().collect();
+/// let features = [Region::new("sq0", Position::try_from(5)?..=Position::try_from(13)?)];
+///
+/// let regions: Vec<_> = ops::complement(&reference_sequences, features.iter().map(Ok))
+///     .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(
+///     regions,
+///     [
+///         Region::new("sq0", Position::try_from(1)?..=Position::try_from(4)?),
+///         Region::new("sq0", Position::try_from(14)?..=Position::try_from(21)?),
+///         Region::new("sq1", Position::try_from(1)?..=Position::try_from(8)?),
+///     ]
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn complement<I, T>(
+    reference_sequences: &ReferenceSequences,
+    features: I,
+) -> Complement<'_, I::IntoIter, T>
+where
+    I: IntoIterator<Item = io::Result<T>>,
+    T: Feature,
+{
+    Complement {
+        reference_sequences,
+        features: Stream::new(features.into_iter(), reference_sequences),
+        reference_sequence_id: 0,
+        position: Some(Position::MIN),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::tests::region;
+
+    #[test]
+    fn test_complement() -> io::Result<()> {
+        let reference_sequences: ReferenceSequences =
+            [("sq0", 34), ("sq1", 8), ("sq2", 13), ("sq3", 5)]
+                .into_iter()
+                .collect();
+
+        let features = [
+            region("sq0", 1, 5),
+            region("sq0", 3, 8),
+            region("sq0", 5, 6),
+            region("sq0", 13, 21),
+            region("sq2", 8, 21),
+        ];
+
+        let actual: Vec<_> =
+            complement(&reference_sequences, features.iter().map(Ok)).collect::<io::Result<_>>()?;
+
+        let expected = [
+            region("sq0", 9, 12),
+            region("sq0", 22, 34),
+            region("sq1", 1, 8),
+            region("sq2", 1, 7),
+            region("sq3", 1, 5),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use std::io;
+
+use super::{intersect::Intersect, window, Feature, ReferenceSequences};
+
+/// A coverage summary of a feature.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CoverageSummary {
+    count: usize,
+    bases_covered: usize,
+    length: usize,
+}
+
+impl CoverageSummary {
+    /// Returns the number of features that intersect the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::CoverageSummary;
+    /// let summary = CoverageSummary::default();
+    /// assert_eq!(summary.count(), 0);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the number of bases in the feature covered by at least one feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::CoverageSummary;
+    /// let summary = CoverageSummary::default();
+    /// assert_eq!(summary.bases_covered(), 0);
+    /// ```
+    pub fn bases_covered(&self) -> usize {
+        self.bases_covered
+    }
+
+    /// Returns the length of the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::CoverageSummary;
+    /// let summary = CoverageSummary::default();
+    /// assert_eq!(summary.length(), 0);
+    /// ```
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the fraction of bases in the feature that are covered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::CoverageSummary;
+    /// let summary = CoverageSummary::default();
+    /// assert_eq!(summary.fraction_covered(), 0.0);
+    /// ```
+    pub fn fraction_covered(&self) -> f64 {
+        if self.length == 0 {
+            0.0
+        } else {
+            self.bases_covered as f64 / self.length as f64
+        }
+    }
+}
+
+/// An iterator over features and their coverage by other features.
+///
+/// This is created by calling [`coverage`].
+pub struct Coverage<'r, A, T, B, U>(Intersect<'r, A, T, B, U>);
+
+impl<'r, A, T, B, U> Iterator for Coverage<'r, A, T, B, U>
+where
+    A: Iterator<Item = io::Result<T>>,
+    T: Feature,
+    B: Iterator<Item = io::Result<U>>,
+    U: Feature,
+{
+    type Item = io::Result<(T, CoverageSummary)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (a, hits) = match self.0.next_entry() {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let a_start = usize::from(a.start);
+        let a_end = usize::from(a.end);
+
+        let mut bases_covered = 0;
+        let mut position = a_start;
+
+        // Hits are sorted by start position.
+        for b in &hits {
+            let start = usize::from(b.start).max(position);
+            let end = usize::from(b.end).min(a_end);
+
+            if start <= end {
+                bases_covered += end - start + 1;
+                position = end + 1;
+            }
+        }
+
+        let summary = CoverageSummary {
+            count: hits.len(),
+            bases_covered,
+            length: a_end - a_start + 1,
+        };
+
+        Some(Ok((a.feature, summary)))
+    }
+}
+
+/// Returns an iterator over each feature in `a` and a summary of its coverage by features in `b`
+/// (`bedtools coverage`).
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::{ops::{self, ReferenceSequences}, Position, Region};
+///
+/// let reference_sequences: ReferenceSequences = [("sq0", 34)].into_iter().collect();
+/// let a = [Region::new("sq0", Position::try_from(5)?..=Position::try_from(14)?)];
+/// let b = [
+///     Region::new("sq0", Position::try_from(1)?..=Position::try_from(8)?),
+///     Region::new("sq0", Position::try_from(8)?..=Position::try_from(10)?),
+/// ];
+///
+/// let mut iter = ops::coverage(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok));
+///
+/// let (_, summary) = iter.next().transpose()?.unwrap();
+/// assert_eq!(summary.count(), 2);
+/// assert_eq!(summary.bases_covered(), 6);
+/// assert_eq!(summary.length(), 10);
+/// assert_eq!(summary.fraction_covered(), 0.6);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn coverage<A, T, B, U>(
+    reference_sequences: &ReferenceSequences,
+    a: A,
+    b: B,
+) -> Coverage<'_, A::IntoIter, T, B::IntoIter, U>
+where
+    A: IntoIterator<Item = io::Result<T>>,
+    T: Feature,
+    B: IntoIterator<Item = io::Result<U>>,
+    U: Feature,
+{
+    Coverage(window(reference_sequences, a, b, 0))
+}
@@ -0,0 +1,186 @@
+use bstr::BStr;
+
+use crate::{region::Interval, Position, Region};
+
+/// A genomic feature.
+///
+/// A feature is anything that lies on a reference sequence, e.g., a BED, GFF, or GTF record.
+pub trait Feature {
+    /// Returns the reference sequence name.
+    fn reference_sequence_name(&self) -> &BStr;
+
+    /// Returns the interval.
+    ///
+    /// An unbounded start or end is treated as the start or end of the reference sequence,
+    /// respectively.
+    fn interval(&self) -> Interval;
+}
+
+impl Feature for Region {
+    fn reference_sequence_name(&self) -> &BStr {
+        self.name()
+    }
+
+    fn interval(&self) -> Interval {
+        self.interval()
+    }
+}
+
+impl<T> Feature for &T
+where
+    T: Feature + ?Sized,
+{
+    fn reference_sequence_name(&self) -> &BStr {
+        (**self).reference_sequence_name()
+    }
+
+    fn interval(&self) -> Interval {
+        (**self).interval()
+    }
+}
+
+/// A value with a genomic region.
+///
+/// This attaches a region to a value that does not carry its own reference sequence name, e.g.,
+/// an alignment record, whose reference sequence name is resolved using the header.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{ops::{Feature, Located}, Position, Region};
+///
+/// let region = Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?);
+/// let located = Located::new(region, "ndls");
+///
+/// assert_eq!(located.reference_sequence_name(), "sq0");
+/// assert_eq!(located.value(), &"ndls");
+/// # Ok::<_, noodles_core::position::TryFromIntError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Located<T> {
+    region: Region,
+    value: T,
+}
+
+impl<T> Located<T> {
+    /// Creates a value with a genomic region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{ops::Located, Region};
+    /// let located = Located::new(Region::new("sq0", ..), ());
+    /// ```
+    pub fn new(region: Region, value: T) -> Self {
+        Self { region, value }
+    }
+
+    /// Returns the region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{ops::Located, Region};
+    /// let located = Located::new(Region::new("sq0", ..), ());
+    /// assert_eq!(located.region(), &Region::new("sq0", ..));
+    /// ```
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// Returns the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{ops::Located, Region};
+    /// let located = Located::new(Region::new("sq0", ..), 8);
+    /// assert_eq!(located.value(), &8);
+    /// ```
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the value, consuming the region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{ops::Located, Region};
+    /// let located = Located::new(Region::new("sq0", ..), 8);
+    /// assert_eq!(located.into_value(), 8);
+    /// ```
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Feature for Located<T> {
+    fn reference_sequence_name(&self) -> &BStr {
+        self.region.name()
+    }
+
+    fn interval(&self) -> Interval {
+        self.region.interval()
+    }
+}
+
+/// Returns the distance between two features.
+///
+/// Overlapping features have a distance of 0, and adjacent features, a distance of 1. This returns
+/// `None` if the features are on different reference sequences.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{ops::distance, Position, Region};
+///
+/// let a = Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?);
+/// let b = Region::new("sq0", Position::try_from(13)?..=Position::try_from(21)?);
+/// assert_eq!(distance(&a, &b), Some(5));
+/// assert_eq!(distance(&b, &a), Some(5));
+///
+/// let c = Region::new("sq1", Position::try_from(5)?..=Position::try_from(8)?);
+/// assert!(distance(&a, &c).is_none());
+/// # Ok::<_, noodles_core::position::TryFromIntError>(())
+/// ```
+pub fn distance<A, B>(a: &A, b: &B) -> Option<usize>
+where
+    A: Feature + ?Sized,
+    B: Feature + ?Sized,
+{
+    if a.reference_sequence_name() != b.reference_sequence_name() {
+        return None;
+    }
+
+    let (a_start, a_end) = resolve_interval(a.interval());
+    let (b_start, b_end) = resolve_interval(b.interval());
+
+    Some(span_distance(a_start, a_end, b_start, b_end))
+}
+
+pub(super) fn resolve_interval(interval: Interval) -> (Position, Position) {
+    (
+        interval.start().unwrap_or(Position::MIN),
+        interval.end().unwrap_or(Position::MAX),
+    )
+}
+
+pub(super) fn span_distance(
+    a_start: Position,
+    a_end: Position,
+    b_start: Position,
+    b_end: Position,
+) -> usize {
+    if b_end < a_start {
+        usize::from(a_start) - usize::from(b_end)
+    } else if a_end < b_start {
+        usize::from(b_start) - usize::from(a_end)
+    } else {
+        0
+    }
+}
+
+pub(super) fn to_region(name: &BStr, start: Position, end: Position) -> Region {
+    Region::new(name.to_vec(), start..=end)
+}
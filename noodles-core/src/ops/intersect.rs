@@ -0,0 +1,254 @@
+use std::{collections::VecDeque, io};
+
+use super::{stream::Entry, stream::Stream, Feature, ReferenceSequences};
+
+type Hits<'a, T, U> = (Entry<T>, Vec<&'a Entry<U>>);
+
+/// An iterator over features and the features they intersect.
+///
+/// This is created by calling [`intersect`] or [`window`].
+pub struct Intersect<'r, A, T, B, U> {
+    a: Stream<'r, A, T>,
+    b: Stream<'r, B, U>,
+    distance: usize,
+    buf: VecDeque<Entry<U>>,
+}
+
+impl<'r, A, T, B, U> Intersect<'r, A, T, B, U>
+where
+    A: Iterator<Item = io::Result<T>>,
+    T: Feature,
+    B: Iterator<Item = io::Result<U>>,
+    U: Feature,
+{
+    fn new(reference_sequences: &'r ReferenceSequences, a: A, b: B, distance: usize) -> Self {
+        Self {
+            a: Stream::new(a, reference_sequences),
+            b: Stream::new(b, reference_sequences),
+            distance,
+            buf: VecDeque::new(),
+        }
+    }
+
+    // Returns the next feature in `a` and the features in `b` that are within the distance, in
+    // order.
+    pub(super) fn next_entry(&mut self) -> io::Result<Option<Hits<'_, T, U>>> {
+        let Some(a) = self.a.next_entry()? else {
+            return Ok(None);
+        };
+
+        let id = a.reference_sequence_id;
+        let lo = usize::from(a.start).saturating_sub(self.distance);
+        let hi = usize::from(a.end).saturating_add(self.distance);
+
+        // Starts are nondecreasing, so features that end before `lo` cannot intersect any of the
+        // remaining features in `a`.
+        self.buf
+            .retain(|b| b.reference_sequence_id == id && usize::from(b.end) >= lo);
+
+        while let Some(b) = self.b.peek()? {
+            if b.reference_sequence_id > id
+                || (b.reference_sequence_id == id && usize::from(b.start) > hi)
+            {
+                break;
+            }
+
+            let b = self.b.next_entry()?.expect("missing peeked entry");
+
+            if b.reference_sequence_id == id && usize::from(b.end) >= lo {
+                self.buf.push_back(b);
+            }
+        }
+
+        let hits = self
+            .buf
+            .iter()
+            .filter(|b| usize::from(b.start) <= hi)
+            .collect();
+
+        Ok(Some((a, hits)))
+    }
+}
+
+impl<'r, A, T, B, U> Iterator for Intersect<'r, A, T, B, U>
+where
+    A: Iterator<Item = io::Result<T>>,
+    T: Feature,
+    B: Iterator<Item = io::Result<U>>,
+    U: Feature + Clone,
+{
+    type Item = io::Result<(T, Vec<U>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_entry() {
+            Ok(Some((a, hits))) => {
+                let hits = hits.into_iter().map(|b| b.feature.clone()).collect();
+                Some(Ok((a.feature, hits)))
+            }
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Returns an iterator over each feature in `a` and the features in `b` it intersects.
+///
+/// Every feature in `a` is returned, including those with no intersecting features, i.e., filter
+/// on a nonempty list to get the features in `a` that intersect `b` (`bedtools intersect -u`) or
+/// an empty list to get those that do not (`bedtools intersect -v`).
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::{ops::{self, ReferenceSequences}, Position, Region};
+///
+/// let reference_sequences: ReferenceSequences = [("sq0", 34)].into_iter().collect();
+///
+/// let a = [
+///     Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?),
+///     Region::new("sq0", Position::try_from(13)?..=Position::try_from(21)?),
+/// ];
+///
+/// let b = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)];
+///
+/// let mut iter = ops::intersect(
+///     &reference_sequences,
+///     a.iter().map(Ok),
+///     b.iter().map(Ok),
+/// );
+///
+/// assert_eq!(iter.next().transpose()?, Some((&a[0], vec![&b[0]])));
+/// assert_eq!(iter.next().transpose()?, Some((&a[1], vec![&b[0]])));
+/// assert!(iter.next().is_none());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn intersect<A, T, B, U>(
+    reference_sequences: &ReferenceSequences,
+    a: A,
+    b: B,
+) -> Intersect<'_, A::IntoIter, T, B::IntoIter, U>
+where
+    A: IntoIterator<Item = io::Result<T>>,
+    T: Feature,
+    B: IntoIterator<Item = io::Result<U>>,
+    U: Feature,
+{
+    window(reference_sequences, a, b, 0)
+}
+
+/// Returns an iterator over each feature in `a` and the features in `b` within a distance of it.
+///
+/// This is [`intersect`] with each feature in `a` extended by `distance` on both sides
+/// (`bedtools window -w`).
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::{ops::{self, ReferenceSequences}, Position, Region};
+///
+/// let reference_sequences: ReferenceSequences = [("sq0", 34)].into_iter().collect();
+/// let a = [Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?)];
+/// let b = [Region::new("sq0", Position::try_from(13)?..=Position::try_from(21)?)];
+///
+/// let mut iter = ops::window(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok), 5);
+/// assert_eq!(iter.next().transpose()?, Some((&a[0], vec![&b[0]])));
+///
+/// let mut iter = ops::window(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok), 4);
+/// assert_eq!(iter.next().transpose()?, Some((&a[0], Vec::new())));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn window<A, T, B, U>(
+    reference_sequences: &ReferenceSequences,
+    a: A,
+    b: B,
+    distance: usize,
+) -> Intersect<'_, A::IntoIter, T, B::IntoIter, U>
+where
+    A: IntoIterator<Item = io::Result<T>>,
+    T: Feature,
+    B: IntoIterator<Item = io::Result<U>>,
+    U: Feature,
+{
+    Intersect::new(reference_sequences, a.into_iter(), b.into_iter(), distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ops::tests::region, Region};
+
+    #[test]
+    fn test_intersect() -> io::Result<()> {
+        let reference_sequences: ReferenceSequences = [("sq0", 34), ("sq1", 34), ("sq2", 34)]
+            .into_iter()
+            .collect();
+
+        let a = [
+            region("sq0", 1, 3),
+            region("sq0", 5, 13),
+            region("sq0", 8, 8),
+            region("sq0", 21, 34),
+            region("sq2", 5, 8),
+        ];
+
+        let b = [
+            region("sq0", 2, 5),
+            region("sq0", 8, 10),
+            region("sq0", 13, 21),
+            region("sq1", 1, 34),
+            region("sq2", 9, 13),
+        ];
+
+        let actual: Vec<_> = intersect(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (&a[0], vec![&b[0]]),
+            (&a[1], vec![&b[0], &b[1], &b[2]]),
+            (&a[2], vec![&b[1]]),
+            (&a[3], vec![&b[2]]),
+            (&a[4], Vec::new()),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersect_with_unsorted_features() {
+        let reference_sequences: ReferenceSequences = [("sq0", 34)].into_iter().collect();
+        let a = [region("sq0", 8, 13), region("sq0", 5, 8)];
+
+        let mut iter = intersect(
+            &reference_sequences,
+            a.iter().map(Ok),
+            std::iter::empty::<io::Result<Region>>(),
+        );
+
+        assert!(iter.next().is_some());
+        assert!(matches!(
+            iter.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_intersect_with_invalid_reference_sequence_name() {
+        let reference_sequences: ReferenceSequences = [("sq0", 34)].into_iter().collect();
+        let a = [region("sq1", 8, 13)];
+
+        let mut iter = intersect(
+            &reference_sequences,
+            a.iter().map(Ok),
+            std::iter::empty::<io::Result<Region>>(),
+        );
+
+        assert!(matches!(
+            iter.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
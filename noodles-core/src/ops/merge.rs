@@ -0,0 +1,154 @@
+use std::io;
+
+use super::{feature::to_region, stream::Stream, Feature};
+use crate::Region;
+
+/// An iterator over merged features.
+///
+/// This is created by calling [`merge`].
+pub struct Merge<'r, I, T> {
+    features: Stream<'r, I, T>,
+    distance: usize,
+}
+
+impl<'r, I, T> Iterator for Merge<'r, I, T>
+where
+    I: Iterator<Item = io::Result<T>>,
+    T: Feature,
+{
+    type Item = io::Result<Region>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.features.next_entry() {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let start = first.start;
+        let mut end = first.end;
+
+        loop {
+            let next = match self.features.peek() {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let max_start = usize::from(end)
+                .saturating_add(1)
+                .saturating_add(self.distance);
+
+            if next.reference_sequence_id != first.reference_sequence_id
+                || usize::from(next.start) > max_start
+            {
+                break;
+            }
+
+            end = end.max(next.end);
+
+            if let Err(e) = self.features.next_entry() {
+                return Some(Err(e));
+            }
+        }
+
+        let name = first.feature.reference_sequence_name();
+        Some(Ok(to_region(name, start, end)))
+    }
+}
+
+/// Returns an iterator over regions of merged features.
+///
+/// Features that overlap, are adjacent, or are at most `distance` bases apart are merged into a
+/// single region (`bedtools merge -d`).
+///
+/// The features must be sorted by start position and grouped by reference sequence, but the
+/// order of the reference sequences does not matter.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::{ops, Position, Region};
+///
+/// let features = [
+///     Region::new("sq0", Position::try_from(5)?..=Position::try_from(8)?),
+///     Region::new("sq0", Position::try_from(9)?..=Position::try_from(13)?),
+///     Region::new("sq0", Position::try_from(21)?..=Position::try_from(34)?),
+/// ];
+///
+/// let regions: Vec<_> = ops::merge(features.iter().map(Ok), 0).collect::<io::Result<_>>()?;
+///
+/// assert_eq!(
+///     regions,
+///     [
+///         Region::new("sq0", Position::try_from(5)?..=Position::try_from(13)?),
+///         Region::new("sq0", Position::try_from(21)?..=Position::try_from(34)?),
+///     ]
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn merge<I, T>(features: I, distance: usize) -> Merge<'static, I::IntoIter, T>
+where
+    I: IntoIterator<Item = io::Result<T>>,
+    T: Feature,
+{
+    Merge {
+        features: Stream::unordered(features.into_iter()),
+        distance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::tests::region;
+
+    #[test]
+    fn test_merge() -> io::Result<()> {
+        let features = [
+            region("sq1", 1, 5),
+            region("sq1", 3, 4),
+            region("sq1", 8, 13),
+            region("sq0", 5, 8),
+            region("sq0", 5, 21),
+            region("sq0", 34, 55),
+        ];
+
+        let actual: Vec<_> = merge(features.iter().map(Ok), 0).collect::<io::Result<_>>()?;
+        let expected = [
+            region("sq1", 1, 5),
+            region("sq1", 8, 13),
+            region("sq0", 5, 21),
+            region("sq0", 34, 55),
+        ];
+        assert_eq!(actual, expected);
+
+        let actual: Vec<_> = merge(features.iter().map(Ok), 2).collect::<io::Result<_>>()?;
+        let expected = [
+            region("sq1", 1, 13),
+            region("sq0", 5, 21),
+            region("sq0", 34, 55),
+        ];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_ungrouped_reference_sequences() {
+        let features = [
+            region("sq0", 1, 5),
+            region("sq1", 1, 5),
+            region("sq0", 8, 13),
+        ];
+        let mut iter = merge(features.iter().map(Ok), 0);
+
+        assert!(iter.next().is_some());
+
+        assert!(matches!(
+            iter.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
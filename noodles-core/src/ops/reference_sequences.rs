@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use bstr::{BStr, BString};
+
+/// An ordered list of reference sequence names and lengths.
+///
+/// This defines the sort order of feature streams and the extent of each reference sequence,
+/// e.g., the `@SQ` lines of a SAM header or the `##contig` lines of a VCF header.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReferenceSequences {
+    entries: Vec<(BString, usize)>,
+    indices: HashMap<BString, usize>,
+}
+
+impl ReferenceSequences {
+    /// Returns the number of reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::ReferenceSequences;
+    /// let reference_sequences: ReferenceSequences = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+    /// assert_eq!(reference_sequences.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::ReferenceSequences;
+    /// let reference_sequences = ReferenceSequences::default();
+    /// assert!(reference_sequences.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the index of the reference sequence with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::ReferenceSequences;
+    /// let reference_sequences: ReferenceSequences = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+    /// assert_eq!(reference_sequences.get_index_of(b"sq1"), Some(1));
+    /// assert!(reference_sequences.get_index_of(b"sq2").is_none());
+    /// ```
+    pub fn get_index_of<N>(&self, name: N) -> Option<usize>
+    where
+        N: AsRef<[u8]>,
+    {
+        self.indices.get(name.as_ref()).copied()
+    }
+
+    /// Returns the name and length of the reference sequence at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::ReferenceSequences;
+    /// let reference_sequences: ReferenceSequences = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+    /// assert_eq!(reference_sequences.get_index(1), Some((b"sq1".as_ref().into(), 13)));
+    /// assert!(reference_sequences.get_index(2).is_none());
+    /// ```
+    pub fn get_index(&self, i: usize) -> Option<(&BStr, usize)> {
+        self.entries
+            .get(i)
+            .map(|(name, length)| (name.as_ref(), *length))
+    }
+
+    /// Returns an iterator over the reference sequence names and lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::ReferenceSequences;
+    /// let reference_sequences: ReferenceSequences = [("sq0", 8)].into_iter().collect();
+    /// let mut iter = reference_sequences.iter();
+    /// assert_eq!(iter.next(), Some((b"sq0".as_ref().into(), 8)));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&BStr, usize)> {
+        self.entries
+            .iter()
+            .map(|(name, length)| (name.as_ref(), *length))
+    }
+}
+
+impl<N> Extend<(N, usize)> for ReferenceSequences
+where
+    N: Into<BString>,
+{
+    fn extend<T: IntoIterator<Item = (N, usize)>>(&mut self, iter: T) {
+        for (name, length) in iter {
+            let name = name.into();
+
+            if let Some(&i) = self.indices.get(&name) {
+                self.entries[i].1 = length;
+            } else {
+                self.indices.insert(name.clone(), self.entries.len());
+                self.entries.push((name, length));
+            }
+        }
+    }
+}
+
+impl<N> FromIterator<(N, usize)> for ReferenceSequences
+where
+    N: Into<BString>,
+{
+    fn from_iter<T: IntoIterator<Item = (N, usize)>>(iter: T) -> Self {
+        let mut reference_sequences = Self::default();
+        reference_sequences.extend(iter);
+        reference_sequences
+    }
+}
@@ -0,0 +1,128 @@
+use std::{collections::HashMap, io};
+
+use bstr::BString;
+
+use super::{feature::resolve_interval, Feature, ReferenceSequences};
+use crate::Position;
+
+/// A feature with its resolved reference sequence index and positions.
+pub(super) struct Entry<T> {
+    pub(super) reference_sequence_id: usize,
+    pub(super) start: Position,
+    pub(super) end: Position,
+    pub(super) feature: T,
+}
+
+enum Order<'r> {
+    ReferenceSequences(&'r ReferenceSequences),
+    // Reference sequence IDs are assigned in the order they are first seen.
+    FirstSeen(HashMap<BString, usize>),
+}
+
+/// A sorted feature stream.
+///
+/// This validates the sort order of the features as they are read.
+pub(super) struct Stream<'r, I, T> {
+    iter: I,
+    order: Order<'r>,
+    peeked: Option<Entry<T>>,
+    last: Option<(usize, Position)>,
+}
+
+impl<'r, I, T> Stream<'r, I, T>
+where
+    I: Iterator<Item = io::Result<T>>,
+    T: Feature,
+{
+    pub(super) fn new(iter: I, reference_sequences: &'r ReferenceSequences) -> Self {
+        Self::with_order(iter, Order::ReferenceSequences(reference_sequences))
+    }
+
+    pub(super) fn unordered(iter: I) -> Self {
+        Self::with_order(iter, Order::FirstSeen(HashMap::new()))
+    }
+
+    fn with_order(iter: I, order: Order<'r>) -> Self {
+        Self {
+            iter,
+            order,
+            peeked: None,
+            last: None,
+        }
+    }
+
+    pub(super) fn peek(&mut self) -> io::Result<Option<&Entry<T>>> {
+        if self.peeked.is_none() {
+            self.peeked = self.read_entry()?;
+        }
+
+        Ok(self.peeked.as_ref())
+    }
+
+    pub(super) fn next_entry(&mut self) -> io::Result<Option<Entry<T>>> {
+        match self.peeked.take() {
+            Some(entry) => Ok(Some(entry)),
+            None => self.read_entry(),
+        }
+    }
+
+    fn read_entry(&mut self) -> io::Result<Option<Entry<T>>> {
+        let Some(feature) = self.iter.next().transpose()? else {
+            return Ok(None);
+        };
+
+        let reference_sequence_id = self.resolve_reference_sequence_id(&feature)?;
+        let (start, end) = resolve_interval(feature.interval());
+
+        if start > end {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid feature interval",
+            ));
+        }
+
+        let key = (reference_sequence_id, start);
+
+        if let Some(last) = self.last {
+            if key < last {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "features are not sorted",
+                ));
+            }
+        }
+
+        self.last = Some(key);
+
+        Ok(Some(Entry {
+            reference_sequence_id,
+            start,
+            end,
+            feature,
+        }))
+    }
+
+    fn resolve_reference_sequence_id(&mut self, feature: &T) -> io::Result<usize> {
+        let name = feature.reference_sequence_name();
+
+        match &mut self.order {
+            Order::ReferenceSequences(reference_sequences) => {
+                reference_sequences.get_index_of(name).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid reference sequence name: {name}"),
+                    )
+                })
+            }
+            Order::FirstSeen(ids) => {
+                if let Some(&id) = ids.get(name.as_ref() as &[u8]) {
+                    Ok(id)
+                } else {
+                    let id = ids.len();
+                    ids.insert(name.into(), id);
+                    Ok(id)
+                }
+            }
+        }
+    }
+}
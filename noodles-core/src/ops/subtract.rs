@@ -0,0 +1,145 @@
+use std::io;
+
+use super::{intersect::Intersect, window, Feature, ReferenceSequences};
+use crate::{region::Interval, Position};
+
+/// An iterator over features and their intervals that are not covered by other features.
+///
+/// This is created by calling [`subtract`].
+pub struct Subtract<'r, A, T, B, U>(Intersect<'r, A, T, B, U>);
+
+impl<'r, A, T, B, U> Iterator for Subtract<'r, A, T, B, U>
+where
+    A: Iterator<Item = io::Result<T>>,
+    T: Feature,
+    B: Iterator<Item = io::Result<U>>,
+    U: Feature,
+{
+    type Item = io::Result<(T, Vec<Interval>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (a, hits) = match self.0.next_entry() {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut intervals = Vec::new();
+        let mut position = Some(a.start);
+
+        // Hits are sorted by start position.
+        for b in hits {
+            let Some(start) = position else {
+                break;
+            };
+
+            if b.start > start {
+                // SAFETY: `b.start` > `start` >= 1.
+                let end = Position::new(usize::from(b.start) - 1).unwrap();
+                intervals.push(Interval::from(start..=end));
+            }
+
+            if b.end >= start {
+                position = b.end.checked_add(1);
+            }
+        }
+
+        if let Some(start) = position {
+            if start <= a.end {
+                intervals.push(Interval::from(start..=a.end));
+            }
+        }
+
+        Some(Ok((a.feature, intervals)))
+    }
+}
+
+/// Returns an iterator over each feature in `a` and its intervals not covered by features in `b`.
+///
+/// A feature that is entirely covered has no intervals (`bedtools subtract`).
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::{ops::{self, ReferenceSequences}, region::Interval, Position, Region};
+///
+/// let reference_sequences: ReferenceSequences = [("sq0", 34)].into_iter().collect();
+/// let a = [Region::new("sq0", Position::try_from(5)?..=Position::try_from(21)?)];
+/// let b = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)];
+///
+/// let mut iter = ops::subtract(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok));
+///
+/// assert_eq!(
+///     iter.next().transpose()?,
+///     Some((
+///         &a[0],
+///         vec![
+///             Interval::from(Position::try_from(5)?..=Position::try_from(7)?),
+///             Interval::from(Position::try_from(14)?..=Position::try_from(21)?),
+///         ]
+///     ))
+/// );
+///
+/// assert!(iter.next().is_none());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn subtract<A, T, B, U>(
+    reference_sequences: &ReferenceSequences,
+    a: A,
+    b: B,
+) -> Subtract<'_, A::IntoIter, T, B::IntoIter, U>
+where
+    A: IntoIterator<Item = io::Result<T>>,
+    T: Feature,
+    B: IntoIterator<Item = io::Result<U>>,
+    U: Feature,
+{
+    Subtract(window(reference_sequences, a, b, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Region;
+
+    #[test]
+    fn test_subtract() -> Result<(), Box<dyn std::error::Error>> {
+        fn interval(
+            start: usize,
+            end: usize,
+        ) -> Result<Interval, crate::position::TryFromIntError> {
+            Ok(Interval::from(
+                Position::try_from(start)?..=Position::try_from(end)?,
+            ))
+        }
+
+        let reference_sequences: ReferenceSequences = [("sq0", 34)].into_iter().collect();
+
+        let a = [
+            Region::new("sq0", interval(1, 21)?),
+            Region::new("sq0", interval(8, 13)?),
+            Region::new("sq0", interval(21, 34)?),
+        ];
+
+        let b = [
+            Region::new("sq0", interval(1, 3)?),
+            Region::new("sq0", interval(5, 13)?),
+            Region::new("sq0", interval(8, 10)?),
+            Region::new("sq0", interval(34, 34)?),
+        ];
+
+        let actual: Vec<_> = subtract(&reference_sequences, a.iter().map(Ok), b.iter().map(Ok))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (&a[0], vec![interval(4, 4)?, interval(14, 21)?]),
+            (&a[1], Vec::new()),
+            (&a[2], vec![interval(21, 33)?]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
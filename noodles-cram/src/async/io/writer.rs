@@ -13,8 +13,9 @@ use tokio::io::{self, AsyncWrite, AsyncWriteExt};
 
 pub use self::builder::Builder;
 use crate::{
-    file_definition::Version, io::writer::Options, DataContainer, FileDefinition, Record,
-    MAGIC_NUMBER,
+    file_definition::Version,
+    io::writer::{Options, ReferenceSequenceMode},
+    DataContainer, FileDefinition, Record, MAGIC_NUMBER,
 };
 
 /// An async CRAM writer.
@@ -118,7 +119,8 @@ where
     /// The position of the stream is expected to be directly after the file definition.
     ///
     /// Entries in the reference sequence dictionary that are missing MD5 checksums (`M5`) will
-    /// automatically be calculated and added to the written record.
+    /// automatically be calculated and added to the written record, unless no reference sequence
+    /// is used ([`ReferenceSequenceMode::None`]).
     ///
     /// # Examples
     ///
@@ -145,12 +147,19 @@ where
 
         let mut header = header.clone();
 
-        add_missing_reference_sequence_checksums(
-            &self.reference_sequence_repository,
-            header.reference_sequences_mut(),
-        )?;
+        if self.options.reference_sequence_mode != ReferenceSequenceMode::None {
+            add_missing_reference_sequence_checksums(
+                &self.reference_sequence_repository,
+                header.reference_sequences_mut(),
+            )?;
+        }
 
-        write_header_container(&mut self.inner, &header).await
+        write_header_container(
+            &mut self.inner,
+            &header,
+            self.options.reference_sequence_mode,
+        )
+        .await
     }
 
     /// Writes a CRAM record.
@@ -185,8 +194,18 @@ where
     ) -> io::Result<()> {
         use crate::data_container::builder::AddRecordError;
 
+        crate::io::writer::lossy::apply(
+            &self.options,
+            &self.reference_sequence_repository,
+            header,
+            &mut record,
+        )?;
+
         loop {
-            match self.data_container_builder.add_record(record) {
+            match self
+                .data_container_builder
+                .add_record(&self.options, record)
+            {
                 Ok(_) => {
                     self.record_counter += 1;
                     return Ok(());
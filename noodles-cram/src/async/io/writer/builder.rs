@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{num::NonZeroUsize, path::Path};
 
 use noodles_fasta as fasta;
 use tokio::{
@@ -8,7 +8,9 @@ use tokio::{
 
 use super::Writer;
 use crate::{
-    data_container::BlockContentEncoderMap, file_definition::Version, io::writer::Options,
+    data_container::BlockContentEncoderMap,
+    file_definition::Version,
+    io::writer::{Options, Profile, QualityScoreBinning, ReferenceSequenceMode, TagFilter},
     DataContainer,
 };
 
@@ -55,6 +57,95 @@ impl Builder {
         self
     }
 
+    /// Sets the reference sequence mode.
+    ///
+    /// The default is [`ReferenceSequenceMode::External`].
+    pub fn set_reference_sequence_mode(mut self, mode: ReferenceSequenceMode) -> Self {
+        self.options.reference_sequence_mode = mode;
+        self
+    }
+
+    /// Sets the compression profile.
+    ///
+    /// This overrides the block content-encoder map, the CRAM version, the number of records per
+    /// slice, and the number of slices per container with the values of the given profile.
+    pub fn set_profile(mut self, profile: Profile) -> Self {
+        self.options.block_content_encoder_map = profile.block_content_encoder_map();
+        self.options.version = profile.version();
+        self.options.records_per_slice = usize::from(profile.records_per_slice());
+        self.options.slices_per_container = usize::from(profile.slices_per_container());
+        self
+    }
+
+    /// Sets the maximum number of records per slice.
+    ///
+    /// The default is 10240.
+    pub fn set_records_per_slice(mut self, records_per_slice: NonZeroUsize) -> Self {
+        self.options.records_per_slice = usize::from(records_per_slice);
+        self
+    }
+
+    /// Sets the maximum number of slices per container.
+    ///
+    /// The default is 1.
+    pub fn set_slices_per_container(mut self, slices_per_container: NonZeroUsize) -> Self {
+        self.options.slices_per_container = usize::from(slices_per_container);
+        self
+    }
+
+    /// Sets the quality score binning.
+    ///
+    /// This is a lossy transformation. By default, quality scores are not binned.
+    pub fn set_quality_score_binning(mut self, binning: QualityScoreBinning) -> Self {
+        self.options.quality_score_binning = Some(binning);
+        self
+    }
+
+    /// Sets whether to preserve the quality scores of unmapped records.
+    ///
+    /// If `false`, the quality scores of unmapped records are discarded.
+    ///
+    /// The default is `true`.
+    pub fn preserve_unmapped_quality_scores(mut self, value: bool) -> Self {
+        self.options.preserve_unmapped_quality_scores = value;
+        self
+    }
+
+    /// Sets whether to preserve the quality scores of secondary records.
+    ///
+    /// If `false`, the quality scores of secondary records are discarded.
+    ///
+    /// The default is `true`.
+    pub fn preserve_secondary_quality_scores(mut self, value: bool) -> Self {
+        self.options.preserve_secondary_quality_scores = value;
+        self
+    }
+
+    /// Sets the quality score of non-variant positions.
+    ///
+    /// If set, quality scores are only kept at variant positions of mapped records, i.e., inserted
+    /// bases and aligned bases that differ from the reference sequence. The quality scores at all
+    /// other positions are replaced with the given score. This is a lossy transformation that
+    /// requires the reference sequence repository to contain the reference sequences of mapped
+    /// records.
+    ///
+    /// By default, all quality scores are kept.
+    pub fn set_non_variant_quality_score(mut self, score: u8) -> Self {
+        self.options.non_variant_quality_score = Some(score);
+        self
+    }
+
+    /// Sets the data field tag filter.
+    ///
+    /// Only the data fields with tags that pass the filter are written. This includes the read
+    /// group (`RG`).
+    ///
+    /// By default, all data fields are written.
+    pub fn set_tag_filter(mut self, tag_filter: TagFilter) -> Self {
+        self.options.tag_filter = Some(tag_filter);
+        self
+    }
+
     /// Builds an async CRAM writer from a path.
     ///
     /// # Examples
@@ -2,12 +2,22 @@ use tokio::io::{self, AsyncWrite, AsyncWriteExt};
 
 use noodles_sam as sam;
 
-pub async fn write_header_container<W>(writer: &mut W, header: &sam::Header) -> io::Result<()>
+use crate::io::writer::ReferenceSequenceMode;
+
+pub async fn write_header_container<W>(
+    writer: &mut W,
+    header: &sam::Header,
+    reference_sequence_mode: ReferenceSequenceMode,
+) -> io::Result<()>
 where
     W: AsyncWrite + Unpin,
 {
     let mut buf = Vec::new();
-    crate::io::writer::header_container::write_header_container(&mut buf, header)?;
+    crate::io::writer::header_container::write_header_container(
+        &mut buf,
+        header,
+        reference_sequence_mode,
+    )?;
     writer.write_all(&buf).await?;
     Ok(())
 }
@@ -12,7 +12,10 @@ use crate::{
         compression_header::data_series_encoding_map::data_series::STANDARD_DATA_SERIES,
         BlockContentEncoderMap, CompressionHeader, ReferenceSequenceContext,
     },
-    io::{writer, BitWriter},
+    io::{
+        writer::{self, ReferenceSequenceMode},
+        BitWriter,
+    },
     record::Flags,
     Record,
 };
@@ -20,7 +23,6 @@ use crate::{
 use super::{Header, Slice};
 
 const CORE_DATA_BLOCK_CONTENT_ID: i32 = 0;
-const MAX_RECORD_COUNT: usize = 10240;
 
 #[derive(Debug, Default)]
 pub struct Builder {
@@ -47,8 +49,12 @@ impl Builder {
     }
 
     #[allow(clippy::result_large_err)]
-    pub fn add_record(&mut self, record: Record) -> Result<&Record, AddRecordError> {
-        if self.records.len() >= MAX_RECORD_COUNT {
+    pub fn add_record(
+        &mut self,
+        max_record_count: usize,
+        record: Record,
+    ) -> Result<&Record, AddRecordError> {
+        if self.records.len() >= max_record_count {
             return Err(AddRecordError::SliceFull(record));
         }
 
@@ -78,28 +84,36 @@ impl Builder {
 
     pub fn build(
         mut self,
-        block_content_encoder_map: &BlockContentEncoderMap,
+        options: &writer::Options,
         reference_sequence_repostitory: &fasta::repository::Repository,
         header: &sam::Header,
         compression_header: &CompressionHeader,
         record_counter: u64,
     ) -> io::Result<Slice> {
-        let (core_data_block, external_blocks) = write_records(
+        let block_content_encoder_map = &options.block_content_encoder_map;
+
+        let (core_data_block, mut external_blocks) = write_records(
             block_content_encoder_map,
             compression_header,
             self.reference_sequence_context,
             &mut self.records,
         )?;
 
-        let mut block_content_ids = Vec::with_capacity(external_blocks.len() + 1);
+        let mut block_content_ids = Vec::with_capacity(external_blocks.len() + 2);
         block_content_ids.push(core_data_block.content_id());
 
         for block in &external_blocks {
             block_content_ids.push(block.content_id());
         }
 
-        let reference_md5 = match self.reference_sequence_context {
-            ReferenceSequenceContext::Some(context) => {
+        let mut embedded_reference_bases_block_content_id = None;
+
+        let reference_md5 = match (
+            options.reference_sequence_mode,
+            self.reference_sequence_context,
+        ) {
+            (ReferenceSequenceMode::None, _) => [0; 16],
+            (reference_sequence_mode, ReferenceSequenceContext::Some(context)) => {
                 let reference_sequence_name = header
                     .reference_sequences()
                     .get_index(context.reference_sequence_id())
@@ -114,24 +128,77 @@ impl Builder {
                 let (start, end) = (context.alignment_start(), context.alignment_end());
                 let sequence = &reference_sequence[start..=end];
 
+                if reference_sequence_mode == ReferenceSequenceMode::Embedded {
+                    let block = build_embedded_reference_bases_block(
+                        block_content_encoder_map,
+                        &block_content_ids,
+                        sequence,
+                    )?;
+
+                    embedded_reference_bases_block_content_id = Some(block.content_id());
+                    block_content_ids.push(block.content_id());
+                    external_blocks.push(block);
+                }
+
                 calculate_normalized_sequence_digest(sequence)
             }
             _ => [0; 16],
         };
 
-        let header = Header::builder()
+        let mut builder = Header::builder()
             .set_reference_sequence_context(self.reference_sequence_context)
             .set_record_count(self.records.len())
             .set_record_counter(record_counter)
             .set_block_count(block_content_ids.len())
             .set_block_content_ids(block_content_ids)
-            .set_reference_md5(reference_md5)
-            .build();
+            .set_reference_md5(reference_md5);
+
+        if let Some(id) = embedded_reference_bases_block_content_id {
+            builder = builder.set_embedded_reference_bases_block_content_id(id);
+        }
+
+        let header = builder.build();
 
         Ok(Slice::new(header, core_data_block, external_blocks))
     }
 }
 
+// The embedded reference bases block uses the next content ID after all the other blocks in the
+// slice and is compressed using the bases (`BA`) data series encoder.
+fn build_embedded_reference_bases_block(
+    block_content_encoder_map: &BlockContentEncoderMap,
+    block_content_ids: &[block::ContentId],
+    sequence: &[u8],
+) -> io::Result<Block> {
+    use crate::data_container::compression_header::data_series_encoding_map::DataSeries;
+
+    let id = block_content_ids
+        .iter()
+        .map(|&id| i32::from(id))
+        .max()
+        .unwrap_or_default()
+        .checked_add(1)
+        .map(block::ContentId::from)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid block content ID"))?;
+
+    let builder = Block::builder()
+        .set_content_type(block::ContentType::ExternalData)
+        .set_content_id(id);
+
+    let encoder = block_content_encoder_map
+        .get_data_series_encoder(block::ContentId::from(DataSeries::Bases))
+        .flatten();
+
+    let builder = match encoder {
+        Some(Encoder::Fqzcomp) | None => builder
+            .set_uncompressed_len(sequence.len())
+            .set_data(Bytes::from(sequence.to_vec())),
+        Some(encoder) => builder.compress_and_set_data(sequence.to_vec(), encoder.clone())?,
+    };
+
+    Ok(builder.build())
+}
+
 fn write_records(
     block_content_encoder_map: &BlockContentEncoderMap,
     compression_header: &CompressionHeader,
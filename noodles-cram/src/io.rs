@@ -3,8 +3,13 @@
 mod bit_reader;
 mod bit_writer;
 pub mod indexed_reader;
+pub mod multithreaded_reader;
+pub mod multithreaded_writer;
 pub mod reader;
 pub mod writer;
 
 pub(crate) use self::{bit_reader::BitReader, bit_writer::BitWriter};
-pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader, multithreaded_reader::MultithreadedReader,
+    multithreaded_writer::MultithreadedWriter, reader::Reader, writer::Writer,
+};
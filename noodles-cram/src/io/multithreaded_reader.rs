@@ -0,0 +1,487 @@
+//! Multithreaded CRAM reader.
+
+mod builder;
+mod records;
+
+pub use self::{builder::Builder, records::Records};
+
+use std::{
+    io::{self, Read},
+    mem,
+    num::NonZeroUsize,
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+
+use bytes::{Bytes, BytesMut};
+use crossbeam_channel::{Receiver, Sender};
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+use crate::{data_container::Header, FileDefinition, Record};
+
+type BufferedTx = Sender<io::Result<Vec<Record>>>;
+type BufferedRx = Receiver<io::Result<Vec<Record>>>;
+type DecodeTx = Sender<(Header, Bytes, BufferedTx)>;
+type DecodeRx = Receiver<(Header, Bytes, BufferedTx)>;
+type ReadTx = Sender<BufferedRx>;
+type ReadRx = Receiver<BufferedRx>;
+
+enum State<R> {
+    Paused(R),
+    Running {
+        reader_handle: JoinHandle<R>,
+        decoder_handles: Vec<JoinHandle<()>>,
+        read_rx: ReadRx,
+    },
+    Done,
+}
+
+/// A multithreaded CRAM reader.
+///
+/// This reads data containers on its own thread and uses a thread pool to decode them. Records
+/// are returned in the same order as they are in the stream.
+///
+/// The file definition and file header are read on the calling thread. Workers are started on
+/// the first call to [`Self::records`], after which the file definition and header can no longer
+/// be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use std::num::NonZeroUsize;
+/// use noodles_cram::io::multithreaded_reader;
+///
+/// let mut reader = multithreaded_reader::Builder::default()
+///     .set_worker_count(NonZeroUsize::try_from(4).unwrap())
+///     .build_from_path("sample.cram")?;
+///
+/// let header = reader.read_header()?;
+///
+/// for result in reader.records(&header) {
+///     let record = result?;
+///     // ...
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct MultithreadedReader<R> {
+    state: State<R>,
+    reference_sequence_repository: fasta::Repository,
+    worker_count: NonZeroUsize,
+    buf: BytesMut,
+}
+
+impl<R> MultithreadedReader<R> {
+    /// Shuts down the reader and returns the underlying reader.
+    ///
+    /// If records were read, the position of the underlying reader is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let mut reader = cram::io::MultithreadedReader::new(io::empty());
+    /// reader.finish()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(&mut self) -> io::Result<R> {
+        let state = mem::replace(&mut self.state, State::Done);
+
+        match state {
+            State::Paused(inner) => Ok(inner),
+            State::Running {
+                reader_handle,
+                mut decoder_handles,
+                read_rx,
+            } => {
+                drop(read_rx);
+
+                for handle in decoder_handles.drain(..) {
+                    handle.join().unwrap();
+                }
+
+                Ok(reader_handle.join().unwrap())
+            }
+            State::Done => panic!("invalid state"),
+        }
+    }
+
+    fn inner_mut(&mut self) -> io::Result<&mut R> {
+        match &mut self.state {
+            State::Paused(inner) => Ok(inner),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reader is already reading records",
+            )),
+        }
+    }
+}
+
+impl<R> MultithreadedReader<R>
+where
+    R: Read + Send + 'static,
+{
+    /// Creates a multithreaded CRAM reader with a worker count of 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let reader = cram::io::MultithreadedReader::new(io::empty());
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Builder::default().build_from_reader(inner)
+    }
+
+    /// Creates a multithreaded CRAM reader with a worker count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram as cram;
+    /// let reader = cram::io::MultithreadedReader::with_worker_count(NonZeroUsize::MIN, io::empty());
+    /// ```
+    pub fn with_worker_count(worker_count: NonZeroUsize, inner: R) -> Self {
+        Builder::default()
+            .set_worker_count(worker_count)
+            .build_from_reader(inner)
+    }
+
+    /// Reads the CRAM file definition.
+    ///
+    /// The CRAM magic number is also checked.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram as cram;
+    /// let mut reader = File::open("sample.cram").map(cram::io::MultithreadedReader::new)?;
+    /// let file_definition = reader.read_file_definition()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_file_definition(&mut self) -> io::Result<FileDefinition> {
+        use super::reader::{read_file_id, read_format, read_magic_number};
+
+        let inner = self.inner_mut()?;
+
+        read_magic_number(inner)?;
+
+        let format = read_format(inner)?;
+        let file_id = read_file_id(inner)?;
+
+        Ok(FileDefinition::new(format, file_id))
+    }
+
+    /// Reads the SAM header.
+    ///
+    /// The position of the stream is expected to be at the CRAM header container, i.e., directly
+    /// after the file definition.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram as cram;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::io::MultithreadedReader::new)?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let header = reader.read_file_header()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_file_header(&mut self) -> io::Result<sam::Header> {
+        use super::reader::header_container::read_header_container;
+
+        let mut buf = mem::take(&mut self.buf);
+        let result = self
+            .inner_mut()
+            .and_then(|inner| read_header_container(inner, &mut buf));
+        self.buf = buf;
+
+        result
+    }
+
+    /// Reads the SAM header.
+    ///
+    /// This verifies the CRAM magic number, discards the file definition, and reads and parses the
+    /// file header as a SAM header.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram as cram;
+    /// let mut reader = File::open("sample.cram").map(cram::io::MultithreadedReader::new)?;
+    /// let header = reader.read_header()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<sam::Header> {
+        self.read_file_definition()?;
+        self.read_file_header()
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// The stream is expected to be at the start of a data container. This starts the reader and
+    /// decoder workers, if not already running. The header used to resolve records is the one
+    /// given in the first call.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram as cram;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::io::MultithreadedReader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// for result in reader.records(&header) {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn records(&mut self, header: &sam::Header) -> Records<'_, R> {
+        self.resume(header);
+        Records::new(self)
+    }
+
+    fn resume(&mut self, header: &sam::Header) {
+        if matches!(self.state, State::Running { .. }) {
+            return;
+        }
+
+        let state = mem::replace(&mut self.state, State::Done);
+
+        let State::Paused(inner) = state else {
+            panic!("invalid state");
+        };
+
+        let worker_count = self.worker_count.get();
+
+        let (decode_tx, decode_rx) = crossbeam_channel::bounded(worker_count);
+        let (read_tx, read_rx) = crossbeam_channel::bounded(worker_count);
+
+        let reader_handle = spawn_reader(inner, mem::take(&mut self.buf), decode_tx, read_tx);
+        let decoder_handles = spawn_decoders(
+            self.worker_count,
+            self.reference_sequence_repository.clone(),
+            Arc::new(header.clone()),
+            decode_rx,
+        );
+
+        self.state = State::Running {
+            reader_handle,
+            decoder_handles,
+            read_rx,
+        };
+    }
+
+    fn read_container_records(&mut self) -> io::Result<Option<Vec<Record>>> {
+        let State::Running { read_rx, .. } = &self.state else {
+            panic!("invalid state");
+        };
+
+        let Ok(buffered_rx) = read_rx.recv() else {
+            return Ok(None);
+        };
+
+        buffered_rx
+            .recv()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            .map(Some)
+    }
+}
+
+impl<R> Drop for MultithreadedReader<R> {
+    fn drop(&mut self) {
+        if !matches!(self.state, State::Done) {
+            let _ = self.finish();
+        }
+    }
+}
+
+// The reader stops at the EOF container or after the first read error, which is sent in place of
+// a decoded container.
+fn spawn_reader<R>(
+    mut reader: R,
+    mut buf: BytesMut,
+    decode_tx: DecodeTx,
+    read_tx: ReadTx,
+) -> JoinHandle<R>
+where
+    R: Read + Send + 'static,
+{
+    thread::spawn(move || {
+        loop {
+            let (buffered_tx, buffered_rx) = crossbeam_channel::bounded(1);
+
+            match read_container(&mut reader, &mut buf) {
+                Ok(Some((header, src))) => {
+                    if read_tx.send(buffered_rx).is_err()
+                        || decode_tx.send((header, src, buffered_tx)).is_err()
+                    {
+                        break;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    buffered_tx.send(Err(e)).ok();
+                    read_tx.send(buffered_rx).ok();
+                    break;
+                }
+            }
+        }
+
+        reader
+    })
+}
+
+fn read_container<R>(reader: &mut R, buf: &mut BytesMut) -> io::Result<Option<(Header, Bytes)>>
+where
+    R: Read,
+{
+    use super::reader::data_container::header::read_header;
+
+    let Some(header) = read_header(reader)? else {
+        return Ok(None);
+    };
+
+    buf.resize(header.len(), 0);
+    reader.read_exact(buf)?;
+
+    Ok(Some((header, buf.split().freeze())))
+}
+
+fn spawn_decoders(
+    worker_count: NonZeroUsize,
+    reference_sequence_repository: fasta::Repository,
+    header: Arc<sam::Header>,
+    decode_rx: DecodeRx,
+) -> Vec<JoinHandle<()>> {
+    (0..worker_count.get())
+        .map(|_| {
+            let reference_sequence_repository = reference_sequence_repository.clone();
+            let header = header.clone();
+            let decode_rx = decode_rx.clone();
+
+            thread::spawn(move || {
+                while let Ok((container_header, src, buffered_tx)) = decode_rx.recv() {
+                    let result = decode(
+                        &reference_sequence_repository,
+                        &header,
+                        &container_header,
+                        src,
+                    );
+
+                    buffered_tx.send(result).ok();
+                }
+            })
+        })
+        .collect()
+}
+
+fn decode(
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+    container_header: &Header,
+    mut src: Bytes,
+) -> io::Result<Vec<Record>> {
+    use super::reader::data_container::{read_compression_header_from_block, read_slice};
+
+    let compression_header = read_compression_header_from_block(&mut src)?;
+
+    let mut records = Vec::new();
+
+    for _ in 0..container_header.landmarks().len() {
+        let slice = read_slice(&mut src)?;
+
+        let mut slice_records = slice.records(&compression_header)?;
+
+        slice.resolve_records(
+            reference_sequence_repository,
+            header,
+            &compression_header,
+            &mut slice_records,
+        )?;
+
+        records.append(&mut slice_records);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use sam::alignment::{
+            io::Write,
+            record::Flags,
+            record_buf::{QualityScores, Sequence},
+            RecordBuf,
+        };
+
+        use crate::io::{writer, Reader};
+
+        let header = sam::Header::default();
+
+        let mut writer = writer::Builder::default()
+            .set_records_per_slice(NonZeroUsize::try_from(3)?)
+            .build_with_writer(Vec::new());
+
+        writer.write_alignment_header(&header)?;
+
+        for i in 0..64 {
+            let record = RecordBuf::builder()
+                .set_name(format!("r{i}").into_bytes().into())
+                .set_flags(Flags::UNMAPPED)
+                .set_sequence(Sequence::from(b"ACGT".to_vec()))
+                .set_quality_scores(QualityScores::from(vec![i, 1, 2, 3]))
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let data = writer.get_ref().clone();
+
+        let mut expected_reader = Reader::new(&data[..]);
+        expected_reader.read_header()?;
+        let expected: Vec<_> = expected_reader
+            .records(&header)
+            .collect::<io::Result<_>>()?;
+
+        let mut reader =
+            MultithreadedReader::with_worker_count(NonZeroUsize::try_from(4)?, Cursor::new(data));
+        let actual_header = reader.read_header()?;
+        assert_eq!(actual_header, header);
+
+        let actual: Vec<_> = reader.records(&header).collect::<io::Result<_>>()?;
+        assert_eq!(actual, expected);
+
+        assert!(matches!(
+            reader.read_header(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        reader.finish()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    num::NonZeroUsize,
+    path::Path,
+};
+
+use bytes::BytesMut;
+use noodles_fasta as fasta;
+
+use super::{MultithreadedReader, State};
+
+/// A multithreaded CRAM reader builder.
+pub struct Builder {
+    reference_sequence_repository: fasta::Repository,
+    worker_count: NonZeroUsize,
+}
+
+impl Builder {
+    /// Sets the reference sequence repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::multithreaded_reader::Builder;
+    /// use noodles_fasta as fasta;
+    ///
+    /// let reference_sequence_repository = fasta::Repository::default();
+    ///
+    /// let builder = Builder::default()
+    ///     .set_reference_sequence_repository(reference_sequence_repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = reference_sequence_repository;
+        self
+    }
+
+    /// Sets the worker count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram::io::multithreaded_reader::Builder;
+    /// let builder = Builder::default().set_worker_count(NonZeroUsize::MIN);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds a multithreaded CRAM reader from a path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_cram::io::multithreaded_reader::Builder;
+    /// let reader = Builder::default().build_from_path("sample.cram")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, src: P) -> io::Result<MultithreadedReader<File>>
+    where
+        P: AsRef<Path>,
+    {
+        File::open(src).map(|file| self.build_from_reader(file))
+    }
+
+    /// Builds a multithreaded CRAM reader from a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram::io::multithreaded_reader::Builder;
+    /// let reader = Builder::default().build_from_reader(io::empty());
+    /// ```
+    pub fn build_from_reader<R>(self, reader: R) -> MultithreadedReader<R>
+    where
+        R: Read + Send + 'static,
+    {
+        MultithreadedReader {
+            state: State::Paused(reader),
+            reference_sequence_repository: self.reference_sequence_repository,
+            worker_count: self.worker_count,
+            buf: BytesMut::new(),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            reference_sequence_repository: fasta::Repository::default(),
+            worker_count: NonZeroUsize::MIN,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::{
+    io::{self, Read},
+    vec,
+};
+
+use super::MultithreadedReader;
+use crate::Record;
+
+/// An iterator over records of a multithreaded CRAM reader.
+///
+/// This is created by calling [`MultithreadedReader::records`].
+pub struct Records<'a, R>
+where
+    R: Read + Send + 'static,
+{
+    reader: &'a mut MultithreadedReader<R>,
+    records: vec::IntoIter<Record>,
+}
+
+impl<'a, R> Records<'a, R>
+where
+    R: Read + Send + 'static,
+{
+    pub(super) fn new(reader: &'a mut MultithreadedReader<R>) -> Self {
+        Self {
+            reader,
+            records: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a, R> Iterator for Records<'a, R>
+where
+    R: Read + Send + 'static,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.records.next() {
+                Some(r) => return Some(Ok(r)),
+                None => match self.reader.read_container_records() {
+                    Ok(Some(records)) => self.records = records.into_iter(),
+                    Ok(None) => return None,
+                    Err(e) => return Some(Err(e)),
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,506 @@
+//! Multithreaded CRAM writer.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    io::{self, Write},
+    mem,
+    num::NonZeroUsize,
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+use super::writer::{Options, ReferenceSequenceMode};
+use crate::{DataContainer, FileDefinition, Record};
+
+type BufferedTx = Sender<io::Result<Vec<u8>>>;
+type BufferedRx = Receiver<io::Result<Vec<u8>>>;
+type EncodeTx = Sender<(crate::data_container::Builder, Arc<sam::Header>, BufferedTx)>;
+type EncodeRx = Receiver<(crate::data_container::Builder, Arc<sam::Header>, BufferedTx)>;
+type WriteTx = Sender<BufferedRx>;
+type WriteRx = Receiver<BufferedRx>;
+
+enum State<W> {
+    Running {
+        writer_handle: JoinHandle<io::Result<W>>,
+        encoder_handles: Vec<JoinHandle<()>>,
+        write_tx: WriteTx,
+        encode_tx: EncodeTx,
+    },
+    Done,
+}
+
+/// A multithreaded CRAM writer.
+///
+/// Records are added to data containers on the calling thread. Full data containers are then
+/// encoded by a thread pool and written in order by a writer thread.
+///
+/// The header given when writing records is expected to be the same header used to write the
+/// file header.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_cram as cram;
+/// use noodles_sam as sam;
+///
+/// let mut writer = cram::io::MultithreadedWriter::new(Vec::new());
+///
+/// let header = sam::Header::default();
+/// writer.write_header(&header)?;
+///
+/// let record = cram::Record::default();
+/// writer.write_record(&header, record)?;
+///
+/// let _data = writer.finish(&header)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct MultithreadedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    state: State<W>,
+    reference_sequence_repository: fasta::Repository,
+    options: Arc<Options>,
+    header: Option<Arc<sam::Header>>,
+    data_container_builder: crate::data_container::Builder,
+    record_counter: u64,
+}
+
+impl<W> MultithreadedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    /// Creates a multithreaded CRAM writer with a worker count of 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let writer = cram::io::MultithreadedWriter::new(io::sink());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Builder::default().build_with_writer(inner)
+    }
+
+    /// Creates a multithreaded CRAM writer with a worker count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram as cram;
+    /// let writer = cram::io::MultithreadedWriter::with_worker_count(NonZeroUsize::MIN, io::sink());
+    /// ```
+    pub fn with_worker_count(worker_count: NonZeroUsize, inner: W) -> Self {
+        Builder::default()
+            .set_worker_count(worker_count)
+            .build_with_writer(inner)
+    }
+
+    /// Finishes the output stream and returns the underlying writer.
+    ///
+    /// This writes any pending records and the final EOF container and shuts down the encoder
+    /// and writer workers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let header = sam::Header::default();
+    /// let mut writer = cram::io::MultithreadedWriter::new(Vec::new());
+    /// writer.write_header(&header)?;
+    ///
+    /// let data = writer.finish(&header)?;
+    /// assert!(!data.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(&mut self, header: &sam::Header) -> io::Result<W> {
+        use super::writer::container::write_eof_container;
+
+        self.flush(header)?;
+
+        let mut buf = Vec::new();
+        write_eof_container(&mut buf)?;
+        self.send_buf(buf)?;
+
+        let state = mem::replace(&mut self.state, State::Done);
+
+        match state {
+            State::Running {
+                writer_handle,
+                mut encoder_handles,
+                write_tx,
+                encode_tx,
+            } => {
+                drop(encode_tx);
+
+                for handle in encoder_handles.drain(..) {
+                    handle.join().unwrap();
+                }
+
+                drop(write_tx);
+
+                writer_handle.join().unwrap()
+            }
+            State::Done => panic!("invalid state"),
+        }
+    }
+
+    /// Writes a CRAM file definition.
+    ///
+    /// The file ID is set as a blank value (`[0x00; 20]`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let mut writer = cram::io::MultithreadedWriter::new(io::sink());
+    /// writer.write_file_definition()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_file_definition(&mut self) -> io::Result<()> {
+        use super::writer::write_file_definition;
+
+        let file_definition = FileDefinition::new(self.options.version, Default::default());
+
+        let mut buf = Vec::new();
+        write_file_definition(&mut buf, &file_definition)?;
+        self.send_buf(buf)
+    }
+
+    /// Writes a CRAM file header container.
+    ///
+    /// The position of the stream is expected to be directly after the file definition.
+    ///
+    /// Entries in the reference sequence dictionary that are missing MD5 checksums (`M5`) will
+    /// automatically be calculated and added to the written record, unless no reference sequence
+    /// is used ([`ReferenceSequenceMode::None`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::io::MultithreadedWriter::new(io::sink());
+    /// writer.write_file_definition()?;
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_file_header(&header)?;
+    ///
+    /// writer.finish(&header)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        use super::writer::{
+            add_missing_reference_sequence_checksums, header_container::write_header_container,
+        };
+
+        self.header = Some(Arc::new(header.clone()));
+
+        let mut header = header.clone();
+
+        if self.options.reference_sequence_mode != ReferenceSequenceMode::None {
+            add_missing_reference_sequence_checksums(
+                &self.reference_sequence_repository,
+                header.reference_sequences_mut(),
+            )?;
+        }
+
+        let mut buf = Vec::new();
+        write_header_container(&mut buf, &header, self.options.reference_sequence_mode)?;
+        self.send_buf(buf)
+    }
+
+    /// Writes a SAM header.
+    ///
+    /// This writes the CRAM magic number, the file definition, and file header using the given SAM
+    /// header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::io::MultithreadedWriter::new(io::sink());
+    ///
+    /// let header = sam::Header::builder().add_comment("noodles-cram").build();
+    /// writer.write_header(&header)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_file_definition()?;
+        self.write_file_header(header)
+    }
+
+    /// Writes a CRAM record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::io::MultithreadedWriter::new(io::sink());
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_header(&header)?;
+    ///
+    /// let record = cram::Record::default();
+    /// writer.write_record(&header, record)?;
+    ///
+    /// writer.finish(&header)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, header: &sam::Header, mut record: Record) -> io::Result<()> {
+        use crate::data_container::builder::AddRecordError;
+
+        super::writer::lossy::apply(
+            &self.options,
+            &self.reference_sequence_repository,
+            header,
+            &mut record,
+        )?;
+
+        loop {
+            match self
+                .data_container_builder
+                .add_record(&self.options, record)
+            {
+                Ok(_) => {
+                    self.record_counter += 1;
+                    return Ok(());
+                }
+                Err(e) => match e {
+                    AddRecordError::ContainerFull(r) => {
+                        record = r;
+                        self.flush(header)?;
+                    }
+                    AddRecordError::SliceFull(r) => {
+                        record = r;
+                    }
+                    _ => return Err(io::Error::from(io::ErrorKind::InvalidInput)),
+                },
+            }
+        }
+    }
+
+    fn flush(&mut self, header: &sam::Header) -> io::Result<()> {
+        if self.data_container_builder.is_empty() {
+            return Ok(());
+        }
+
+        let State::Running {
+            write_tx,
+            encode_tx,
+            ..
+        } = &self.state
+        else {
+            panic!("invalid state");
+        };
+
+        let header = self
+            .header
+            .get_or_insert_with(|| Arc::new(header.clone()))
+            .clone();
+
+        let data_container_builder = mem::replace(
+            &mut self.data_container_builder,
+            DataContainer::builder(self.record_counter),
+        );
+
+        let (buffered_tx, buffered_rx) = crossbeam_channel::bounded(1);
+
+        write_tx.send(buffered_rx).map_err(|_| writer_closed())?;
+
+        encode_tx
+            .send((data_container_builder, header, buffered_tx))
+            .map_err(|_| writer_closed())
+    }
+
+    fn send_buf(&mut self, buf: Vec<u8>) -> io::Result<()> {
+        let State::Running { write_tx, .. } = &self.state else {
+            panic!("invalid state");
+        };
+
+        let (buffered_tx, buffered_rx) = crossbeam_channel::bounded(1);
+        buffered_tx.send(Ok(buf)).unwrap();
+
+        write_tx.send(buffered_rx).map_err(|_| writer_closed())
+    }
+}
+
+impl<W> Drop for MultithreadedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    fn drop(&mut self) {
+        if !matches!(self.state, State::Done) {
+            let header = self.header.clone().unwrap_or_default();
+            let _ = self.finish(&header);
+        }
+    }
+}
+
+impl<W> sam::alignment::io::Write for MultithreadedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::alignment::Record,
+    ) -> io::Result<()> {
+        let r = Record::try_from_alignment_record(header, record)?;
+        self.write_record(header, r)
+    }
+
+    fn finish(&mut self, header: &sam::Header) -> io::Result<()> {
+        MultithreadedWriter::finish(self, header).map(|_| ())
+    }
+}
+
+fn writer_closed() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "writer worker is closed")
+}
+
+fn spawn_writer<W>(mut writer: W, write_rx: WriteRx) -> JoinHandle<io::Result<W>>
+where
+    W: Write + Send + 'static,
+{
+    thread::spawn(move || {
+        while let Ok(buffered_rx) = write_rx.recv() {
+            if let Ok(result) = buffered_rx.recv() {
+                let buf = result?;
+                writer.write_all(&buf)?;
+            }
+        }
+
+        Ok(writer)
+    })
+}
+
+fn spawn_encoders(
+    worker_count: NonZeroUsize,
+    reference_sequence_repository: fasta::Repository,
+    options: Arc<Options>,
+    encode_rx: EncodeRx,
+) -> Vec<JoinHandle<()>> {
+    (0..worker_count.get())
+        .map(|_| {
+            let reference_sequence_repository = reference_sequence_repository.clone();
+            let options = options.clone();
+            let encode_rx = encode_rx.clone();
+
+            thread::spawn(move || {
+                while let Ok((data_container_builder, header, buffered_tx)) = encode_rx.recv() {
+                    let result = encode(
+                        &options,
+                        &reference_sequence_repository,
+                        &header,
+                        data_container_builder,
+                    );
+
+                    buffered_tx.send(result).ok();
+                }
+            })
+        })
+        .collect()
+}
+
+fn encode(
+    options: &Options,
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+    data_container_builder: crate::data_container::Builder,
+) -> io::Result<Vec<u8>> {
+    use super::writer::data_container::write_data_container;
+
+    let base_count = data_container_builder.base_count();
+
+    let data_container =
+        data_container_builder.build(options, reference_sequence_repository, header)?;
+
+    let mut buf = Vec::new();
+    write_data_container(&mut buf, &data_container, base_count)?;
+
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_records() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::alignment::{
+            io::Write,
+            record::Flags,
+            record_buf::{QualityScores, Sequence},
+            RecordBuf,
+        };
+
+        use crate::io::{writer, Reader};
+
+        let header = sam::Header::default();
+
+        let records: Vec<_> = (0..64)
+            .map(|i| {
+                RecordBuf::builder()
+                    .set_name(format!("r{i}").into_bytes().into())
+                    .set_flags(Flags::UNMAPPED)
+                    .set_sequence(Sequence::from(b"ACGT".to_vec()))
+                    .set_quality_scores(QualityScores::from(vec![i, 1, 2, 3]))
+                    .build()
+            })
+            .collect();
+
+        let mut writer = Builder::from(
+            writer::Builder::default().set_records_per_slice(NonZeroUsize::try_from(3)?),
+        )
+        .set_worker_count(NonZeroUsize::try_from(4)?)
+        .build_with_writer(Vec::new());
+
+        writer.write_alignment_header(&header)?;
+
+        for record in &records {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        let buf = writer.finish(&header)?;
+
+        let mut reader = Reader::new(&buf[..]);
+        reader.read_header()?;
+
+        let actual: Vec<_> = reader
+            .records(&header)
+            .map(|result| result.and_then(|record| record.try_into_alignment_record(&header)))
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(actual, records);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::Path,
+    sync::Arc,
+};
+
+use super::{spawn_encoders, spawn_writer, MultithreadedWriter, State};
+use crate::{io::writer, DataContainer};
+
+/// A multithreaded CRAM writer builder.
+///
+/// Writer options are set using a [`writer::Builder`], which can be converted into this builder.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroUsize;
+/// use noodles_cram::io::{multithreaded_writer, writer::{self, Profile}};
+///
+/// let builder = multithreaded_writer::Builder::from(
+///     writer::Builder::default().set_profile(Profile::Small),
+/// )
+/// .set_worker_count(NonZeroUsize::MIN);
+/// ```
+pub struct Builder {
+    writer_builder: writer::Builder,
+    worker_count: NonZeroUsize,
+}
+
+impl Builder {
+    /// Sets the worker count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram::io::multithreaded_writer::Builder;
+    /// let builder = Builder::default().set_worker_count(NonZeroUsize::MIN);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds a multithreaded CRAM writer from a path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_cram::io::multithreaded_writer::Builder;
+    /// let writer = Builder::default().build_with_path("out.cram")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_with_path<P>(self, dst: P) -> io::Result<MultithreadedWriter<File>>
+    where
+        P: AsRef<Path>,
+    {
+        File::create(dst).map(|file| self.build_with_writer(file))
+    }
+
+    /// Builds a multithreaded CRAM writer from a writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram::io::multithreaded_writer::Builder;
+    /// let writer = Builder::default().build_with_writer(io::sink());
+    /// ```
+    pub fn build_with_writer<W>(self, writer: W) -> MultithreadedWriter<W>
+    where
+        W: Write + Send + 'static,
+    {
+        let (reference_sequence_repository, options) = self.writer_builder.into_parts();
+        let options = Arc::new(options);
+
+        let worker_count = self.worker_count.get();
+
+        let (write_tx, write_rx) = crossbeam_channel::bounded(worker_count);
+        let (encode_tx, encode_rx) = crossbeam_channel::bounded(worker_count);
+
+        let writer_handle = spawn_writer(writer, write_rx);
+        let encoder_handles = spawn_encoders(
+            self.worker_count,
+            reference_sequence_repository.clone(),
+            options.clone(),
+            encode_rx,
+        );
+
+        MultithreadedWriter {
+            state: State::Running {
+                writer_handle,
+                encoder_handles,
+                write_tx,
+                encode_tx,
+            },
+            reference_sequence_repository,
+            options,
+            header: None,
+            data_container_builder: DataContainer::builder(0),
+            record_counter: 0,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::from(writer::Builder::default())
+    }
+}
+
+impl From<writer::Builder> for Builder {
+    fn from(writer_builder: writer::Builder) -> Self {
+        Self {
+            writer_builder,
+            worker_count: NonZeroUsize::MIN,
+        }
+    }
+}
@@ -350,7 +350,7 @@ where
     }
 }
 
-fn read_magic_number<R>(reader: &mut R) -> io::Result<()>
+pub(crate) fn read_magic_number<R>(reader: &mut R) -> io::Result<()>
 where
     R: Read,
 {
@@ -367,7 +367,7 @@ where
     }
 }
 
-fn read_format<R>(reader: &mut R) -> io::Result<Version>
+pub(crate) fn read_format<R>(reader: &mut R) -> io::Result<Version>
 where
     R: Read,
 {
@@ -376,7 +376,7 @@ where
     Ok(Version::new(buf[0], buf[1]))
 }
 
-fn read_file_id<R>(reader: &mut R) -> io::Result<[u8; 20]>
+pub(crate) fn read_file_id<R>(reader: &mut R) -> io::Result<[u8; 20]>
 where
     R: Read,
 {
@@ -4,12 +4,20 @@ pub(crate) mod builder;
 pub(crate) mod container;
 pub(crate) mod data_container;
 pub(crate) mod header_container;
+pub(crate) mod lossy;
 pub(crate) mod num;
 mod options;
+mod profile;
+mod quality_score_binning;
 pub(crate) mod record;
+mod reference_sequence_mode;
+mod tag_filter;
 
-pub use self::builder::Builder;
 pub(crate) use self::options::Options;
+pub use self::{
+    builder::Builder, profile::Profile, quality_score_binning::QualityScoreBinning,
+    reference_sequence_mode::ReferenceSequenceMode, tag_filter::TagFilter,
+};
 
 use std::{
     io::{self, Write},
@@ -142,7 +150,8 @@ where
     /// The position of the stream is expected to be directly after the file definition.
     ///
     /// Entries in the reference sequence dictionary that are missing MD5 checksums (`M5`) will
-    /// automatically be calculated and added to the written record.
+    /// automatically be calculated and added to the written record, unless no reference sequence
+    /// is used ([`ReferenceSequenceMode::None`]).
     ///
     /// # Examples
     ///
@@ -165,12 +174,18 @@ where
 
         let mut header = header.clone();
 
-        add_missing_reference_sequence_checksums(
-            &self.reference_sequence_repository,
-            header.reference_sequences_mut(),
-        )?;
+        if self.options.reference_sequence_mode != ReferenceSequenceMode::None {
+            add_missing_reference_sequence_checksums(
+                &self.reference_sequence_repository,
+                header.reference_sequences_mut(),
+            )?;
+        }
 
-        write_header_container(&mut self.inner, &header)
+        write_header_container(
+            &mut self.inner,
+            &header,
+            self.options.reference_sequence_mode,
+        )
     }
 
     /// Writes a SAM header.
@@ -217,8 +232,18 @@ where
     pub fn write_record(&mut self, header: &sam::Header, mut record: Record) -> io::Result<()> {
         use crate::data_container::builder::AddRecordError;
 
+        lossy::apply(
+            &self.options,
+            &self.reference_sequence_repository,
+            header,
+            &mut record,
+        )?;
+
         loop {
-            match self.data_container_builder.add_record(record) {
+            match self
+                .data_container_builder
+                .add_record(&self.options, record)
+            {
                 Ok(_) => {
                     self.record_counter += 1;
                     return Ok(());
@@ -283,7 +308,10 @@ where
     }
 }
 
-fn write_file_definition<W>(writer: &mut W, file_definition: &FileDefinition) -> io::Result<()>
+pub(crate) fn write_file_definition<W>(
+    writer: &mut W,
+    file_definition: &FileDefinition,
+) -> io::Result<()>
 where
     W: Write,
 {
@@ -398,4 +426,119 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_record_with_reference_sequence_modes() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::{
+            alignment::{
+                record::{
+                    cigar::{op::Kind, Op},
+                    Flags,
+                },
+                record_buf::{QualityScores, Sequence as RecordSequence},
+                RecordBuf,
+            },
+            header::record::value::{map::ReferenceSequence, Map},
+        };
+
+        use crate::io::Reader;
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"ACGTACGTAC".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(10)?),
+            )
+            .build();
+
+        let records = [
+            RecordBuf::builder()
+                .set_name(b"r0".to_vec().into())
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .set_sequence(RecordSequence::from(b"CGAA".to_vec()))
+                .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+                .build(),
+            RecordBuf::builder()
+                .set_name(b"r1".to_vec().into())
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(5)?)
+                .set_cigar(
+                    [
+                        Op::new(Kind::Match, 2),
+                        Op::new(Kind::Insertion, 1),
+                        Op::new(Kind::Match, 2),
+                    ]
+                    .into_iter()
+                    .collect(),
+                )
+                .set_sequence(RecordSequence::from(b"ACTGT".to_vec()))
+                .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50, 38]))
+                .build(),
+        ];
+
+        for (mode, repository) in [
+            (ReferenceSequenceMode::Embedded, repository.clone()),
+            (ReferenceSequenceMode::None, fasta::Repository::default()),
+        ] {
+            let mut writer = Builder::default()
+                .set_reference_sequence_mode(mode)
+                .set_reference_sequence_repository(repository)
+                .build_with_writer(Vec::new());
+
+            writer.write_header(&header)?;
+
+            for record in &records {
+                sam::alignment::io::Write::write_alignment_record(&mut writer, &header, record)?;
+            }
+
+            writer.try_finish(&header)?;
+
+            let mut reader = Reader::new(writer.get_ref().as_slice());
+            let actual_header = reader.read_header()?;
+
+            let data_container = reader
+                .read_data_container()?
+                .ok_or("missing data container")?;
+
+            assert!(!data_container
+                .compression_header()
+                .preservation_map()
+                .is_reference_required());
+
+            let slice = &data_container.slices()[0];
+            assert_eq!(
+                slice
+                    .header()
+                    .embedded_reference_bases_block_content_id()
+                    .is_some(),
+                mode == ReferenceSequenceMode::Embedded
+            );
+
+            let mut reader = Reader::new(writer.get_ref().as_slice());
+            reader.read_header()?;
+
+            let actual: Vec<_> = reader
+                .records(&actual_header)
+                .map(|result| {
+                    result.and_then(|record| record.try_into_alignment_record(&actual_header))
+                })
+                .collect::<Result<_, _>>()?;
+
+            assert_eq!(actual, records);
+        }
+
+        Ok(())
+    }
 }
@@ -1,12 +1,13 @@
 use std::{
     fs::File,
     io::{self, Write},
+    num::NonZeroUsize,
     path::Path,
 };
 
 use noodles_fasta as fasta;
 
-use super::{Options, Writer};
+use super::{Options, Profile, QualityScoreBinning, ReferenceSequenceMode, TagFilter, Writer};
 use crate::{
     codecs::Encoder, data_container::BlockContentEncoderMap, file_definition::Version,
     DataContainer,
@@ -91,6 +92,176 @@ impl Builder {
         self
     }
 
+    /// Sets the reference sequence mode.
+    ///
+    /// This determines whether the reference sequence is external to the file
+    /// ([`ReferenceSequenceMode::External`]), embedded in each slice
+    /// ([`ReferenceSequenceMode::Embedded`]), or not used at all ([`ReferenceSequenceMode::None`]).
+    /// A reference sequence repository is not required when no reference sequence is used.
+    ///
+    /// The default is [`ReferenceSequenceMode::External`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::{Builder, ReferenceSequenceMode};
+    /// let builder = Builder::default().set_reference_sequence_mode(ReferenceSequenceMode::None);
+    /// ```
+    pub fn set_reference_sequence_mode(mut self, mode: ReferenceSequenceMode) -> Self {
+        self.options.reference_sequence_mode = mode;
+        self
+    }
+
+    /// Sets the compression profile.
+    ///
+    /// This overrides the block content-encoder map, the CRAM version, the number of records per
+    /// slice, and the number of slices per container with the values of the given profile. These
+    /// can still be changed individually after setting a profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::{Builder, Profile};
+    /// let builder = Builder::default().set_profile(Profile::Small);
+    /// ```
+    pub fn set_profile(mut self, profile: Profile) -> Self {
+        self.options.block_content_encoder_map = profile.block_content_encoder_map();
+        self.options.version = profile.version();
+        self.options.records_per_slice = usize::from(profile.records_per_slice());
+        self.options.slices_per_container = usize::from(profile.slices_per_container());
+        self
+    }
+
+    /// Sets the maximum number of records per slice.
+    ///
+    /// The default is 10240.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram::io::writer::Builder;
+    ///
+    /// let builder = Builder::default()
+    ///     .set_records_per_slice(NonZeroUsize::try_from(25000)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_records_per_slice(mut self, records_per_slice: NonZeroUsize) -> Self {
+        self.options.records_per_slice = usize::from(records_per_slice);
+        self
+    }
+
+    /// Sets the maximum number of slices per container.
+    ///
+    /// The default is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_cram::io::writer::Builder;
+    ///
+    /// let builder = Builder::default()
+    ///     .set_slices_per_container(NonZeroUsize::try_from(4)?);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_slices_per_container(mut self, slices_per_container: NonZeroUsize) -> Self {
+        self.options.slices_per_container = usize::from(slices_per_container);
+        self
+    }
+
+    /// Sets the quality score binning.
+    ///
+    /// This is a lossy transformation. By default, quality scores are not binned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::{Builder, QualityScoreBinning};
+    /// let builder = Builder::default()
+    ///     .set_quality_score_binning(QualityScoreBinning::illumina_8_bin());
+    /// ```
+    pub fn set_quality_score_binning(mut self, binning: QualityScoreBinning) -> Self {
+        self.options.quality_score_binning = Some(binning);
+        self
+    }
+
+    /// Sets whether to preserve the quality scores of unmapped records.
+    ///
+    /// If `false`, the quality scores of unmapped records are discarded.
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::Builder;
+    /// let builder = Builder::default().preserve_unmapped_quality_scores(false);
+    /// ```
+    pub fn preserve_unmapped_quality_scores(mut self, value: bool) -> Self {
+        self.options.preserve_unmapped_quality_scores = value;
+        self
+    }
+
+    /// Sets whether to preserve the quality scores of secondary records.
+    ///
+    /// If `false`, the quality scores of secondary records are discarded.
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::Builder;
+    /// let builder = Builder::default().preserve_secondary_quality_scores(false);
+    /// ```
+    pub fn preserve_secondary_quality_scores(mut self, value: bool) -> Self {
+        self.options.preserve_secondary_quality_scores = value;
+        self
+    }
+
+    /// Sets the quality score of non-variant positions.
+    ///
+    /// If set, quality scores are only kept at variant positions of mapped records, i.e., inserted
+    /// bases and aligned bases that differ from the reference sequence. The quality scores at all
+    /// other positions are replaced with the given score. This is a lossy transformation that
+    /// requires the reference sequence repository to contain the reference sequences of mapped
+    /// records.
+    ///
+    /// By default, all quality scores are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::Builder;
+    /// let builder = Builder::default().set_non_variant_quality_score(30);
+    /// ```
+    pub fn set_non_variant_quality_score(mut self, score: u8) -> Self {
+        self.options.non_variant_quality_score = Some(score);
+        self
+    }
+
+    /// Sets the data field tag filter.
+    ///
+    /// Only the data fields with tags that pass the filter are written. This includes the read
+    /// group (`RG`).
+    ///
+    /// By default, all data fields are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::{Builder, TagFilter};
+    /// use noodles_sam::alignment::record::data::field::Tag;
+    ///
+    /// let tag_filter = TagFilter::Include([Tag::READ_GROUP].into_iter().collect());
+    /// let builder = Builder::default().set_tag_filter(tag_filter);
+    /// ```
+    pub fn set_tag_filter(mut self, tag_filter: TagFilter) -> Self {
+        self.options.tag_filter = Some(tag_filter);
+        self
+    }
+
     /// Builds a CRAM writer from a path.
     ///
     /// # Examples
@@ -115,29 +286,38 @@ impl Builder {
     /// use noodles_cram::io::writer::Builder;
     /// let writer = Builder::default().build_with_writer(Vec::new());
     /// ```
-    pub fn build_with_writer<W>(mut self, writer: W) -> Writer<W>
+    pub fn build_with_writer<W>(self, writer: W) -> Writer<W>
     where
         W: Write,
     {
-        if uses_cram_3_1_codecs(&self.options.block_content_encoder_map) {
-            self.options.version = Version::new(3, 1);
-        }
+        let (reference_sequence_repository, options) = self.into_parts();
 
         Writer {
             inner: writer,
-            reference_sequence_repository: self.reference_sequence_repository,
-            options: self.options,
+            reference_sequence_repository,
+            options,
             data_container_builder: DataContainer::builder(0),
             record_counter: 0,
         }
     }
+
+    pub(crate) fn into_parts(mut self) -> (fasta::Repository, Options) {
+        if uses_cram_3_1_codecs(&self.options.block_content_encoder_map) {
+            self.options.version = Version::new(3, 1);
+        }
+
+        (self.reference_sequence_repository, self.options)
+    }
 }
 
 pub fn uses_cram_3_1_codecs(block_content_encoder_map: &BlockContentEncoderMap) -> bool {
     fn is_cram_3_1_codec(encoder: &Encoder) -> bool {
         matches!(
             encoder,
-            Encoder::RansNx16(_) | Encoder::AdaptiveArithmeticCoding(_) | Encoder::NameTokenizer
+            Encoder::RansNx16(_)
+                | Encoder::AdaptiveArithmeticCoding(_)
+                | Encoder::NameTokenizer
+                | Encoder::Fqzcomp
         )
     }
 
@@ -7,19 +7,26 @@ use flate2::Compression;
 use noodles_sam as sam;
 
 use self::header::write_header;
-use super::container::write_block;
+use super::{container::write_block, ReferenceSequenceMode};
 use crate::{
     codecs::Encoder,
     container::{block::ContentType, Block},
 };
 
-pub fn write_header_container<W>(writer: &mut W, header: &sam::Header) -> io::Result<()>
+pub fn write_header_container<W>(
+    writer: &mut W,
+    header: &sam::Header,
+    reference_sequence_mode: ReferenceSequenceMode,
+) -> io::Result<()>
 where
     W: Write,
 {
     const ENCODER: Encoder = Encoder::Gzip(Compression::new(6));
 
-    validate_reference_sequences(header.reference_sequences())?;
+    // Reference sequence MD5 checksums cannot be calculated when no reference sequence is used.
+    if reference_sequence_mode != ReferenceSequenceMode::None {
+        validate_reference_sequences(header.reference_sequences())?;
+    }
 
     let header_data = serialize_header(header)?;
     let header_data_len = i32::try_from(header_data.len())
@@ -85,7 +92,7 @@ mod tests {
         let header = sam::Header::builder().set_header(header_header).build();
 
         let mut actual = Vec::new();
-        write_header_container(&mut actual, &header)?;
+        write_header_container(&mut actual, &header, ReferenceSequenceMode::default())?;
 
         let header_data = b"@HD\tVN:1.6\n";
         let header_data_len = i32::try_from(header_data.len())?;
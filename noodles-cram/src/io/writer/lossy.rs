@@ -0,0 +1,406 @@
+//! Lossy record transformations.
+
+use std::io;
+
+use noodles_core::Position;
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::{record::data::field::Tag, record_buf::QualityScores},
+};
+
+use super::Options;
+use crate::record::{Feature, Flags, Record};
+
+const MISSING_QUALITY_SCORE: u8 = 0xff;
+
+/// Applies the lossy options to a record.
+pub(crate) fn apply(
+    options: &Options,
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+    record: &mut Record,
+) -> io::Result<()> {
+    if let Some(tag_filter) = &options.tag_filter {
+        let tags: Vec<_> = record
+            .tags
+            .keys()
+            .filter(|tag| !tag_filter.contains(*tag))
+            .collect();
+
+        for tag in tags {
+            record.tags.remove(&tag);
+        }
+
+        if !tag_filter.contains(Tag::READ_GROUP) {
+            record.read_group_id = None;
+        }
+    }
+
+    if record.quality_scores.is_empty() {
+        return Ok(());
+    }
+
+    let flags = record.bam_flags();
+
+    if (flags.is_unmapped() && !options.preserve_unmapped_quality_scores)
+        || (flags.is_secondary() && !options.preserve_secondary_quality_scores)
+    {
+        discard_quality_scores(record);
+        return Ok(());
+    }
+
+    if let Some(score) = options.non_variant_quality_score {
+        if !flags.is_unmapped() {
+            mask_non_variant_quality_scores(reference_sequence_repository, header, record, score)?;
+        }
+    }
+
+    if let Some(binning) = &options.quality_score_binning {
+        map_quality_scores(record, |_, score| binning.bin(score));
+    }
+
+    Ok(())
+}
+
+// Missing quality scores are stored as an array of 0xff.
+fn discard_quality_scores(record: &mut Record) {
+    record
+        .features
+        .retain(|feature| !matches!(feature, Feature::Scores(..) | Feature::QualityScore(..)));
+
+    record
+        .cram_bit_flags
+        .insert(Flags::QUALITY_SCORES_STORED_AS_ARRAY);
+
+    record.quality_scores = QualityScores::from(vec![MISSING_QUALITY_SCORE; record.read_length]);
+}
+
+fn mask_non_variant_quality_scores(
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+    record: &mut Record,
+    score: u8,
+) -> io::Result<()> {
+    let Some(alignment_start) = record.alignment_start() else {
+        return Ok(());
+    };
+
+    let reference_sequence_name = record
+        .reference_sequence(header.reference_sequences())
+        .transpose()?
+        .map(|(name, _)| name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence"))?;
+
+    let reference_sequence = reference_sequence_repository
+        .get(reference_sequence_name)
+        .transpose()?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence"))?;
+
+    let is_variant = find_variant_positions(&reference_sequence, record, alignment_start);
+
+    map_quality_scores(record, |i, s| if is_variant[i] { s } else { score });
+
+    Ok(())
+}
+
+// A read position is a variant position if it is an inserted base or an aligned base that differs
+// from the reference base.
+fn find_variant_positions(
+    reference_sequence: &fasta::record::Sequence,
+    record: &Record,
+    alignment_start: Position,
+) -> Vec<bool> {
+    fn differs(reference_sequence: &fasta::record::Sequence, position: usize, base: u8) -> bool {
+        Position::new(position)
+            .and_then(|position| reference_sequence.get(position))
+            .map(|reference_base| !reference_base.eq_ignore_ascii_case(&base))
+            .unwrap_or(true)
+    }
+
+    let mut is_variant = vec![false; record.read_length];
+
+    for ((reference_position, read_position), feature) in
+        record.features.with_positions(alignment_start)
+    {
+        let reference_position = usize::from(reference_position);
+        let i = usize::from(read_position) - 1;
+
+        match feature {
+            Feature::Bases(_, bases) => {
+                for (j, &base) in bases.iter().enumerate() {
+                    if let Some(v) = is_variant.get_mut(i + j) {
+                        *v = differs(reference_sequence, reference_position + j, base);
+                    }
+                }
+            }
+            Feature::ReadBase(_, base, _) => {
+                if let Some(v) = is_variant.get_mut(i) {
+                    *v = differs(reference_sequence, reference_position, *base);
+                }
+            }
+            Feature::Substitution(..) | Feature::InsertBase(..) => {
+                if let Some(v) = is_variant.get_mut(i) {
+                    *v = true;
+                }
+            }
+            Feature::Insertion(_, bases) => {
+                for v in is_variant.iter_mut().skip(i).take(bases.len()) {
+                    *v = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    is_variant
+}
+
+// `f` is called with the 0-based read position and quality score.
+fn map_quality_scores<F>(record: &mut Record, f: F)
+where
+    F: Fn(usize, u8) -> u8,
+{
+    for (i, score) in record.quality_scores.as_mut().iter_mut().enumerate() {
+        *score = f(i, *score);
+    }
+
+    for feature in record.features.iter_mut() {
+        match feature {
+            Feature::Scores(position, scores) => {
+                let start = usize::from(*position) - 1;
+
+                for (j, score) in scores.iter_mut().enumerate() {
+                    *score = f(start + j, *score);
+                }
+            }
+            Feature::QualityScore(position, score) | Feature::ReadBase(position, _, score) => {
+                *score = f(usize::from(*position) - 1, *score);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_record() -> Result<Record, noodles_core::position::TryFromIntError> {
+        let position = Position::try_from(1)?;
+
+        Ok(Record::builder()
+            .set_bam_flags(sam::alignment::record::Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_read_length(4)
+            .set_alignment_start(position)
+            .set_features(
+                vec![
+                    Feature::Bases(position, b"ACTT".to_vec()),
+                    Feature::Scores(position, vec![8, 13, 21, 34]),
+                ]
+                .into(),
+            )
+            .set_bases(b"ACTT".to_vec().into())
+            .set_quality_scores(vec![8, 13, 21, 34].into())
+            .build())
+    }
+
+    fn scores(record: &Record) -> Option<&[u8]> {
+        record.features.iter().find_map(|feature| match feature {
+            Feature::Scores(_, scores) => Some(&scores[..]),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_apply_with_quality_score_binning() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::io::writer::QualityScoreBinning;
+
+        let options = Options {
+            quality_score_binning: Some(QualityScoreBinning::illumina_8_bin()),
+            ..Default::default()
+        };
+
+        let mut record = build_record()?;
+        apply(
+            &options,
+            &fasta::Repository::default(),
+            &sam::Header::default(),
+            &mut record,
+        )?;
+
+        assert_eq!(record.quality_scores.as_ref(), [6, 15, 22, 33]);
+        assert_eq!(scores(&record), Some(&[6, 15, 22, 33][..]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_with_non_variant_quality_score() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"ACGT".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(4)?),
+            )
+            .build();
+
+        let options = Options {
+            non_variant_quality_score: Some(30),
+            ..Default::default()
+        };
+
+        let mut record = build_record()?;
+        apply(&options, &repository, &header, &mut record)?;
+
+        assert_eq!(record.quality_scores.as_ref(), [30, 30, 21, 30]);
+        assert_eq!(scores(&record), Some(&[30, 30, 21, 30][..]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_with_discarded_secondary_quality_scores() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let options = Options {
+            preserve_secondary_quality_scores: false,
+            ..Default::default()
+        };
+
+        let mut record = build_record()?;
+        record.bam_bit_flags = sam::alignment::record::Flags::SECONDARY;
+
+        apply(
+            &options,
+            &fasta::Repository::default(),
+            &sam::Header::default(),
+            &mut record,
+        )?;
+
+        assert!(record.cram_flags().are_quality_scores_stored_as_array());
+        assert_eq!(record.quality_scores.as_ref(), [0xff; 4]);
+        assert!(scores(&record).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_with_tag_filter() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::alignment::record_buf::data::field::Value;
+
+        use crate::io::writer::TagFilter;
+
+        let options = Options {
+            tag_filter: Some(TagFilter::Exclude(
+                [Tag::ALIGNMENT_HIT_COUNT, Tag::READ_GROUP]
+                    .into_iter()
+                    .collect(),
+            )),
+            ..Default::default()
+        };
+
+        let mut record = build_record()?;
+        record.read_group_id = Some(0);
+        record.tags = [
+            (Tag::ALIGNMENT_HIT_COUNT, Value::from(1)),
+            (Tag::COMMENT, Value::from("noodles")),
+        ]
+        .into_iter()
+        .collect();
+
+        apply(
+            &options,
+            &fasta::Repository::default(),
+            &sam::Header::default(),
+            &mut record,
+        )?;
+
+        assert_eq!(record.read_group_id, None);
+        assert!(record.tags.get(&Tag::ALIGNMENT_HIT_COUNT).is_none());
+        assert!(record.tags.get(&Tag::COMMENT).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_and_read_records_with_discarded_quality_scores(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use sam::{
+            alignment::{
+                io::Write,
+                record::{
+                    cigar::{op::Kind, Op},
+                    Flags,
+                },
+                record_buf::Sequence,
+                RecordBuf,
+            },
+            header::record::value::{map::ReferenceSequence, Map},
+        };
+
+        use crate::io::{
+            writer::{Builder, ReferenceSequenceMode},
+            Reader,
+        };
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let records = [Flags::SECONDARY, Flags::UNMAPPED].map(|flags| {
+            let mut builder = RecordBuf::builder()
+                .set_flags(flags)
+                .set_sequence(Sequence::from(b"ACGT".to_vec()))
+                .set_quality_scores(QualityScores::from(vec![8, 13, 21, 34]));
+
+            if !flags.is_unmapped() {
+                builder = builder
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::MIN)
+                    .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect());
+            }
+
+            builder.build()
+        });
+
+        let mut writer = Builder::default()
+            .set_reference_sequence_mode(ReferenceSequenceMode::None)
+            .preserve_unmapped_quality_scores(false)
+            .preserve_secondary_quality_scores(false)
+            .build_with_writer(Vec::new());
+
+        writer.write_alignment_header(&header)?;
+
+        for record in &records {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        writer.finish(&header)?;
+
+        let mut reader = Reader::new(writer.get_ref().as_slice());
+        reader.read_header()?;
+
+        for result in reader.records(&header) {
+            let record = result?.try_into_alignment_record(&header)?;
+            assert_eq!(record.sequence().as_ref(), b"ACGT");
+            assert!(record.quality_scores().is_empty());
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,23 @@
+use super::{QualityScoreBinning, ReferenceSequenceMode, TagFilter};
 use crate::{data_container::BlockContentEncoderMap, file_definition::Version};
 
+pub(crate) const DEFAULT_RECORDS_PER_SLICE: usize = 10240;
+pub(crate) const DEFAULT_SLICES_PER_CONTAINER: usize = 1;
+
 #[derive(Clone, Debug)]
 pub struct Options {
     pub preserve_read_names: bool,
     pub encode_alignment_start_positions_as_deltas: bool,
     pub version: Version,
     pub block_content_encoder_map: BlockContentEncoderMap,
+    pub records_per_slice: usize,
+    pub slices_per_container: usize,
+    pub reference_sequence_mode: ReferenceSequenceMode,
+    pub quality_score_binning: Option<QualityScoreBinning>,
+    pub preserve_unmapped_quality_scores: bool,
+    pub preserve_secondary_quality_scores: bool,
+    pub non_variant_quality_score: Option<u8>,
+    pub tag_filter: Option<TagFilter>,
 }
 
 impl Default for Options {
@@ -15,6 +27,14 @@ impl Default for Options {
             encode_alignment_start_positions_as_deltas: true,
             version: Version::default(),
             block_content_encoder_map: BlockContentEncoderMap::default(),
+            records_per_slice: DEFAULT_RECORDS_PER_SLICE,
+            slices_per_container: DEFAULT_SLICES_PER_CONTAINER,
+            reference_sequence_mode: ReferenceSequenceMode::default(),
+            quality_score_binning: None,
+            preserve_unmapped_quality_scores: true,
+            preserve_secondary_quality_scores: true,
+            non_variant_quality_score: None,
+            tag_filter: None,
         }
     }
 }
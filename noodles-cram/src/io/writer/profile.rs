@@ -0,0 +1,267 @@
+//! CRAM writer compression profile.
+
+use std::num::NonZeroUsize;
+
+use crate::{
+    codecs::{aac, rans_4x8, rans_nx16, Encoder},
+    data_container::{
+        compression_header::data_series_encoding_map::{
+            data_series::STANDARD_DATA_SERIES, DataSeries,
+        },
+        BlockContentEncoderMap,
+    },
+    file_definition::Version,
+};
+
+/// A CRAM writer compression profile.
+///
+/// A profile is a preset of block content encoders, container sizes, and the CRAM version, trading
+/// off compression ratio and speed. These are similar to the `fast`, `normal`, `small`, and
+/// `archive` profiles of htslib.
+///
+/// | profile   | version | records per slice | codecs                                         |
+/// | --------- | ------- | ----------------- | ---------------------------------------------- |
+/// | `Fast`    | 3.0     | 10000             | gzip (level 1)                                 |
+/// | `Normal`  | 3.0     | 10240             | rANS 4x8, gzip                                 |
+/// | `Small`   | 3.1     | 25000             | rANS Nx16, name tokenizer, fqzcomp, bzip2      |
+/// | `Archive` | 3.1     | 100000            | AAC, name tokenizer, fqzcomp, bzip2, LZMA      |
+///
+/// All profiles write one slice per container.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Profile {
+    /// Fastest to write but with the largest output.
+    Fast,
+    /// A balance of speed and size.
+    #[default]
+    Normal,
+    /// Smaller output using CRAM 3.1 codecs.
+    Small,
+    /// Smallest output but slowest to write.
+    Archive,
+}
+
+impl Profile {
+    /// Returns the CRAM version used by the profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{file_definition::Version, io::writer::Profile};
+    /// assert_eq!(Profile::Normal.version(), Version::new(3, 0));
+    /// assert_eq!(Profile::Small.version(), Version::new(3, 1));
+    /// ```
+    pub fn version(&self) -> Version {
+        match self {
+            Self::Fast | Self::Normal => Version::new(3, 0),
+            Self::Small | Self::Archive => Version::new(3, 1),
+        }
+    }
+
+    /// Returns the maximum number of records per slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::Profile;
+    /// assert_eq!(usize::from(Profile::Archive.records_per_slice()), 100000);
+    /// ```
+    pub fn records_per_slice(&self) -> NonZeroUsize {
+        let n = match self {
+            Self::Fast => 10000,
+            Self::Normal => 10240,
+            Self::Small => 25000,
+            Self::Archive => 100000,
+        };
+
+        // SAFETY: `n` is nonzero.
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    /// Returns the maximum number of slices per container.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::Profile;
+    /// assert_eq!(usize::from(Profile::Normal.slices_per_container()), 1);
+    /// ```
+    pub fn slices_per_container(&self) -> NonZeroUsize {
+        NonZeroUsize::MIN
+    }
+
+    /// Builds the block content-encoder map used by the profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::Profile;
+    /// let block_content_encoder_map = Profile::Small.block_content_encoder_map();
+    /// ```
+    pub fn block_content_encoder_map(&self) -> BlockContentEncoderMap {
+        match self {
+            Self::Fast => build_fast_block_content_encoder_map(),
+            Self::Normal => build_normal_block_content_encoder_map(),
+            Self::Small => build_small_block_content_encoder_map(),
+            Self::Archive => build_archive_block_content_encoder_map(),
+        }
+    }
+}
+
+fn build_fast_block_content_encoder_map() -> BlockContentEncoderMap {
+    use flate2::Compression;
+
+    let encoder = Encoder::Gzip(Compression::fast());
+
+    build_block_content_encoder_map(encoder.clone(), |_| encoder.clone())
+}
+
+fn build_normal_block_content_encoder_map() -> BlockContentEncoderMap {
+    use flate2::Compression;
+
+    build_block_content_encoder_map(Encoder::Gzip(Compression::default()), |data_series| {
+        match data_series {
+            DataSeries::ReadNames | DataSeries::SoftClip | DataSeries::Insertion => {
+                Encoder::Gzip(Compression::default())
+            }
+            DataSeries::Bases | DataSeries::QualityScores => Encoder::Rans4x8(rans_4x8::Order::One),
+            _ => Encoder::Rans4x8(rans_4x8::Order::Zero),
+        }
+    })
+}
+
+fn build_small_block_content_encoder_map() -> BlockContentEncoderMap {
+    use bzip2::Compression;
+
+    build_block_content_encoder_map(Encoder::RansNx16(rans_nx16::Flags::ORDER), |data_series| {
+        match data_series {
+            DataSeries::ReadNames => Encoder::NameTokenizer,
+            DataSeries::QualityScores => Encoder::Fqzcomp,
+            DataSeries::SoftClip | DataSeries::Insertion => Encoder::Bzip2(Compression::default()),
+            DataSeries::Bases => Encoder::RansNx16(rans_nx16::Flags::ORDER),
+            _ => Encoder::RansNx16(rans_nx16::Flags::PACK | rans_nx16::Flags::RLE),
+        }
+    })
+}
+
+fn build_archive_block_content_encoder_map() -> BlockContentEncoderMap {
+    use bzip2::Compression;
+
+    const LZMA_COMPRESSION_LEVEL: u32 = 9;
+
+    build_block_content_encoder_map(
+        Encoder::AdaptiveArithmeticCoding(aac::Flags::ORDER),
+        |data_series| match data_series {
+            DataSeries::ReadNames => Encoder::NameTokenizer,
+            DataSeries::QualityScores => Encoder::Fqzcomp,
+            DataSeries::SoftClip => Encoder::Bzip2(Compression::best()),
+            DataSeries::Insertion => Encoder::Lzma(LZMA_COMPRESSION_LEVEL),
+            DataSeries::Bases => Encoder::AdaptiveArithmeticCoding(aac::Flags::ORDER),
+            _ => Encoder::AdaptiveArithmeticCoding(aac::Flags::PACK | aac::Flags::RLE),
+        },
+    )
+}
+
+fn build_block_content_encoder_map<F>(
+    core_data_encoder: Encoder,
+    data_series_encoder: F,
+) -> BlockContentEncoderMap
+where
+    F: Fn(DataSeries) -> Encoder,
+{
+    let mut builder =
+        BlockContentEncoderMap::builder().set_core_data_encoder(Some(core_data_encoder));
+
+    for &data_series in STANDARD_DATA_SERIES {
+        let encoder = data_series_encoder(data_series);
+        builder = builder.set_data_series_encoder(data_series, Some(encoder));
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::writer::builder::uses_cram_3_1_codecs;
+
+    #[test]
+    fn test_block_content_encoder_map() {
+        for profile in [
+            Profile::Fast,
+            Profile::Normal,
+            Profile::Small,
+            Profile::Archive,
+        ] {
+            let block_content_encoder_map = profile.block_content_encoder_map();
+
+            assert_eq!(
+                uses_cram_3_1_codecs(&block_content_encoder_map),
+                profile.version() == Version::new(3, 1)
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_and_read_records() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_sam::{
+            self as sam,
+            alignment::{
+                io::Write,
+                record::Flags,
+                record_buf::{QualityScores, Sequence},
+                RecordBuf,
+            },
+        };
+
+        use crate::io::{writer::Builder, Reader};
+
+        let header = sam::Header::default();
+
+        let records: Vec<_> = (0..5)
+            .map(|i| {
+                RecordBuf::builder()
+                    .set_name(format!("r{i}").into_bytes().into())
+                    .set_flags(Flags::UNMAPPED)
+                    .set_sequence(Sequence::from(b"ACGTNACGTA".to_vec()))
+                    .set_quality_scores(QualityScores::from(vec![i, 1, 2, 3, 4, 5, 6, 7, 8, 9]))
+                    .build()
+            })
+            .collect();
+
+        for profile in [
+            Profile::Fast,
+            Profile::Normal,
+            Profile::Small,
+            Profile::Archive,
+        ] {
+            let mut writer = Builder::default()
+                .set_profile(profile)
+                .set_records_per_slice(NonZeroUsize::try_from(2)?)
+                .build_with_writer(Vec::new());
+
+            writer.write_alignment_header(&header)?;
+
+            for record in &records {
+                writer.write_alignment_record(&header, record)?;
+            }
+
+            writer.finish(&header)?;
+
+            let mut reader = Reader::new(writer.get_ref().as_slice());
+            let file_definition = reader.read_file_definition()?;
+            assert_eq!(file_definition.version(), profile.version());
+            reader.read_file_header()?;
+
+            let actual: Vec<_> = reader
+                .records(&header)
+                .map(|result| result.and_then(|record| record.try_into_alignment_record(&header)))
+                .collect::<Result<_, _>>()?;
+
+            assert_eq!(actual, records);
+        }
+
+        Ok(())
+    }
+}
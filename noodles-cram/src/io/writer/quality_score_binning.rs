@@ -0,0 +1,80 @@
+use std::ops::RangeInclusive;
+
+/// A CRAM writer quality score binning.
+///
+/// Quality score binning is a lossy transformation that maps ranges of quality scores to a single
+/// representative score. This reduces the number of distinct scores and improves compression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualityScoreBinning(Vec<u8>);
+
+impl QualityScoreBinning {
+    /// Creates a quality score binning from a list of bins.
+    ///
+    /// Each bin is a range of quality scores and the score they are mapped to. Scores not in any
+    /// bin are unchanged. If bins overlap, the last bin takes precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::QualityScoreBinning;
+    ///
+    /// let binning = QualityScoreBinning::new([(0..=19, 10), (20..=93, 30)]);
+    /// assert_eq!(binning.bin(8), 10);
+    /// assert_eq!(binning.bin(37), 30);
+    /// ```
+    pub fn new<I>(bins: I) -> Self
+    where
+        I: IntoIterator<Item = (RangeInclusive<u8>, u8)>,
+    {
+        let mut table: Vec<_> = (0..=u8::MAX).collect();
+
+        for (range, score) in bins {
+            for i in range {
+                table[usize::from(i)] = score;
+            }
+        }
+
+        Self(table)
+    }
+
+    /// Creates an Illumina 8-level quality score binning.
+    ///
+    /// Scores 0 and 1 are unchanged, and the remaining scores are binned as follows: 2-9 to 6,
+    /// 10-19 to 15, 20-24 to 22, 25-29 to 27, 30-34 to 33, 35-39 to 37, and 40-93 to 40.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::QualityScoreBinning;
+    ///
+    /// let binning = QualityScoreBinning::illumina_8_bin();
+    /// assert_eq!(binning.bin(1), 1);
+    /// assert_eq!(binning.bin(8), 6);
+    /// assert_eq!(binning.bin(41), 40);
+    /// ```
+    pub fn illumina_8_bin() -> Self {
+        Self::new([
+            (2..=9, 6),
+            (10..=19, 15),
+            (20..=24, 22),
+            (25..=29, 27),
+            (30..=34, 33),
+            (35..=39, 37),
+            (40..=93, 40),
+        ])
+    }
+
+    /// Returns the binned quality score of the given score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::QualityScoreBinning;
+    /// let binning = QualityScoreBinning::new([(0..=19, 10)]);
+    /// assert_eq!(binning.bin(13), 10);
+    /// assert_eq!(binning.bin(21), 21);
+    /// ```
+    pub fn bin(&self, score: u8) -> u8 {
+        self.0[usize::from(score)]
+    }
+}
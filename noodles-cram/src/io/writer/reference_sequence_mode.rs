@@ -0,0 +1,28 @@
+/// A CRAM writer reference sequence mode.
+///
+/// This determines how the reference sequence of mapped records is stored.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ReferenceSequenceMode {
+    /// The reference sequence is external to the file.
+    ///
+    /// A reference sequence is required to write and read mapped records.
+    #[default]
+    External,
+    /// The part of the reference sequence covered by each slice is embedded in the slice.
+    ///
+    /// A reference sequence is required to write but not to read mapped records.
+    Embedded,
+    /// No reference sequence is used.
+    ///
+    /// Bases are stored verbatim, and a reference sequence is not required to write or read mapped
+    /// records. This is useful for sequences that have no reference assembly, e.g., de novo
+    /// assemblies.
+    None,
+}
+
+impl ReferenceSequenceMode {
+    /// Returns whether a reference sequence is required to restore the bases of mapped records.
+    pub(crate) fn is_reference_required(&self) -> bool {
+        matches!(self, Self::External)
+    }
+}
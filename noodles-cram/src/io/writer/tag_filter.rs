@@ -0,0 +1,39 @@
+use std::collections::HashSet;
+
+use noodles_sam::alignment::record::data::field::Tag;
+
+/// A CRAM writer data field tag filter.
+///
+/// This determines which data fields of a record are written.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TagFilter {
+    /// Only write the fields with the given tags.
+    Include(HashSet<Tag>),
+    /// Write all fields except those with the given tags.
+    Exclude(HashSet<Tag>),
+}
+
+impl TagFilter {
+    /// Returns whether a field with the given tag is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::io::writer::TagFilter;
+    /// use noodles_sam::alignment::record::data::field::Tag;
+    ///
+    /// let filter = TagFilter::Include([Tag::READ_GROUP].into_iter().collect());
+    /// assert!(filter.contains(Tag::READ_GROUP));
+    /// assert!(!filter.contains(Tag::ALIGNMENT_HIT_COUNT));
+    ///
+    /// let filter = TagFilter::Exclude([Tag::READ_GROUP].into_iter().collect());
+    /// assert!(!filter.contains(Tag::READ_GROUP));
+    /// assert!(filter.contains(Tag::ALIGNMENT_HIT_COUNT));
+    /// ```
+    pub fn contains(&self, tag: Tag) -> bool {
+        match self {
+            Self::Include(tags) => tags.contains(&tag),
+            Self::Exclude(tags) => !tags.contains(&tag),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! FASTA I/O.
 
 pub mod indexed_reader;
+pub mod indexed_writer;
 mod indexer;
 pub mod reader;
 pub mod writer;
@@ -13,7 +14,10 @@ use std::{
 
 use noodles_bgzf as bgzf;
 
-pub use self::{indexed_reader::IndexedReader, indexer::Indexer, reader::Reader, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader, indexed_writer::IndexedWriter, indexer::Indexer, reader::Reader,
+    writer::Writer,
+};
 use super::fai;
 
 /// A buffered FASTA reader.
@@ -0,0 +1,184 @@
+//! Indexed bgzipped FASTA writer.
+
+mod builder;
+
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf::{self as bgzf, gzi};
+
+pub use self::builder::Builder;
+use super::writer::write_record;
+use crate::{fai, Record};
+
+/// An indexed bgzipped FASTA writer.
+///
+/// This compresses records using a [`bgzf::MultithreadedWriter`] and builds a FASTA index (FAI)
+/// and gzip index (GZI) in the same pass.
+pub struct IndexedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    inner: bgzf::MultithreadedWriter<W>,
+    line_base_count: usize,
+    block_aligned: bool,
+    position: u64,
+    index: fai::Index,
+    buf: Vec<u8>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    /// Creates an indexed bgzipped FASTA writer with default options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// let writer = fasta::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Builder::default().build_from_writer(inner)
+    }
+
+    /// Writes a FASTA record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+    ///
+    /// let mut writer = fasta::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let definition = Definition::new("sq0", None);
+    /// let sequence = Sequence::from(b"ACGT".to_vec());
+    /// let record = fasta::Record::new(definition, sequence);
+    ///
+    /// writer.write_record(&record)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        const LINE_FEED: u8 = b'\n';
+
+        self.buf.clear();
+        write_record(&mut self.buf, record, self.line_base_count)?;
+
+        if self.block_aligned && self.buf.len() > self.inner.remaining_block_len() {
+            self.inner.flush()?;
+        }
+
+        // The definition is always followed by a line feed.
+        let definition_len = self
+            .buf
+            .iter()
+            .position(|&b| b == LINE_FEED)
+            .map(|i| i + 1)
+            .unwrap_or(self.buf.len());
+
+        let length = record.sequence().len();
+        let line_bases = length.min(self.line_base_count);
+        let line_width = if line_bases == 0 { 0 } else { line_bases + 1 };
+
+        self.index.push(fai::Record::new(
+            record.name(),
+            length as u64,
+            self.position + definition_len as u64,
+            line_bases as u64,
+            line_width as u64,
+        ));
+
+        self.inner.write_all(&self.buf)?;
+        self.position += self.buf.len() as u64;
+
+        Ok(())
+    }
+
+    /// Finishes the output stream and returns the underlying writer, FASTA index, and gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, fai, record::{Definition, Sequence}};
+    ///
+    /// let mut writer = fasta::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let definition = Definition::new("sq0", None);
+    /// let sequence = Sequence::from(b"ACGT".to_vec());
+    /// writer.write_record(&fasta::Record::new(definition, sequence))?;
+    ///
+    /// let (_, index, gzi_index) = writer.finish()?;
+    ///
+    /// assert_eq!(index, [fai::Record::new("sq0", 4, 5, 4, 5)]);
+    /// assert_eq!(gzi_index, [(0, 0)]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn finish(&mut self) -> io::Result<(W, fai::Index, gzi::Index)> {
+        let (inner, gzi_index) = self.inner.finish_with_gzi_index()?;
+        let index = mem::take(&mut self.index);
+        Ok((inner, index, gzi_index.unwrap_or_default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{Definition, Sequence};
+
+    #[test]
+    fn test_write_record() -> io::Result<()> {
+        let mut writer = Builder::default()
+            .set_line_base_count(4)
+            .build_from_writer(Vec::new());
+
+        for (name, sequence) in [("sq0", &b"ACGTACGTAC"[..]), ("sq1", b"NNN")] {
+            let definition = Definition::new(name, None);
+            let sequence = Sequence::from(sequence.to_vec());
+            writer.write_record(&Record::new(definition, sequence))?;
+        }
+
+        let (data, index, _) = writer.finish()?;
+
+        assert_eq!(
+            index,
+            [
+                fai::Record::new("sq0", 10, 5, 4, 5),
+                fai::Record::new("sq1", 3, 23, 3, 4),
+            ]
+        );
+
+        let mut reader = crate::io::Reader::new(bgzf::Reader::new(&data[..]));
+        let records: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].sequence().as_ref(), b"ACGTACGTAC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_block_alignment() -> io::Result<()> {
+        let mut writer = Builder::default()
+            .set_block_aligned(true)
+            .build_from_writer(Vec::new());
+
+        let sequence = Sequence::from(vec![b'N'; 40000]);
+
+        for name in ["sq0", "sq1"] {
+            let definition = Definition::new(name, None);
+            writer.write_record(&Record::new(definition, sequence.clone()))?;
+        }
+
+        let (_, index, gzi_index) = writer.finish()?;
+
+        // Each record starts at the beginning of a block.
+        assert_eq!(gzi_index.len(), 2);
+        assert_eq!(index[1].offset() - 5, gzi_index[1].1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::{io::Write, num::NonZeroUsize};
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+
+use super::IndexedWriter;
+
+const DEFAULT_LINE_BASE_COUNT: usize = 80;
+
+/// An indexed bgzipped FASTA writer builder.
+pub struct Builder {
+    line_base_count: usize,
+    block_aligned: bool,
+    bgzf_builder: bgzf::multithreaded_writer::Builder,
+}
+
+impl Builder {
+    /// Sets the number of bases per line.
+    ///
+    /// By default, this is set to 80.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::io::indexed_writer::Builder;
+    /// let builder = Builder::default().set_line_base_count(100);
+    /// ```
+    pub fn set_line_base_count(mut self, line_base_count: usize) -> Self {
+        self.line_base_count = line_base_count;
+        self
+    }
+
+    /// Sets whether records start at BGZF block boundaries.
+    ///
+    /// When enabled, a block is flushed early if the next record does not fit in it. Records
+    /// larger than a block still span multiple blocks.
+    ///
+    /// By default, block alignment is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::io::indexed_writer::Builder;
+    /// let builder = Builder::default().set_block_aligned(true);
+    /// ```
+    pub fn set_block_aligned(mut self, block_aligned: bool) -> Self {
+        self.block_aligned = block_aligned;
+        self
+    }
+
+    /// Sets the compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// use noodles_fasta::io::indexed_writer::Builder;
+    /// let builder = Builder::default().set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.bgzf_builder = self.bgzf_builder.set_compression_level(compression_level);
+        self
+    }
+
+    /// Sets the worker count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_fasta::io::indexed_writer::Builder;
+    /// let builder = Builder::default().set_worker_count(NonZeroUsize::MIN);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.bgzf_builder = self.bgzf_builder.set_worker_count(worker_count);
+        self
+    }
+
+    /// Builds an indexed bgzipped FASTA writer from a writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::io::indexed_writer::Builder;
+    /// let writer = Builder::default().build_from_writer(io::sink());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> IndexedWriter<W>
+    where
+        W: Write + Send + 'static,
+    {
+        IndexedWriter {
+            inner: self
+                .bgzf_builder
+                .set_gzi_indexing(true)
+                .build_from_writer(writer),
+            line_base_count: self.line_base_count,
+            block_aligned: self.block_aligned,
+            position: 0,
+            index: Vec::new(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            line_base_count: DEFAULT_LINE_BASE_COUNT,
+            block_aligned: false,
+            bgzf_builder: bgzf::multithreaded_writer::Builder::default(),
+        }
+    }
+}
@@ -6,7 +6,7 @@ mod record;
 use std::io::{self, Write};
 
 pub use self::builder::Builder;
-use self::record::write_record;
+pub(super) use self::record::write_record;
 use crate::Record;
 
 /// A FASTA writer.
@@ -6,7 +6,7 @@ use std::io::{self, Write};
 use self::{definition::write_definition, sequence::write_sequence};
 use crate::Record;
 
-pub(super) fn write_record<W>(
+pub(crate) fn write_record<W>(
     writer: &mut W,
     record: &Record,
     line_base_count: usize,
@@ -1,12 +1,18 @@
 //! FASTQ I/O.
 
+pub mod indexed_writer;
 mod indexer;
+mod interleaved_writer;
+pub mod paired_reader;
 pub mod reader;
 mod writer;
 
 use std::{fs::File, io::BufReader, path::Path};
 
-pub use self::{indexer::Indexer, reader::Reader, writer::Writer};
+pub use self::{
+    indexed_writer::IndexedWriter, indexer::Indexer, interleaved_writer::InterleavedWriter,
+    paired_reader::PairedReader, reader::Reader, writer::Writer,
+};
 use super::fai;
 
 /// Indexes a FASTQ file.
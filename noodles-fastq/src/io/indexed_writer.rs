@@ -0,0 +1,185 @@
+//! Indexed bgzipped FASTQ writer.
+
+mod builder;
+
+use std::{
+    io::{self, Write},
+    mem, str,
+};
+
+use noodles_bgzf::{self as bgzf, gzi};
+
+pub use self::builder::Builder;
+use super::writer::write_record;
+use crate::{fai, Record};
+
+/// An indexed bgzipped FASTQ writer.
+///
+/// This compresses records using a [`bgzf::MultithreadedWriter`] and builds a FASTQ index (FAI)
+/// and gzip index (GZI) in the same pass.
+pub struct IndexedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    inner: bgzf::MultithreadedWriter<W>,
+    block_aligned: bool,
+    position: u64,
+    index: fai::Index,
+    buf: Vec<u8>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write + Send + 'static,
+{
+    /// Creates an indexed bgzipped FASTQ writer with default options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// let writer = fastq::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Builder::default().build_from_writer(inner)
+    }
+
+    /// Writes a FASTQ record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq::{self as fastq, record::Definition};
+    ///
+    /// let mut writer = fastq::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let record = fastq::Record::new(Definition::new("r0", ""), "ACGT", "NDLS");
+    /// writer.write_record(&record)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        const LINE_FEED: u8 = b'\n';
+        const PLUS_LINE_LEN: u64 = 2;
+
+        let name = str::from_utf8(record.name())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.buf.clear();
+        write_record(&mut self.buf, record)?;
+
+        if self.block_aligned && self.buf.len() > self.inner.remaining_block_len() {
+            self.inner.flush()?;
+        }
+
+        // The definition is always followed by a line feed.
+        let definition_len = self
+            .buf
+            .iter()
+            .position(|&b| b == LINE_FEED)
+            .map(|i| i + 1)
+            .unwrap_or(self.buf.len());
+
+        let length = record.sequence().len() as u64;
+        let sequence_offset = self.position + definition_len as u64;
+        let quality_scores_offset = sequence_offset + length + 1 + PLUS_LINE_LEN;
+
+        self.index.push(fai::Record::new(
+            name,
+            length,
+            sequence_offset,
+            length,
+            length + 1,
+            quality_scores_offset,
+        ));
+
+        self.inner.write_all(&self.buf)?;
+        self.position += self.buf.len() as u64;
+
+        Ok(())
+    }
+
+    /// Finishes the output stream and returns the underlying writer, FASTQ index, and gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq::{self as fastq, fai, record::Definition};
+    ///
+    /// let mut writer = fastq::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let record = fastq::Record::new(Definition::new("r0", ""), "ACGT", "NDLS");
+    /// writer.write_record(&record)?;
+    ///
+    /// let (_, index, gzi_index) = writer.finish()?;
+    ///
+    /// assert_eq!(index, [fai::Record::new("r0", 4, 4, 4, 5, 11)]);
+    /// assert_eq!(gzi_index, [(0, 0)]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(&mut self) -> io::Result<(W, fai::Index, gzi::Index)> {
+        let (inner, gzi_index) = self.inner.finish_with_gzi_index()?;
+        let index = mem::take(&mut self.index);
+        Ok((inner, index, gzi_index.unwrap_or_default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{io::Indexer, record::Definition};
+
+    #[test]
+    fn test_write_record() -> io::Result<()> {
+        let mut writer = IndexedWriter::new(Vec::new());
+
+        writer.write_record(&Record::new(Definition::new("r0", ""), "ACGT", "NDLS"))?;
+        writer.write_record(&Record::new(
+            Definition::new("r1", "LN:4"),
+            "NNNNNNNNNN",
+            "NDLSNDLSND",
+        ))?;
+
+        let (data, index, _) = writer.finish()?;
+
+        let mut indexer = Indexer::new(bgzf::Reader::new(&data[..]));
+        let mut expected = Vec::new();
+
+        while let Some(record) = indexer.index_record()? {
+            expected.push(record);
+        }
+
+        assert_eq!(index, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_block_alignment() -> io::Result<()> {
+        let mut writer = Builder::default()
+            .set_block_aligned(true)
+            .build_from_writer(Vec::new());
+
+        let sequence = vec![b'N'; 20000];
+        let quality_scores = vec![b'!'; 20000];
+
+        for name in ["r0", "r1"] {
+            let record = Record::new(
+                Definition::new(name, ""),
+                sequence.clone(),
+                quality_scores.clone(),
+            );
+
+            writer.write_record(&record)?;
+        }
+
+        let (_, index, gzi_index) = writer.finish()?;
+
+        // Each record starts at the beginning of a block.
+        assert_eq!(gzi_index.len(), 2);
+        assert_eq!(index[1].sequence_offset() - 4, gzi_index[1].1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use std::{io::Write, num::NonZeroUsize};
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+
+use super::IndexedWriter;
+
+/// An indexed bgzipped FASTQ writer builder.
+#[derive(Default)]
+pub struct Builder {
+    block_aligned: bool,
+    bgzf_builder: bgzf::multithreaded_writer::Builder,
+}
+
+impl Builder {
+    /// Sets whether records start at BGZF block boundaries.
+    ///
+    /// When enabled, a block is flushed early if the next record does not fit in it. Records
+    /// larger than a block still span multiple blocks.
+    ///
+    /// By default, block alignment is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::io::indexed_writer::Builder;
+    /// let builder = Builder::default().set_block_aligned(true);
+    /// ```
+    pub fn set_block_aligned(mut self, block_aligned: bool) -> Self {
+        self.block_aligned = block_aligned;
+        self
+    }
+
+    /// Sets the compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// use noodles_fastq::io::indexed_writer::Builder;
+    /// let builder = Builder::default().set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.bgzf_builder = self.bgzf_builder.set_compression_level(compression_level);
+        self
+    }
+
+    /// Sets the worker count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_fastq::io::indexed_writer::Builder;
+    /// let builder = Builder::default().set_worker_count(NonZeroUsize::MIN);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.bgzf_builder = self.bgzf_builder.set_worker_count(worker_count);
+        self
+    }
+
+    /// Builds an indexed bgzipped FASTQ writer from a writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq::io::indexed_writer::Builder;
+    /// let writer = Builder::default().build_from_writer(io::sink());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> IndexedWriter<W>
+    where
+        W: Write + Send + 'static,
+    {
+        IndexedWriter {
+            inner: self
+                .bgzf_builder
+                .set_gzi_indexing(true)
+                .build_from_writer(writer),
+            block_aligned: self.block_aligned,
+            position: 0,
+            index: Vec::new(),
+            buf: Vec::new(),
+        }
+    }
+}
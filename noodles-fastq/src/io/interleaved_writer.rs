@@ -0,0 +1,103 @@
+use std::io::{self, Write};
+
+use super::{
+    paired_reader::{mate_names_mismatch_message, strip_mate_suffix},
+    Writer,
+};
+use crate::Record;
+
+/// An interleaved FASTQ writer.
+///
+/// This writes pairs of records to a single stream, each first mate followed by its second mate.
+pub struct InterleavedWriter<W> {
+    inner: Writer<W>,
+}
+
+impl<W> InterleavedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an interleaved FASTQ writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// let writer = fastq::io::InterleavedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: Writer::new(inner),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// let writer = fastq::io::InterleavedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Writes a pair of FASTQ records.
+    ///
+    /// This returns an [`io::ErrorKind::InvalidInput`] error if the mate names do not match,
+    /// ignoring a `/1` or `/2` suffix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq::{self as fastq, record::Definition};
+    ///
+    /// let mut writer = fastq::io::InterleavedWriter::new(Vec::new());
+    ///
+    /// let r1 = fastq::Record::new(Definition::new("r0/1", ""), "ACGT", "NDLS");
+    /// let r2 = fastq::Record::new(Definition::new("r0/2", ""), "TGCA", "SLDN");
+    /// writer.write_record_pair(&r1, &r2)?;
+    ///
+    /// assert_eq!(
+    ///     writer.get_ref(),
+    ///     b"@r0/1\nACGT\n+\nNDLS\n@r0/2\nTGCA\n+\nSLDN\n"
+    /// );
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_record_pair(&mut self, r1: &Record, r2: &Record) -> io::Result<()> {
+        if strip_mate_suffix(r1.name()) != strip_mate_suffix(r2.name()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                mate_names_mismatch_message(r1, r2),
+            ));
+        }
+
+        self.inner.write_record(r1)?;
+        self.inner.write_record(r2)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::Definition;
+
+    #[test]
+    fn test_write_record_pair_with_mismatched_names() {
+        let mut writer = InterleavedWriter::new(Vec::new());
+
+        let r1 = Record::new(Definition::new("r0/1", ""), "ACGT", "NDLS");
+        let r2 = Record::new(Definition::new("r1/2", ""), "TGCA", "SLDN");
+
+        assert!(matches!(
+            writer.write_record_pair(&r1, &r2),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert!(writer.get_ref().is_empty());
+    }
+}
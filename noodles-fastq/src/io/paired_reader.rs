@@ -0,0 +1,227 @@
+//! Paired-end FASTQ reader.
+
+mod record_pairs;
+
+pub use self::record_pairs::RecordPairs;
+
+use std::io::{self, BufRead};
+
+use super::Reader;
+use crate::Record;
+
+enum Readers<R> {
+    Split(Reader<R>, Reader<R>),
+    Interleaved(Reader<R>),
+}
+
+/// A paired-end FASTQ reader.
+///
+/// This reads mates either from two streams (e.g., R1 and R2 files) or from a single interleaved
+/// stream, where each first mate is followed by its second mate. The names of both mates must
+/// match, ignoring a `/1` or `/2` suffix. Casava comments (e.g., `1:N:0:ATCACG`) are part of the
+/// description and are not compared.
+pub struct PairedReader<R> {
+    readers: Readers<R>,
+}
+
+impl<R> PairedReader<R>
+where
+    R: BufRead,
+{
+    /// Creates a paired-end FASTQ reader from two streams.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq as fastq;
+    /// let reader = fastq::io::PairedReader::new(io::empty(), io::empty());
+    /// ```
+    pub fn new(r1: R, r2: R) -> Self {
+        Self {
+            readers: Readers::Split(Reader::new(r1), Reader::new(r2)),
+        }
+    }
+
+    /// Creates a paired-end FASTQ reader from an interleaved stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq as fastq;
+    /// let reader = fastq::io::PairedReader::interleaved(io::empty());
+    /// ```
+    pub fn interleaved(inner: R) -> Self {
+        Self {
+            readers: Readers::Interleaved(Reader::new(inner)),
+        }
+    }
+
+    /// Reads a pair of FASTQ records.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0, the
+    /// streams reached EOF.
+    ///
+    /// This returns an [`io::ErrorKind::InvalidData`] error if the mate names do not match or if
+    /// one stream ends before the other (or an interleaved stream ends with an unpaired record).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq as fastq;
+    ///
+    /// let r1 = b"@r0/1\nACGT\n+\nNDLS\n";
+    /// let r2 = b"@r0/2\nTGCA\n+\nSLDN\n";
+    /// let mut reader = fastq::io::PairedReader::new(&r1[..], &r2[..]);
+    ///
+    /// let mut record1 = fastq::Record::default();
+    /// let mut record2 = fastq::Record::default();
+    /// reader.read_record_pair(&mut record1, &mut record2)?;
+    ///
+    /// assert_eq!(record1.sequence(), b"ACGT");
+    /// assert_eq!(record2.sequence(), b"TGCA");
+    ///
+    /// assert_eq!(reader.read_record_pair(&mut record1, &mut record2)?, 0);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_record_pair(&mut self, r1: &mut Record, r2: &mut Record) -> io::Result<usize> {
+        let (n1, n2) = match &mut self.readers {
+            Readers::Split(reader1, reader2) => {
+                (reader1.read_record(r1)?, reader2.read_record(r2)?)
+            }
+            Readers::Interleaved(reader) => {
+                let n1 = reader.read_record(r1)?;
+
+                let n2 = if n1 == 0 { 0 } else { reader.read_record(r2)? };
+
+                (n1, n2)
+            }
+        };
+
+        match (n1, n2) {
+            (0, 0) => Ok(0),
+            (_, 0) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing mate 2 for {}", String::from_utf8_lossy(r1.name())),
+            )),
+            (0, _) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing mate 1 for {}", String::from_utf8_lossy(r2.name())),
+            )),
+            (n1, n2) => {
+                validate_mate_names(r1, r2)?;
+                Ok(n1 + n2)
+            }
+        }
+    }
+
+    /// Returns an iterator over record pairs starting from the current stream positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq as fastq;
+    ///
+    /// let data = b"@r0/1\nACGT\n+\nNDLS\n@r0/2\nTGCA\n+\nSLDN\n";
+    /// let mut reader = fastq::io::PairedReader::interleaved(&data[..]);
+    ///
+    /// let mut record_pairs = reader.record_pairs();
+    ///
+    /// let (r1, r2) = record_pairs.next().transpose()?.unwrap();
+    /// assert_eq!(r1.name(), b"r0/1");
+    /// assert_eq!(r2.name(), b"r0/2");
+    ///
+    /// assert!(record_pairs.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn record_pairs(&mut self) -> RecordPairs<'_, R> {
+        RecordPairs::new(self)
+    }
+}
+
+/// Returns the mate name without the `/1` or `/2` suffix.
+pub(crate) fn strip_mate_suffix(name: &[u8]) -> &[u8] {
+    match name {
+        [prefix @ .., b'/', b'1' | b'2'] => prefix,
+        _ => name,
+    }
+}
+
+fn validate_mate_names(r1: &Record, r2: &Record) -> io::Result<()> {
+    if strip_mate_suffix(r1.name()) == strip_mate_suffix(r2.name()) {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            mate_names_mismatch_message(r1, r2),
+        ))
+    }
+}
+
+pub(crate) fn mate_names_mismatch_message(r1: &Record, r2: &Record) -> String {
+    format!(
+        "mate names do not match: {} != {}",
+        String::from_utf8_lossy(r1.name()),
+        String::from_utf8_lossy(r2.name())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_record_pair() -> io::Result<()> {
+        let r1 = b"@r0/1 1:N:0:ACGT\nACGT\n+\nNDLS\n@r1 1:N:0:ACGT\nACGT\n+\nNDLS\n";
+        let r2 = b"@r0/2 2:N:0:ACGT\nTGCA\n+\nSLDN\n@r1 2:N:0:ACGT\nTGCA\n+\nSLDN\n";
+
+        let mut reader = PairedReader::new(&r1[..], &r2[..]);
+        let mut record1 = Record::default();
+        let mut record2 = Record::default();
+
+        assert!(reader.read_record_pair(&mut record1, &mut record2)? > 0);
+        assert_eq!(record1.name(), b"r0/1");
+        assert_eq!(record2.name(), b"r0/2");
+
+        assert!(reader.read_record_pair(&mut record1, &mut record2)? > 0);
+        assert_eq!(record1.name(), b"r1");
+        assert_eq!(record2.name(), b"r1");
+
+        assert_eq!(reader.read_record_pair(&mut record1, &mut record2)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_pair_with_desynchronized_streams() {
+        fn t(mut reader: PairedReader<&[u8]>) {
+            let mut record1 = Record::default();
+            let mut record2 = Record::default();
+
+            assert!(matches!(
+                reader.read_record_pair(&mut record1, &mut record2),
+                Err(e) if e.kind() == io::ErrorKind::InvalidData
+            ));
+        }
+
+        let r0 = b"@r0/1\nACGT\n+\nNDLS\n";
+        let r1 = b"@r1/2\nTGCA\n+\nSLDN\n";
+
+        t(PairedReader::new(&r0[..], &r1[..]));
+        t(PairedReader::new(&r0[..], &[][..]));
+        t(PairedReader::new(&[][..], &r1[..]));
+        t(PairedReader::interleaved(&r0[..]));
+    }
+
+    #[test]
+    fn test_strip_mate_suffix() {
+        assert_eq!(strip_mate_suffix(b"r0/1"), b"r0");
+        assert_eq!(strip_mate_suffix(b"r0/2"), b"r0");
+        assert_eq!(strip_mate_suffix(b"r0/3"), b"r0/3");
+        assert_eq!(strip_mate_suffix(b"r0"), b"r0");
+        assert_eq!(strip_mate_suffix(b"/1"), b"");
+    }
+}
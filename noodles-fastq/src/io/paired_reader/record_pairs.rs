@@ -0,0 +1,44 @@
+use std::io::{self, BufRead};
+
+use super::PairedReader;
+use crate::Record;
+
+/// An iterator over record pairs of a paired-end FASTQ reader.
+///
+/// This is created by calling [`PairedReader::record_pairs`].
+pub struct RecordPairs<'a, R> {
+    inner: &'a mut PairedReader<R>,
+    r1: Record,
+    r2: Record,
+}
+
+impl<'a, R> RecordPairs<'a, R>
+where
+    R: BufRead,
+{
+    pub(crate) fn new(inner: &'a mut PairedReader<R>) -> Self {
+        Self {
+            inner,
+            r1: Record::default(),
+            r2: Record::default(),
+        }
+    }
+}
+
+impl<'a, R> Iterator for RecordPairs<'a, R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<(Record, Record)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.r1.clear();
+        self.r2.clear();
+
+        match self.inner.read_record_pair(&mut self.r1, &mut self.r2) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok((self.r1.clone(), self.r2.clone()))),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -44,6 +44,7 @@ pub mod r#async;
 
 pub mod fai;
 pub mod io;
+pub mod quality_encoding;
 pub mod record;
 
 pub use self::record::Record;
@@ -0,0 +1,217 @@
+//! FASTQ quality score encodings.
+
+use std::io;
+
+/// A FASTQ quality score encoding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QualityEncoding {
+    /// Phred quality scores offset by 33 (Sanger, Illumina 1.8+).
+    Phred33,
+    /// Phred quality scores offset by 64 (Illumina 1.3–1.7).
+    Phred64,
+    /// Solexa quality scores offset by 64 (Solexa, Illumina 1.0).
+    Solexa,
+}
+
+const PHRED_33_OFFSET: u8 = b'!';
+const PHRED_64_OFFSET: u8 = b'@';
+const SOLEXA_MIN: u8 = b';';
+const MAX: u8 = b'~';
+
+impl QualityEncoding {
+    fn decode(self, b: u8) -> Option<u8> {
+        if b > MAX {
+            return None;
+        }
+
+        match self {
+            Self::Phred33 => b.checked_sub(PHRED_33_OFFSET),
+            Self::Phred64 => b.checked_sub(PHRED_64_OFFSET),
+            Self::Solexa => {
+                if b < SOLEXA_MIN {
+                    None
+                } else {
+                    let score = i32::from(b) - i32::from(PHRED_64_OFFSET);
+                    Some(solexa_to_phred(score))
+                }
+            }
+        }
+    }
+
+    fn encode(self, score: u8) -> u8 {
+        match self {
+            Self::Phred33 => PHRED_33_OFFSET + score.min(MAX - PHRED_33_OFFSET),
+            Self::Phred64 => PHRED_64_OFFSET + score.min(MAX - PHRED_64_OFFSET),
+            Self::Solexa => {
+                let score = phred_to_solexa(score).min(i32::from(MAX - PHRED_64_OFFSET));
+                // `score` is in the range [-5, 62].
+                (i32::from(PHRED_64_OFFSET) + score) as u8
+            }
+        }
+    }
+}
+
+/// Detects the quality score encoding from a list of encoded quality scores.
+///
+/// This uses the range of observed values to guess the encoding: any value below `;` (59)
+/// indicates Phred+33; otherwise, any value below `@` (64) indicates Solexa; otherwise, Phred+64
+/// is assumed. Scanning stops early when the encoding is unambiguous.
+///
+/// This returns `None` if there are no quality scores or a value is out of the printable range
+/// (`!`..=`~`).
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fastq::{self as fastq, quality_encoding::{self, QualityEncoding}};
+///
+/// let data = b"@r0\nACGT\n+\nhhhh\n@r1\nACGT\n+\nNDLS\n";
+/// let mut reader = fastq::io::Reader::new(&data[..]);
+/// let records: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+///
+/// let encoding = quality_encoding::detect(records.iter().map(|r| r.quality_scores()));
+/// assert_eq!(encoding, Some(QualityEncoding::Phred64));
+///
+/// assert_eq!(quality_encoding::detect([&b"NDL5"[..]]), Some(QualityEncoding::Phred33));
+/// assert_eq!(quality_encoding::detect([&b";;@@"[..]]), Some(QualityEncoding::Solexa));
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn detect<'a, I>(quality_scores: I) -> Option<QualityEncoding>
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    let mut min = None;
+
+    for scores in quality_scores {
+        for &b in scores {
+            if !(PHRED_33_OFFSET..=MAX).contains(&b) {
+                return None;
+            }
+
+            if b < SOLEXA_MIN {
+                return Some(QualityEncoding::Phred33);
+            }
+
+            min = Some(min.map_or(b, |m: u8| m.min(b)));
+        }
+    }
+
+    min.map(|m| {
+        if m < PHRED_64_OFFSET {
+            QualityEncoding::Solexa
+        } else {
+            QualityEncoding::Phred64
+        }
+    })
+}
+
+/// Converts encoded quality scores from one encoding to another in place.
+///
+/// Scores that cannot be represented in the destination encoding are clamped to its maximum.
+/// Solexa scores are mapped to and from Phred scores using their log-odds relationship, which is
+/// lossy for low scores.
+///
+/// This returns an [`io::ErrorKind::InvalidData`] error if a value is invalid in the source
+/// encoding.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fastq::quality_encoding::{self, QualityEncoding};
+///
+/// let mut quality_scores = b"hhNZ".to_vec();
+/// quality_encoding::convert(&mut quality_scores, QualityEncoding::Phred64, QualityEncoding::Phred33)?;
+/// assert_eq!(quality_scores, b"II/;");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn convert(
+    quality_scores: &mut [u8],
+    src: QualityEncoding,
+    dst: QualityEncoding,
+) -> io::Result<()> {
+    if src == dst {
+        return Ok(());
+    }
+
+    for b in quality_scores {
+        let score = src.decode(*b).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid {src:?} quality score: {:?}", char::from(*b)),
+            )
+        })?;
+
+        *b = dst.encode(score);
+    }
+
+    Ok(())
+}
+
+fn solexa_to_phred(score: i32) -> u8 {
+    let q = 10.0 * (10f64.powf(f64::from(score) / 10.0) + 1.0).log10();
+    q.round() as u8
+}
+
+fn phred_to_solexa(score: u8) -> i32 {
+    const MIN: i32 = -5;
+
+    if score == 0 {
+        return MIN;
+    }
+
+    let q = 10.0 * (10f64.powf(f64::from(score) / 10.0) - 1.0).log10();
+    (q.round() as i32).max(MIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect([]), None);
+        assert_eq!(detect([&b""[..]]), None);
+        assert_eq!(
+            detect([&b"hh"[..], &b"!"[..]]),
+            Some(QualityEncoding::Phred33)
+        );
+        assert_eq!(
+            detect([&b"hh"[..], &b";"[..]]),
+            Some(QualityEncoding::Solexa)
+        );
+        assert_eq!(
+            detect([&b"hh"[..], &b"@"[..]]),
+            Some(QualityEncoding::Phred64)
+        );
+        assert_eq!(detect([&b"h\x7f"[..]]), None);
+    }
+
+    #[test]
+    fn test_convert() -> io::Result<()> {
+        use QualityEncoding::{Phred33, Phred64, Solexa};
+
+        let mut buf = b"!+5?I~".to_vec();
+        convert(&mut buf, Phred33, Phred64)?;
+        assert_eq!(buf, b"@JT^h~");
+        convert(&mut buf, Phred64, Phred33)?;
+        assert_eq!(buf, b"!+5?I_");
+
+        let mut buf = b";@Jh".to_vec();
+        convert(&mut buf, Solexa, Phred33)?;
+        assert_eq!(buf, b"\"$+I");
+
+        let mut buf = b"!\"$+I".to_vec();
+        convert(&mut buf, Phred33, Solexa)?;
+        assert_eq!(buf, b";;@Jh");
+
+        let mut buf = b"!".to_vec();
+        assert!(matches!(
+            convert(&mut buf, Phred64, Phred33),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 //! FASTQ record.
 
-mod definition;
+pub mod definition;
 
 pub use self::definition::Definition;
 
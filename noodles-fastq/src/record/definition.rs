@@ -1,3 +1,9 @@
+//! FASTQ record definition.
+
+pub mod illumina;
+
+pub use self::illumina::Illumina;
+
 /// A FASTQ record definition.
 ///
 /// A definition represents a definition line, i.e., a read name and, optionally, a description.
@@ -0,0 +1,344 @@
+//! Illumina (CASAVA 1.8+) FASTQ record definition.
+
+use std::{error, fmt, num, str};
+
+use super::Definition;
+
+const DELIMITER: char = ':';
+
+/// An Illumina (CASAVA 1.8+) FASTQ record definition.
+///
+/// This is a structured representation of a definition line with the form
+///
+/// ```text
+/// @<instrument>:<run number>:<flowcell ID>:<lane>:<tile>:<x>:<y>[:<UMI>] <read>:<is filtered>:<control number>:<index>
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Illumina {
+    instrument: String,
+    run_number: u32,
+    flowcell_id: String,
+    lane: u32,
+    tile: u32,
+    x: u32,
+    y: u32,
+    umi: Option<String>,
+    read_number: u8,
+    is_filtered: bool,
+    control_number: u32,
+    index: String,
+}
+
+impl Illumina {
+    /// Returns the instrument ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::{definition::Illumina, Definition};
+    /// let definition = Definition::new("NDLS:8:FC0:1:2:3:5", "1:N:0:ACGT");
+    /// let illumina = Illumina::try_from(&definition)?;
+    /// assert_eq!(illumina.instrument(), "NDLS");
+    /// # Ok::<_, noodles_fastq::record::definition::illumina::ParseError>(())
+    /// ```
+    pub fn instrument(&self) -> &str {
+        &self.instrument
+    }
+
+    /// Returns the run number.
+    pub fn run_number(&self) -> u32 {
+        self.run_number
+    }
+
+    /// Returns the flowcell ID.
+    pub fn flowcell_id(&self) -> &str {
+        &self.flowcell_id
+    }
+
+    /// Returns the flowcell lane.
+    pub fn lane(&self) -> u32 {
+        self.lane
+    }
+
+    /// Returns the tile number within the flowcell lane.
+    pub fn tile(&self) -> u32 {
+        self.tile
+    }
+
+    /// Returns the x-coordinate of the cluster within the tile.
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Returns the y-coordinate of the cluster within the tile.
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// Returns the unique molecular identifier (UMI), if present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::{definition::Illumina, Definition};
+    ///
+    /// let definition = Definition::new("NDLS:8:FC0:1:2:3:5", "1:N:0:ACGT");
+    /// let illumina = Illumina::try_from(&definition)?;
+    /// assert!(illumina.umi().is_none());
+    ///
+    /// let definition = Definition::new("NDLS:8:FC0:1:2:3:5:TTGA", "1:N:0:ACGT");
+    /// let illumina = Illumina::try_from(&definition)?;
+    /// assert_eq!(illumina.umi(), Some("TTGA"));
+    /// # Ok::<_, noodles_fastq::record::definition::illumina::ParseError>(())
+    /// ```
+    pub fn umi(&self) -> Option<&str> {
+        self.umi.as_deref()
+    }
+
+    /// Returns the read number, e.g., 1 or 2 for paired-end reads.
+    pub fn read_number(&self) -> u8 {
+        self.read_number
+    }
+
+    /// Returns whether the read was filtered, i.e., did not pass the filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::{definition::Illumina, Definition};
+    /// let definition = Definition::new("NDLS:8:FC0:1:2:3:5", "1:Y:0:ACGT");
+    /// let illumina = Illumina::try_from(&definition)?;
+    /// assert!(illumina.is_filtered());
+    /// # Ok::<_, noodles_fastq::record::definition::illumina::ParseError>(())
+    /// ```
+    pub fn is_filtered(&self) -> bool {
+        self.is_filtered
+    }
+
+    /// Returns the control number.
+    ///
+    /// This is 0 when none of the control bits are on.
+    pub fn control_number(&self) -> u32 {
+        self.control_number
+    }
+
+    /// Returns the index.
+    ///
+    /// This is either the index sequence (e.g., `ACGT` or `ACGT+TTGA` for dual indexes) or the
+    /// sample number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::{definition::Illumina, Definition};
+    /// let definition = Definition::new("NDLS:8:FC0:1:2:3:5", "1:N:0:ACGT+TTGA");
+    /// let illumina = Illumina::try_from(&definition)?;
+    /// assert_eq!(illumina.index(), "ACGT+TTGA");
+    /// # Ok::<_, noodles_fastq::record::definition::illumina::ParseError>(())
+    /// ```
+    pub fn index(&self) -> &str {
+        &self.index
+    }
+}
+
+impl fmt::Display for Illumina {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.instrument,
+            self.run_number,
+            self.flowcell_id,
+            self.lane,
+            self.tile,
+            self.x,
+            self.y
+        )?;
+
+        if let Some(umi) = self.umi() {
+            write!(f, ":{umi}")?;
+        }
+
+        let is_filtered = if self.is_filtered { 'Y' } else { 'N' };
+
+        write!(
+            f,
+            " {}:{}:{}:{}",
+            self.read_number, is_filtered, self.control_number, self.index
+        )
+    }
+}
+
+/// An error returned when an Illumina FASTQ record definition fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is not valid UTF-8.
+    InvalidUtf8(str::Utf8Error),
+    /// The name is invalid.
+    InvalidName,
+    /// The description is invalid.
+    InvalidDescription,
+    /// A numeric field is invalid.
+    InvalidNumber(num::ParseIntError),
+    /// The filter flag is invalid.
+    ///
+    /// This must be `Y` or `N`.
+    InvalidIsFiltered,
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidUtf8(e) => Some(e),
+            Self::InvalidNumber(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8(_) => f.write_str("invalid UTF-8"),
+            Self::InvalidName => f.write_str("invalid name"),
+            Self::InvalidDescription => f.write_str("invalid description"),
+            Self::InvalidNumber(_) => f.write_str("invalid number"),
+            Self::InvalidIsFiltered => f.write_str("invalid is filtered"),
+        }
+    }
+}
+
+impl TryFrom<&Definition> for Illumina {
+    type Error = ParseError;
+
+    fn try_from(definition: &Definition) -> Result<Self, Self::Error> {
+        let name = str::from_utf8(definition.name()).map_err(ParseError::InvalidUtf8)?;
+        let description =
+            str::from_utf8(definition.description()).map_err(ParseError::InvalidUtf8)?;
+
+        // The description may be followed by other comments.
+        let description = description
+            .split_ascii_whitespace()
+            .next()
+            .ok_or(ParseError::InvalidDescription)?;
+
+        let name_fields: Vec<_> = name.split(DELIMITER).collect();
+
+        let (instrument, run_number, flowcell_id, lane, tile, x, y, umi) = match name_fields[..] {
+            [instrument, run_number, flowcell_id, lane, tile, x, y] => {
+                (instrument, run_number, flowcell_id, lane, tile, x, y, None)
+            }
+            [instrument, run_number, flowcell_id, lane, tile, x, y, umi] => (
+                instrument,
+                run_number,
+                flowcell_id,
+                lane,
+                tile,
+                x,
+                y,
+                Some(umi),
+            ),
+            _ => return Err(ParseError::InvalidName),
+        };
+
+        let description_fields: Vec<_> = description.split(DELIMITER).collect();
+
+        let [read_number, is_filtered, control_number, index] = description_fields[..] else {
+            return Err(ParseError::InvalidDescription);
+        };
+
+        let is_filtered = match is_filtered {
+            "Y" => true,
+            "N" => false,
+            _ => return Err(ParseError::InvalidIsFiltered),
+        };
+
+        Ok(Self {
+            instrument: instrument.into(),
+            run_number: parse_number(run_number)?,
+            flowcell_id: flowcell_id.into(),
+            lane: parse_number(lane)?,
+            tile: parse_number(tile)?,
+            x: parse_number(x)?,
+            y: parse_number(y)?,
+            umi: umi.map(String::from),
+            read_number: read_number.parse().map_err(ParseError::InvalidNumber)?,
+            is_filtered,
+            control_number: parse_number(control_number)?,
+            index: index.into(),
+        })
+    }
+}
+
+fn parse_number(s: &str) -> Result<u32, ParseError> {
+    s.parse().map_err(ParseError::InvalidNumber)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_definition_for_illumina() {
+        let definition = Definition::new(
+            "A00123:8:HNDLSDSXX:4:1101:15537:1000:ACGTTGCA",
+            "2:Y:18:ATCACG+TTAGGC",
+        );
+
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Ok(Illumina {
+                instrument: String::from("A00123"),
+                run_number: 8,
+                flowcell_id: String::from("HNDLSDSXX"),
+                lane: 4,
+                tile: 1101,
+                x: 15537,
+                y: 1000,
+                umi: Some(String::from("ACGTTGCA")),
+                read_number: 2,
+                is_filtered: true,
+                control_number: 18,
+                index: String::from("ATCACG+TTAGGC"),
+            })
+        );
+
+        let definition = Definition::new("r0", "");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidDescription)
+        );
+
+        let definition = Definition::new("r0", "1:N:0:ACGT");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidName)
+        );
+
+        let definition = Definition::new("NDLS:8:FC0:1:2:3:5", "1:N:0");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidDescription)
+        );
+
+        let definition = Definition::new("NDLS:8:FC0:1:2:3:5", "1:X:0:ACGT");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidIsFiltered)
+        );
+
+        let definition = Definition::new("NDLS:8:FC0:one:2:3:5", "1:N:0:ACGT");
+        assert!(matches!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidNumber(_))
+        ));
+    }
+
+    #[test]
+    fn test_fmt() -> Result<(), ParseError> {
+        let definition = Definition::new("NDLS:8:FC0:1:2:3:5:TTGA", "1:N:0:ACGT");
+        let illumina = Illumina::try_from(&definition)?;
+        assert_eq!(illumina.to_string(), "NDLS:8:FC0:1:2:3:5:TTGA 1:N:0:ACGT");
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+//! GFF3 feature graph.
+
+mod builder;
+mod feature;
+
+pub use self::{
+    builder::{BuildError, Builder},
+    feature::Feature,
+};
+
+use std::collections::HashMap;
+
+/// A GFF3 feature graph.
+///
+/// A feature graph links features to their parents and children using the `ID` and `Parent`
+/// attributes. Records that share an `ID` (e.g., the segments of a CDS) form a single
+/// discontinuous feature, and a feature can have more than one parent.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_gff as gff;
+///
+/// let data = b"##gff-version 3
+/// sq0\tNOODLES\tgene\t8\t55\t.\t+\t.\tID=gene0
+/// sq0\tNOODLES\tmRNA\t8\t55\t.\t+\t.\tID=mRNA0;Parent=gene0
+/// sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=mRNA0
+/// sq0\tNOODLES\texon\t21\t55\t.\t+\t.\tParent=mRNA0
+/// ";
+/// let mut reader = gff::io::Reader::new(&data[..]);
+///
+/// let graph = reader.feature_graphs().next().transpose()?.unwrap();
+///
+/// let gene = graph.get("gene0").unwrap();
+/// assert_eq!(graph.roots().collect::<Vec<_>>(), [gene]);
+///
+/// let mrna = graph.children(gene).next().unwrap();
+/// assert_eq!(mrna.id(), Some("mRNA0"));
+///
+/// let exons: Vec<_> = graph.children(mrna).map(|exon| exon.start()).collect();
+/// assert_eq!(exons.len(), 2);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeatureGraph {
+    features: Vec<Feature>,
+    ids: HashMap<String, usize>,
+}
+
+impl FeatureGraph {
+    /// Returns a builder to create a feature graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::FeatureGraph;
+    /// let builder = FeatureGraph::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the features.
+    ///
+    /// Features are in the order they are first seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::FeatureGraph;
+    /// let graph = FeatureGraph::default();
+    /// assert!(graph.features().is_empty());
+    /// ```
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Returns the feature with the given ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::{self as gff, FeatureGraph};
+    ///
+    /// let record: gff::Record = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?;
+    /// let graph = FeatureGraph::builder().add_record(record).build()?;
+    ///
+    /// assert!(graph.get("gene0").is_some());
+    /// assert!(graph.get("gene1").is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get(&self, id: &str) -> Option<&Feature> {
+        self.ids.get(id).map(|&i| &self.features[i])
+    }
+
+    /// Returns an iterator over features that have no parents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::{self as gff, FeatureGraph};
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)
+    ///     .add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tParent=gene0".parse()?)
+    ///     .build()?;
+    ///
+    /// let roots: Vec<_> = graph.roots().map(|feature| feature.ty()).collect();
+    /// assert_eq!(roots, ["gene"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn roots(&self) -> impl Iterator<Item = &Feature> {
+        self.features
+            .iter()
+            .filter(|feature| feature.parent_indices.is_empty())
+    }
+
+    /// Returns an iterator over the parents of the given feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::{self as gff, FeatureGraph};
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=mRNA0".parse()?)
+    ///     .add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=mRNA1".parse()?)
+    ///     .add_record("sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=mRNA0,mRNA1".parse()?)
+    ///     .build()?;
+    ///
+    /// let exon = &graph.features()[2];
+    /// let parents: Vec<_> = graph.parents(exon).filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(parents, ["mRNA0", "mRNA1"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parents<'a>(&'a self, feature: &'a Feature) -> impl Iterator<Item = &'a Feature> {
+        feature
+            .parent_indices
+            .iter()
+            .map(move |&i| &self.features[i])
+    }
+
+    /// Returns an iterator over the children of the given feature.
+    ///
+    /// Children are in the order they are first seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::{self as gff, FeatureGraph};
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)
+    ///     .add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=mRNA0;Parent=gene0".parse()?)
+    ///     .build()?;
+    ///
+    /// let gene = graph.get("gene0").unwrap();
+    /// let children: Vec<_> = graph.children(gene).filter_map(|feature| feature.id()).collect();
+    /// assert_eq!(children, ["mRNA0"]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn children<'a>(&'a self, feature: &'a Feature) -> impl Iterator<Item = &'a Feature> {
+        feature
+            .child_indices
+            .iter()
+            .map(move |&i| &self.features[i])
+    }
+}
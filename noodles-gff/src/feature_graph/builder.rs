@@ -0,0 +1,184 @@
+use std::{collections::HashMap, error, fmt};
+
+use super::{Feature, FeatureGraph};
+use crate::{record::attributes::field::tag, Record};
+
+/// A GFF3 feature graph builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    records: Vec<Record>,
+}
+
+/// An error returned when a GFF3 feature graph fails to build.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BuildError {
+    /// An ID is invalid.
+    ///
+    /// An ID must be a single value.
+    InvalidId,
+    /// The records of a discontinuous feature have different reference sequence names or types.
+    InconsistentFeature(String),
+    /// A `Parent` refers to an ID that does not exist.
+    MissingParent(String),
+}
+
+impl error::Error for BuildError {}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidId => f.write_str("invalid ID"),
+            Self::InconsistentFeature(id) => write!(f, "inconsistent feature: {id}"),
+            Self::MissingParent(id) => write!(f, "missing parent: {id}"),
+        }
+    }
+}
+
+impl Builder {
+    /// Adds a record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::FeatureGraph;
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(graph.features().len(), 1);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_record(mut self, record: Record) -> Self {
+        self.records.push(record);
+        self
+    }
+
+    pub(crate) fn push_record(&mut self, record: Record) {
+        self.records.push(record);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Builds a feature graph.
+    ///
+    /// Parents may be referenced before they are defined. This returns an error if a `Parent`
+    /// refers to an ID that is not in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::{feature_graph::BuildError, FeatureGraph};
+    ///
+    /// let result = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tParent=gene0".parse()?)
+    ///     .build();
+    ///
+    /// assert_eq!(result, Err(BuildError::MissingParent(String::from("gene0"))));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn build(self) -> Result<FeatureGraph, BuildError> {
+        let mut features: Vec<Feature> = Vec::new();
+        let mut ids = HashMap::new();
+        let mut parent_ids: Vec<Vec<String>> = Vec::new();
+
+        for record in self.records {
+            let id = match record.attributes().get(tag::ID) {
+                Some(value) => Some(
+                    value
+                        .as_string()
+                        .map(String::from)
+                        .ok_or(BuildError::InvalidId)?,
+                ),
+                None => None,
+            };
+
+            let record_parent_ids: Vec<String> = record
+                .attributes()
+                .get(tag::PARENT)
+                .map(|value| value.iter().cloned().collect())
+                .unwrap_or_default();
+
+            let i = if let Some(i) = id.as_ref().and_then(|id| ids.get(id).copied()) {
+                let feature: &mut Feature = &mut features[i];
+
+                if feature.reference_sequence_name() != record.reference_sequence_name()
+                    || feature.ty() != record.ty()
+                {
+                    let id = feature.id().map(String::from).unwrap_or_default();
+                    return Err(BuildError::InconsistentFeature(id));
+                }
+
+                feature.records.push(record);
+
+                i
+            } else {
+                let i = features.len();
+
+                if let Some(id) = id {
+                    ids.insert(id, i);
+                }
+
+                features.push(Feature::new(record));
+                parent_ids.push(Vec::new());
+
+                i
+            };
+
+            for parent_id in record_parent_ids {
+                if !parent_ids[i].contains(&parent_id) {
+                    parent_ids[i].push(parent_id);
+                }
+            }
+        }
+
+        for (i, feature_parent_ids) in parent_ids.into_iter().enumerate() {
+            for parent_id in feature_parent_ids {
+                let j = ids
+                    .get(&parent_id)
+                    .copied()
+                    .ok_or(BuildError::MissingParent(parent_id))?;
+
+                features[i].parent_indices.push(j);
+                features[j].child_indices.push(i);
+            }
+        }
+
+        Ok(FeatureGraph { features, ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let result = Builder::default()
+            .add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0,gene1".parse()?)
+            .build();
+        assert_eq!(result, Err(BuildError::InvalidId));
+
+        let result = Builder::default()
+            .add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)
+            .add_record("sq1\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)
+            .build();
+        assert_eq!(
+            result,
+            Err(BuildError::InconsistentFeature(String::from("cds0")))
+        );
+
+        let result = Builder::default()
+            .add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)
+            .add_record("sq0\tNOODLES\texon\t21\t34\t.\t+\t.\tID=cds0".parse()?)
+            .build();
+        assert_eq!(
+            result,
+            Err(BuildError::InconsistentFeature(String::from("cds0")))
+        );
+
+        Ok(())
+    }
+}
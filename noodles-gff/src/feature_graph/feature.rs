@@ -0,0 +1,162 @@
+use noodles_core::Position;
+
+use crate::{record::attributes::field::tag, Record};
+
+/// A GFF3 feature.
+///
+/// A feature is one or more records. Records that share an `ID` are the segments of a single
+/// discontinuous feature.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Feature {
+    pub(super) records: Vec<Record>,
+    pub(super) parent_indices: Vec<usize>,
+    pub(super) child_indices: Vec<usize>,
+}
+
+impl Feature {
+    pub(super) fn new(record: Record) -> Self {
+        Self {
+            records: vec![record],
+            parent_indices: Vec::new(),
+            child_indices: Vec::new(),
+        }
+    }
+
+    fn first_record(&self) -> &Record {
+        // SAFETY: A feature has at least one record.
+        &self.records[0]
+    }
+
+    /// Returns the feature ID.
+    ///
+    /// This is the value of the `ID` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::FeatureGraph;
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(graph.features()[0].id(), Some("gene0"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn id(&self) -> Option<&str> {
+        self.first_record()
+            .attributes()
+            .get(tag::ID)
+            .and_then(|value| value.as_string())
+    }
+
+    /// Returns the reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::FeatureGraph;
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(graph.features()[0].reference_sequence_name(), "sq0");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        self.first_record().reference_sequence_name()
+    }
+
+    /// Returns the feature type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::FeatureGraph;
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(graph.features()[0].ty(), "gene");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn ty(&self) -> &str {
+        self.first_record().ty()
+    }
+
+    /// Returns the start position.
+    ///
+    /// For a discontinuous feature, this is the minimum start position of its records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_gff::FeatureGraph;
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)
+    ///     .add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(graph.features()[0].start(), Position::try_from(8)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn start(&self) -> Position {
+        self.records
+            .iter()
+            .map(|record| record.start())
+            .min()
+            .unwrap_or(Position::MIN)
+    }
+
+    /// Returns the end position.
+    ///
+    /// For a discontinuous feature, this is the maximum end position of its records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_gff::FeatureGraph;
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)
+    ///     .add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(graph.features()[0].end(), Position::try_from(34)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn end(&self) -> Position {
+        self.records
+            .iter()
+            .map(|record| record.end())
+            .max()
+            .unwrap_or(Position::MIN)
+    }
+
+    /// Returns the records.
+    ///
+    /// A discontinuous feature has more than one record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::FeatureGraph;
+    ///
+    /// let graph = FeatureGraph::builder()
+    ///     .add_record("sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0".parse()?)
+    ///     .add_record("sq0\tNOODLES\tCDS\t21\t34\t.\t+\t0\tID=cds0".parse()?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(graph.features().len(), 1);
+    /// assert_eq!(graph.features()[0].records().len(), 2);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+}
@@ -1,10 +1,11 @@
 //! GFF reader and iterators.
 
+mod feature_graphs;
 mod lazy_line;
 mod lines;
 mod records;
 
-pub use self::{lines::Lines, records::Records};
+pub use self::{feature_graphs::FeatureGraphs, lines::Lines, records::Records};
 
 use std::{
     io::{self, BufRead, Read, Seek},
@@ -200,6 +201,44 @@ where
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self.lines())
     }
+
+    /// Returns an iterator over feature graphs starting from the current stream position.
+    ///
+    /// The records are split into a feature graph at each `###` (forward references are
+    /// resolved) directive, and a `Parent` can only refer to an `ID` in the same section. Without
+    /// this directive, all records are in a single feature graph. This stops at either EOF or
+    /// when the `FASTA` directive is read, whichever comes first.
+    ///
+    /// A `Parent` that refers to an `ID` that is not in its section is an
+    /// [`io::ErrorKind::InvalidData`] error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_gff as gff;
+    ///
+    /// let data = b"##gff-version 3
+    /// sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene0
+    /// ####
+    /// sq0\tNOODLES\tgene\t21\t34\t.\t+\t.\tID=gene1
+    /// sq0\tNOODLES\tmRNA\t21\t34\t.\t+\t.\tParent=gene1
+    /// ";
+    /// let mut reader = gff::io::Reader::new(&data[..]);
+    /// let mut feature_graphs = reader.feature_graphs();
+    ///
+    /// let graph = feature_graphs.next().transpose()?.unwrap();
+    /// assert_eq!(graph.features().len(), 1);
+    ///
+    /// let graph = feature_graphs.next().transpose()?.unwrap();
+    /// assert_eq!(graph.features().len(), 2);
+    ///
+    /// assert!(feature_graphs.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn feature_graphs(&mut self) -> FeatureGraphs<'_, R> {
+        FeatureGraphs::new(self.lines())
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -344,6 +383,57 @@ ACGT
         Ok(())
     }
 
+    #[test]
+    fn test_feature_graphs() -> io::Result<()> {
+        let data = b"\
+##gff-version 3
+sq0\tNOODLES\tgene\t8\t55\t.\t+\t.\tID=gene0
+sq0\tNOODLES\tCDS\t8\t13\t.\t+\t0\tID=cds0;Parent=mRNA0,mRNA1
+sq0\tNOODLES\tmRNA\t8\t55\t.\t+\t.\tID=mRNA0;Parent=gene0
+sq0\tNOODLES\tmRNA\t8\t34\t.\t+\t.\tID=mRNA1;Parent=gene0
+sq0\tNOODLES\tCDS\t21\t34\t.\t+\t2\tID=cds0;Parent=mRNA0,mRNA1
+###
+###
+sq0\tNOODLES\tmRNA\t89\t144\t.\t+\t.\tParent=gene0
+##FASTA
+>sq0
+ACGT
+";
+
+        let mut reader = Reader::new(&data[..]);
+        let mut feature_graphs = reader.feature_graphs();
+
+        let graph = feature_graphs
+            .next()
+            .transpose()?
+            .expect("missing feature graph");
+
+        assert_eq!(graph.features().len(), 4);
+
+        let cds = graph.get("cds0").expect("missing cds0");
+        assert_eq!(cds.records().len(), 2);
+
+        let parent_ids: Vec<_> = graph.parents(cds).filter_map(|f| f.id()).collect();
+        assert_eq!(parent_ids, ["mRNA0", "mRNA1"]);
+
+        let gene = graph.get("gene0").expect("missing gene0");
+        let child_ids: Vec<_> = graph.children(gene).filter_map(|f| f.id()).collect();
+        assert_eq!(child_ids, ["mRNA0", "mRNA1"]);
+
+        let root_ids: Vec<_> = graph.roots().filter_map(|f| f.id()).collect();
+        assert_eq!(root_ids, ["gene0"]);
+
+        // The parent is in the previous section.
+        assert!(matches!(
+            feature_graphs.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(feature_graphs.next().is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         fn t(buf: &mut String, mut reader: &[u8], expected: &str) -> io::Result<()> {
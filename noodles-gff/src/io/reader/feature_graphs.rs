@@ -0,0 +1,61 @@
+use std::io::{self, BufRead};
+
+use super::Lines;
+use crate::{feature_graph, Directive, FeatureGraph, Line};
+
+/// An iterator over feature graphs of a GFF reader.
+///
+/// This is created by calling [`super::Reader::feature_graphs`].
+pub struct FeatureGraphs<'a, R> {
+    lines: Lines<'a, R>,
+    is_eof: bool,
+}
+
+impl<'a, R> FeatureGraphs<'a, R>
+where
+    R: BufRead,
+{
+    pub(crate) fn new(lines: Lines<'a, R>) -> Self {
+        Self {
+            lines,
+            is_eof: false,
+        }
+    }
+
+    fn read_feature_graph(&mut self) -> io::Result<Option<FeatureGraph>> {
+        let mut builder = feature_graph::Builder::default();
+
+        while !self.is_eof {
+            match self.lines.next().transpose()? {
+                None | Some(Line::Directive(Directive::StartOfFasta)) => self.is_eof = true,
+                Some(Line::Directive(Directive::ForwardReferencesAreResolved)) => {
+                    if !builder.is_empty() {
+                        break;
+                    }
+                }
+                Some(Line::Record(record)) => builder.push_record(record),
+                Some(_) => {}
+            }
+        }
+
+        if builder.is_empty() {
+            Ok(None)
+        } else {
+            builder
+                .build()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+    }
+}
+
+impl<'a, R> Iterator for FeatureGraphs<'a, R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<FeatureGraph>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_feature_graph().transpose()
+    }
+}
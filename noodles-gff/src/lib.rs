@@ -35,12 +35,13 @@
 pub mod r#async;
 
 pub mod directive;
+pub mod feature_graph;
 pub mod io;
 pub mod lazy;
 pub mod line;
 pub mod record;
 
-pub use self::{directive::Directive, line::Line, record::Record};
+pub use self::{directive::Directive, feature_graph::FeatureGraph, line::Line, record::Record};
 
 #[deprecated(since = "0.33.0", note = "Use `noodles_gff::io::Reader` instead.")]
 pub use self::io::Reader;
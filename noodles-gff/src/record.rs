@@ -12,7 +12,8 @@ pub use self::{
 
 use std::{error, fmt, num, str::FromStr};
 
-use noodles_core::Position;
+use bstr::{BStr, ByteSlice};
+use noodles_core::{ops, region::Interval, Position};
 
 pub(crate) const MISSING_FIELD: &str = ".";
 const FIELD_DELIMITER: char = '\t';
@@ -174,6 +175,16 @@ impl Record {
     }
 }
 
+impl ops::Feature for Record {
+    fn reference_sequence_name(&self) -> &BStr {
+        self.reference_sequence_name().as_bytes().as_bstr()
+    }
+
+    fn interval(&self) -> Interval {
+        Interval::from(self.start()..=self.end())
+    }
+}
+
 impl Default for Record {
     fn default() -> Self {
         Builder::new().build()
@@ -9,7 +9,8 @@ pub use self::{attributes::Attributes, builder::Builder, frame::Frame, strand::S
 
 use std::{error, fmt, num, str::FromStr};
 
-use noodles_core::Position;
+use bstr::{BStr, ByteSlice};
+use noodles_core::{ops, region::Interval, Position};
 
 pub(crate) const MISSING_FIELD: &str = ".";
 
@@ -168,6 +169,16 @@ impl Record {
     }
 }
 
+impl ops::Feature for Record {
+    fn reference_sequence_name(&self) -> &BStr {
+        self.reference_sequence_name().as_bytes().as_bstr()
+    }
+
+    fn interval(&self) -> Interval {
+        Interval::from(self.start()..=self.end())
+    }
+}
+
 impl Default for Record {
     fn default() -> Self {
         Self::builder().build()
@@ -1,59 +1,272 @@
 use std::pin::Pin;
 
 use bytes::Bytes;
-use futures::{stream, Stream, TryStreamExt};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 
 use super::{response::ticket::BlockUrl, Client, Error};
 
 pub(crate) fn chunks<'a>(
     client: &'a Client,
     urls: &'a [BlockUrl],
-) -> impl Stream<Item = crate::Result<Bytes>> + 'a {
-    Box::pin(
-        stream::try_unfold((client, urls, 0), |(client, urls, i)| async move {
-            match urls.get(i) {
-                Some(url) => {
-                    let st = resolve_data(client, url).await;
-                    Ok(Some((st, (client, urls, i + 1))))
+) -> Pin<Box<dyn Stream<Item = crate::Result<Bytes>> + 'a>> {
+    if client.concurrency().get() == 1 && client.retry_policy().max_retries() == 0 {
+        Box::pin(
+            stream::try_unfold((client, urls, 0), |(client, urls, i)| async move {
+                match urls.get(i) {
+                    Some(url) => {
+                        let st = resolve_data(client, url).await;
+                        Ok(Some((st, (client, urls, i + 1))))
+                    }
+                    None => Ok(None),
                 }
-                None => Ok(None),
-            }
-        })
-        .try_flatten(),
-    )
+            })
+            .try_flatten(),
+        )
+    } else {
+        // `buffered` polls up to `concurrency` requests at a time and yields their results in
+        // order.
+        Box::pin(
+            stream::iter(urls)
+                .map(move |url| fetch_data(client, url))
+                .buffered(client.concurrency().get()),
+        )
+    }
 }
 
 async fn resolve_data(
     client: &Client,
     block_url: &BlockUrl,
 ) -> Pin<Box<dyn Stream<Item = crate::Result<Bytes>>>> {
-    use base64::prelude::{Engine as _, BASE64_STANDARD};
-
-    const DELIMITER: &str = ";base64,";
-
     let url = block_url.url();
 
     if url.scheme() == "data" {
-        // _Htsget retrieval API spec v1.3.0_ § "Inline data block URIs": "client should ignore the
-        // media type (if any), treating the payload as a partial blob."
-        if let Some((_, encoded_data)) = url.as_str().split_once(DELIMITER) {
-            match BASE64_STANDARD.decode(encoded_data) {
-                Ok(data) => Box::pin(stream::once(async { Ok(Bytes::from(data)) })),
-                Err(e) => Box::pin(stream::once(async { Err(Error::Decode(e)) })),
-            }
-        } else {
-            Box::pin(stream::once(async { Err(Error::InvalidDataUrl) }))
-        }
+        let result = decode_data_url(block_url);
+        Box::pin(stream::once(async { result }))
     } else {
-        let mut request = client.http_client().get(url.clone());
+        match send(client, block_url).await {
+            Ok(response) => Box::pin(response.bytes_stream().map_err(Error::Request)),
+            Err(e) => Box::pin(stream::once(async { Err(e) })),
+        }
+    }
+}
 
-        for (key, value) in block_url.headers() {
-            request = request.header(key, value);
+async fn fetch_data(client: &Client, block_url: &BlockUrl) -> crate::Result<Bytes> {
+    if block_url.url().scheme() == "data" {
+        return decode_data_url(block_url);
+    }
+
+    let retry_policy = client.retry_policy();
+    let mut retry = 0;
+
+    loop {
+        let result = match send(client, block_url).await {
+            Ok(response) => response.bytes().await.map_err(Error::Request),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(data) => return Ok(data),
+            Err(e) if is_retryable(&e) && retry < retry_policy.max_retries() => {
+                tokio::time::sleep(retry_policy.backoff(retry)).await;
+                retry += 1;
+            }
+            Err(e) => return Err(e),
         }
+    }
+}
 
-        match request.send().await.map_err(Error::Request) {
-            Ok(response) => Box::pin(response.bytes_stream().map_err(Error::Request)),
-            Err(e) => Box::pin(stream::once(async { Err(e) })),
+// Returns whether the request failed to connect, timed out, or the server responded with a
+// server error (5xx) or too many requests (429).
+fn is_retryable(e: &Error) -> bool {
+    use reqwest::StatusCode;
+
+    match e {
+        Error::Request(e) => {
+            e.is_connect()
+                || e.is_timeout()
+                || e.status().is_some_and(|status| {
+                    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+                })
         }
+        _ => false,
+    }
+}
+
+async fn send(client: &Client, block_url: &BlockUrl) -> crate::Result<reqwest::Response> {
+    let mut request = client.http_client().get(block_url.url().clone());
+
+    for (key, value) in block_url.headers() {
+        request = request.header(key, value);
+    }
+
+    request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(Error::Request)
+}
+
+fn decode_data_url(block_url: &BlockUrl) -> crate::Result<Bytes> {
+    use base64::prelude::{Engine as _, BASE64_STANDARD};
+
+    const DELIMITER: &str = ";base64,";
+
+    // _Htsget retrieval API spec v1.3.0_ § "Inline data block URIs": "client should ignore the
+    // media type (if any), treating the payload as a partial blob."
+    let (_, encoded_data) = block_url
+        .url()
+        .as_str()
+        .split_once(DELIMITER)
+        .ok_or(Error::InvalidDataUrl)?;
+
+    BASE64_STANDARD
+        .decode(encoded_data)
+        .map(Bytes::from)
+        .map_err(Error::Decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+        time::Instant,
+    };
+
+    use super::*;
+    use crate::client::RetryPolicy;
+
+    // Starts a local HTTP server that sends the given responses, one per connection, and returns
+    // its URL and a handle that resolves to the number of requests received.
+    async fn serve(
+        responses: Vec<(u16, &'static str)>,
+    ) -> std::io::Result<(String, JoinHandle<std::io::Result<usize>>)> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/", listener.local_addr()?);
+
+        let handle = tokio::spawn(async move {
+            let mut request_count = 0;
+
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await?;
+
+                let mut buf = Vec::new();
+                let mut chunk = [0; 1024];
+
+                while !buf.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut chunk).await? {
+                        0 => break,
+                        n => buf.extend_from_slice(&chunk[..n]),
+                    }
+                }
+
+                request_count += 1;
+
+                let response = format!(
+                    "HTTP/1.1 {status} \r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+
+                stream.write_all(response.as_bytes()).await?;
+                stream.shutdown().await?;
+            }
+
+            Ok(request_count)
+        });
+
+        Ok((url, handle))
+    }
+
+    fn build_client(retry_policy: RetryPolicy) -> Result<Client, Box<dyn std::error::Error>> {
+        let http_client = reqwest::Client::builder().no_proxy().build()?;
+
+        Ok(Client::builder()
+            .set_http_client(http_client)
+            .set_retry_policy(retry_policy)
+            .build("https://localhost/".parse()?))
+    }
+
+    #[tokio::test]
+    async fn test_chunks_with_retries() -> Result<(), Box<dyn std::error::Error>> {
+        let initial_backoff = Duration::from_millis(20);
+        let client = build_client(RetryPolicy::new(2, initial_backoff))?;
+
+        let (url, handle) = serve(vec![(503, ""), (429, ""), (200, "noodles")]).await?;
+        let urls = [BlockUrl::new(url.parse()?, HashMap::new(), None)];
+
+        let start = Instant::now();
+        let actual: Vec<_> = chunks(&client, &urls).try_collect().await?;
+        let elapsed = start.elapsed();
+
+        assert_eq!(actual, [Bytes::from_static(b"noodles")]);
+        assert_eq!(handle.await??, 3);
+        // The backoffs are 20 ms and 40 ms.
+        assert!(elapsed >= initial_backoff * 3);
+
+        let (url, handle) = serve(vec![(500, ""), (502, ""), (503, "")]).await?;
+        let urls = [BlockUrl::new(url.parse()?, HashMap::new(), None)];
+        let mut stream = chunks(&client, &urls);
+        assert!(matches!(stream.next().await, Some(Err(Error::Request(_)))));
+        assert_eq!(handle.await??, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunks_with_client_error() -> Result<(), Box<dyn std::error::Error>> {
+        let client = build_client(RetryPolicy::new(2, Duration::from_millis(20)))?;
+
+        let (url, handle) = serve(vec![(404, "")]).await?;
+        let urls = [BlockUrl::new(url.parse()?, HashMap::new(), None)];
+
+        let mut stream = chunks(&client, &urls);
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(Error::Request(e))) if e.status() == Some(reqwest::StatusCode::NOT_FOUND)
+        ));
+
+        assert_eq!(handle.await??, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunks_with_concurrency() -> Result<(), Box<dyn std::error::Error>> {
+        let client = Client::builder()
+            .set_concurrency(NonZeroUsize::new(2).unwrap())
+            .build("https://localhost/".parse()?);
+
+        let urls = [
+            "data:;base64,bm9vZGxlcw==",
+            "data:;base64,LQ==",
+            "data:application/octet-stream;base64,aHRzZ2V0",
+        ]
+        .into_iter()
+        .map(|s| {
+            s.parse()
+                .map(|url| BlockUrl::new(url, HashMap::new(), None))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let actual: Vec<_> = chunks(&client, &urls).try_collect().await?;
+        let expected = [
+            Bytes::from_static(b"noodles"),
+            Bytes::from_static(b"-"),
+            Bytes::from_static(b"htsget"),
+        ];
+        assert_eq!(actual, expected);
+
+        let urls = [BlockUrl::new("data:,".parse()?, HashMap::new(), None)];
+        let mut stream = chunks(&client, &urls);
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(Error::InvalidDataUrl))
+        ));
+
+        Ok(())
     }
 }
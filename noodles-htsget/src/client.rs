@@ -1,3 +1,12 @@
+//! htsget client.
+
+mod builder;
+mod retry_policy;
+
+pub use self::{builder::Builder, retry_policy::RetryPolicy};
+
+use std::num::NonZeroUsize;
+
 use url::Url;
 
 use super::{reads, request, request::Kind, variants};
@@ -7,6 +16,8 @@ use super::{reads, request, request::Kind, variants};
 pub struct Client {
     http_client: reqwest::Client,
     base_url: Url,
+    concurrency: NonZeroUsize,
+    retry_policy: RetryPolicy,
 }
 
 impl Client {
@@ -21,7 +32,19 @@ impl Client {
     /// # Ok::<_, url::ParseError>(())
     /// ```
     pub fn new(base_url: Url) -> Self {
-        Self::with_http_client(reqwest::Client::new(), base_url)
+        Builder::default().build(base_url)
+    }
+
+    /// Returns a builder to create a client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget as htsget;
+    /// let builder = htsget::Client::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
     }
 
     /// Creates a htsget client with the given HTTP client.
@@ -36,10 +59,9 @@ impl Client {
     /// # Ok::<_, url::ParseError>(())
     /// ```
     pub fn with_http_client(http_client: reqwest::Client, base_url: Url) -> Self {
-        Self {
-            http_client,
-            base_url,
-        }
+        Builder::default()
+            .set_http_client(http_client)
+            .build(base_url)
     }
 
     pub(crate) fn http_client(&self) -> &reqwest::Client {
@@ -50,6 +72,14 @@ impl Client {
         &self.base_url
     }
 
+    pub(crate) fn concurrency(&self) -> NonZeroUsize {
+        self.concurrency
+    }
+
+    pub(crate) fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
     /// Creates a reads request for the given ID.
     ///
     /// # Examples
@@ -0,0 +1,97 @@
+use std::num::NonZeroUsize;
+
+use url::Url;
+
+use super::{Client, RetryPolicy};
+
+/// An htsget client builder.
+#[derive(Debug)]
+pub struct Builder {
+    http_client: Option<reqwest::Client>,
+    concurrency: NonZeroUsize,
+    retry_policy: RetryPolicy,
+}
+
+impl Builder {
+    /// Sets the HTTP client.
+    ///
+    /// By default, a new HTTP client is created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::client::Builder;
+    /// let builder = Builder::default().set_http_client(reqwest::Client::new());
+    /// ```
+    pub fn set_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Sets the maximum number of ticket URLs to fetch concurrently.
+    ///
+    /// Data is always returned in ticket order. When this is greater than 1, each block is
+    /// buffered in memory.
+    ///
+    /// By default, this is 1, i.e., blocks are fetched one after another.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_htsget::client::Builder;
+    /// let builder = Builder::default().set_concurrency(NonZeroUsize::new(4).unwrap());
+    /// ```
+    pub fn set_concurrency(mut self, concurrency: NonZeroUsize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets the retry policy for fetching ticket URLs.
+    ///
+    /// When retries are enabled, each block is buffered in memory.
+    ///
+    /// By default, failed blocks are not retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use noodles_htsget::client::{Builder, RetryPolicy};
+    ///
+    /// let retry_policy = RetryPolicy::new(3, Duration::from_millis(250));
+    /// let builder = Builder::default().set_retry_policy(retry_policy);
+    /// ```
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Builds an htsget client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::client::Builder;
+    /// let client = Builder::default().build("https://localhost/".parse()?);
+    /// # Ok::<_, url::ParseError>(())
+    /// ```
+    pub fn build(self, base_url: Url) -> Client {
+        Client {
+            http_client: self.http_client.unwrap_or_default(),
+            base_url,
+            concurrency: self.concurrency,
+            retry_policy: self.retry_policy,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            http_client: None,
+            concurrency: NonZeroUsize::MIN,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
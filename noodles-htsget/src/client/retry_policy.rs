@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A retry policy for fetching ticket URLs.
+///
+/// Requests that fail to connect, time out, or get a server error (5xx) or too many requests (429)
+/// response are retried with an exponential backoff, starting at the initial backoff and doubling
+/// after each attempt, up to 30 seconds. Other failures are not retried.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    initial_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a retry policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use noodles_htsget::client::RetryPolicy;
+    /// let retry_policy = RetryPolicy::new(3, Duration::from_millis(250));
+    /// ```
+    pub const fn new(max_retries: usize, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+        }
+    }
+
+    /// Returns the maximum number of retries per request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use noodles_htsget::client::RetryPolicy;
+    /// let retry_policy = RetryPolicy::new(3, Duration::from_millis(250));
+    /// assert_eq!(retry_policy.max_retries(), 3);
+    /// ```
+    pub const fn max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    /// Returns the backoff before the first retry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use noodles_htsget::client::RetryPolicy;
+    /// let retry_policy = RetryPolicy::new(3, Duration::from_millis(250));
+    /// assert_eq!(retry_policy.initial_backoff(), Duration::from_millis(250));
+    /// ```
+    pub const fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    /// Returns the backoff before the given retry (0-based).
+    pub(crate) fn backoff(&self, retry: usize) -> Duration {
+        let factor = 1u32.checked_shl(retry as u32).unwrap_or(u32::MAX);
+
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Returns a retry policy that does not retry.
+    fn default() -> Self {
+        Self::new(0, DEFAULT_INITIAL_BACKOFF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let retry_policy = RetryPolicy::new(3, Duration::from_millis(100));
+        assert_eq!(retry_policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(retry_policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(retry_policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(retry_policy.backoff(64), MAX_BACKOFF);
+    }
+}
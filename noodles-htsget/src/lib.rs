@@ -1,13 +1,18 @@
 #![warn(missing_docs)]
 
 //! **noodles-htsget** is an htsget 1.3 client.
+//!
+//! Server-side support to resolve htsget tickets for local files is available in [`server`] when
+//! the `server` feature is enabled.
 
 pub(crate) mod chunks;
-mod client;
+pub mod client;
 mod format;
 pub mod reads;
 pub(crate) mod request;
 pub mod response;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod variants;
 
 pub use self::{client::Client, format::Format, response::Response};
@@ -1,15 +1,25 @@
 //! htsget response.
 
 mod error;
-pub(crate) mod ticket;
+pub mod ticket;
 
-pub use self::error::Error;
-pub(crate) use self::ticket::Ticket;
+pub use self::{error::Error, ticket::Ticket};
+
+#[cfg(any(feature = "bam", feature = "vcf"))]
+use std::io;
 
 use bytes::Bytes;
 use futures::Stream;
+#[cfg(feature = "bam")]
+use noodles_bam as bam;
+#[cfg(feature = "bam")]
+use noodles_sam as sam;
+#[cfg(feature = "vcf")]
+use noodles_vcf as vcf;
 
 use super::Client;
+#[cfg(any(feature = "bam", feature = "vcf"))]
+use super::Format;
 
 /// An htsget response.
 #[derive(Debug)]
@@ -29,9 +39,275 @@ impl Response {
         &self.id
     }
 
+    /// Returns the ticket.
+    pub fn ticket(&self) -> &Ticket {
+        &self.ticket
+    }
+
     /// Returns the data from the ticket URLs.
+    ///
+    /// The ticket URLs are fetched using the concurrency and retry policy of the client (see
+    /// [`crate::client::Builder`]). Data is returned in ticket order.
     pub fn chunks(&self) -> impl Stream<Item = crate::Result<Bytes>> + '_ {
         use super::chunks::chunks;
         chunks(&self.client, self.ticket.urls())
     }
+
+    /// Reads the header and returns a stream over BAM records from the ticket URLs.
+    ///
+    /// This returns an [`io::ErrorKind::InvalidData`] error if the ticket format is not BAM.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles_htsget as htsget;
+    ///
+    /// let client = htsget::Client::new("https://localhost/".parse()?);
+    /// let response = client.reads("NDLS0001").send().await?;
+    ///
+    /// let (header, mut records) = response.bam_records().await?;
+    ///
+    /// while let Some(record) = records.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "bam")]
+    pub async fn bam_records(
+        &self,
+    ) -> io::Result<(
+        sam::Header,
+        impl Stream<Item = io::Result<bam::Record>> + '_,
+    )> {
+        use futures::stream;
+
+        self.validate_format(Format::Bam)?;
+
+        let mut reader = bam::r#async::io::Reader::new(self.reader());
+        let header = reader.read_header().await?;
+
+        let records = Box::pin(stream::try_unfold(
+            (reader, bam::Record::default()),
+            |(mut reader, mut record)| async move {
+                match reader.read_record(&mut record).await? {
+                    0 => Ok(None),
+                    _ => Ok(Some((record.clone(), (reader, record)))),
+                }
+            },
+        ));
+
+        Ok((header, records))
+    }
+
+    /// Reads the header and returns a stream over VCF records from the ticket URLs.
+    ///
+    /// This returns an [`io::ErrorKind::InvalidData`] error if the ticket format is not VCF.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles_htsget as htsget;
+    ///
+    /// let client = htsget::Client::new("https://localhost/".parse()?);
+    /// let response = client.variants("NDLS0001").send().await?;
+    ///
+    /// let (header, mut records) = response.vcf_records().await?;
+    ///
+    /// while let Some(record) = records.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "vcf")]
+    pub async fn vcf_records(
+        &self,
+    ) -> io::Result<(
+        vcf::Header,
+        impl Stream<Item = io::Result<vcf::Record>> + '_,
+    )> {
+        use futures::stream;
+        use noodles_bgzf as bgzf;
+
+        self.validate_format(Format::Vcf)?;
+
+        let mut reader = vcf::r#async::io::Reader::new(bgzf::AsyncReader::new(self.reader()));
+        let header = reader.read_header().await?;
+
+        let records = Box::pin(stream::try_unfold(
+            (reader, vcf::Record::default()),
+            |(mut reader, mut record)| async move {
+                match reader.read_record(&mut record).await? {
+                    0 => Ok(None),
+                    _ => Ok(Some((record.clone(), (reader, record)))),
+                }
+            },
+        ));
+
+        Ok((header, records))
+    }
+
+    #[cfg(any(feature = "bam", feature = "vcf"))]
+    fn validate_format(&self, expected: Format) -> io::Result<()> {
+        let actual = self.ticket.format();
+
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid format: expected {expected:?}, got {actual:?}"),
+            ))
+        }
+    }
+
+    #[cfg(any(feature = "bam", feature = "vcf"))]
+    fn reader(&self) -> impl tokio::io::AsyncRead + Unpin + '_ {
+        use futures::TryStreamExt;
+        use tokio_util::io::StreamReader;
+
+        let chunks = self
+            .chunks()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e));
+
+        StreamReader::new(Box::pin(chunks))
+    }
+}
+
+#[cfg(all(test, any(feature = "bam", feature = "vcf")))]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::response::ticket::BlockUrl;
+
+    fn build_response(format: crate::Format, data: &[u8]) -> Result<Response, url::ParseError> {
+        use base64::prelude::{Engine as _, BASE64_STANDARD};
+
+        // The data is split into two blocks to exercise reading across chunks.
+        let (a, b) = data.split_at(data.len() / 2);
+
+        let urls = [a, b]
+            .into_iter()
+            .map(|buf| {
+                format!("data:;base64,{}", BASE64_STANDARD.encode(buf))
+                    .parse()
+                    .map(|url| BlockUrl::new(url, HashMap::new(), None))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let client = Client::new("https://localhost/".parse()?);
+        let ticket = Ticket::new(format, urls, None);
+
+        Ok(Response::new(client, String::from("NDLS0001"), ticket))
+    }
+
+    #[cfg(feature = "bam")]
+    #[tokio::test]
+    async fn test_bam_records() -> Result<(), Box<dyn std::error::Error>> {
+        use futures::TryStreamExt;
+        use noodles_core::Position;
+        use sam::alignment::{
+            io::Write,
+            record::cigar::{op::Kind, Op},
+            RecordBuf,
+        };
+
+        let header: sam::Header = "@SQ\tSN:sq0\tLN:34\n".parse()?;
+
+        let mut writer = bam::io::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for start in [8, 13] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        let data = writer.into_inner().finish()?;
+        let response = build_response(Format::Bam, &data)?;
+
+        let (actual_header, records) = response.bam_records().await?;
+        assert_eq!(actual_header, header);
+
+        let records: Vec<_> = records.try_collect().await?;
+
+        let positions = records
+            .iter()
+            .map(|record| record.alignment_start().transpose())
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(positions, [Position::new(8), Position::new(13)]);
+
+        let response = build_response(Format::Vcf, &data)?;
+        assert!(matches!(
+            response.bam_records().await,
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "vcf")]
+    #[tokio::test]
+    async fn test_vcf_records() -> Result<(), Box<dyn std::error::Error>> {
+        use futures::TryStreamExt;
+        use noodles_bgzf as bgzf;
+        use noodles_core::Position;
+        use vcf::variant::{io::Write, RecordBuf};
+
+        let header: vcf::Header = "\
+##fileformat=VCFv4.4
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+
+        let mut writer = vcf::io::Writer::new(bgzf::Writer::new(Vec::new()));
+        writer.write_header(&header)?;
+
+        for start in [8, 13] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name("sq0")
+                .set_variant_start(Position::try_from(start)?)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let data = writer.into_inner().finish()?;
+        let response = build_response(Format::Vcf, &data)?;
+
+        let (actual_header, records) = response.vcf_records().await?;
+        assert_eq!(actual_header, header);
+
+        let records: Vec<_> = records.try_collect().await?;
+
+        let positions = records
+            .iter()
+            .map(|record| record.variant_start().transpose())
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(positions, [Position::new(8), Position::new(13)]);
+
+        let response = build_response(Format::Bam, &data)?;
+        assert!(matches!(
+            response.vcf_records().await,
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
 }
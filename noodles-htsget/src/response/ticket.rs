@@ -1,44 +1,160 @@
+//! htsget response ticket.
+
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::Format;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+/// A block class.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
-enum Class {
+pub enum Class {
+    /// Header data.
     Header,
+    /// Body data.
     Body,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
-pub(crate) struct BlockUrl {
+/// A ticket block URL.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockUrl {
     url: Url,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     headers: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     class: Option<Class>,
 }
 
 impl BlockUrl {
+    /// Creates a ticket block URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use noodles_htsget::response::ticket::{BlockUrl, Class};
+    ///
+    /// let url = "https://localhost/data/NDLS0001".parse()?;
+    /// let headers = [(String::from("Range"), String::from("bytes=0-65535"))]
+    ///     .into_iter()
+    ///     .collect();
+    /// let block_url = BlockUrl::new(url, headers, Some(Class::Header));
+    /// # Ok::<_, url::ParseError>(())
+    /// ```
+    pub fn new(url: Url, headers: HashMap<String, String>, class: Option<Class>) -> Self {
+        Self {
+            url,
+            headers,
+            class,
+        }
+    }
+
+    /// Returns the URL.
     pub fn url(&self) -> &Url {
         &self.url
     }
 
+    /// Returns the headers to send with the request.
     pub fn headers(&self) -> &HashMap<String, String> {
         &self.headers
     }
+
+    /// Returns the block class.
+    pub fn class(&self) -> Option<Class> {
+        self.class
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
-pub(crate) struct Ticket {
+/// An htsget response ticket.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Ticket {
     format: Format,
     urls: Vec<BlockUrl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     md5: Option<String>,
 }
 
 impl Ticket {
+    /// Creates an htsget response ticket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{self as htsget, response::Ticket};
+    /// let ticket = Ticket::new(htsget::Format::Bam, Vec::new(), None);
+    /// ```
+    pub fn new(format: Format, urls: Vec<BlockUrl>, md5: Option<String>) -> Self {
+        Self { format, urls, md5 }
+    }
+
+    /// Returns the format of the data.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the block URLs.
     pub fn urls(&self) -> &[BlockUrl] {
         &self.urls
     }
+
+    /// Returns the MD5 digest of the data.
+    pub fn md5(&self) -> Option<&str> {
+        self.md5.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_tokens, Token};
+
+    use super::*;
+
+    #[test]
+    fn test_serde() -> Result<(), url::ParseError> {
+        let ticket = Ticket::new(
+            Format::Bam,
+            vec![BlockUrl::new(
+                "data:;base64,".parse()?,
+                HashMap::new(),
+                Some(Class::Body),
+            )],
+            None,
+        );
+
+        assert_tokens(
+            &ticket,
+            &[
+                Token::Struct {
+                    name: "Ticket",
+                    len: 2,
+                },
+                Token::Str("format"),
+                Token::UnitVariant {
+                    name: "Format",
+                    variant: "BAM",
+                },
+                Token::Str("urls"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "BlockUrl",
+                    len: 2,
+                },
+                Token::Str("url"),
+                Token::Str("data:;base64,"),
+                Token::Str("class"),
+                Token::Some,
+                Token::UnitVariant {
+                    name: "Class",
+                    variant: "body",
+                },
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,188 @@
+//! htsget server building blocks.
+//!
+//! This resolves region queries against indexed local files into htsget tickets. A ticket
+//! describes the byte ranges of a file that a client concatenates to build a valid partial file:
+//! the header, the data blocks that intersect the queried regions, and the EOF marker.
+//!
+//! No HTTP layer is included. The caller maps byte ranges to URLs of its data service, e.g., using
+//! [`ticket`], and serializes the ticket as the `htsget` field of a JSON response.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bam::bai;
+//! use noodles_htsget::{self as htsget, server};
+//!
+//! let index = bai::read("sample.bam.bai")?;
+//! let regions = ["sq0:8-13".parse().unwrap()];
+//!
+//! let mut reader = File::open("sample.bam")?;
+//! let byte_ranges = server::bam::byte_ranges(&mut reader, &index, &regions)?;
+//!
+//! let url = "https://localhost/data/sample.bam".parse().unwrap();
+//! let ticket = server::ticket(htsget::Format::Bam, &url, &byte_ranges);
+//! # Ok::<_, io::Error>(())
+//! ```
+
+pub mod bam;
+pub mod bcf;
+mod bgzf;
+mod byte_range;
+pub mod cram;
+pub mod vcf;
+
+pub use self::byte_range::ByteRange;
+
+use std::{
+    collections::HashMap,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+use noodles_core::Region;
+use url::Url;
+
+use crate::{
+    response::{
+        ticket::{BlockUrl, Class},
+        Ticket,
+    },
+    Format,
+};
+
+/// Builds an htsget ticket from byte ranges of a resource.
+///
+/// Each byte range is mapped to a block URL with the given URL and a `Range` header.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_htsget::{self as htsget, response::ticket::Class, server::{self, ByteRange}};
+///
+/// let url = "https://localhost/data/sample.bam".parse()?;
+/// let byte_ranges = [ByteRange::new(Class::Header, 0, 256), ByteRange::new(Class::Body, 256, 1024)];
+/// let ticket = server::ticket(htsget::Format::Bam, &url, &byte_ranges);
+///
+/// let urls = ticket.urls();
+/// assert_eq!(urls.len(), 2);
+/// assert_eq!(urls[0].headers().get("Range").map(|s| s.as_str()), Some("bytes=0-255"));
+/// assert_eq!(urls[1].class(), Some(Class::Body));
+/// # Ok::<_, url::ParseError>(())
+/// ```
+pub fn ticket(format: Format, url: &Url, byte_ranges: &[ByteRange]) -> Ticket {
+    const RANGE: &str = "Range";
+
+    let urls = byte_ranges
+        .iter()
+        .filter(|byte_range| !byte_range.is_empty())
+        .map(|byte_range| {
+            let headers: HashMap<_, _> = [(String::from(RANGE), byte_range.to_header_value())]
+                .into_iter()
+                .collect();
+
+            BlockUrl::new(url.clone(), headers, Some(byte_range.class()))
+        })
+        .collect();
+
+    Ticket::new(format, urls, None)
+}
+
+/// Merges overlapping and adjacent body byte ranges.
+fn merge_byte_ranges(mut byte_ranges: Vec<ByteRange>) -> Vec<ByteRange> {
+    byte_ranges.sort_unstable_by_key(|byte_range| byte_range.start());
+
+    let mut merged: Vec<ByteRange> = Vec::with_capacity(byte_ranges.len());
+
+    for byte_range in byte_ranges {
+        match merged.last_mut() {
+            Some(last) if byte_range.start() <= last.end() => {
+                *last = ByteRange::new(Class::Body, last.start(), last.end().max(byte_range.end()));
+            }
+            _ => merged.push(byte_range),
+        }
+    }
+
+    merged
+}
+
+/// Returns the end of the data and the end of the file.
+///
+/// These are equal if the file does not end with the given EOF marker.
+fn read_eof_bounds<R>(reader: &mut R, eof: &[u8]) -> io::Result<(u64, u64)>
+where
+    R: Read + Seek,
+{
+    let len = reader.seek(SeekFrom::End(0))?;
+    let eof_len = eof.len() as u64;
+
+    if len < eof_len {
+        return Ok((len, len));
+    }
+
+    let mut buf = vec![0; eof.len()];
+    reader.seek(SeekFrom::Start(len - eof_len))?;
+    reader.read_exact(&mut buf)?;
+
+    if buf == eof {
+        Ok((len - eof_len, len))
+    } else {
+        Ok((len, len))
+    }
+}
+
+fn invalid_reference_sequence_name(region: &Region) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid reference sequence name: {}", region.name()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Concatenates the byte ranges of a resource, as a client would.
+    pub(super) fn read_byte_ranges(src: &[u8], byte_ranges: &[ByteRange]) -> Vec<u8> {
+        byte_ranges
+            .iter()
+            .flat_map(|byte_range| &src[byte_range.start() as usize..byte_range.end() as usize])
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_byte_ranges() {
+        let byte_ranges = vec![
+            ByteRange::new(Class::Body, 13, 21),
+            ByteRange::new(Class::Body, 0, 5),
+            ByteRange::new(Class::Body, 5, 8),
+            ByteRange::new(Class::Body, 16, 18),
+        ];
+
+        assert_eq!(
+            merge_byte_ranges(byte_ranges),
+            [
+                ByteRange::new(Class::Body, 0, 8),
+                ByteRange::new(Class::Body, 13, 21),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_eof_bounds() -> io::Result<()> {
+        const EOF: [u8; 3] = [0x45, 0x4f, 0x46];
+
+        let mut reader = Cursor::new(b"ndlsEOF".to_vec());
+        assert_eq!(read_eof_bounds(&mut reader, &EOF)?, (4, 7));
+
+        let mut reader = Cursor::new(b"ndls".to_vec());
+        assert_eq!(read_eof_bounds(&mut reader, &EOF)?, (4, 4));
+
+        let mut reader = Cursor::new(b"EO".to_vec());
+        assert_eq!(read_eof_bounds(&mut reader, &EOF)?, (2, 2));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! BAM htsget server support.
+
+use std::io::{self, Read, Seek};
+
+use noodles_bam as bam;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+
+use super::{bgzf, invalid_reference_sequence_name, ByteRange};
+
+/// Resolves the byte ranges of a BAM file for the given regions.
+///
+/// The index is typically a BAI or CSI. If `regions` is empty, the entire file is selected.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam::bai;
+/// use noodles_htsget::server;
+///
+/// let index = bai::read("sample.bam.bai")?;
+/// let regions = ["sq0:8-13".parse().unwrap()];
+///
+/// let mut reader = File::open("sample.bam")?;
+/// let byte_ranges = server::bam::byte_ranges(&mut reader, &index, &regions)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn byte_ranges<R, I>(
+    reader: &mut R,
+    index: &I,
+    regions: &[Region],
+) -> io::Result<Vec<ByteRange>>
+where
+    R: Read + Seek,
+    I: BinningIndex,
+{
+    let mut bam_reader = bam::io::Reader::new(&mut *reader);
+    let header = bam_reader.read_header()?;
+    let header_end = bam_reader.get_ref().virtual_position();
+
+    let chunks = if regions.is_empty() {
+        None
+    } else {
+        let mut chunks = Vec::new();
+
+        for region in regions {
+            let reference_sequence_id = header
+                .reference_sequences()
+                .get_index_of(region.name())
+                .ok_or_else(|| invalid_reference_sequence_name(region))?;
+
+            chunks.extend(index.query(reference_sequence_id, region.interval())?);
+        }
+
+        Some(chunks)
+    };
+
+    bgzf::byte_ranges(reader, header_end, chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use noodles_bam::bai;
+    use noodles_core::Position;
+    use noodles_csi::binning_index::{index::reference_sequence::bin::Chunk, Indexer};
+    use noodles_sam::{
+        self as sam,
+        alignment::{
+            io::Write as _,
+            record::cigar::{op::Kind, Op},
+            RecordBuf,
+        },
+    };
+
+    use super::*;
+    use crate::server::tests::read_byte_ranges;
+
+    #[test]
+    fn test_byte_ranges() -> Result<(), Box<dyn std::error::Error>> {
+        let header: sam::Header =
+            "@HD\tVN:1.6\tSO:coordinate\n@SQ\tSN:sq0\tLN:34\n@SQ\tSN:sq1\tLN:34\n".parse()?;
+
+        let mut writer = bam::io::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        let mut indexer = Indexer::default();
+
+        for (name, reference_sequence_id, start) in [("r0", 0, 8), ("r1", 1, 13), ("r2", 1, 21)] {
+            // Each record is written to its own block.
+            writer.get_mut().flush()?;
+            let start_position = writer.get_ref().virtual_position();
+
+            let alignment_start = Position::try_from(start)?;
+            let alignment_end = Position::try_from(start + 3)?;
+
+            let record = RecordBuf::builder()
+                .set_name(name.as_bytes().to_vec().into())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(alignment_start)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+
+            let chunk = Chunk::new(start_position, writer.get_ref().virtual_position());
+            let context = (reference_sequence_id, alignment_start, alignment_end, true);
+            indexer.add_record(Some(context), chunk)?;
+        }
+
+        let src = writer.into_inner().finish()?;
+        let index: bai::Index = indexer.build(header.reference_sequences().len());
+
+        let regions = ["sq1".parse()?];
+        let byte_ranges = byte_ranges(&mut io::Cursor::new(&src), &index, &regions)?;
+        let data = read_byte_ranges(&src, &byte_ranges);
+
+        let mut reader = bam::io::Reader::new(&data[..]);
+        let actual_header = reader.read_header()?;
+        assert_eq!(actual_header, header);
+
+        let names: Vec<_> = reader
+            .record_bufs(&actual_header)
+            .map(|result| result.map(|record| record.name().cloned()))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"r1".into()), Some(b"r2".into())]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+//! BCF htsget server support.
+
+use std::io::{self, Read, Seek};
+
+use noodles_bcf as bcf;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+
+use super::{bgzf, invalid_reference_sequence_name, ByteRange};
+
+/// Resolves the byte ranges of a BCF file for the given regions.
+///
+/// The index is typically a CSI. If `regions` is empty, the entire file is selected.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_csi as csi;
+/// use noodles_htsget::server;
+///
+/// let index = csi::read("sample.bcf.csi")?;
+/// let regions = ["sq0:8-13".parse().unwrap()];
+///
+/// let mut reader = File::open("sample.bcf")?;
+/// let byte_ranges = server::bcf::byte_ranges(&mut reader, &index, &regions)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn byte_ranges<R, I>(
+    reader: &mut R,
+    index: &I,
+    regions: &[Region],
+) -> io::Result<Vec<ByteRange>>
+where
+    R: Read + Seek,
+    I: BinningIndex,
+{
+    let mut bcf_reader = bcf::io::Reader::new(&mut *reader);
+    let header = bcf_reader.read_header()?;
+    let header_end = bcf_reader.get_ref().virtual_position();
+
+    let chunks = if regions.is_empty() {
+        None
+    } else {
+        let mut chunks = Vec::new();
+
+        for region in regions {
+            let reference_sequence_id = std::str::from_utf8(region.name())
+                .ok()
+                .and_then(|name| header.string_maps().contigs().get_index_of(name))
+                .ok_or_else(|| invalid_reference_sequence_name(region))?;
+
+            chunks.extend(index.query(reference_sequence_id, region.interval())?);
+        }
+
+        Some(chunks)
+    };
+
+    bgzf::byte_ranges(reader, header_end, chunks)
+}
@@ -0,0 +1,151 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::binning_index::index::reference_sequence::bin::Chunk;
+
+use super::{merge_byte_ranges, read_eof_bounds, ByteRange};
+use crate::response::ticket::Class;
+
+// § 4.1.2 End-of-file marker (2020-12-03)
+static BGZF_EOF: [u8; 28] = [
+    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
+    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// Resolves the byte ranges of a BGZF-compressed file.
+///
+/// If `chunks` is `None`, the entire body is selected.
+pub(super) fn byte_ranges<R>(
+    reader: &mut R,
+    header_end: bgzf::VirtualPosition,
+    chunks: Option<Vec<Chunk>>,
+) -> io::Result<Vec<ByteRange>>
+where
+    R: Read + Seek,
+{
+    let (data_end, eof_end) = read_eof_bounds(reader, &BGZF_EOF)?;
+
+    // The header range includes the entire block in which the header ends.
+    let header_end = end_of(reader, header_end)?;
+
+    let mut byte_ranges = vec![ByteRange::new(Class::Header, 0, header_end)];
+
+    let body_ranges = match chunks {
+        Some(chunks) => {
+            let mut body_ranges = Vec::with_capacity(chunks.len());
+
+            for chunk in chunks {
+                let start = chunk.start().compressed().max(header_end);
+                let end = end_of(reader, chunk.end())?.min(data_end);
+
+                if start < end {
+                    body_ranges.push(ByteRange::new(Class::Body, start, end));
+                }
+            }
+
+            merge_byte_ranges(body_ranges)
+        }
+        None => vec![ByteRange::new(Class::Body, header_end, data_end)],
+    };
+
+    byte_ranges.extend(body_ranges.into_iter().filter(|r| !r.is_empty()));
+
+    if data_end < eof_end {
+        byte_ranges.push(ByteRange::new(Class::Body, data_end, eof_end));
+    }
+
+    Ok(byte_ranges)
+}
+
+/// Returns the compressed offset of the first block boundary at or after a virtual position.
+fn end_of<R>(reader: &mut R, position: bgzf::VirtualPosition) -> io::Result<u64>
+where
+    R: Read + Seek,
+{
+    if position.uncompressed() == 0 {
+        Ok(position.compressed())
+    } else {
+        read_block_end(reader, position.compressed())
+    }
+}
+
+fn read_block_end<R>(reader: &mut R, block_start: u64) -> io::Result<u64>
+where
+    R: Read + Seek,
+{
+    const HEADER_LEN: usize = 18;
+    const MAGIC_NUMBER: [u8; 4] = [0x1f, 0x8b, 0x08, 0x04];
+    const BC: [u8; 2] = [b'B', b'C'];
+
+    let mut buf = [0; HEADER_LEN];
+    reader.seek(SeekFrom::Start(block_start))?;
+    reader.read_exact(&mut buf)?;
+
+    if buf[..4] != MAGIC_NUMBER || buf[12..14] != BC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid BGZF block header",
+        ));
+    }
+
+    // BSIZE is the total block size minus 1.
+    let bsize = u16::from_le_bytes([buf[16], buf[17]]);
+
+    Ok(block_start + u64::from(bsize) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+
+    #[test]
+    fn test_byte_ranges() -> io::Result<()> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+
+        writer.write_all(b"header")?;
+        let header_end = writer.virtual_position();
+        writer.write_all(b"r0")?;
+        writer.flush()?;
+
+        let b1 = writer.virtual_position();
+        writer.write_all(b"r1")?;
+        writer.flush()?;
+
+        let b2 = writer.virtual_position();
+        writer.write_all(b"r2")?;
+        let r2_end = writer.virtual_position();
+        writer.flush()?;
+
+        let b3 = writer.virtual_position();
+        let data = writer.finish()?;
+        let len = data.len() as u64;
+
+        let (c1, c2, c3) = (b1.compressed(), b2.compressed(), b3.compressed());
+
+        let mut reader = Cursor::new(data);
+
+        let chunks = vec![Chunk::new(header_end, b1), Chunk::new(b2, r2_end)];
+
+        assert_eq!(
+            byte_ranges(&mut reader, header_end, Some(chunks))?,
+            [
+                ByteRange::new(Class::Header, 0, c1),
+                ByteRange::new(Class::Body, c2, c3),
+                ByteRange::new(Class::Body, c3, len),
+            ]
+        );
+
+        assert_eq!(
+            byte_ranges(&mut reader, header_end, None)?,
+            [
+                ByteRange::new(Class::Header, 0, c1),
+                ByteRange::new(Class::Body, c1, c3),
+                ByteRange::new(Class::Body, c3, len),
+            ]
+        );
+
+        Ok(())
+    }
+}
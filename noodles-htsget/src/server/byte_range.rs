@@ -0,0 +1,70 @@
+use crate::response::ticket::Class;
+
+/// A byte range of a resource.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ByteRange {
+    class: Class,
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    /// Creates a byte range.
+    ///
+    /// The range is half-open, i.e., `end` is exclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{response::ticket::Class, server::ByteRange};
+    /// let byte_range = ByteRange::new(Class::Header, 0, 256);
+    /// ```
+    pub fn new(class: Class, start: u64, end: u64) -> Self {
+        Self { class, start, end }
+    }
+
+    /// Returns the class of the data in the range.
+    pub fn class(&self) -> Class {
+        self.class
+    }
+
+    /// Returns the start offset.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the end offset (exclusive).
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Returns the number of bytes in the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{response::ticket::Class, server::ByteRange};
+    /// let byte_range = ByteRange::new(Class::Body, 8, 13);
+    /// assert_eq!(byte_range.len(), 5);
+    /// ```
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Returns whether the range is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_htsget::{response::ticket::Class, server::ByteRange};
+    /// assert!(ByteRange::new(Class::Body, 8, 8).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // RFC 9110 § 14.1.2 "Byte Ranges": the last position is inclusive.
+    pub(super) fn to_header_value(self) -> String {
+        format!("bytes={}-{}", self.start, self.end - 1)
+    }
+}
@@ -0,0 +1,181 @@
+//! CRAM htsget server support.
+
+use std::io::{self, Read, Seek};
+
+use noodles_core::{region::Interval, Region};
+use noodles_cram::{self as cram, crai};
+
+use super::{invalid_reference_sequence_name, merge_byte_ranges, read_eof_bounds, ByteRange};
+use crate::response::ticket::Class;
+
+// § 9 "End of file container" (2022-04-12)
+static EOF: [u8; 38] = [
+    0x0f, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x0f, 0xe0, 0x45, 0x4f, 0x46, 0x00, 0x00, 0x00,
+    0x00, 0x01, 0x00, 0x05, 0xbd, 0xd9, 0x4f, 0x00, 0x01, 0x00, 0x06, 0x06, 0x01, 0x00, 0x01, 0x00,
+    0x01, 0x00, 0xee, 0x63, 0x01, 0x4b,
+];
+
+/// Resolves the byte ranges of a CRAM file for the given regions.
+///
+/// Each data range is a whole container with at least one slice that intersects a region. If
+/// `regions` is empty, the entire file is selected.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_cram::crai;
+/// use noodles_htsget::server;
+///
+/// let index = crai::read("sample.cram.crai")?;
+/// let regions = ["sq0:8-13".parse().unwrap()];
+///
+/// let mut reader = File::open("sample.cram")?;
+/// let byte_ranges = server::cram::byte_ranges(&mut reader, &index, &regions)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn byte_ranges<R>(
+    reader: &mut R,
+    index: &crai::Index,
+    regions: &[Region],
+) -> io::Result<Vec<ByteRange>>
+where
+    R: Read + Seek,
+{
+    let mut cram_reader = cram::io::Reader::new(&mut *reader);
+    let header = cram_reader.read_header()?;
+    let header_end = cram_reader.position()?;
+
+    let (data_end, eof_end) = read_eof_bounds(reader, &EOF)?;
+
+    let mut byte_ranges = vec![ByteRange::new(Class::Header, 0, header_end)];
+
+    if regions.is_empty() {
+        byte_ranges.push(ByteRange::new(Class::Body, header_end, data_end));
+    } else {
+        let mut container_offsets: Vec<_> = index.iter().map(|record| record.offset()).collect();
+        container_offsets.sort_unstable();
+        container_offsets.dedup();
+
+        let mut body_ranges = Vec::new();
+
+        for region in regions {
+            let reference_sequence_id = header
+                .reference_sequences()
+                .get_index_of(region.name())
+                .ok_or_else(|| invalid_reference_sequence_name(region))?;
+
+            let interval = region.interval();
+
+            for record in index {
+                if record.reference_sequence_id() != Some(reference_sequence_id)
+                    || !intersects(record, interval)
+                {
+                    continue;
+                }
+
+                let start = record.offset();
+
+                let i = container_offsets.partition_point(|&offset| offset <= start);
+                let end = container_offsets.get(i).copied().unwrap_or(data_end);
+
+                body_ranges.push(ByteRange::new(Class::Body, start, end));
+            }
+        }
+
+        byte_ranges.extend(merge_byte_ranges(body_ranges));
+    }
+
+    byte_ranges.retain(|byte_range| !byte_range.is_empty());
+
+    if data_end < eof_end {
+        byte_ranges.push(ByteRange::new(Class::Body, data_end, eof_end));
+    }
+
+    Ok(byte_ranges)
+}
+
+fn intersects(record: &crai::Record, interval: Interval) -> bool {
+    let Some(start) = record.alignment_start() else {
+        return false;
+    };
+
+    let end = start
+        .checked_add(record.alignment_span().saturating_sub(1))
+        .unwrap_or(start);
+
+    interval.intersects((start..=end).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, num::NonZeroUsize};
+
+    use noodles_core::Position;
+    use noodles_cram::io::writer::ReferenceSequenceMode;
+    use noodles_sam::{
+        self as sam,
+        alignment::{
+            io::Write,
+            record::cigar::{op::Kind, Op},
+            record_buf::Sequence,
+            RecordBuf,
+        },
+    };
+
+    use super::*;
+    use crate::server::tests::read_byte_ranges;
+
+    #[test]
+    fn test_byte_ranges() -> Result<(), Box<dyn std::error::Error>> {
+        let header: sam::Header =
+            "@HD\tVN:1.6\tSO:coordinate\n@SQ\tSN:sq0\tLN:34\n@SQ\tSN:sq1\tLN:34\n".parse()?;
+
+        // Each record is written to its own container.
+        let mut writer = cram::io::writer::Builder::default()
+            .set_reference_sequence_mode(ReferenceSequenceMode::None)
+            .set_records_per_slice(NonZeroUsize::MIN)
+            .build_with_writer(Vec::new());
+
+        writer.write_header(&header)?;
+
+        for (name, reference_sequence_id, start) in [("r0", 0, 8), ("r1", 1, 13), ("r2", 1, 21)] {
+            let record = RecordBuf::builder()
+                .set_name(name.as_bytes().into())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .set_sequence(Sequence::from(b"ACGT".to_vec()))
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.try_finish(&header)?;
+        let src = writer.get_ref().clone();
+
+        let dst =
+            env::temp_dir().join(format!("noodles-htsget-server-{}.cram", std::process::id()));
+        fs::write(&dst, &src)?;
+        let index = cram::index(&dst);
+        fs::remove_file(&dst)?;
+        let index = index?;
+
+        let regions = ["sq1".parse()?];
+        let byte_ranges = byte_ranges(&mut io::Cursor::new(&src), &index, &regions)?;
+        let data = read_byte_ranges(&src, &byte_ranges);
+
+        let mut reader = cram::io::Reader::new(&data[..]);
+        let actual_header = reader.read_header()?;
+        assert_eq!(actual_header, header);
+
+        let names: Vec<_> = reader
+            .records(&actual_header)
+            .map(|result| result.map(|record| record.name().cloned()))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"r1".into()), Some(b"r2".into())]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! VCF htsget server support.
+
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+use noodles_vcf as vcf;
+
+use super::{invalid_reference_sequence_name, ByteRange};
+
+/// Resolves the byte ranges of a bgzipped VCF file for the given regions.
+///
+/// The index is typically a tabix index or CSI and must include a header, which is used to
+/// resolve reference sequence names. If `regions` is empty, the entire file is selected.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_htsget::server;
+/// use noodles_tabix as tabix;
+///
+/// let index = tabix::read("sample.vcf.gz.tbi")?;
+/// let regions = ["sq0:8-13".parse().unwrap()];
+///
+/// let mut reader = File::open("sample.vcf.gz")?;
+/// let byte_ranges = server::vcf::byte_ranges(&mut reader, &index, &regions)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn byte_ranges<R, I>(
+    reader: &mut R,
+    index: &I,
+    regions: &[Region],
+) -> io::Result<Vec<ByteRange>>
+where
+    R: Read + Seek,
+    I: BinningIndex,
+{
+    let mut vcf_reader = vcf::io::Reader::new(bgzf::Reader::new(&mut *reader));
+    vcf_reader.read_header()?;
+    let header_end = vcf_reader.get_ref().virtual_position();
+
+    let chunks = if regions.is_empty() {
+        None
+    } else {
+        let reference_sequence_names = index
+            .header()
+            .map(|header| header.reference_sequence_names())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing index header"))?;
+
+        let mut chunks = Vec::new();
+
+        for region in regions {
+            let reference_sequence_id = std::str::from_utf8(region.name())
+                .ok()
+                .and_then(|name| reference_sequence_names.get_index_of(name))
+                .ok_or_else(|| invalid_reference_sequence_name(region))?;
+
+            chunks.extend(index.query(reference_sequence_id, region.interval())?);
+        }
+
+        Some(chunks)
+    };
+
+    super::bgzf::byte_ranges(reader, header_end, chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use noodles_core::Position;
+    use noodles_csi::binning_index::index::{header, reference_sequence::bin::Chunk};
+    use noodles_tabix as tabix;
+    use noodles_vcf::variant::{io::Write as _, RecordBuf};
+
+    use super::*;
+    use crate::server::tests::read_byte_ranges;
+
+    #[test]
+    fn test_byte_ranges() -> Result<(), Box<dyn std::error::Error>> {
+        let header: vcf::Header = "\
+##fileformat=VCFv4.4
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+
+        let mut writer = vcf::io::Writer::new(bgzf::Writer::new(Vec::new()));
+        writer.write_header(&header)?;
+
+        let mut indexer = tabix::index::Indexer::default();
+        indexer.set_header(header::Builder::vcf().build());
+
+        for (reference_sequence_name, start) in [("sq0", 8), ("sq1", 13), ("sq1", 21)] {
+            // Each record is written to its own block.
+            writer.get_mut().flush()?;
+            let start_position = writer.get_ref().virtual_position();
+
+            let position = Position::try_from(start)?;
+
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(reference_sequence_name)
+                .set_variant_start(position)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(&header, &record)?;
+
+            let chunk = Chunk::new(start_position, writer.get_ref().virtual_position());
+            indexer.add_record(reference_sequence_name, position, position, chunk)?;
+        }
+
+        let src = writer.into_inner().finish()?;
+        let index = indexer.build();
+
+        let regions = ["sq1".parse()?];
+        let byte_ranges = byte_ranges(&mut io::Cursor::new(&src), &index, &regions)?;
+        let data = read_byte_ranges(&src, &byte_ranges);
+
+        let mut reader = vcf::io::Reader::new(bgzf::Reader::new(&data[..]));
+        let actual_header = reader.read_header()?;
+        assert_eq!(actual_header, header);
+
+        let positions: Vec<_> = reader
+            .record_bufs(&actual_header)
+            .map(|result| {
+                result.map(|record| {
+                    (
+                        record.reference_sequence_name().to_string(),
+                        record.variant_start(),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            positions,
+            [
+                (String::from("sq1"), Position::new(13)),
+                (String::from("sq1"), Position::new(21)),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+//! Sequence digests.
+//!
+//! refget 2.0 identifies sequences by two checksums: the MD5 checksum, as used in the SAM `@SQ`
+//! `M5` field, and the GA4GH identifier (`SQ.<sha512t24u>`). Both are computed over the
+//! normalized sequence, i.e., uppercase without whitespace.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use md5::Md5;
+use sha2::{Digest, Sha512};
+
+const GA4GH_IDENTIFIER_PREFIX: &str = "SQ.";
+
+/// Digests of a normalized sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SequenceDigests {
+    length: u64,
+    md5: String,
+    sha512t24u: String,
+}
+
+impl SequenceDigests {
+    /// Computes the digests of a sequence.
+    ///
+    /// The sequence is normalized before digesting, i.e., bases are uppercased, and whitespace is
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::digest::SequenceDigests;
+    ///
+    /// let digests = SequenceDigests::compute(b"acgt");
+    ///
+    /// assert_eq!(digests.length(), 4);
+    /// assert_eq!(digests.md5(), "f1f8f4bf413b16ad135722aa4591043e");
+    /// assert_eq!(digests.sha512t24u(), "aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2");
+    /// ```
+    pub fn compute(sequence: &[u8]) -> Self {
+        const CHUNK_SIZE: usize = 8192;
+
+        let mut md5_hasher = Md5::new();
+        let mut sha512_hasher = Sha512::new();
+        let mut length = 0;
+
+        let mut buf = Vec::with_capacity(CHUNK_SIZE);
+
+        for chunk in sequence.chunks(CHUNK_SIZE) {
+            buf.clear();
+
+            buf.extend(
+                chunk
+                    .iter()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .map(|b| b.to_ascii_uppercase()),
+            );
+
+            md5_hasher.update(&buf);
+            sha512_hasher.update(&buf);
+            length += buf.len() as u64;
+        }
+
+        Self {
+            length,
+            md5: format!("{:x}", md5_hasher.finalize()),
+            sha512t24u: truncate_sha512(&sha512_hasher.finalize()),
+        }
+    }
+
+    /// Returns the length of the normalized sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::digest::SequenceDigests;
+    /// let digests = SequenceDigests::compute(b"ACGT\nAC");
+    /// assert_eq!(digests.length(), 6);
+    /// ```
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns the MD5 checksum as a lowercase hex string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::digest::SequenceDigests;
+    /// let digests = SequenceDigests::compute(b"ACGT");
+    /// assert_eq!(digests.md5(), "f1f8f4bf413b16ad135722aa4591043e");
+    /// ```
+    pub fn md5(&self) -> &str {
+        &self.md5
+    }
+
+    /// Returns the truncated SHA-512 digest (sha512t24u).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::digest::SequenceDigests;
+    /// let digests = SequenceDigests::compute(b"ACGT");
+    /// assert_eq!(digests.sha512t24u(), "aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2");
+    /// ```
+    pub fn sha512t24u(&self) -> &str {
+        &self.sha512t24u
+    }
+
+    /// Returns the GA4GH identifier.
+    ///
+    /// This is the sha512t24u digest prefixed with the sequence type (`SQ.`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::digest::SequenceDigests;
+    /// let digests = SequenceDigests::compute(b"ACGT");
+    /// assert_eq!(digests.ga4gh_identifier(), "SQ.aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2");
+    /// ```
+    pub fn ga4gh_identifier(&self) -> String {
+        format!("{GA4GH_IDENTIFIER_PREFIX}{}", self.sha512t24u)
+    }
+}
+
+/// Computes the sha512t24u digest of the given data.
+///
+/// This is the first 24 bytes of the SHA-512 digest encoded as unpadded URL-safe base64. Unlike
+/// [`SequenceDigests::compute`], the input is not normalized.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_refget::digest::sha512t24u;
+/// assert_eq!(sha512t24u(b"ACGT"), "aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2");
+/// ```
+pub fn sha512t24u(data: &[u8]) -> String {
+    truncate_sha512(&Sha512::digest(data))
+}
+
+fn truncate_sha512(digest: &[u8]) -> String {
+    const LENGTH: usize = 24;
+    URL_SAFE_NO_PAD.encode(&digest[..LENGTH])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute() {
+        let digests = SequenceDigests::compute(b"");
+        assert_eq!(digests.length(), 0);
+        assert_eq!(digests.md5(), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(digests.sha512t24u(), "z4PhNX7vuL3xVChQ1m2AB9Yg5AULVxXc");
+
+        // Normalization is applied across chunk boundaries.
+        let mut sequence = b"acgt\n".repeat(4096);
+        let expected = SequenceDigests::compute(&b"ACGT".repeat(4096));
+        assert_eq!(SequenceDigests::compute(&sequence), expected);
+
+        sequence.make_ascii_uppercase();
+        assert_eq!(SequenceDigests::compute(&sequence), expected);
+    }
+}
@@ -3,7 +3,15 @@
 //! **noodles-refget** is a refget 2.0 client.
 
 mod client;
+#[cfg(feature = "digest")]
+pub mod digest;
+#[cfg(feature = "fasta")]
+pub mod repository;
+#[cfg(feature = "digest")]
+pub mod seqcol;
 pub mod sequence;
+#[cfg(feature = "server")]
+pub mod server;
 
 pub use self::{client::Client, sequence::Sequence};
 
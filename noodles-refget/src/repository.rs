@@ -0,0 +1,377 @@
+//! refget-backed FASTA sequence repository adapter.
+//!
+//! [`Adapter`] resolves sequences by digest using a refget server. This allows, e.g., CRAM files
+//! to be decoded using only the MD5 checksums in the `@SQ` `M5` fields of the header.
+//!
+//! Fetched sequences can be stored in a local cache directory. The layout is compatible with the
+//! default htslib `REF_CACHE` layout (`%2s/%2s/%s`), i.e., the sequence with MD5 checksum
+//! `d7eba311421bbc9d3ada44709dd61534` is stored at `<cache_dir>/d7/eb/a311421bbc9d3ada44709dd61534`
+//! as uppercase sequence bytes without line breaks.
+//!
+//! The adapter blocks while waiting on requests. When used from within a Tokio runtime, the
+//! runtime must be multithreaded. Otherwise, requests are run on a private runtime that is created
+//! on first use.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_fasta as fasta;
+//! use noodles_refget::{self as refget, repository};
+//!
+//! let client = refget::Client::new("https://localhost/".parse().unwrap());
+//!
+//! let adapter = repository::Builder::default()
+//!     .set_cache_dir("/home/noodles/.cache/hts-ref")
+//!     .add_digest("sq0", "d7eba311421bbc9d3ada44709dd61534")
+//!     .build(client)?;
+//!
+//! let repository = fasta::Repository::new(adapter);
+//! let sequence = repository.get(b"sq0").transpose()?;
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod builder;
+mod cache;
+
+pub use self::builder::Builder;
+
+use std::{collections::HashMap, io, sync::OnceLock};
+
+use noodles_fasta::{
+    self as fasta,
+    record::{Definition, Sequence},
+};
+
+use self::cache::Cache;
+use crate::{Client, Error};
+
+/// A refget-backed FASTA sequence repository adapter.
+pub struct Adapter {
+    client: Client,
+    digests: HashMap<Vec<u8>, String>,
+    cache: Option<Cache>,
+    runtime: OnceLock<tokio::runtime::Runtime>,
+}
+
+impl Adapter {
+    /// Returns a builder to create an adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::repository::Adapter;
+    /// let builder = Adapter::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    fn resolve_digest(&self, name: &[u8]) -> Option<String> {
+        if let Some(digest) = self.digests.get(name) {
+            return Some(digest.clone());
+        }
+
+        let name = std::str::from_utf8(name).ok()?;
+
+        if is_md5_checksum(name) {
+            Some(name.to_ascii_lowercase())
+        } else if is_ga4gh_identifier(name) {
+            Some(name.into())
+        } else {
+            None
+        }
+    }
+
+    fn fetch(&self, digest: &str) -> io::Result<Option<Vec<u8>>> {
+        use reqwest::StatusCode;
+        use tokio::runtime::Handle;
+
+        let future = self.client.sequence(digest).send();
+
+        let result = match Handle::try_current() {
+            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+            Err(_) => {
+                let runtime = match self.runtime.get() {
+                    Some(runtime) => runtime,
+                    None => {
+                        let runtime = tokio::runtime::Builder::new_current_thread()
+                            .enable_all()
+                            .build()?;
+
+                        self.runtime.get_or_init(|| runtime)
+                    }
+                };
+
+                runtime.block_on(future)
+            }
+        };
+
+        match result {
+            Ok(sequence) => Ok(Some(normalize_sequence(&sequence.sequence()))),
+            Err(Error::Request(e)) if e.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+            Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+        }
+    }
+}
+
+impl fasta::repository::Adapter for Adapter {
+    fn get(&mut self, name: &[u8]) -> Option<io::Result<fasta::Record>> {
+        let digest = self.resolve_digest(name)?;
+
+        let cached_sequence = match self.cache.as_ref().map(|cache| cache.get(&digest)) {
+            Some(Ok(sequence)) => sequence,
+            Some(Err(e)) => return Some(Err(e)),
+            None => None,
+        };
+
+        let sequence = match cached_sequence {
+            Some(sequence) => sequence,
+            None => {
+                let sequence = match self.fetch(&digest) {
+                    Ok(Some(sequence)) => sequence,
+                    Ok(None) => return None,
+                    Err(e) => return Some(Err(e)),
+                };
+
+                if let Some(cache) = self.cache.as_ref() {
+                    if let Err(e) = cache.insert(&digest, &sequence) {
+                        return Some(Err(e));
+                    }
+                }
+
+                sequence
+            }
+        };
+
+        let definition = Definition::new(name, None);
+        Some(Ok(fasta::Record::new(definition, Sequence::from(sequence))))
+    }
+}
+
+// Sequences are normalized like htslib: uppercase without whitespace.
+fn normalize_sequence(src: &[u8]) -> Vec<u8> {
+    src.iter()
+        .filter(|b| !b.is_ascii_whitespace())
+        .map(|b| b.to_ascii_uppercase())
+        .collect()
+}
+
+fn is_md5_checksum(s: &str) -> bool {
+    const MD5_CHECKSUM_LEN: usize = 32;
+    s.len() == MD5_CHECKSUM_LEN && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn is_ga4gh_identifier(s: &str) -> bool {
+    s.starts_with("SQ.") || s.starts_with("ga4gh:SQ.")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    use fasta::repository::Adapter as _;
+
+    use super::*;
+
+    const MD5_CHECKSUM: &str = "f1f8f4bf413b16ad135722aa4591043e";
+    const SEQUENCE: &[u8] = b"ACGTACGT";
+
+    // Starts a mock refget server that serves a single sequence.
+    fn start_server() -> io::Result<(url::Url, Arc<AtomicUsize>)> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let request_count = Arc::new(AtomicUsize::new(0));
+        let count = request_count.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+
+                let mut line = String::new();
+
+                while reader.read_line(&mut line).is_ok() && line != "\r\n" {
+                    line.clear();
+                }
+
+                count.fetch_add(1, Ordering::SeqCst);
+
+                let path = request_line.split(' ').nth(1).unwrap_or_default();
+
+                let (status, body) = if path == format!("/sequence/{MD5_CHECKSUM}") {
+                    ("200 OK", SEQUENCE)
+                } else {
+                    ("404 Not Found", &b""[..])
+                };
+
+                let header = format!(
+                    "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    body.len()
+                );
+
+                stream.write_all(header.as_bytes()).ok();
+                stream.write_all(body).ok();
+            }
+        });
+
+        let base_url = format!("http://{addr}/").parse().unwrap();
+
+        Ok((base_url, request_count))
+    }
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let (base_url, request_count) = start_server()?;
+
+        let cache_dir =
+            std::env::temp_dir().join(format!("noodles-refget-repository-{}", std::process::id()));
+
+        let mut adapter = Builder::default()
+            .set_cache_dir(&cache_dir)
+            .add_digest("sq0", MD5_CHECKSUM)
+            .build(Client::new(base_url))?;
+
+        let record = adapter.get(b"sq0").transpose()?;
+        assert_eq!(
+            record.as_ref().map(|r| r.sequence().as_ref()),
+            Some(SEQUENCE)
+        );
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+
+        let path = cache_dir.join("f1").join("f8").join(&MD5_CHECKSUM[4..]);
+        assert_eq!(std::fs::read(path)?, SEQUENCE);
+
+        // The sequence is read from the cache, even when requested by its digest.
+        let record = adapter.get(MD5_CHECKSUM.as_bytes()).transpose()?;
+        assert!(record.is_some());
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+
+        assert!(adapter.get(b"sq1").is_none());
+
+        let record = adapter
+            .get(b"00000000000000000000000000000000")
+            .transpose()?;
+        assert!(record.is_none());
+        assert_eq!(request_count.load(Ordering::SeqCst), 2);
+
+        std::fs::remove_dir_all(cache_dir)?;
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_in_runtime() -> io::Result<()> {
+        let (base_url, request_count) = start_server()?;
+
+        let adapter = Builder::default()
+            .add_digest("sq0", MD5_CHECKSUM)
+            .build(Client::new(base_url))?;
+
+        let repository = fasta::Repository::new(adapter);
+
+        let sequence = repository.get(b"sq0").transpose()?;
+        assert_eq!(sequence.as_ref().map(|s| s.as_ref()), Some(SEQUENCE));
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+
+        // The adapter does not own a runtime and can be dropped in an async context.
+        drop(repository);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_cram_reader() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_core::Position;
+        use noodles_cram as cram;
+        use noodles_sam::{
+            self as sam,
+            alignment::{
+                io::Write as _,
+                record::{
+                    cigar::{op::Kind, Op},
+                    Flags,
+                },
+                record_buf::{QualityScores, Sequence as RecordSequence},
+                RecordBuf,
+            },
+            header::record::value::{
+                map::{reference_sequence::tag, ReferenceSequence},
+                Map,
+            },
+        };
+
+        let (base_url, request_count) = start_server()?;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::builder()
+                    .set_length(NonZeroUsize::try_from(SEQUENCE.len())?)
+                    .insert(tag::MD5_CHECKSUM, MD5_CHECKSUM)
+                    .build()?,
+            )
+            .build();
+
+        let record = RecordBuf::builder()
+            .set_name(b"r0".to_vec().into())
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(3)?)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .set_sequence(RecordSequence::from(b"GTAC".to_vec()))
+            .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+            .build();
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(SEQUENCE.to_vec()),
+        )]);
+
+        let mut writer = cram::io::writer::Builder::default()
+            .set_reference_sequence_repository(repository)
+            .build_with_writer(Vec::new());
+
+        writer.write_header(&header)?;
+        writer.write_alignment_record(&header, &record)?;
+        writer.try_finish(&header)?;
+
+        let adapter = Builder::default()
+            .add_digests_from_header(&header)
+            .build(Client::new(base_url))?;
+
+        let mut reader = cram::io::reader::Builder::default()
+            .set_reference_sequence_repository(fasta::Repository::new(adapter))
+            .build_from_reader(writer.get_ref().as_slice());
+
+        let actual_header = reader.read_header()?;
+
+        let sequences = reader
+            .records(&actual_header)
+            .map(|result| result.map(|record| record.sequence().as_ref().to_vec()))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(sequences, [b"GTAC".to_vec()]);
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use std::{collections::HashMap, io, path::PathBuf, sync::OnceLock};
+
+use noodles_sam::{self as sam, header::record::value::map::reference_sequence::tag};
+
+use super::{cache::Cache, Adapter};
+use crate::Client;
+
+/// A refget-backed FASTA sequence repository adapter builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    digests: HashMap<Vec<u8>, String>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Sets the cache directory.
+    ///
+    /// Fetched sequences are stored using the htslib `REF_CACHE` layout, i.e.,
+    /// `<cache_dir>/%2s/%2s/%s`. Only sequences identified by an MD5 checksum are cached.
+    ///
+    /// By default, sequences are not cached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::repository::Builder;
+    /// let builder = Builder::default().set_cache_dir("/home/noodles/.cache/hts-ref");
+    /// ```
+    pub fn set_cache_dir<P>(mut self, cache_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Adds a mapping from a sequence name to a digest.
+    ///
+    /// The digest is either an MD5 checksum, e.g., from a SAM header `@SQ` `M5` field, or a GA4GH
+    /// identifier (`SQ.…`). Names that are not mapped are resolved as digests themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::repository::Builder;
+    /// let builder = Builder::default().add_digest("sq0", "d7eba311421bbc9d3ada44709dd61534");
+    /// ```
+    pub fn add_digest<N, D>(mut self, name: N, digest: D) -> Self
+    where
+        N: Into<Vec<u8>>,
+        D: Into<String>,
+    {
+        self.digests.insert(name.into(), digest.into());
+        self
+    }
+
+    /// Adds mappings from reference sequence names to the MD5 checksums in a SAM header.
+    ///
+    /// Reference sequences without an `M5` field are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_refget::repository::Builder;
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     header::record::value::{
+    ///         map::{reference_sequence::tag, ReferenceSequence},
+    ///         Map,
+    ///     },
+    /// };
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(
+    ///         "sq0",
+    ///         Map::<ReferenceSequence>::builder()
+    ///             .set_length(NonZeroUsize::try_from(8)?)
+    ///             .insert(tag::MD5_CHECKSUM, "d7eba311421bbc9d3ada44709dd61534")
+    ///             .build()?,
+    ///     )
+    ///     .build();
+    ///
+    /// let builder = Builder::default().add_digests_from_header(&header);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_digests_from_header(mut self, header: &sam::Header) -> Self {
+        for (name, reference_sequence) in header.reference_sequences() {
+            if let Some(md5_checksum) = reference_sequence
+                .other_fields()
+                .get(&tag::MD5_CHECKSUM)
+                .and_then(|value| std::str::from_utf8(value).ok())
+            {
+                self.digests
+                    .insert(name.to_vec(), md5_checksum.to_ascii_lowercase());
+            }
+        }
+
+        self
+    }
+
+    /// Builds a refget-backed FASTA sequence repository adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_refget::{self as refget, repository::Builder};
+    /// let client = refget::Client::new("https://localhost/".parse().unwrap());
+    /// let adapter = Builder::default().build(client)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build(self, client: Client) -> io::Result<Adapter> {
+        Ok(Adapter {
+            client,
+            digests: self.digests,
+            cache: self.cache_dir.map(Cache::new),
+            runtime: OnceLock::new(),
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::PathBuf,
+    process,
+};
+
+// A local sequence cache using the htslib `REF_CACHE` layout (`%2s/%2s/%s`).
+pub(super) struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    pub(super) fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub(super) fn get(&self, digest: &str) -> io::Result<Option<Vec<u8>>> {
+        let Some(path) = self.build_path(digest) else {
+            return Ok(None);
+        };
+
+        match fs::read(path) {
+            Ok(sequence) => Ok(Some(sequence)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(super) fn insert(&self, digest: &str, sequence: &[u8]) -> io::Result<()> {
+        let Some(path) = self.build_path(digest) else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file first so that concurrent readers never see a partial
+        // sequence.
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(format!(".tmp.{}", process::id()));
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(sequence)?;
+        drop(file);
+
+        fs::rename(tmp_path, path)
+    }
+
+    // Only MD5 checksums are cached.
+    fn build_path(&self, digest: &str) -> Option<PathBuf> {
+        if !super::is_md5_checksum(digest) {
+            return None;
+        }
+
+        let digest = digest.to_ascii_lowercase();
+
+        Some(
+            self.root
+                .join(&digest[0..2])
+                .join(&digest[2..4])
+                .join(&digest[4..]),
+        )
+    }
+}
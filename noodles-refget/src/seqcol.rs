@@ -0,0 +1,304 @@
+//! Sequence collections (seqcol).
+//!
+//! A sequence collection is an ordered set of named sequences, e.g., the sequences of a reference
+//! FASTA. It is identified by a digest computed from the collection attributes (`names`,
+//! `lengths`, and `sequences`).
+//!
+//! The `@SQ` records of a SAM header only define the `names` and `lengths` attributes. These can
+//! be read as a [`CoordinateSystem`] (with the `sam` feature) and compared to the coordinate
+//! system of a collection.
+//!
+//! Each attribute array is serialized as canonical JSON (RFC 8785) and digested with sha512t24u
+//! (level 1). The collection digest (level 0) is the sha512t24u digest of the canonical JSON
+//! object of the inherent attribute digests, i.e., `names` and `sequences`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_fasta as fasta;
+//! use noodles_refget::seqcol::SequenceCollection;
+//!
+//! let data = b">chrX\nTTGGGGAA\n>chr1\nGGAA\n>chr2\nGCGC\n";
+//! let mut reader = fasta::io::Reader::new(&data[..]);
+//! let collection = SequenceCollection::read_fasta(&mut reader)?;
+//!
+//! assert_eq!(collection.digest(), "XZlrcEGi6mlopZ2uD8ObHkQB1d0oDwKk");
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod coordinate_system;
+
+pub use self::coordinate_system::CoordinateSystem;
+
+use std::io::{self, BufRead};
+
+use noodles_fasta as fasta;
+
+use crate::digest::{sha512t24u, SequenceDigests};
+
+/// A sequence collection.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SequenceCollection {
+    names: Vec<String>,
+    sequences: Vec<SequenceDigests>,
+}
+
+impl SequenceCollection {
+    /// Creates a sequence collection by digesting every record in a FASTA.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta as fasta;
+    /// use noodles_refget::seqcol::SequenceCollection;
+    ///
+    /// let data = b">sq0\nACGT\n>sq1\nNNNN\n";
+    /// let mut reader = fasta::io::Reader::new(&data[..]);
+    /// let collection = SequenceCollection::read_fasta(&mut reader)?;
+    ///
+    /// assert_eq!(collection.names(), ["sq0", "sq1"]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_fasta<R>(reader: &mut fasta::io::Reader<R>) -> io::Result<Self>
+    where
+        R: BufRead,
+    {
+        let mut collection = Self::default();
+
+        for result in reader.records() {
+            let record = result?;
+            let name = parse_name(record.name())?;
+            let digests = SequenceDigests::compute(record.sequence().as_ref());
+            collection.push(name, digests);
+        }
+
+        Ok(collection)
+    }
+
+    /// Adds a sequence to the collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::{digest::SequenceDigests, seqcol::SequenceCollection};
+    ///
+    /// let mut collection = SequenceCollection::default();
+    /// collection.push("sq0", SequenceDigests::compute(b"ACGT"));
+    ///
+    /// assert_eq!(collection.len(), 1);
+    /// ```
+    pub fn push<N>(&mut self, name: N, digests: SequenceDigests)
+    where
+        N: Into<String>,
+    {
+        self.names.push(name.into());
+        self.sequences.push(digests);
+    }
+
+    /// Returns the number of sequences in the collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::SequenceCollection;
+    /// let collection = SequenceCollection::default();
+    /// assert_eq!(collection.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns whether the collection has no sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::SequenceCollection;
+    /// let collection = SequenceCollection::default();
+    /// assert!(collection.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns the sequence names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::SequenceCollection;
+    /// let collection = SequenceCollection::default();
+    /// assert!(collection.names().is_empty());
+    /// ```
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the sequence digests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::SequenceCollection;
+    /// let collection = SequenceCollection::default();
+    /// assert!(collection.sequences().is_empty());
+    /// ```
+    pub fn sequences(&self) -> &[SequenceDigests] {
+        &self.sequences
+    }
+
+    /// Returns the digests of the sequence with the given name.
+    ///
+    /// This can be used to verify `@SQ` records against a reference, e.g., by comparing the `LN`
+    /// field to [`SequenceDigests::length`] and the `M5` field to [`SequenceDigests::md5`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::{digest::SequenceDigests, seqcol::SequenceCollection};
+    ///
+    /// let mut collection = SequenceCollection::default();
+    /// collection.push("sq0", SequenceDigests::compute(b"ACGT"));
+    ///
+    /// let digests = collection.get("sq0").expect("missing sq0");
+    /// assert_eq!(digests.md5(), "f1f8f4bf413b16ad135722aa4591043e");
+    ///
+    /// assert!(collection.get("sq1").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&SequenceDigests> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| &self.sequences[i])
+    }
+
+    /// Returns the level 1 digest of the `names` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::{digest::SequenceDigests, seqcol::SequenceCollection};
+    ///
+    /// let mut collection = SequenceCollection::default();
+    /// collection.push("chrX", SequenceDigests::compute(b"TTGGGGAA"));
+    /// collection.push("chr1", SequenceDigests::compute(b"GGAA"));
+    /// collection.push("chr2", SequenceDigests::compute(b"GCGC"));
+    ///
+    /// assert_eq!(collection.names_digest(), "Fw1r9eRxfOZD98KKrhlYQNEdSRHoVxAG");
+    /// ```
+    pub fn names_digest(&self) -> String {
+        digest_array(self.names.iter().cloned())
+    }
+
+    /// Returns the level 1 digest of the `lengths` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::{digest::SequenceDigests, seqcol::SequenceCollection};
+    ///
+    /// let mut collection = SequenceCollection::default();
+    /// collection.push("chrX", SequenceDigests::compute(b"TTGGGGAA"));
+    /// collection.push("chr1", SequenceDigests::compute(b"GGAA"));
+    /// collection.push("chr2", SequenceDigests::compute(b"GCGC"));
+    ///
+    /// assert_eq!(collection.lengths_digest(), "cGRMZIb3AVgkcAfNv39RN7hnT5Chk7RX");
+    /// ```
+    pub fn lengths_digest(&self) -> String {
+        digest_array(self.sequences.iter().map(|digests| digests.length()))
+    }
+
+    /// Returns the level 1 digest of the `sequences` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::{digest::SequenceDigests, seqcol::SequenceCollection};
+    ///
+    /// let mut collection = SequenceCollection::default();
+    /// collection.push("chrX", SequenceDigests::compute(b"TTGGGGAA"));
+    /// collection.push("chr1", SequenceDigests::compute(b"GGAA"));
+    /// collection.push("chr2", SequenceDigests::compute(b"GCGC"));
+    ///
+    /// assert_eq!(collection.sequences_digest(), "0uDQVLuHaOZi1u76LjV__yrVUIz9Bwhr");
+    /// ```
+    pub fn sequences_digest(&self) -> String {
+        digest_array(
+            self.sequences
+                .iter()
+                .map(|digests| digests.ga4gh_identifier()),
+        )
+    }
+
+    /// Returns the collection (level 0) digest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::{digest::SequenceDigests, seqcol::SequenceCollection};
+    ///
+    /// let mut collection = SequenceCollection::default();
+    /// collection.push("chrX", SequenceDigests::compute(b"TTGGGGAA"));
+    /// collection.push("chr1", SequenceDigests::compute(b"GGAA"));
+    /// collection.push("chr2", SequenceDigests::compute(b"GCGC"));
+    ///
+    /// assert_eq!(collection.digest(), "XZlrcEGi6mlopZ2uD8ObHkQB1d0oDwKk");
+    /// ```
+    pub fn digest(&self) -> String {
+        // Level 1 digests are URL-safe base64 and never need to be escaped. Keys are in canonical
+        // (lexicographic) order.
+        let object = format!(
+            r#"{{"names":"{}","sequences":"{}"}}"#,
+            self.names_digest(),
+            self.sequences_digest()
+        );
+
+        sha512t24u(object.as_bytes())
+    }
+}
+
+fn parse_name(buf: &[u8]) -> io::Result<String> {
+    String::from_utf8(buf.into()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Serializes the array as canonical JSON and digests it.
+fn digest_array<I, T>(values: I) -> String
+where
+    I: Iterator<Item = T>,
+    T: Into<serde_json::Value>,
+{
+    let array: serde_json::Value = values.map(Into::into).collect();
+    sha512t24u(array.to_string().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_fasta() -> io::Result<()> {
+        let data = b">sq0\nacgt\nac\n>sq1\nNNNN\n";
+        let mut reader = fasta::io::Reader::new(&data[..]);
+        let collection = SequenceCollection::read_fasta(&mut reader)?;
+
+        let mut expected = SequenceCollection::default();
+        expected.push("sq0", SequenceDigests::compute(b"ACGTAC"));
+        expected.push("sq1", SequenceDigests::compute(b"NNNN"));
+
+        assert_eq!(collection, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_digest_array() {
+        // The JSON is serialized without whitespace and with escaped strings.
+        assert_eq!(
+            digest_array(["a\"b"].into_iter()),
+            sha512t24u(br#"["a\"b"]"#)
+        );
+        assert_eq!(digest_array([8, 13].into_iter()), sha512t24u(b"[8,13]"));
+    }
+}
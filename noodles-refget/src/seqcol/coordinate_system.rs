@@ -0,0 +1,182 @@
+#[cfg(feature = "sam")]
+use noodles_sam as sam;
+
+use super::{digest_array, SequenceCollection};
+
+/// The coordinate system of a sequence collection.
+///
+/// This is the `names` and `lengths` attributes of a sequence collection. Unlike a
+/// [`SequenceCollection`], it does not require the sequences, e.g., when read from the `@SQ`
+/// records of a SAM header.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CoordinateSystem {
+    names: Vec<String>,
+    lengths: Vec<u64>,
+}
+
+impl CoordinateSystem {
+    /// Adds a sequence to the coordinate system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::CoordinateSystem;
+    ///
+    /// let mut coordinate_system = CoordinateSystem::default();
+    /// coordinate_system.push("sq0", 8);
+    ///
+    /// assert_eq!(coordinate_system.names(), ["sq0"]);
+    /// ```
+    pub fn push<N>(&mut self, name: N, length: u64)
+    where
+        N: Into<String>,
+    {
+        self.names.push(name.into());
+        self.lengths.push(length);
+    }
+
+    /// Returns the sequence names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::CoordinateSystem;
+    /// let coordinate_system = CoordinateSystem::default();
+    /// assert!(coordinate_system.names().is_empty());
+    /// ```
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the sequence lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::CoordinateSystem;
+    /// let coordinate_system = CoordinateSystem::default();
+    /// assert!(coordinate_system.lengths().is_empty());
+    /// ```
+    pub fn lengths(&self) -> &[u64] {
+        &self.lengths
+    }
+
+    /// Returns the level 1 digest of the `names` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::CoordinateSystem;
+    ///
+    /// let mut coordinate_system = CoordinateSystem::default();
+    /// coordinate_system.push("chrX", 8);
+    /// coordinate_system.push("chr1", 4);
+    /// coordinate_system.push("chr2", 4);
+    ///
+    /// assert_eq!(coordinate_system.names_digest(), "Fw1r9eRxfOZD98KKrhlYQNEdSRHoVxAG");
+    /// ```
+    pub fn names_digest(&self) -> String {
+        digest_array(self.names.iter().cloned())
+    }
+
+    /// Returns the level 1 digest of the `lengths` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::seqcol::CoordinateSystem;
+    ///
+    /// let mut coordinate_system = CoordinateSystem::default();
+    /// coordinate_system.push("chrX", 8);
+    /// coordinate_system.push("chr1", 4);
+    /// coordinate_system.push("chr2", 4);
+    ///
+    /// assert_eq!(coordinate_system.lengths_digest(), "cGRMZIb3AVgkcAfNv39RN7hnT5Chk7RX");
+    /// ```
+    pub fn lengths_digest(&self) -> String {
+        digest_array(self.lengths.iter().copied())
+    }
+}
+
+impl From<&SequenceCollection> for CoordinateSystem {
+    fn from(collection: &SequenceCollection) -> Self {
+        Self {
+            names: collection.names().to_vec(),
+            lengths: collection
+                .sequences()
+                .iter()
+                .map(|digests| digests.length())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "sam")]
+impl From<&sam::Header> for CoordinateSystem {
+    fn from(header: &sam::Header) -> Self {
+        let mut coordinate_system = Self::default();
+
+        for (name, reference_sequence) in header.reference_sequences() {
+            let length = usize::from(reference_sequence.length()) as u64;
+            coordinate_system.push(name.to_string(), length);
+        }
+
+        coordinate_system
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sequence_collection() {
+        use crate::digest::SequenceDigests;
+
+        let mut collection = SequenceCollection::default();
+        collection.push("sq0", SequenceDigests::compute(b"ACGT"));
+        collection.push("sq1", SequenceDigests::compute(b"NNNNNNNN"));
+
+        let mut expected = CoordinateSystem::default();
+        expected.push("sq0", 4);
+        expected.push("sq1", 8);
+
+        assert_eq!(CoordinateSystem::from(&collection), expected);
+    }
+
+    #[cfg(feature = "sam")]
+    #[test]
+    fn test_from_sam_header() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_fasta as fasta;
+
+        // The sequence collection example from the seqcol specification.
+        let header: sam::Header =
+            "@SQ\tSN:chrX\tLN:8\n@SQ\tSN:chr1\tLN:4\n@SQ\tSN:chr2\tLN:4\n".parse()?;
+
+        let coordinate_system = CoordinateSystem::from(&header);
+        assert_eq!(coordinate_system.names(), ["chrX", "chr1", "chr2"]);
+        assert_eq!(coordinate_system.lengths(), [8, 4, 4]);
+        assert_eq!(
+            coordinate_system.names_digest(),
+            "Fw1r9eRxfOZD98KKrhlYQNEdSRHoVxAG"
+        );
+        assert_eq!(
+            coordinate_system.lengths_digest(),
+            "cGRMZIb3AVgkcAfNv39RN7hnT5Chk7RX"
+        );
+
+        let data = b">chrX\nTTGGGGAA\n>chr1\nGGAA\n>chr2\nGCGC\n";
+        let mut reader = fasta::io::Reader::new(&data[..]);
+        let collection = SequenceCollection::read_fasta(&mut reader)?;
+        assert_eq!(coordinate_system, CoordinateSystem::from(&collection));
+
+        let header: sam::Header =
+            "@SQ\tSN:chrX\tLN:8\n@SQ\tSN:chr1\tLN:5\n@SQ\tSN:chr2\tLN:4\n".parse()?;
+        assert_ne!(
+            CoordinateSystem::from(&header),
+            CoordinateSystem::from(&collection)
+        );
+
+        Ok(())
+    }
+}
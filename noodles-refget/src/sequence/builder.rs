@@ -56,7 +56,12 @@ impl Builder {
             request = request.query(&query);
         }
 
-        let response = request.send().await.map_err(Error::Request)?;
+        let response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::Request)?;
+
         let sequence = response.bytes().await.map_err(Error::Request)?;
 
         Ok(Sequence::new(self.client, self.id, sequence))
@@ -0,0 +1,511 @@
+//! Local refget server.
+//!
+//! [`Server`] answers refget 2.0 requests from an indexed FASTA. It implements the `sequence`,
+//! `metadata`, and `service-info` endpoints but does not listen for connections itself; requests
+//! are passed to [`Server::handle`] by any HTTP server.
+//!
+//! Sequences are identified by their MD5 checksums or GA4GH identifiers, which are computed for
+//! every sequence in the FASTA when the server is created. Circular sequences and the `Range`
+//! header are not supported.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io::{self, Cursor};
+//! use noodles_fasta::{self as fasta, fai};
+//! use noodles_refget::server::Server;
+//! use reqwest::StatusCode;
+//!
+//! let data = b">sq0\nACGT\n";
+//! let index = vec![fai::Record::new("sq0", 4, 5, 4, 5)];
+//! let reader = fasta::io::IndexedReader::new(Cursor::new(&data[..]), index);
+//! let mut server = Server::new(reader)?;
+//!
+//! let response = server.handle("/sequence/f1f8f4bf413b16ad135722aa4591043e?start=1&end=3")?;
+//! assert_eq!(response.status(), StatusCode::OK);
+//! assert_eq!(response.body(), b"CG");
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod response;
+
+pub use self::response::Response;
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Seek},
+};
+
+use noodles_core::{Position, Region};
+use noodles_fasta as fasta;
+use reqwest::StatusCode;
+use serde::Serialize;
+
+use self::response::{JSON_CONTENT_TYPE, SEQUENCE_CONTENT_TYPE};
+use crate::{digest::SequenceDigests, seqcol::SequenceCollection};
+
+const GA4GH_NAMESPACE_PREFIX: &str = "ga4gh:";
+
+/// A local refget server.
+pub struct Server<R> {
+    reader: fasta::io::IndexedReader<R>,
+    collection: SequenceCollection,
+    ids: HashMap<String, usize>,
+}
+
+impl<R> Server<R>
+where
+    R: BufRead + Seek,
+{
+    /// Creates a refget server.
+    ///
+    /// This reads and digests every sequence in the FASTA.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_fasta::{self as fasta, fai};
+    /// use noodles_refget::server::Server;
+    ///
+    /// let data = b">sq0\nACGT\n";
+    /// let index = vec![fai::Record::new("sq0", 4, 5, 4, 5)];
+    /// let reader = fasta::io::IndexedReader::new(Cursor::new(&data[..]), index);
+    /// let server = Server::new(reader)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn new(mut reader: fasta::io::IndexedReader<R>) -> io::Result<Self> {
+        let entries: Vec<_> = reader
+            .index()
+            .iter()
+            .map(|record| (record.name().to_vec(), record.length()))
+            .collect();
+
+        let mut collection = SequenceCollection::default();
+        let mut ids = HashMap::new();
+
+        for (i, (raw_name, length)) in entries.into_iter().enumerate() {
+            let sequence = read_sequence(&mut reader, &raw_name, 0, length)?;
+            let digests = SequenceDigests::compute(&sequence);
+
+            ids.insert(digests.md5().into(), i);
+            ids.insert(digests.ga4gh_identifier(), i);
+
+            let name = String::from_utf8(raw_name)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            collection.push(name, digests);
+        }
+
+        Ok(Self {
+            reader,
+            collection,
+            ids,
+        })
+    }
+
+    /// Returns the sequence collection of the FASTA.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_fasta::{self as fasta, fai};
+    /// use noodles_refget::server::Server;
+    ///
+    /// let data = b">sq0\nACGT\n";
+    /// let index = vec![fai::Record::new("sq0", 4, 5, 4, 5)];
+    /// let reader = fasta::io::IndexedReader::new(Cursor::new(&data[..]), index);
+    /// let server = Server::new(reader)?;
+    ///
+    /// assert_eq!(server.collection().names(), ["sq0"]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn collection(&self) -> &SequenceCollection {
+        &self.collection
+    }
+
+    /// Handles a request.
+    ///
+    /// `path_and_query` is the request target relative to the refget base URL, e.g.,
+    /// `/sequence/<id>?start=8&end=13`, `/sequence/<id>/metadata`, or `/sequence/service-info`.
+    /// Requests that cannot be answered return an error response. I/O errors are returned as
+    /// errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_fasta::{self as fasta, fai};
+    /// use noodles_refget::server::Server;
+    /// use reqwest::StatusCode;
+    ///
+    /// let data = b">sq0\nACGT\n";
+    /// let index = vec![fai::Record::new("sq0", 4, 5, 4, 5)];
+    /// let reader = fasta::io::IndexedReader::new(Cursor::new(&data[..]), index);
+    /// let mut server = Server::new(reader)?;
+    ///
+    /// let response = server.handle("/sequence/SQ.aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2")?;
+    /// assert_eq!(response.status(), StatusCode::OK);
+    /// assert_eq!(response.body(), b"ACGT");
+    ///
+    /// let response = server.handle("/sequence/d41d8cd98f00b204e9800998ecf8427e")?;
+    /// assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn handle(&mut self, path_and_query: &str) -> io::Result<Response> {
+        let (path, query) = match path_and_query.split_once('?') {
+            Some((path, query)) => (path, query),
+            None => (path_and_query, ""),
+        };
+
+        let path = path.trim_start_matches('/');
+
+        let Some(path) = path.strip_prefix("sequence/") else {
+            return Ok(Response::error(StatusCode::NOT_FOUND));
+        };
+
+        if path == "service-info" {
+            return Ok(service_info());
+        }
+
+        if let Some(id) = path.strip_suffix("/metadata") {
+            return Ok(self.metadata(id));
+        }
+
+        if path.contains('/') {
+            return Ok(Response::error(StatusCode::NOT_FOUND));
+        }
+
+        self.sequence(path, query)
+    }
+
+    fn resolve(&self, id: &str) -> Option<usize> {
+        let id = id.strip_prefix(GA4GH_NAMESPACE_PREFIX).unwrap_or(id);
+
+        self.ids
+            .get(id)
+            .or_else(|| self.ids.get(&id.to_ascii_lowercase()))
+            .copied()
+    }
+
+    fn sequence(&mut self, id: &str, query: &str) -> io::Result<Response> {
+        let Some(i) = self.resolve(id) else {
+            return Ok(Response::error(StatusCode::NOT_FOUND));
+        };
+
+        let record = &self.reader.index()[i];
+        let length = record.length();
+
+        let (start, end) = match parse_interval(query) {
+            Ok(interval) => interval,
+            Err(status) => return Ok(Response::error(status)),
+        };
+
+        let end = end.unwrap_or(length);
+
+        if let Some(start) = start {
+            if start > end {
+                return Ok(Response::error(StatusCode::NOT_IMPLEMENTED));
+            } else if start >= length {
+                return Ok(Response::error(StatusCode::RANGE_NOT_SATISFIABLE));
+            }
+        }
+
+        if end > length {
+            return Ok(Response::error(StatusCode::RANGE_NOT_SATISFIABLE));
+        }
+
+        let name = record.name().to_vec();
+        let sequence = read_sequence(&mut self.reader, &name, start.unwrap_or(0), end)?;
+
+        Ok(Response::new(
+            StatusCode::OK,
+            SEQUENCE_CONTENT_TYPE,
+            sequence,
+        ))
+    }
+
+    fn metadata(&self, id: &str) -> Response {
+        #[derive(Serialize)]
+        struct MetadataResponse<'a> {
+            metadata: Metadata<'a>,
+        }
+
+        #[derive(Serialize)]
+        struct Metadata<'a> {
+            md5: &'a str,
+            ga4gh: String,
+            length: u64,
+            aliases: &'static [String],
+        }
+
+        let Some(i) = self.resolve(id) else {
+            return Response::error(StatusCode::NOT_FOUND);
+        };
+
+        let digests = &self.collection.sequences()[i];
+
+        let response = MetadataResponse {
+            metadata: Metadata {
+                md5: digests.md5(),
+                ga4gh: digests.ga4gh_identifier(),
+                length: digests.length(),
+                aliases: &[],
+            },
+        };
+
+        json_response(&response)
+    }
+}
+
+fn service_info() -> Response {
+    #[derive(Serialize)]
+    struct ServiceInfo {
+        id: &'static str,
+        name: &'static str,
+        #[serde(rename = "type")]
+        ty: ServiceType,
+        version: &'static str,
+        refget: Refget,
+    }
+
+    #[derive(Serialize)]
+    struct ServiceType {
+        group: &'static str,
+        artifact: &'static str,
+        version: &'static str,
+    }
+
+    #[derive(Serialize)]
+    struct Refget {
+        circular_supported: bool,
+        algorithms: [&'static str; 2],
+        identifier_types: &'static [String],
+        subsequence_limit: Option<u32>,
+    }
+
+    let service_info = ServiceInfo {
+        id: "noodles-refget",
+        name: "noodles-refget",
+        ty: ServiceType {
+            group: "org.ga4gh",
+            artifact: "refget",
+            version: "2.0.0",
+        },
+        version: env!("CARGO_PKG_VERSION"),
+        refget: Refget {
+            circular_supported: false,
+            algorithms: ["md5", "ga4gh"],
+            identifier_types: &[],
+            subsequence_limit: None,
+        },
+    };
+
+    json_response(&service_info)
+}
+
+fn json_response<T>(value: &T) -> Response
+where
+    T: Serialize,
+{
+    match serde_json::to_vec(value) {
+        Ok(body) => Response::new(StatusCode::OK, JSON_CONTENT_TYPE, body),
+        Err(_) => Response::error(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+fn parse_interval(query: &str) -> Result<(Option<u64>, Option<u64>), StatusCode> {
+    let mut start = None;
+    let mut end = None;
+
+    for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        let n = value.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+        match key.as_ref() {
+            "start" => start = Some(n),
+            "end" => end = Some(n),
+            _ => {}
+        }
+    }
+
+    Ok((start, end))
+}
+
+// Reads the 0-based, half-open interval [start, end) of the sequence as uppercase bases.
+fn read_sequence<R>(
+    reader: &mut fasta::io::IndexedReader<R>,
+    name: &[u8],
+    start: u64,
+    end: u64,
+) -> io::Result<Vec<u8>>
+where
+    R: BufRead + Seek,
+{
+    if start >= end {
+        return Ok(Vec::new());
+    }
+
+    let to_position = |n: u64| {
+        usize::try_from(n)
+            .ok()
+            .and_then(Position::new)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid position"))
+    };
+
+    let interval = to_position(start + 1)?..=to_position(end)?;
+    let region = Region::new(name, interval);
+
+    let record = reader.query(&region)?;
+    let mut sequence: Vec<u8> = record.sequence().as_ref().into();
+    sequence.make_ascii_uppercase();
+
+    Ok(sequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use fasta::fai;
+
+    use super::*;
+
+    const MD5: &str = "f1f8f4bf413b16ad135722aa4591043e";
+
+    fn build_server() -> io::Result<Server<Cursor<&'static [u8]>>> {
+        const DATA: &[u8] = b">sq0\nAC\ngt\n>sq1\n";
+
+        let index = vec![
+            fai::Record::new("sq0", 4, 5, 2, 3),
+            fai::Record::new("sq1", 0, 16, 0, 0),
+        ];
+
+        let reader = fasta::io::IndexedReader::new(Cursor::new(DATA), index);
+        Server::new(reader)
+    }
+
+    #[test]
+    fn test_new() -> io::Result<()> {
+        let server = build_server()?;
+
+        let collection = server.collection();
+        assert_eq!(collection.names(), ["sq0", "sq1"]);
+        assert_eq!(collection.sequences()[0].md5(), MD5);
+        assert_eq!(
+            collection.sequences()[1].md5(),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_sequence() -> io::Result<()> {
+        let mut server = build_server()?;
+
+        let response = server.handle(&format!("/sequence/{MD5}"))?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.content_type(), SEQUENCE_CONTENT_TYPE);
+        assert_eq!(response.body(), b"ACGT");
+
+        let response =
+            server.handle("sequence/ga4gh:SQ.aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2?start=1")?;
+        assert_eq!(response.body(), b"CGT");
+
+        let response = server.handle(&format!("/sequence/{MD5}?start=1&end=3"))?;
+        assert_eq!(response.body(), b"CG");
+
+        let response = server.handle(&format!("/sequence/{MD5}?start=2&end=2"))?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.body().is_empty());
+
+        let response = server.handle("/sequence/F1F8F4BF413B16AD135722AA4591043E")?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = server.handle("/sequence/d41d8cd98f00b204e9800998ecf8427e")?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.body().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_sequence_with_invalid_request() -> io::Result<()> {
+        let mut server = build_server()?;
+
+        for (path_and_query, expected) in [
+            (String::from("/sequence/SQ.ndls"), StatusCode::NOT_FOUND),
+            (String::from("/reference/ndls"), StatusCode::NOT_FOUND),
+            (format!("/sequence/{MD5}?start=x"), StatusCode::BAD_REQUEST),
+            (format!("/sequence/{MD5}?end=-1"), StatusCode::BAD_REQUEST),
+            (
+                format!("/sequence/{MD5}?start=3&end=1"),
+                StatusCode::NOT_IMPLEMENTED,
+            ),
+            (
+                format!("/sequence/{MD5}?start=4"),
+                StatusCode::RANGE_NOT_SATISFIABLE,
+            ),
+            (
+                format!("/sequence/{MD5}?end=5"),
+                StatusCode::RANGE_NOT_SATISFIABLE,
+            ),
+        ] {
+            let response = server.handle(&path_and_query)?;
+            assert_eq!(response.status(), expected, "{path_and_query}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_metadata() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(serde::Deserialize)]
+        struct MetadataResponse {
+            metadata: crate::sequence::Metadata,
+        }
+
+        let mut server = build_server()?;
+
+        let response = server.handle(&format!("/sequence/{MD5}/metadata"))?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.content_type(), JSON_CONTENT_TYPE);
+
+        let actual: MetadataResponse = serde_json::from_slice(response.body())?;
+        let metadata = actual.metadata;
+        assert_eq!(metadata.md5(), MD5);
+        assert_eq!(
+            metadata.ga4gh(),
+            Some("SQ.aKF498dAxcJAqme6QYQ7EZ07-fiw8Kw2")
+        );
+        assert_eq!(metadata.length(), 4);
+        assert!(metadata.aliases().is_empty());
+
+        let response = server.handle("/sequence/SQ.ndls/metadata")?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handle_service_info() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(serde::Deserialize)]
+        struct ServiceInfoResponse {
+            refget: crate::sequence::Service,
+        }
+
+        let mut server = build_server()?;
+
+        let response = server.handle("/sequence/service-info")?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let actual: ServiceInfoResponse = serde_json::from_slice(response.body())?;
+        let service = actual.refget;
+        assert!(!service.circular_supported());
+        assert_eq!(service.algorithms(), ["md5", "ga4gh"]);
+        assert!(service.subsequence_limit().is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use reqwest::StatusCode;
+
+pub(super) const SEQUENCE_CONTENT_TYPE: &str =
+    "text/vnd.ga4gh.refget.v2.0.0+plain; charset=us-ascii";
+pub(super) const JSON_CONTENT_TYPE: &str = "application/vnd.ga4gh.refget.v2.0.0+json";
+const ERROR_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+
+/// A refget server response.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Response {
+    status: StatusCode,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    pub(super) fn new(status: StatusCode, content_type: &'static str, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            content_type,
+            body,
+        }
+    }
+
+    pub(super) fn error(status: StatusCode) -> Self {
+        let body = status.canonical_reason().unwrap_or_default().into();
+        Self::new(status, ERROR_CONTENT_TYPE, body)
+    }
+
+    /// Returns the HTTP status code.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the value of the `Content-Type` header.
+    pub fn content_type(&self) -> &str {
+        self.content_type
+    }
+
+    /// Returns the body.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns the body, consuming the response.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
@@ -1,5 +1,6 @@
 //! Alignment record.
 
+pub mod calmd;
 pub mod io;
 pub mod record;
 pub mod record_buf;
@@ -0,0 +1,594 @@
+//! MD and NM tag calculation.
+//!
+//! This calculates the mismatched positions (`MD`) and edit distance (`NM`) of an alignment record
+//! from its CIGAR, sequence, and reference sequence, similar to `samtools calmd`.
+//!
+//! A read base matches a reference base when they are equal, case-insensitively, or when the read
+//! base is `=`. `N` never matches.
+
+use std::io;
+
+use bstr::{BStr, BString};
+use noodles_fasta as fasta;
+
+use super::{
+    record::{cigar::op::Kind, data::field::Tag},
+    record_buf::data::field::Value,
+    Record, RecordBuf,
+};
+use crate::Header;
+
+/// Calculated MD and NM values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tags {
+    mismatched_positions: BString,
+    edit_distance: u32,
+}
+
+impl Tags {
+    /// Returns the mismatched positions (`MD`).
+    pub fn mismatched_positions(&self) -> &BStr {
+        self.mismatched_positions.as_ref()
+    }
+
+    /// Returns the edit distance (`NM`).
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+}
+
+/// A difference between an existing data field and its calculated value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Discrepancy {
+    tag: Tag,
+    actual: Value,
+    expected: Value,
+}
+
+impl Discrepancy {
+    /// Returns the data field tag.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the existing value.
+    pub fn actual(&self) -> &Value {
+        &self.actual
+    }
+
+    /// Returns the calculated value.
+    pub fn expected(&self) -> &Value {
+        &self.expected
+    }
+}
+
+/// Calculates the MD and NM values of an alignment record.
+///
+/// This returns `None` if the record is unmapped or has no alignment start, CIGAR, or sequence.
+///
+/// # Errors
+///
+/// An error is returned if the reference sequence is missing from the repository or if the
+/// alignment is inconsistent with the sequence or reference sequence.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_fasta as fasta;
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{
+///         calmd,
+///         record::{
+///             cigar::{op::Kind, Op},
+///             Flags,
+///         },
+///         RecordBuf,
+///     },
+///     header::record::value::{map::ReferenceSequence, Map},
+/// };
+/// use std::num::NonZeroUsize;
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(
+///         "sq0",
+///         Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+///     )
+///     .build();
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     fasta::record::Definition::new("sq0", None),
+///     fasta::record::Sequence::from(b"ACGTACGT".to_vec()),
+/// )]);
+///
+/// let record = RecordBuf::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::MIN)
+///     .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+///     .set_sequence(b"ACTT".to_vec().into())
+///     .build();
+///
+/// let tags = calmd::calculate(&header, &repository, &record)?.expect("missing tags");
+/// assert_eq!(tags.mismatched_positions(), "2G1");
+/// assert_eq!(tags.edit_distance(), 1);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn calculate<R>(
+    header: &Header,
+    repository: &fasta::Repository,
+    record: &R,
+) -> io::Result<Option<Tags>>
+where
+    R: Record,
+{
+    if record.flags()?.is_unmapped() || record.cigar().is_empty() || record.sequence().is_empty() {
+        return Ok(None);
+    }
+
+    let Some(alignment_start) = record.alignment_start().transpose()? else {
+        return Ok(None);
+    };
+
+    let Some((name, _)) = record.reference_sequence(header).transpose()? else {
+        return Ok(None);
+    };
+
+    let reference_sequence = repository.get(name).transpose()?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("missing reference sequence: {name}"),
+        )
+    })?;
+
+    let reference_bases = reference_sequence
+        .as_ref()
+        .get(usize::from(alignment_start) - 1..)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "alignment start is past the end of the reference sequence",
+            )
+        })?;
+
+    let read_bases = record.sequence();
+
+    calculate_tags(record.cigar().iter(), read_bases.iter(), reference_bases).map(Some)
+}
+
+/// Validates the MD and NM data fields of an alignment record.
+///
+/// This compares existing `MD` and `NM` data fields to their calculated values. Missing fields
+/// are not reported.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta as fasta;
+/// use noodles_sam::{self as sam, alignment::{calmd, RecordBuf}};
+///
+/// let header = sam::Header::default();
+/// let repository = fasta::Repository::default();
+/// let record = RecordBuf::default();
+///
+/// assert!(calmd::validate(&header, &repository, &record)?.is_empty());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn validate<R>(
+    header: &Header,
+    repository: &fasta::Repository,
+    record: &R,
+) -> io::Result<Vec<Discrepancy>>
+where
+    R: Record,
+{
+    match calculate(header, repository, record)? {
+        Some(tags) => find_discrepancies(record, &tags),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Calculates and sets the MD and NM data fields of an alignment record.
+///
+/// Existing fields are overwritten. Existing fields that differ from the calculated values are
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta as fasta;
+/// use noodles_sam::{self as sam, alignment::{calmd, RecordBuf}};
+///
+/// let header = sam::Header::default();
+/// let repository = fasta::Repository::default();
+/// let mut record = RecordBuf::default();
+///
+/// assert!(calmd::update(&header, &repository, &mut record)?.is_empty());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn update(
+    header: &Header,
+    repository: &fasta::Repository,
+    record: &mut RecordBuf,
+) -> io::Result<Vec<Discrepancy>> {
+    let Some(tags) = calculate(header, repository, record)? else {
+        return Ok(Vec::new());
+    };
+
+    let discrepancies = find_discrepancies(record, &tags)?;
+
+    let data = record.data_mut();
+
+    data.insert(
+        Tag::MISMATCHED_POSITIONS,
+        Value::String(tags.mismatched_positions),
+    );
+    data.insert(Tag::EDIT_DISTANCE, Value::from(tags.edit_distance));
+
+    Ok(discrepancies)
+}
+
+fn calculate_tags<C, S>(cigar: C, mut read_bases: S, reference_bases: &[u8]) -> io::Result<Tags>
+where
+    C: Iterator<Item = io::Result<super::record::cigar::Op>>,
+    S: Iterator<Item = u8>,
+{
+    let mut mismatched_positions = BString::default();
+    let mut edit_distance = 0;
+    let mut match_count = 0;
+
+    let mut reference_bases = reference_bases.iter().copied();
+
+    let mut next_read_base = || {
+        read_bases.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "read length does not match CIGAR",
+            )
+        })
+    };
+
+    let mut next_reference_base = || {
+        reference_bases.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "alignment extends past the end of the reference sequence",
+            )
+        })
+    };
+
+    for result in cigar {
+        let op = result?;
+        let len = op.len();
+
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                for _ in 0..len {
+                    let read_base = next_read_base()?;
+                    let reference_base = next_reference_base()?.to_ascii_uppercase();
+
+                    if bases_match(read_base, reference_base) {
+                        match_count += 1;
+                    } else {
+                        push_match_count(&mut mismatched_positions, &mut match_count);
+                        mismatched_positions.push(reference_base);
+                        edit_distance += 1;
+                    }
+                }
+            }
+            Kind::Insertion => {
+                for _ in 0..len {
+                    next_read_base()?;
+                }
+
+                edit_distance += len;
+            }
+            Kind::Deletion => {
+                push_match_count(&mut mismatched_positions, &mut match_count);
+                mismatched_positions.push(b'^');
+
+                for _ in 0..len {
+                    let reference_base = next_reference_base()?;
+                    mismatched_positions.push(reference_base.to_ascii_uppercase());
+                }
+
+                edit_distance += len;
+            }
+            Kind::Skip => {
+                for _ in 0..len {
+                    next_reference_base()?;
+                }
+            }
+            Kind::SoftClip => {
+                for _ in 0..len {
+                    next_read_base()?;
+                }
+            }
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    push_match_count(&mut mismatched_positions, &mut match_count);
+
+    let edit_distance =
+        u32::try_from(edit_distance).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Tags {
+        mismatched_positions,
+        edit_distance,
+    })
+}
+
+fn bases_match(read_base: u8, reference_base: u8) -> bool {
+    const MATCH: u8 = b'=';
+    const ANY: u8 = b'N';
+
+    if read_base == MATCH {
+        return true;
+    }
+
+    let read_base = read_base.to_ascii_uppercase();
+    read_base == reference_base && read_base != ANY
+}
+
+fn push_match_count(dst: &mut BString, match_count: &mut usize) {
+    dst.extend_from_slice(match_count.to_string().as_bytes());
+    *match_count = 0;
+}
+
+fn find_discrepancies<R>(record: &R, tags: &Tags) -> io::Result<Vec<Discrepancy>>
+where
+    R: Record,
+{
+    use super::record::data::field::Value as FieldValue;
+
+    let data = record.data();
+    let mut discrepancies = Vec::new();
+
+    if let Some(value) = data.get(&Tag::MISMATCHED_POSITIONS).transpose()? {
+        let is_match = matches!(
+            value,
+            FieldValue::String(s) if s == tags.mismatched_positions()
+        );
+
+        if !is_match {
+            discrepancies.push(Discrepancy {
+                tag: Tag::MISMATCHED_POSITIONS,
+                actual: Value::try_from(value)?,
+                expected: Value::String(tags.mismatched_positions.clone()),
+            });
+        }
+    }
+
+    if let Some(value) = data.get(&Tag::EDIT_DISTANCE).transpose()? {
+        if value.as_int() != Some(i64::from(tags.edit_distance)) {
+            discrepancies.push(Discrepancy {
+                tag: Tag::EDIT_DISTANCE,
+                actual: Value::try_from(value)?,
+                expected: Value::from(tags.edit_distance),
+            });
+        }
+    }
+
+    Ok(discrepancies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::record::{cigar::Op, Flags};
+
+    fn calculate_from(cigar: &[Op], read_bases: &[u8], reference_bases: &[u8]) -> io::Result<Tags> {
+        calculate_tags(
+            cigar.iter().copied().map(Ok),
+            read_bases.iter().copied(),
+            reference_bases,
+        )
+    }
+
+    fn tags(mismatched_positions: &str, edit_distance: u32) -> Tags {
+        Tags {
+            mismatched_positions: mismatched_positions.into(),
+            edit_distance,
+        }
+    }
+
+    #[test]
+    fn test_calculate_tags() -> io::Result<()> {
+        const REFERENCE_BASES: &[u8] = b"ACGTACGTAC";
+
+        assert_eq!(
+            calculate_from(&[Op::new(Kind::Match, 4)], b"ACGT", REFERENCE_BASES)?,
+            tags("4", 0)
+        );
+
+        assert_eq!(
+            calculate_from(&[Op::new(Kind::Match, 4)], b"TCGA", REFERENCE_BASES)?,
+            tags("0A2T0", 2)
+        );
+
+        // Matches are case-insensitive, `=` always matches, and `N` never matches.
+        assert_eq!(
+            calculate_from(&[Op::new(Kind::Match, 4)], b"a=NT", b"ACNT")?,
+            tags("2N1", 1)
+        );
+
+        assert_eq!(
+            calculate_from(
+                &[
+                    Op::new(Kind::SoftClip, 2),
+                    Op::new(Kind::Match, 2),
+                    Op::new(Kind::Insertion, 1),
+                    Op::new(Kind::Match, 1),
+                    Op::new(Kind::Deletion, 2),
+                    Op::new(Kind::Match, 2),
+                    Op::new(Kind::HardClip, 3),
+                ],
+                b"NNACTGTG",
+                REFERENCE_BASES,
+            )?,
+            tags("3^TA0C1", 4)
+        );
+
+        assert_eq!(
+            calculate_from(
+                &[
+                    Op::new(Kind::Match, 2),
+                    Op::new(Kind::Skip, 4),
+                    Op::new(Kind::SequenceMatch, 2)
+                ],
+                b"ACGA",
+                REFERENCE_BASES,
+            )?,
+            tags("3T0", 1)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_tags_with_invalid_alignment() {
+        assert!(matches!(
+            calculate_from(&[Op::new(Kind::Match, 4)], b"ACG", b"ACGT"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            calculate_from(&[Op::new(Kind::Match, 4)], b"ACGT", b"ACG"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_core::Position;
+
+        use crate::header::record::value::{map::ReferenceSequence, Map};
+
+        let header = Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"ACGTACGT".to_vec()),
+        )]);
+
+        let build_record = |data| -> Result<_, Box<dyn std::error::Error>> {
+            Ok(RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(5)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .set_sequence(b"ACGA".to_vec().into())
+                .set_data(data)
+                .build())
+        };
+
+        let record = build_record(
+            [
+                (Tag::MISMATCHED_POSITIONS, Value::from("3T0")),
+                (Tag::EDIT_DISTANCE, Value::Int32(1)),
+            ]
+            .into_iter()
+            .collect(),
+        )?;
+
+        assert!(validate(&header, &repository, &record)?.is_empty());
+
+        let record = build_record(
+            [
+                (Tag::MISMATCHED_POSITIONS, Value::from("4")),
+                (Tag::EDIT_DISTANCE, Value::from("1")),
+            ]
+            .into_iter()
+            .collect(),
+        )?;
+
+        assert_eq!(
+            validate(&header, &repository, &record)?,
+            [
+                Discrepancy {
+                    tag: Tag::MISMATCHED_POSITIONS,
+                    actual: Value::from("4"),
+                    expected: Value::from("3T0"),
+                },
+                Discrepancy {
+                    tag: Tag::EDIT_DISTANCE,
+                    actual: Value::from("1"),
+                    expected: Value::from(1u32),
+                },
+            ]
+        );
+
+        let header = Header::builder()
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        assert!(matches!(
+            validate(&header, &repository, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_core::Position;
+
+        use crate::header::record::value::{map::ReferenceSequence, Map};
+
+        let header = Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"ACGTACGT".to_vec()),
+        )]);
+
+        let mut record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::MIN)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .set_sequence(b"ACTT".to_vec().into())
+            .set_data([(Tag::EDIT_DISTANCE, Value::from(0))].into_iter().collect())
+            .build();
+
+        assert_eq!(
+            update(&header, &repository, &mut record)?,
+            [Discrepancy {
+                tag: Tag::EDIT_DISTANCE,
+                actual: Value::from(0),
+                expected: Value::from(1u32),
+            }]
+        );
+
+        assert_eq!(
+            record.data().get(&Tag::MISMATCHED_POSITIONS),
+            Some(&Value::from("2G1"))
+        );
+        assert_eq!(
+            record.data().get(&Tag::EDIT_DISTANCE),
+            Some(&Value::from(1))
+        );
+
+        Ok(())
+    }
+}
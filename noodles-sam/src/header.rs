@@ -85,6 +85,7 @@ use std::str::{self, FromStr};
 
 use bstr::BString;
 use indexmap::IndexMap;
+use noodles_core::ops;
 
 pub use self::programs::Programs;
 use self::record::value::{
@@ -405,6 +406,34 @@ impl Header {
     }
 }
 
+impl From<&Header> for ops::ReferenceSequences {
+    /// Converts the reference sequences of a SAM header to interval operation reference
+    /// sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::ReferenceSequences;
+    /// use noodles_sam as sam;
+    ///
+    /// let header: sam::Header = "@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq1\tLN:13\n".parse()?;
+    /// let reference_sequences = ReferenceSequences::from(&header);
+    ///
+    /// assert_eq!(reference_sequences.get_index_of(b"sq1"), Some(1));
+    /// assert_eq!(reference_sequences.get_index(1), Some((b"sq1".as_ref().into(), 13)));
+    /// # Ok::<(), sam::header::ParseError>(())
+    /// ```
+    fn from(header: &Header) -> Self {
+        header
+            .reference_sequences()
+            .iter()
+            .map(|(name, reference_sequence)| {
+                (name.clone(), usize::from(reference_sequence.length()))
+            })
+            .collect()
+    }
+}
+
 impl FromStr for Header {
     type Err = ParseError;
 
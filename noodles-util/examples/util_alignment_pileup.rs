@@ -0,0 +1,122 @@
+//! Prints a text pileup of a coordinate-sorted alignment file.
+//!
+//! Each line lists the reference sequence name, position, reference base, depth, read bases, and
+//! base quality scores, similar to the output of `samtools mpileup [--fasta-ref <fasta-src>]
+//! <src>`. Read starts and ends are not marked.
+
+use std::{
+    env,
+    io::{self, BufWriter, Write},
+};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_util::alignment::{
+    self,
+    iter::pileup::{self, Event, Indel},
+};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let fasta_src = args.next();
+
+    let mut reader_builder = alignment::io::reader::Builder::default();
+    let mut pileup_builder = pileup::Builder::default();
+
+    if let Some(fasta_src) = fasta_src {
+        let repository = fasta::io::indexed_reader::Builder::default()
+            .build_from_path(fasta_src)
+            .map(IndexedReader::new)
+            .map(fasta::Repository::new)?;
+
+        reader_builder = reader_builder.set_reference_sequence_repository(repository.clone());
+        pileup_builder = pileup_builder.set_reference_sequence_repository(repository);
+    }
+
+    let mut reader = reader_builder.build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = BufWriter::new(stdout);
+
+    for result in pileup_builder.build(&header, reader.records(&header)) {
+        let column = result?;
+
+        let (name, _) = header
+            .reference_sequences()
+            .get_index(column.reference_sequence_id())
+            .expect("invalid reference sequence ID");
+
+        let reference_base = column.reference_base().unwrap_or(b'N');
+
+        let mut bases = Vec::new();
+        let mut quality_scores = Vec::new();
+
+        for entry in column.entries() {
+            match entry.event() {
+                Event::Base {
+                    base,
+                    quality_score,
+                    indel,
+                    ..
+                } => {
+                    let base = if base.eq_ignore_ascii_case(&reference_base) {
+                        if entry.is_reverse_complemented() {
+                            b','
+                        } else {
+                            b'.'
+                        }
+                    } else if entry.is_reverse_complemented() {
+                        base.to_ascii_lowercase()
+                    } else {
+                        base.to_ascii_uppercase()
+                    };
+
+                    bases.push(base);
+
+                    match indel {
+                        Some(Indel::Insertion(inserted_bases)) => {
+                            write!(bases, "+{}", inserted_bases.len())?;
+                            bases.extend(inserted_bases);
+                        }
+                        Some(Indel::Deletion(len)) => {
+                            write!(bases, "-{len}")?;
+                            bases.extend((0..*len).map(|_| b'N'));
+                        }
+                        None => {}
+                    }
+
+                    quality_scores.push(quality_score.unwrap_or(0) + b'!');
+                }
+                Event::Deletion => {
+                    bases.push(b'*');
+                    quality_scores.push(b'!');
+                }
+                Event::Skip => {
+                    bases.push(if entry.is_reverse_complemented() {
+                        b'<'
+                    } else {
+                        b'>'
+                    });
+                    quality_scores.push(b'!');
+                }
+            }
+        }
+
+        write!(
+            writer,
+            "{name}\t{}\t{}\t{}\t",
+            column.position(),
+            char::from(reference_base),
+            column.depth()
+        )?;
+
+        writer.write_all(&bases)?;
+        writer.write_all(b"\t")?;
+        writer.write_all(&quality_scores)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
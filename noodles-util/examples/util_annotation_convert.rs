@@ -0,0 +1,40 @@
+//! Converts a GTF file to GFF3 or a GFF3 file to GTF.
+//!
+//! The input format is determined from the extension of the source (`.gtf`, `.gff`, or `.gff3`).
+//! The results are written to stdout.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+use noodles_gff as gff;
+use noodles_gtf as gtf;
+use noodles_util::annotation::convert;
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let extension = Path::new(&src).extension().and_then(|ext| ext.to_str());
+    let reader = File::open(&src).map(BufReader::new)?;
+    let stdout = io::stdout().lock();
+
+    match extension {
+        Some("gtf") => {
+            let mut reader = gtf::Reader::new(reader);
+            let mut writer = gff::io::Writer::new(stdout);
+            convert::gtf_to_gff(&mut reader, &mut writer)
+        }
+        Some("gff" | "gff3") => {
+            let mut reader = gff::io::Reader::new(reader);
+            let mut writer = gtf::Writer::new(stdout);
+            convert::gff_to_gtf(&mut reader, &mut writer)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid input format: expected .gtf, .gff, or .gff3",
+        )),
+    }
+}
@@ -2,3 +2,5 @@
 
 pub mod io;
 pub mod iter;
+pub mod merge;
+pub mod sort;
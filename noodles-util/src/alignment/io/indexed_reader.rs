@@ -1,6 +1,7 @@
 //! Indexed alignment reader.
 
 mod builder;
+mod regions;
 
 pub use self::builder::Builder;
 
@@ -108,4 +109,43 @@ where
 
         Ok(records)
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The index chunks of all regions are merged, and the file is read in a single sequential
+    /// pass. Each record that intersects at least one region is only returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_util::alignment;
+    ///
+    /// let mut reader = alignment::io::indexed_reader::Builder::default()
+    ///     .build_from_path("sample.bam")?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let regions = ["sq0:8-13".parse()?, "sq1:21-34".parse()?];
+    ///
+    /// for result in reader.query_regions(&header, &regions)? {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r> {
+        let intervals = regions::Intervals::new(header, regions)?;
+
+        let records: Box<dyn Iterator<Item = io::Result<Box<dyn Record>>>> = match self {
+            Self::Sam(reader) => Box::new(regions::query_sam(reader, header, intervals)?),
+            Self::Bam(reader) => Box::new(regions::query_bam(reader, header, intervals)?),
+            Self::Cram(reader) => Box::new(regions::query_cram(reader, header, intervals)?),
+        };
+
+        Ok(records)
+    }
 }
@@ -0,0 +1,497 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read, Seek, SeekFrom},
+    iter, vec,
+};
+
+use noodles_bam as bam;
+use noodles_bgzf as bgzf;
+use noodles_core::{region::Interval, Position, Region};
+use noodles_cram as cram;
+use noodles_csi::{self as csi, binning_index::merge_chunks, BinningIndex};
+use noodles_sam::{self as sam, alignment::Record};
+
+/// Inclusive intervals grouped by reference sequence ID.
+///
+/// The intervals of each reference sequence are sorted and non-overlapping.
+pub(super) struct Intervals(HashMap<usize, Vec<(Position, Position)>>);
+
+impl Intervals {
+    pub(super) fn new(header: &sam::Header, regions: &[Region]) -> io::Result<Self> {
+        let mut intervals: HashMap<usize, Vec<(Position, Position)>> = HashMap::new();
+
+        for region in regions {
+            let reference_sequence_id = header
+                .reference_sequences()
+                .get_index_of(region.name())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid reference sequence name",
+                    )
+                })?;
+
+            let interval = region.interval();
+            let start = interval.start().unwrap_or(Position::MIN);
+            let end = interval.end().unwrap_or(Position::MAX);
+
+            intervals
+                .entry(reference_sequence_id)
+                .or_default()
+                .push((start, end));
+        }
+
+        for reference_sequence_intervals in intervals.values_mut() {
+            *reference_sequence_intervals = merge_intervals(reference_sequence_intervals);
+        }
+
+        Ok(Self(intervals))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (usize, Interval)> + '_ {
+        self.0
+            .iter()
+            .flat_map(|(&reference_sequence_id, intervals)| {
+                intervals
+                    .iter()
+                    .map(move |&(start, end)| (reference_sequence_id, Interval::from(start..=end)))
+            })
+    }
+
+    fn intersects(&self, reference_sequence_id: usize, start: Position, end: Position) -> bool {
+        let Some(intervals) = self.0.get(&reference_sequence_id) else {
+            return false;
+        };
+
+        let i = intervals.partition_point(|&(_, interval_end)| interval_end < start);
+
+        intervals
+            .get(i)
+            .map(|&(interval_start, _)| interval_start <= end)
+            .unwrap_or(false)
+    }
+
+    fn intersects_record(&self, header: &sam::Header, record: &dyn Record) -> io::Result<bool> {
+        match (
+            record.reference_sequence_id(header).transpose()?,
+            record.alignment_start().transpose()?,
+            record.alignment_end().transpose()?,
+        ) {
+            (Some(id), Some(start), Some(end)) => Ok(self.intersects(id, start, end)),
+            _ => Ok(false),
+        }
+    }
+}
+
+fn merge_intervals(intervals: &[(Position, Position)]) -> Vec<(Position, Position)> {
+    let mut intervals = intervals.to_vec();
+    intervals.sort_unstable();
+
+    let mut merged_intervals: Vec<(Position, Position)> = Vec::with_capacity(intervals.len());
+
+    for (start, end) in intervals {
+        match merged_intervals.last_mut() {
+            Some((_, prev_end)) if start <= *prev_end => *prev_end = end.max(*prev_end),
+            _ => merged_intervals.push((start, end)),
+        }
+    }
+
+    merged_intervals
+}
+
+fn query_chunks(
+    index: &dyn BinningIndex,
+    intervals: &Intervals,
+) -> io::Result<Vec<csi::binning_index::index::reference_sequence::bin::Chunk>> {
+    let mut chunks = Vec::new();
+
+    for (reference_sequence_id, interval) in intervals.iter() {
+        chunks.extend(index.query(reference_sequence_id, interval)?);
+    }
+
+    Ok(merge_chunks(&chunks))
+}
+
+fn filter_records<'r, I>(
+    header: &'r sam::Header,
+    intervals: Intervals,
+    records: I,
+) -> impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r
+where
+    I: Iterator<Item = io::Result<Box<dyn Record>>> + 'r,
+{
+    records.filter_map(move |result| {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => return Some(Err(e)),
+        };
+
+        match intervals.intersects_record(header, record.as_ref()) {
+            Ok(true) => Some(Ok(record)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    })
+}
+
+pub(super) fn query_bam<'r, 'h: 'r, R>(
+    reader: &'r mut bam::io::IndexedReader<bgzf::Reader<R>>,
+    header: &'h sam::Header,
+    intervals: Intervals,
+) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r>
+where
+    R: Read + Seek + 'r,
+{
+    let chunks = query_chunks(reader.index(), &intervals)?;
+    let mut reader = bam::io::Reader::from(csi::io::Query::new(reader.get_mut(), chunks));
+    let mut record = bam::Record::default();
+
+    let records = iter::from_fn(move || match reader.read_record(&mut record) {
+        Ok(0) => None,
+        Ok(_) => Some(Ok(Box::new(record.clone()) as Box<dyn Record>)),
+        Err(e) => Some(Err(e)),
+    });
+
+    Ok(filter_records(header, intervals, records))
+}
+
+pub(super) fn query_sam<'r, 'h: 'r, R>(
+    reader: &'r mut sam::io::IndexedReader<R>,
+    header: &'h sam::Header,
+    intervals: Intervals,
+) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r>
+where
+    R: Read + Seek + 'r,
+{
+    let chunks = query_chunks(reader.index(), &intervals)?;
+    let mut reader = sam::io::Reader::from(csi::io::Query::new(reader.get_mut(), chunks));
+    let mut record = sam::Record::default();
+
+    let records = iter::from_fn(move || match reader.read_record(&mut record) {
+        Ok(0) => None,
+        Ok(_) => Some(Ok(Box::new(record.clone()) as Box<dyn Record>)),
+        Err(e) => Some(Err(e)),
+    });
+
+    Ok(filter_records(header, intervals, records))
+}
+
+pub(super) fn query_cram<'r, 'h: 'r, R>(
+    reader: &'r mut cram::io::IndexedReader<R>,
+    header: &'h sam::Header,
+    intervals: Intervals,
+) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + 'r>
+where
+    R: Read + Seek + 'r,
+{
+    let mut offsets: Vec<_> = reader
+        .index()
+        .iter()
+        .filter(|index_record| {
+            match (
+                index_record.reference_sequence_id(),
+                index_record.alignment_start(),
+            ) {
+                (Some(id), Some(start)) => {
+                    let span = index_record.alignment_span().max(1);
+                    let end = start.checked_add(span - 1).unwrap_or(Position::MAX);
+                    intervals.intersects(id, start, end)
+                }
+                _ => false,
+            }
+        })
+        .map(|index_record| index_record.offset())
+        .collect();
+
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    let mut offsets = offsets.into_iter();
+    let mut records: vec::IntoIter<cram::Record> = Vec::new().into_iter();
+
+    Ok(iter::from_fn(move || loop {
+        if let Some(record) = records.next() {
+            let is_in_intervals = match (
+                record.reference_sequence_id(),
+                record.alignment_start(),
+                record.alignment_end(),
+            ) {
+                (Some(id), Some(start), Some(end)) => intervals.intersects(id, start, end),
+                _ => false,
+            };
+
+            if is_in_intervals {
+                return Some(
+                    record
+                        .try_into_alignment_record(header)
+                        .map(|record| Box::new(record) as Box<dyn Record>),
+                );
+            }
+        } else {
+            let offset = offsets.next()?;
+
+            match read_container_records(reader, header, offset) {
+                Ok(container_records) => records = container_records.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }))
+}
+
+fn read_container_records<R>(
+    reader: &mut cram::io::IndexedReader<R>,
+    header: &sam::Header,
+    offset: u64,
+) -> io::Result<Vec<cram::Record>>
+where
+    R: Read + Seek,
+{
+    reader.get_mut().seek(SeekFrom::Start(offset))?;
+
+    let container = reader
+        .read_data_container()?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing data container"))?;
+
+    let compression_header = container.compression_header();
+    let mut records = Vec::new();
+
+    for slice in container.slices() {
+        let mut slice_records = slice.records(compression_header)?;
+
+        slice.resolve_records(
+            reader.reference_sequence_repository(),
+            header,
+            compression_header,
+            &mut slice_records,
+        )?;
+
+        records.extend(slice_records);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, num::NonZeroUsize};
+
+    use noodles_csi::binning_index::{index::reference_sequence::bin::Chunk, Indexer};
+    use noodles_sam::{
+        alignment::{
+            io::Write,
+            record::{
+                cigar::{op::Kind, Op},
+                Flags,
+            },
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
+    use super::*;
+    use crate::alignment::io::IndexedReader;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(55)?),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(55)?),
+            )
+            .build())
+    }
+
+    fn build_records() -> Result<Vec<RecordBuf>, noodles_core::position::TryFromIntError> {
+        [(0, 1), (0, 8), (0, 21), (1, 5), (1, 34)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (reference_sequence_id, alignment_start))| {
+                Ok(RecordBuf::builder()
+                    .set_name(format!("r{i}").as_bytes().into())
+                    .set_flags(Flags::empty())
+                    .set_reference_sequence_id(reference_sequence_id)
+                    .set_alignment_start(Position::try_from(alignment_start)?)
+                    .set_cigar([Op::new(Kind::Match, 5)].into_iter().collect())
+                    .set_sequence(b"ACGTA".to_vec().into())
+                    .set_quality_scores(vec![45; 5].into())
+                    .build())
+            })
+            .collect()
+    }
+
+    // Queries overlapping and unordered regions and checks that each record is returned once and in
+    // order.
+    fn assert_query_regions<R>(
+        reader: &mut IndexedReader<R>,
+        header: &sam::Header,
+        records: &[RecordBuf],
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        R: Read + Seek,
+    {
+        let regions = [
+            "sq0:3-9".parse()?,
+            "sq0:9-10".parse()?,
+            "sq1:30-40".parse()?,
+            "sq0:1-2".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(header, &regions)?
+            .map(|result| {
+                result.and_then(|record| RecordBuf::try_from_alignment_record(header, &record))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [records[0].clone(), records[1].clone(), records[4].clone()];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_bam() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let records = build_records()?;
+
+        let mut writer = bam::io::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for record in &records {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        let src = writer.into_inner().finish()?;
+
+        let mut reader = bam::io::Reader::new(&src[..]);
+        reader.read_header()?;
+
+        let mut indexer = Indexer::default();
+        let mut record = bam::Record::default();
+        let mut chunk_start = reader.get_ref().virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let chunk_end = reader.get_ref().virtual_position();
+
+            let alignment_context = (
+                record.reference_sequence_id().transpose()?.unwrap(),
+                record.alignment_start().transpose()?.unwrap(),
+                Record::alignment_end(&record).transpose()?.unwrap(),
+                true,
+            );
+
+            indexer.add_record(Some(alignment_context), Chunk::new(chunk_start, chunk_end))?;
+
+            chunk_start = chunk_end;
+        }
+
+        let index: csi::Index = indexer.build(header.reference_sequences().len());
+
+        let mut reader = IndexedReader::Bam(bam::io::IndexedReader::new(Cursor::new(src), index));
+        assert_query_regions(&mut reader, &header, &records)
+    }
+
+    #[test]
+    fn test_query_bgzipped_sam() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let records = build_records()?;
+
+        let mut writer = sam::io::Writer::new(bgzf::Writer::new(Vec::new()));
+        writer.write_header(&header)?;
+
+        for record in &records {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        let src = writer.into_inner().finish()?;
+
+        let mut reader = sam::io::Reader::new(bgzf::Reader::new(&src[..]));
+        reader.read_header()?;
+
+        let mut indexer = Indexer::default();
+        let mut record = RecordBuf::default();
+        let mut chunk_start = reader.get_ref().virtual_position();
+
+        while reader.read_record_buf(&header, &mut record)? != 0 {
+            let chunk_end = reader.get_ref().virtual_position();
+
+            let alignment_context = (
+                record.reference_sequence_id().unwrap(),
+                record.alignment_start().unwrap(),
+                record.alignment_end().unwrap(),
+                true,
+            );
+
+            indexer.add_record(Some(alignment_context), Chunk::new(chunk_start, chunk_end))?;
+
+            chunk_start = chunk_end;
+        }
+
+        let index: csi::Index = indexer.build(header.reference_sequences().len());
+
+        let mut reader = IndexedReader::Sam(sam::io::IndexedReader::new(Cursor::new(src), index));
+        assert_query_regions(&mut reader, &header, &records)
+    }
+
+    #[test]
+    fn test_query_cram() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{env, fs, process};
+
+        use cram::io::writer::ReferenceSequenceMode;
+
+        let header = build_header()?;
+        let records = build_records()?;
+
+        let mut writer = cram::io::writer::Builder::default()
+            .set_reference_sequence_mode(ReferenceSequenceMode::None)
+            .build_with_writer(Vec::new());
+
+        writer.write_header(&header)?;
+
+        for record in &records {
+            writer.write_alignment_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+        let src = writer.get_ref().clone();
+
+        // `cram::index` only indexes files.
+        let dst = env::temp_dir().join(format!(
+            "noodles-util-regions-test_query_cram-{}.cram",
+            process::id()
+        ));
+
+        fs::write(&dst, &src)?;
+        let result = cram::index(&dst);
+        fs::remove_file(&dst)?;
+        let index = result?;
+
+        let mut reader = IndexedReader::Cram(cram::io::IndexedReader::new(Cursor::new(src), index));
+        assert_query_regions(&mut reader, &header, &records)
+    }
+
+    #[test]
+    fn test_merge_intervals() -> Result<(), noodles_core::position::TryFromIntError> {
+        let intervals = [
+            (Position::try_from(8)?, Position::try_from(13)?),
+            (Position::try_from(2)?, Position::try_from(5)?),
+            (Position::try_from(5)?, Position::try_from(7)?),
+            (Position::try_from(21)?, Position::try_from(34)?),
+        ];
+
+        let actual = merge_intervals(&intervals);
+
+        let expected = [
+            (Position::try_from(2)?, Position::try_from(7)?),
+            (Position::try_from(8)?, Position::try_from(13)?),
+            (Position::try_from(21)?, Position::try_from(34)?),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
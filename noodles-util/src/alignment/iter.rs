@@ -1,5 +1,6 @@
 //! Composable iterators for alignment records.
 
-mod pileup;
+mod depth;
+pub mod pileup;
 
-pub use self::pileup::Pileup as Depth;
+pub use self::{depth::Depth, pileup::Pileup};
@@ -1,328 +1,695 @@
-use std::{collections::VecDeque, io};
+//! Pileup iterator.
+
+mod builder;
+pub mod column;
+
+pub use self::{
+    builder::Builder,
+    column::{
+        entry::{Event, Indel},
+        Column, Entry,
+    },
+};
+
+use std::{collections::VecDeque, io, iter::Fuse, sync::Arc};
 
 use noodles_core::Position;
+use noodles_fasta as fasta;
 use noodles_sam::{
-    self as sam,
-    alignment::{record::Flags, Record},
+    alignment::{
+        record::{Flags, MappingQuality},
+        Record,
+    },
     Header,
 };
 
-type ActiveWindowRange = (Position, Position);
-
-#[derive(Debug)]
-enum State {
-    Empty,
-    Pile(ActiveWindowRange),
-    Pop(ActiveWindowRange),
-    Drain,
-    Done,
-}
-
 /// A pileup iterator.
 ///
-/// This takes an iterator of coordinate-sorted records and emits reference sequence column
-/// statistics.
+/// This takes an iterator of coordinate-sorted records and emits a column for each covered
+/// reference sequence position. Each column lists the reads that cover the position with their
+/// aligned base or event, base quality score, strand, mapping quality, and position in the read.
+///
+/// The records can span multiple reference sequences.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_util::alignment::{self, iter::Pileup};
+///
+/// let mut reader = alignment::io::reader::Builder::default().build_from_path("sample.bam")?;
+/// let header = reader.read_header()?;
+///
+/// for result in Pileup::new(&header, reader.records(&header)) {
+///     let column = result?;
+///     println!("{}\t{}", column.position(), column.depth());
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
 pub struct Pileup<'h, I> {
     header: &'h Header,
-    records: I,
-    state: State,
-    position: Position,
-    window: VecDeque<u64>,
-    next_record: Option<Box<dyn Record>>,
+    records: Fuse<I>,
+    excluded_flags: Flags,
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    reference_sequence_repository: Option<fasta::Repository>,
+    reference_sequence: Option<(usize, Option<fasta::record::Sequence>)>,
+    position: Option<(usize, Position)>,
+    active_reads: VecDeque<Read>,
+    next_read: Option<Read>,
+    last_read_position: Option<(usize, Position)>,
 }
 
 impl<'h, I> Pileup<'h, I>
 where
     I: Iterator<Item = io::Result<Box<dyn Record>>>,
 {
-    /// Creates a pileup iterator.
+    /// Creates a pileup iterator with default filters.
+    ///
+    /// The given iterator must be coordinate-sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, alignment::Record};
+    /// use noodles_util::alignment::iter::Pileup;
+    ///
+    /// let header = sam::Header::default();
+    /// let records: Vec<io::Result<Box<dyn Record>>> = Vec::new();
     ///
-    /// The given iterator must be coordinate-sorted on a single reference sequence.
+    /// let mut pileup = Pileup::new(&header, records.into_iter());
+    /// assert!(pileup.next().is_none());
+    /// ```
     pub fn new(header: &'h Header, records: I) -> Self {
-        Self {
-            header,
-            records,
-            state: State::Empty,
-            position: Position::MIN,
-            window: VecDeque::new(),
-            next_record: None,
-        }
+        Builder::default().build(header, records)
     }
 
-    fn initialize(&mut self) -> io::Result<Option<ActiveWindowRange>> {
-        if self.next_record.is_none() {
-            for result in &mut self.records {
-                let record = result?;
-                let flags = record.flags()?;
+    fn read_next_read(&mut self) -> io::Result<Option<Read>> {
+        for result in &mut self.records {
+            let record = result?;
+
+            let flags = record.flags()?;
 
-                if filter(flags) {
+            if flags.intersects(self.excluded_flags) {
+                continue;
+            }
+
+            let mapping_quality = record.mapping_quality().transpose()?;
+
+            if let Some(mapping_quality) = mapping_quality {
+                if mapping_quality.get() < self.min_mapping_quality {
                     continue;
                 }
+            }
 
-                self.next_record = Some(record);
+            if let Some(read) = Read::try_from_record(self.header, &record, flags, mapping_quality)?
+            {
+                let read_position = (read.reference_sequence_id, read.start);
+
+                if self
+                    .last_read_position
+                    .is_some_and(|last_read_position| read_position < last_read_position)
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "records are not coordinate-sorted",
+                    ));
+                }
 
-                break;
+                self.last_read_position = Some(read_position);
+
+                return Ok(Some(read));
             }
         }
 
-        if let Some(record) = self.next_record.take() {
-            let (_, start, end) = alignment_context(self.header, &record)?;
-            self.position = start;
-            pile_record(&mut self.window, start, end, &record)?;
-            Ok(Some((start, end)))
-        } else {
-            Ok(None)
-        }
+        Ok(None)
     }
 
-    fn pile_records(
-        &mut self,
-        active_window_range: ActiveWindowRange,
-    ) -> io::Result<Option<ActiveWindowRange>> {
-        let (mut active_window_start, mut active_window_end) = active_window_range;
-
-        if let Some(record) = self.next_record.take() {
-            let (_, start, end) = alignment_context(self.header, &record)?;
-            pile_record(&mut self.window, start, end, &record)?;
-            active_window_end = end.max(active_window_end);
+    fn fill_next_read(&mut self) -> io::Result<()> {
+        if self.next_read.is_none() {
+            self.next_read = self.read_next_read()?;
         }
 
-        while let Some(record) = self.records.next().transpose()? {
-            let flags = record.flags()?;
+        Ok(())
+    }
 
-            if filter(flags) {
-                continue;
-            }
+    fn pile_reads(&mut self, reference_sequence_id: usize, position: Position) -> io::Result<()> {
+        loop {
+            self.fill_next_read()?;
 
-            let (_, start, end) = alignment_context(self.header, &record)?;
+            let Some(read) = self.next_read.as_ref() else {
+                break;
+            };
 
-            if start > active_window_end {
-                self.next_record = Some(record);
-                return Ok(None);
-            } else if start > active_window_start {
-                self.next_record = Some(record);
-                active_window_start = start;
-                return Ok(Some((active_window_start, active_window_end)));
+            if (read.reference_sequence_id, read.start) > (reference_sequence_id, position) {
+                break;
             }
 
-            pile_record(&mut self.window, start, end, &record)?;
-            active_window_end = end.max(active_window_end);
+            // SAFETY: `self.next_read` is `Some`.
+            let read = self.next_read.take().unwrap();
+            self.active_reads.push_back(read);
         }
 
-        Ok(None)
+        Ok(())
     }
 
-    fn pop_front_full(&mut self) -> Option<(Position, u64)> {
-        let position = self.position;
-        let record = self.window.pop_front()?;
+    fn reference_base(
+        &mut self,
+        reference_sequence_id: usize,
+        position: Position,
+    ) -> io::Result<Option<u8>> {
+        let Some(repository) = self.reference_sequence_repository.as_ref() else {
+            return Ok(None);
+        };
+
+        let is_cached = matches!(
+            self.reference_sequence,
+            Some((id, _)) if id == reference_sequence_id
+        );
+
+        if !is_cached {
+            let (name, _) = self
+                .header
+                .reference_sequences()
+                .get_index(reference_sequence_id)
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+                })?;
+
+            let sequence = repository.get(name).transpose()?;
+            self.reference_sequence = Some((reference_sequence_id, sequence));
+        }
+
+        Ok(self
+            .reference_sequence
+            .as_ref()
+            .and_then(|(_, sequence)| sequence.as_ref())
+            .and_then(|sequence| sequence.get(position))
+            .copied())
+    }
+
+    fn pop_column(
+        &mut self,
+        reference_sequence_id: usize,
+        position: Position,
+    ) -> io::Result<Column> {
+        let reference_base = self.reference_base(reference_sequence_id, position)?;
+
+        let mut entries = Vec::with_capacity(self.active_reads.len());
+
+        for read in &mut self.active_reads {
+            let Some((read_position, event)) = read.events.pop_front() else {
+                continue;
+            };
+
+            if let Event::Base {
+                quality_score: Some(quality_score),
+                ..
+            } = event
+            {
+                if quality_score < self.min_base_quality {
+                    continue;
+                }
+            }
+
+            entries.push(Entry {
+                name: read.name.clone(),
+                flags: read.flags,
+                mapping_quality: read.mapping_quality,
+                read_position,
+                event,
+            });
+        }
 
-        self.position = self
-            .position
-            .checked_add(1)
-            .expect("attempt to add with overflow");
+        self.active_reads.retain(|read| !read.events.is_empty());
 
-        Some((position, record))
+        Ok(Column {
+            reference_sequence_id,
+            position,
+            reference_base,
+            entries,
+        })
     }
 }
 
-impl<'a, I> Iterator for Pileup<'a, I>
+impl<'h, I> Iterator for Pileup<'h, I>
 where
     I: Iterator<Item = io::Result<Box<dyn Record>>>,
 {
-    type Item = io::Result<(Position, u64)>;
+    type Item = io::Result<Column>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            self.state = match self.state {
-                State::Empty => match self.initialize() {
-                    Ok(None) => State::Done,
-                    Ok(Some(active_window_range)) => State::Pile(active_window_range),
-                    Err(e) => return Some(Err(e)),
-                },
-                State::Pile(active_window_range) => match self.pile_records(active_window_range) {
-                    Ok(None) => State::Drain,
-                    Ok(Some(next_active_window_range)) => State::Pop(next_active_window_range),
-                    Err(e) => return Some(Err(e)),
-                },
-                State::Pop((active_window_start, active_window_end)) => {
-                    if self.position < active_window_start {
-                        // SAFETY: active_window_start - self.position < self.window.len()
-                        let value = self.pop_front_full().unwrap();
-                        return Some(Ok(value));
-                    } else {
-                        State::Pile((active_window_start, active_window_end))
-                    }
+            if self.active_reads.is_empty() {
+                if let Err(e) = self.fill_next_read() {
+                    return Some(Err(e));
                 }
-                State::Drain => match self.pop_front_full() {
-                    Some(value) => return Some(Ok(value)),
-                    None => State::Empty,
-                },
-                State::Done => return None,
+
+                let read = self.next_read.as_ref()?;
+                self.position = Some((read.reference_sequence_id, read.start));
+            }
+
+            // SAFETY: The position is set when there are active or pending reads.
+            let (reference_sequence_id, position) = self.position.unwrap();
+
+            if let Err(e) = self.pile_reads(reference_sequence_id, position) {
+                return Some(Err(e));
+            }
+
+            let column = match self.pop_column(reference_sequence_id, position) {
+                Ok(column) => column,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let next_position = position
+                .checked_add(1)
+                .expect("attempt to add with overflow");
+
+            self.position = Some((reference_sequence_id, next_position));
+
+            if !column.entries.is_empty() {
+                return Some(Ok(column));
             }
         }
     }
 }
 
-fn alignment_context<R>(header: &Header, record: &R) -> io::Result<(usize, Position, Position)>
-where
-    R: Record,
-{
-    match (
-        record.reference_sequence_id(header).transpose()?,
-        record.alignment_start().transpose()?,
-        record.alignment_end().transpose()?,
-    ) {
-        (Some(id), Some(start), Some(end)) => Ok((id, start, end)),
-        _ => Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "missing reference sequence ID or alignment start",
-        )),
-    }
+struct Read {
+    name: Option<Arc<[u8]>>,
+    flags: Flags,
+    mapping_quality: Option<MappingQuality>,
+    reference_sequence_id: usize,
+    start: Position,
+    events: VecDeque<(usize, Event)>,
 }
 
-fn filter(flags: Flags) -> bool {
-    flags.is_unmapped() || flags.is_secondary() || flags.is_qc_fail() || flags.is_duplicate()
-}
+impl Read {
+    fn try_from_record(
+        header: &Header,
+        record: &dyn Record,
+        flags: Flags,
+        mapping_quality: Option<MappingQuality>,
+    ) -> io::Result<Option<Self>> {
+        let (Some(reference_sequence_id), Some(start)) = (
+            record.reference_sequence_id(header).transpose()?,
+            record.alignment_start().transpose()?,
+        ) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing reference sequence ID or alignment start",
+            ));
+        };
 
-fn pile_record<R>(
-    window: &mut VecDeque<u64>,
-    start: Position,
-    end: Position,
-    record: &R,
-) -> io::Result<()>
-where
-    R: Record,
-{
-    let span = usize::from(end) - usize::from(start) + 1;
+        let sequence: Vec<_> = record.sequence().iter().collect();
+        let quality_scores: Vec<_> = record.quality_scores().iter().collect();
+        let events = build_events(&record.cigar(), &sequence, &quality_scores)?;
 
-    if span > window.len() {
-        window.resize(span, 0);
-    }
+        if events.is_empty() {
+            return Ok(None);
+        }
+
+        let name = record.name().map(|name| Arc::from(name.as_bytes()));
 
-    let cigar = record.cigar();
-    pile(window, start, start, &cigar)
+        Ok(Some(Self {
+            name,
+            flags,
+            mapping_quality,
+            reference_sequence_id,
+            start,
+            events,
+        }))
+    }
 }
 
-fn pile<C>(
-    window: &mut VecDeque<u64>,
-    offset: Position,
-    start: Position,
+fn build_events<C>(
     cigar: &C,
-) -> io::Result<()>
+    sequence: &[u8],
+    quality_scores: &[u8],
+) -> io::Result<VecDeque<(usize, Event)>>
 where
-    C: sam::alignment::record::Cigar,
+    C: noodles_sam::alignment::record::Cigar,
 {
-    use sam::alignment::record::cigar::op::Kind;
+    use noodles_sam::alignment::record::cigar::op::Kind;
+
+    const MISSING_BASE: u8 = b'N';
 
-    let offset = usize::from(offset) - 1;
-    let start = usize::from(start) - 1;
-    let mut i = start - offset;
+    fn set_indel(events: &mut VecDeque<(usize, Event)>, value: Indel) {
+        if let Some((_, Event::Base { indel, .. })) = events.back_mut() {
+            *indel = Some(value);
+        }
+    }
+
+    let mut events = VecDeque::new();
+    let mut read_position = 0;
+    let mut pending_insertion: Option<Vec<u8>> = None;
 
     for result in cigar.iter() {
         let op = result?;
+        let len = op.len();
 
         match op.kind() {
             Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
-                let end = i + op.len();
+                for i in read_position..read_position + len {
+                    let base = sequence.get(i).copied().unwrap_or(MISSING_BASE);
+                    let quality_score = quality_scores.get(i).copied();
+
+                    events.push_back((
+                        i,
+                        Event::Base {
+                            base,
+                            quality_score,
+                            indel: None,
+                            preceding_insertion: pending_insertion.take(),
+                        },
+                    ));
+                }
 
-                for depth in window.range_mut(i..end) {
-                    *depth += 1;
+                read_position += len;
+            }
+            Kind::Insertion => {
+                let end = read_position + len;
+                let bases = sequence
+                    .get(read_position..end)
+                    .map(|bases| bases.to_vec())
+                    .unwrap_or_else(|| vec![MISSING_BASE; len]);
+
+                // An insertion that does not follow an aligned base, e.g., after a soft clip or
+                // deletion, is attached to the next aligned base.
+                if matches!(events.back(), Some((_, Event::Base { .. }))) {
+                    set_indel(&mut events, Indel::Insertion(bases));
+                } else {
+                    pending_insertion.get_or_insert_with(Vec::new).extend(bases);
                 }
 
-                i = end;
+                read_position = end;
+            }
+            Kind::Deletion => {
+                set_indel(&mut events, Indel::Deletion(len));
+                events.extend((0..len).map(|_| (read_position, Event::Deletion)));
             }
-            Kind::Deletion | Kind::Skip => i += op.len(),
-            _ => {}
+            Kind::Skip => events.extend((0..len).map(|_| (read_position, Event::Skip))),
+            Kind::SoftClip => read_position += len,
+            Kind::HardClip | Kind::Pad => {}
         }
     }
 
-    Ok(())
+    Ok(events)
 }
 
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroUsize;
 
+    use noodles_sam::{
+        alignment::{
+            record::cigar::{op::Kind, Op},
+            record_buf::{Name, QualityScores, Sequence},
+            RecordBuf,
+        },
+        header::record::value::{map::ReferenceSequence, Map},
+    };
+
     use super::*;
-    use sam::alignment::RecordBuf;
+
+    fn build_header() -> Header {
+        Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(NonZeroUsize::MAX))
+            .add_reference_sequence("sq1", Map::<ReferenceSequence>::new(NonZeroUsize::MAX))
+            .build()
+    }
+
+    fn build_record(
+        name: &str,
+        reference_sequence_id: usize,
+        alignment_start: usize,
+        ops: &[(Kind, usize)],
+        sequence: &[u8],
+    ) -> Result<Box<dyn Record>, Box<dyn std::error::Error>> {
+        let record = RecordBuf::builder()
+            .set_name(Name::from(name.as_bytes()))
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(reference_sequence_id)
+            .set_alignment_start(Position::try_from(alignment_start)?)
+            .set_mapping_quality(MappingQuality::new(60).unwrap())
+            .set_cigar(ops.iter().map(|&(kind, len)| Op::new(kind, len)).collect())
+            .set_sequence(Sequence::from(sequence.to_vec()))
+            .set_quality_scores(QualityScores::from(vec![30; sequence.len()]))
+            .build();
+
+        Ok(Box::new(record))
+    }
+
+    fn summarize(column: &Column) -> (usize, usize, Vec<Event>) {
+        (
+            column.reference_sequence_id(),
+            usize::from(column.position()),
+            column
+                .entries()
+                .iter()
+                .map(|entry| entry.event().clone())
+                .collect(),
+        )
+    }
+
+    fn base(base: u8, indel: Option<Indel>) -> Event {
+        Event::Base {
+            base,
+            quality_score: Some(30),
+            indel,
+            preceding_insertion: None,
+        }
+    }
 
     #[test]
     fn test_next() -> Result<(), Box<dyn std::error::Error>> {
-        use sam::{
-            alignment::record::cigar::{op::Kind, Op},
-            header::record::value::{map::ReferenceSequence, Map},
-        };
-
-        // 1 2 3 4 5 6 7 8 9
-        //   [   ]
-        //   [     ]
-        //     [ ]
-        //       [ ]
-        //             [ ]
-        //             [   ]
-        let records: Vec<_> = [
-            (
+        // 1 2 3 4 5 6
+        //   A C - G T        r0: 2M1D2M
+        //     C G G          r1: 1M1I2M
+        // 1 2
+        // T T                r2: 2M (sq1)
+        let records = [
+            build_record(
+                "r0",
                 0,
-                Position::try_from(2)?,
-                [Op::new(Kind::Match, 3)].into_iter().collect(),
-            ),
-            (
-                0,
-                Position::try_from(2)?,
-                [Op::new(Kind::Match, 4)].into_iter().collect(),
-            ),
-            (
+                2,
+                &[(Kind::Match, 2), (Kind::Deletion, 1), (Kind::Match, 2)],
+                b"ACGT",
+            )?,
+            build_record(
+                "r1",
                 0,
-                Position::try_from(3)?,
-                [Op::new(Kind::Match, 2)].into_iter().collect(),
-            ),
+                3,
+                &[(Kind::Match, 1), (Kind::Insertion, 1), (Kind::Match, 2)],
+                b"CAGG",
+            )?,
+            build_record("r2", 1, 1, &[(Kind::Match, 2)], b"TT")?,
+        ];
+
+        let header = build_header();
+        let pileup = Pileup::new(&header, records.into_iter().map(Ok));
+        let actual: Vec<_> = pileup
+            .map(|result| result.map(|column| summarize(&column)))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (0, 2, vec![base(b'A', None)]),
             (
                 0,
-                Position::try_from(4)?,
-                [Op::new(Kind::Match, 2)].into_iter().collect(),
+                3,
+                vec![
+                    base(b'C', Some(Indel::Deletion(1))),
+                    base(b'C', Some(Indel::Insertion(b"A".to_vec()))),
+                ],
             ),
+            (0, 4, vec![Event::Deletion, base(b'G', None)]),
+            (0, 5, vec![base(b'G', None), base(b'G', None)]),
+            (0, 6, vec![base(b'T', None)]),
+            (1, 1, vec![base(b'T', None)]),
+            (1, 2, vec![base(b'T', None)]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_leading_insertion() -> Result<(), Box<dyn std::error::Error>> {
+        let records = [build_record(
+            "r0",
+            0,
+            2,
+            &[
+                (Kind::SoftClip, 2),
+                (Kind::Insertion, 1),
+                (Kind::Match, 1),
+                (Kind::Deletion, 1),
+                (Kind::Insertion, 2),
+                (Kind::Match, 1),
+            ],
+            b"NNTACGA",
+        )?];
+
+        let header = build_header();
+        let pileup = Pileup::new(&header, records.into_iter().map(Ok));
+        let actual: Vec<_> = pileup
+            .map(|result| result.map(|column| summarize(&column)))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
             (
                 0,
-                Position::try_from(7)?,
-                [Op::new(Kind::Match, 2)].into_iter().collect(),
+                2,
+                vec![Event::Base {
+                    base: b'A',
+                    quality_score: Some(30),
+                    indel: Some(Indel::Deletion(1)),
+                    preceding_insertion: Some(b"T".to_vec()),
+                }],
             ),
+            (0, 3, vec![Event::Deletion]),
             (
                 0,
-                Position::try_from(7)?,
-                [Op::new(Kind::Match, 3)].into_iter().collect(),
+                4,
+                vec![Event::Base {
+                    base: b'A',
+                    quality_score: Some(30),
+                    indel: None,
+                    preceding_insertion: Some(b"CG".to_vec()),
+                }],
             ),
-        ]
-        .into_iter()
-        .map(|(reference_sequence_id, position, cigar)| {
-            RecordBuf::builder()
-                .set_flags(Flags::empty())
-                .set_reference_sequence_id(reference_sequence_id)
-                .set_alignment_start(position)
-                .set_cigar(cigar)
-                .build()
-        })
-        .map(|record| Ok(Box::new(record) as Box<dyn Record>))
-        .collect();
+        ];
 
-        let header = Header::builder()
-            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(NonZeroUsize::MAX))
-            .build();
+        assert_eq!(actual, expected);
 
-        let pileup = Pileup::new(&header, records.into_iter());
-        let actual: Vec<_> = pileup.collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_read_positions_and_reference_bases() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let records = [build_record(
+            "r0",
+            0,
+            2,
+            &[
+                (Kind::SoftClip, 2),
+                (Kind::Match, 1),
+                (Kind::Deletion, 1),
+                (Kind::Match, 1),
+            ],
+            b"NNAC",
+        )?];
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"GATC".to_vec()),
+        )]);
+
+        let header = build_header();
+        let columns: Vec<_> = Builder::default()
+            .set_reference_sequence_repository(repository)
+            .build(&header, records.into_iter().map(Ok))
+            .collect::<io::Result<_>>()?;
+
+        let actual: Vec<_> = columns
+            .iter()
+            .map(|column| {
+                (
+                    column.reference_base(),
+                    column.entries()[0].read_position(),
+                    column.entries()[0].name().map(|name| name.to_vec()),
+                )
+            })
+            .collect();
 
         let expected = [
-            (Position::try_from(2)?, 2),
-            (Position::try_from(3)?, 3),
-            (Position::try_from(4)?, 4),
-            (Position::try_from(5)?, 2),
-            (Position::try_from(7)?, 2),
-            (Position::try_from(8)?, 2),
-            (Position::try_from(9)?, 1),
+            (Some(b'A'), 2, Some(b"r0".to_vec())),
+            (Some(b'T'), 3, Some(b"r0".to_vec())),
+            (Some(b'C'), 3, Some(b"r0".to_vec())),
         ];
 
         assert_eq!(actual, expected);
 
         Ok(())
     }
+
+    #[test]
+    fn test_next_with_filters() -> Result<(), Box<dyn std::error::Error>> {
+        let mut duplicate = RecordBuf::builder()
+            .set_flags(Flags::DUPLICATE)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::MIN)
+            .set_cigar([Op::new(Kind::Match, 1)].into_iter().collect())
+            .set_sequence(Sequence::from(b"A".to_vec()))
+            .build();
+
+        let low_mapping_quality = {
+            let mut record = duplicate.clone();
+            *record.flags_mut() = Flags::empty();
+            *record.mapping_quality_mut() = MappingQuality::new(5);
+            record
+        };
+
+        let low_base_quality = {
+            let mut record = low_mapping_quality.clone();
+            *record.mapping_quality_mut() = MappingQuality::new(60);
+            *record.quality_scores_mut() = QualityScores::from(vec![2]);
+            record
+        };
+
+        *duplicate.mapping_quality_mut() = MappingQuality::new(60);
+
+        let records: Vec<Box<dyn Record>> = vec![
+            Box::new(duplicate),
+            Box::new(low_mapping_quality),
+            Box::new(low_base_quality),
+        ];
+
+        let header = build_header();
+        let mut pileup = Builder::default()
+            .set_min_mapping_quality(10)
+            .set_min_base_quality(13)
+            .build(&header, records.into_iter().map(Ok));
+
+        assert!(pileup.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let records = [
+            build_record("r0", 0, 5, &[(Kind::Match, 2)], b"AC")?,
+            build_record("r1", 0, 2, &[(Kind::Match, 2)], b"AC")?,
+        ];
+
+        let header = build_header();
+        let mut pileup = Pileup::new(&header, records.into_iter().map(Ok));
+
+        assert!(matches!(
+            pileup.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        // The unsorted record follows a gap in coverage.
+        let records = [
+            build_record("r0", 0, 5, &[(Kind::Match, 2)], b"AC")?,
+            build_record("r1", 0, 2, &[(Kind::Match, 2)], b"AC")?,
+        ];
+
+        let result: io::Result<Vec<_>> =
+            Pileup::new(&header, records.into_iter().map(Ok)).collect();
+
+        assert!(matches!(result, Err(e) if e.kind() == io::ErrorKind::InvalidData));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Column>();
+    }
 }
@@ -0,0 +1,134 @@
+use std::{collections::VecDeque, io};
+
+use noodles_fasta as fasta;
+use noodles_sam::{
+    alignment::{record::Flags, Record},
+    Header,
+};
+
+use super::Pileup;
+
+/// A pileup iterator builder.
+#[derive(Debug)]
+pub struct Builder {
+    excluded_flags: Flags,
+    min_mapping_quality: u8,
+    min_base_quality: u8,
+    reference_sequence_repository: Option<fasta::Repository>,
+}
+
+impl Builder {
+    /// Sets the flags that exclude a record.
+    ///
+    /// A record is excluded if it has any of the given flags set. By default, unmapped,
+    /// secondary, QC fail, and duplicate records are excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::record::Flags;
+    /// use noodles_util::alignment::iter::pileup;
+    /// let builder = pileup::Builder::default().set_excluded_flags(Flags::UNMAPPED);
+    /// ```
+    pub fn set_excluded_flags(mut self, excluded_flags: Flags) -> Self {
+        self.excluded_flags = excluded_flags;
+        self
+    }
+
+    /// Sets the minimum mapping quality of a record.
+    ///
+    /// Records with a missing mapping quality are not excluded. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::iter::pileup;
+    /// let builder = pileup::Builder::default().set_min_mapping_quality(20);
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.min_mapping_quality = min_mapping_quality;
+        self
+    }
+
+    /// Sets the minimum base quality of an aligned base.
+    ///
+    /// Aligned bases with a lower quality score are not included in a column. Deleted and skipped
+    /// reference bases are never excluded. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::iter::pileup;
+    /// let builder = pileup::Builder::default().set_min_base_quality(13);
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: u8) -> Self {
+        self.min_base_quality = min_base_quality;
+        self
+    }
+
+    /// Sets the reference sequence repository.
+    ///
+    /// When set, each column includes its reference base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// use noodles_util::alignment::iter::pileup;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let builder = pileup::Builder::default().set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = Some(reference_sequence_repository);
+        self
+    }
+
+    /// Builds a pileup iterator from an iterator of coordinate-sorted records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, alignment::Record};
+    /// use noodles_util::alignment::iter::pileup;
+    ///
+    /// let header = sam::Header::default();
+    /// let records: Vec<io::Result<Box<dyn Record>>> = Vec::new();
+    ///
+    /// let mut pileup = pileup::Builder::default().build(&header, records.into_iter());
+    /// assert!(pileup.next().is_none());
+    /// ```
+    pub fn build<I>(self, header: &Header, records: I) -> Pileup<'_, I>
+    where
+        I: Iterator<Item = io::Result<Box<dyn Record>>>,
+    {
+        Pileup {
+            header,
+            records: records.fuse(),
+            excluded_flags: self.excluded_flags,
+            min_mapping_quality: self.min_mapping_quality,
+            min_base_quality: self.min_base_quality,
+            reference_sequence_repository: self.reference_sequence_repository,
+            reference_sequence: None,
+            position: None,
+            active_reads: VecDeque::new(),
+            next_read: None,
+            last_read_position: None,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            excluded_flags: Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+            min_mapping_quality: 0,
+            min_base_quality: 0,
+            reference_sequence_repository: None,
+        }
+    }
+}
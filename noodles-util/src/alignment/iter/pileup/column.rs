@@ -0,0 +1,47 @@
+//! Pileup column.
+
+pub mod entry;
+
+pub use self::entry::Entry;
+
+use noodles_core::Position;
+
+/// A pileup column.
+///
+/// A column is the set of reads that cover a single reference sequence position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Column {
+    pub(crate) reference_sequence_id: usize,
+    pub(crate) position: Position,
+    pub(crate) reference_base: Option<u8>,
+    pub(crate) entries: Vec<Entry>,
+}
+
+impl Column {
+    /// Returns the reference sequence ID.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the reference sequence position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the reference base.
+    ///
+    /// This is only set when the pileup has a reference sequence repository.
+    pub fn reference_base(&self) -> Option<u8> {
+        self.reference_base
+    }
+
+    /// Returns the entries.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns the number of entries.
+    pub fn depth(&self) -> usize {
+        self.entries.len()
+    }
+}
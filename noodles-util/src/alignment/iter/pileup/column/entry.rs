@@ -0,0 +1,71 @@
+//! Pileup column entry.
+
+mod event;
+
+pub use self::event::{Event, Indel};
+
+use std::sync::Arc;
+
+use noodles_sam::alignment::record::{Flags, MappingQuality};
+
+/// A pileup column entry.
+///
+/// An entry is a single read's contribution to a pileup column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub(crate) name: Option<Arc<[u8]>>,
+    pub(crate) flags: Flags,
+    pub(crate) mapping_quality: Option<MappingQuality>,
+    pub(crate) read_position: usize,
+    pub(crate) event: Event,
+}
+
+impl Entry {
+    /// Returns the read name.
+    pub fn name(&self) -> Option<&[u8]> {
+        self.name.as_deref()
+    }
+
+    /// Returns the read flags.
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    /// Returns whether the read is aligned to the reverse strand.
+    pub fn is_reverse_complemented(&self) -> bool {
+        self.flags.is_reverse_complemented()
+    }
+
+    /// Returns the read mapping quality.
+    pub fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality
+    }
+
+    /// Returns the 0-based position in the read.
+    ///
+    /// For a deleted or skipped reference base, this is the position of the next read base.
+    pub fn read_position(&self) -> usize {
+        self.read_position
+    }
+
+    /// Returns the event.
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Returns the read base, if the event is an aligned base.
+    pub fn base(&self) -> Option<u8> {
+        match self.event {
+            Event::Base { base, .. } => Some(base),
+            _ => None,
+        }
+    }
+
+    /// Returns the base quality score, if the event is an aligned base.
+    pub fn quality_score(&self) -> Option<u8> {
+        match self.event {
+            Event::Base { quality_score, .. } => quality_score,
+            _ => None,
+        }
+    }
+}
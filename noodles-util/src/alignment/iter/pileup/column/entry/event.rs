@@ -0,0 +1,29 @@
+/// A pileup entry event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// An aligned base.
+    Base {
+        /// The read base.
+        base: u8,
+        /// The base quality score, if available.
+        quality_score: Option<u8>,
+        /// The insertion or deletion that directly follows the base, if any.
+        indel: Option<Indel>,
+        /// Read bases inserted directly before the base that do not follow another aligned base,
+        /// if any, e.g., the insertion in `2S1I3M`.
+        preceding_insertion: Option<Vec<u8>>,
+    },
+    /// A deleted reference base.
+    Deletion,
+    /// A skipped reference base, e.g., an intron.
+    Skip,
+}
+
+/// An insertion or deletion following an aligned base.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Indel {
+    /// Inserted read bases.
+    Insertion(Vec<u8>),
+    /// The number of deleted reference bases.
+    Deletion(usize),
+}
@@ -0,0 +1,438 @@
+//! Sorted alignment record merging.
+
+use std::{collections::HashMap, io};
+
+use noodles_sam::{
+    self as sam,
+    alignment::{record::data::field::Tag, record_buf::data::field::Value, Record, RecordBuf},
+    header::record::value::{
+        map::{program, Program},
+        Map,
+    },
+};
+
+use super::sort::SortOrder;
+use crate::sort::merger::{Merger, Source};
+
+/// A merger of sorted alignment record inputs.
+///
+/// The headers of the inputs are reconciled into a single output header:
+///
+///   * reference sequences (`@SQ`) are unioned in order of first appearance, and reference
+///     sequences with the same name must have the same length;
+///   * read groups (`@RG`) and programs (`@PG`) are unioned, and records that share an ID but
+///     differ are renamed by appending a numeric suffix; and
+///   * comments (`@CO`) are unioned.
+///
+/// Records are then rewritten to use the reference sequence IDs, read group IDs (`RG`), and
+/// program IDs (`PG`) of the output header.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, alignment::Record};
+/// use noodles_util::alignment::{merge::Merge, sort::SortOrder};
+///
+/// let header_a = sam::Header::default();
+/// let records_a = Vec::<io::Result<Box<dyn Record>>>::new();
+///
+/// let header_b = sam::Header::default();
+/// let records_b = Vec::<io::Result<Box<dyn Record>>>::new();
+///
+/// let merge = Merge::new(
+///     SortOrder::Coordinate,
+///     vec![
+///         (&header_a, records_a.into_iter()),
+///         (&header_b, records_b.into_iter()),
+///     ],
+/// )?;
+///
+/// let header = merge.header().clone();
+///
+/// for result in merge {
+///     let record = result?;
+///     // ...
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Merge<'a> {
+    header: sam::Header,
+    merger: Merger<'a, RecordBuf>,
+}
+
+impl<'a> Merge<'a> {
+    /// Creates a merger from a list of headers and their sorted records.
+    ///
+    /// Each input must already be sorted by the given sort order.
+    pub fn new<I>(sort_order: SortOrder, inputs: Vec<(&'a sam::Header, I)>) -> io::Result<Self>
+    where
+        I: Iterator<Item = io::Result<Box<dyn Record>>> + 'a,
+    {
+        let headers: Vec<_> = inputs.iter().map(|(header, _)| *header).collect();
+        let (header, mappings) = reconcile_headers(&headers, sort_order)?;
+
+        let sources = inputs
+            .into_iter()
+            .zip(mappings)
+            .map(|((src_header, records), mapping)| {
+                Box::new(records.map(move |result| {
+                    result
+                        .and_then(|record| {
+                            RecordBuf::try_from_alignment_record(src_header, &record)
+                        })
+                        .map(|mut record| {
+                            mapping.apply(&mut record);
+                            record
+                        })
+                })) as Source<'a, RecordBuf>
+            })
+            .collect();
+
+        Ok(Self {
+            header,
+            merger: Merger::new(sources, move |a, b| sort_order.compare(a, b)),
+        })
+    }
+
+    /// Returns the reconciled output header.
+    pub fn header(&self) -> &sam::Header {
+        &self.header
+    }
+}
+
+impl<'a> Iterator for Merge<'a> {
+    type Item = io::Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.merger.next()
+    }
+}
+
+/// Reconciles a list of SAM headers into a single header.
+///
+/// See [`Merge`] for the rules used to combine headers.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use noodles_sam::{
+///     self as sam,
+///     header::record::value::{map::ReferenceSequence, Map},
+/// };
+/// use noodles_util::alignment::{merge, sort::SortOrder};
+///
+/// let header_a = sam::Header::builder()
+///     .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?))
+///     .build();
+///
+/// let header_b = sam::Header::builder()
+///     .add_reference_sequence("sq1", Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?))
+///     .build();
+///
+/// let header = merge::merge_headers(&[&header_a, &header_b], SortOrder::Coordinate)?;
+/// assert_eq!(header.reference_sequences().len(), 2);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn merge_headers(headers: &[&sam::Header], sort_order: SortOrder) -> io::Result<sam::Header> {
+    reconcile_headers(headers, sort_order).map(|(header, _)| header)
+}
+
+#[derive(Default)]
+struct Mapping {
+    reference_sequence_ids: Vec<usize>,
+    read_group_ids: HashMap<Vec<u8>, Vec<u8>>,
+    program_ids: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl Mapping {
+    fn apply(&self, record: &mut RecordBuf) {
+        let remap_id = |id: &mut Option<usize>| {
+            if let Some(i) = id {
+                if let Some(j) = self.reference_sequence_ids.get(*i) {
+                    *i = *j;
+                }
+            }
+        };
+
+        remap_id(record.reference_sequence_id_mut());
+        remap_id(record.mate_reference_sequence_id_mut());
+
+        let data = record.data_mut();
+
+        for (tag, ids) in [
+            (Tag::READ_GROUP, &self.read_group_ids),
+            (Tag::PROGRAM, &self.program_ids),
+        ] {
+            if let Some(Value::String(value)) = data.get_mut(&tag) {
+                if let Some(id) = ids.get(value.as_slice()) {
+                    *value = id.clone().into();
+                }
+            }
+        }
+    }
+}
+
+fn reconcile_headers(
+    headers: &[&sam::Header],
+    sort_order: SortOrder,
+) -> io::Result<(sam::Header, Vec<Mapping>)> {
+    let mut header = sam::Header::default();
+    let mut mappings = Vec::with_capacity(headers.len());
+
+    *header.header_mut() = headers.iter().find_map(|h| h.header()).cloned();
+    sort_order.update_header(&mut header);
+
+    for src in headers {
+        let mut mapping = Mapping::default();
+
+        for (name, reference_sequence) in src.reference_sequences() {
+            let reference_sequences = header.reference_sequences_mut();
+
+            let id = match reference_sequences.get_full(name) {
+                Some((id, _, dst)) if dst.length() == reference_sequence.length() => id,
+                Some(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("reference sequence length mismatch: {name}"),
+                    ))
+                }
+                None => {
+                    reference_sequences.insert(name.clone(), reference_sequence.clone());
+                    reference_sequences.len() - 1
+                }
+            };
+
+            mapping.reference_sequence_ids.push(id);
+        }
+
+        if sort_order == SortOrder::Coordinate
+            && !mapping
+                .reference_sequence_ids
+                .windows(2)
+                .all(|ids| ids[0] < ids[1])
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reference sequence order is incompatible with other inputs",
+            ));
+        }
+
+        for (id, read_group) in src.read_groups() {
+            let read_groups = header.read_groups_mut();
+            let new_id = candidate_id(id, read_group, |id| read_groups.get(id));
+
+            if new_id != id.as_slice() {
+                mapping.read_group_ids.insert(id.to_vec(), new_id.clone());
+            }
+
+            read_groups.insert(new_id.into(), read_group.clone());
+        }
+
+        let mut programs: Vec<(Vec<u8>, Map<Program>)> = src
+            .programs()
+            .as_ref()
+            .iter()
+            .map(|(id, map)| (id.to_vec(), map.clone()))
+            .collect();
+
+        // Renaming an ID also renames references to it in previous program IDs, which may in
+        // turn make another program in this input conflict. This repeats until it is stable.
+        loop {
+            let mut changed = false;
+
+            for (id, map) in &mut programs {
+                rename_previous_program_id(map, &mapping.program_ids);
+
+                let dst = header.programs().as_ref();
+                let new_id = candidate_id(id, map, |id| dst.get(id));
+
+                if mapping.program_ids.get(id.as_slice()) != Some(&new_id)
+                    && new_id != id.as_slice()
+                {
+                    mapping.program_ids.insert(id.clone(), new_id);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        for (id, map) in programs {
+            let new_id = mapping.program_ids.get(&id).cloned().unwrap_or(id);
+            header.programs_mut().as_mut().insert(new_id.into(), map);
+        }
+
+        for comment in src.comments() {
+            if !header.comments().contains(comment) {
+                header.add_comment(comment.clone());
+            }
+        }
+
+        mappings.push(mapping);
+    }
+
+    Ok((header, mappings))
+}
+
+fn rename_previous_program_id(map: &mut Map<Program>, program_ids: &HashMap<Vec<u8>, Vec<u8>>) {
+    let other_fields = map.other_fields_mut();
+
+    if let Some(previous_id) = other_fields.get(&program::tag::PREVIOUS_PROGRAM_ID) {
+        if let Some(new_id) = program_ids.get(previous_id.as_slice()) {
+            other_fields.insert(program::tag::PREVIOUS_PROGRAM_ID, new_id.clone().into());
+        }
+    }
+}
+
+/// Returns the ID to use for a record in the destination map.
+///
+/// This is the given ID if it is unused or maps to an identical record. Otherwise, the first
+/// available ID with a numeric suffix (`-1`, `-2`, etc.) is used.
+fn candidate_id<'v, V, F>(id: &[u8], value: &V, get: F) -> Vec<u8>
+where
+    V: PartialEq + 'v,
+    F: Fn(&[u8]) -> Option<&'v V>,
+{
+    let mut candidate = id.to_vec();
+    let mut n = 0;
+
+    loop {
+        match get(&candidate) {
+            Some(v) if v != value => {
+                n += 1;
+                candidate = format!("{}-{n}", String::from_utf8_lossy(id)).into_bytes();
+            }
+            _ => return candidate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_core::Position;
+    use noodles_sam::header::record::value::map::{read_group, ReadGroup, ReferenceSequence};
+
+    use super::*;
+
+    #[test]
+    fn test_reconcile_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let rg_a = Map::<ReadGroup>::builder()
+            .insert(read_group::tag::SAMPLE, "a")
+            .build()?;
+        let rg_b = Map::<ReadGroup>::builder()
+            .insert(read_group::tag::SAMPLE, "b")
+            .build()?;
+
+        let header_a = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .add_read_group("rg0", rg_a.clone())
+            .add_program("pg0", Map::default())
+            .add_comment("noodles")
+            .build();
+
+        let header_b = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+            )
+            .add_read_group("rg0", rg_b.clone())
+            .add_program("pg0", Map::default())
+            .add_comment("noodles")
+            .build();
+
+        let (header, mappings) = reconcile_headers(&[&header_a, &header_b], SortOrder::Coordinate)?;
+
+        let names: Vec<_> = header.reference_sequences().keys().collect();
+        assert_eq!(names, ["sq0", "sq1"]);
+
+        let read_groups: Vec<_> = header.read_groups().iter().collect();
+        assert_eq!(read_groups.len(), 2);
+        assert_eq!(read_groups[0], (&"rg0".into(), &rg_a));
+        assert_eq!(read_groups[1], (&"rg0-1".into(), &rg_b));
+
+        assert_eq!(header.programs().as_ref().len(), 1);
+        assert_eq!(header.comments().len(), 1);
+
+        assert_eq!(mappings[1].reference_sequence_ids, [0, 1]);
+        assert_eq!(
+            mappings[1].read_group_ids.get(&b"rg0"[..]),
+            Some(&b"rg0-1".to_vec())
+        );
+
+        let mut record = RecordBuf::builder()
+            .set_reference_sequence_id(1)
+            .set_alignment_start(Position::MIN)
+            .set_data(
+                [(Tag::READ_GROUP, Value::from("rg0"))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        mappings[1].apply(&mut record);
+
+        assert_eq!(
+            record.data().get(&Tag::READ_GROUP),
+            Some(&Value::from("rg0-1"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reconcile_headers_with_invalid_reference_sequences(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header_a = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        let header_b = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+            )
+            .build();
+
+        assert!(matches!(
+            reconcile_headers(&[&header_a, &header_b], SortOrder::Coordinate),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let header_c = sam::Header::builder()
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(13)?),
+            )
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(8)?),
+            )
+            .build();
+
+        assert!(matches!(
+            reconcile_headers(&[&header_a, &header_c], SortOrder::Coordinate),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(reconcile_headers(&[&header_a, &header_c], SortOrder::QueryName).is_ok());
+
+        Ok(())
+    }
+}
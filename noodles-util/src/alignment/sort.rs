@@ -0,0 +1,263 @@
+//! External-memory alignment record sorting.
+//!
+//! Records are buffered in memory up to a chunk size. Each full buffer is sorted and written to a
+//! temporary BAM file, and the sorted chunks are then merged.
+
+mod builder;
+mod chunk;
+mod sort_order;
+
+pub use self::{builder::Builder, sort_order::SortOrder};
+
+use std::{io, mem, path::PathBuf};
+
+use noodles_sam::{
+    self as sam,
+    alignment::{Record, RecordBuf},
+};
+
+use crate::sort::{
+    chunk::Chunk,
+    merger::{Merger, Source},
+};
+
+/// An alignment record sorter.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_sam::{self as sam, alignment::RecordBuf};
+/// use noodles_util::alignment::sort;
+///
+/// let header = sam::Header::default();
+/// let mut sorter = sort::Builder::default().build(&header);
+///
+/// sorter.add_record_buf(RecordBuf::default())?;
+///
+/// let header = sorter.header().clone();
+///
+/// for result in sorter.finish()? {
+///     let record = result?;
+///     // ...
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Sorter {
+    header: sam::Header,
+    sort_order: SortOrder,
+    chunk_size: usize,
+    temp_dir: PathBuf,
+    buf: Vec<RecordBuf>,
+    chunks: Vec<Chunk>,
+}
+
+impl Sorter {
+    /// Returns the output header.
+    ///
+    /// This is the input header with an updated sort order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::sort;
+    ///
+    /// let header = sam::Header::default();
+    /// let sorter = sort::Builder::default().build(&header);
+    ///
+    /// assert!(sorter.header().header().is_some());
+    /// ```
+    pub fn header(&self) -> &sam::Header {
+        &self.header
+    }
+
+    /// Adds an alignment record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::sort;
+    ///
+    /// let header = sam::Header::default();
+    /// let mut sorter = sort::Builder::default().build(&header);
+    ///
+    /// let record = RecordBuf::default();
+    /// sorter.add_record(&record)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn add_record<R>(&mut self, record: &R) -> io::Result<()>
+    where
+        R: Record,
+    {
+        let record = RecordBuf::try_from_alignment_record(&self.header, record)?;
+        self.add_record_buf(record)
+    }
+
+    /// Adds an alignment record buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::sort;
+    ///
+    /// let header = sam::Header::default();
+    /// let mut sorter = sort::Builder::default().build(&header);
+    /// sorter.add_record_buf(RecordBuf::default())?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn add_record_buf(&mut self, record: RecordBuf) -> io::Result<()> {
+        self.buf.push(record);
+
+        if self.buf.len() >= self.chunk_size {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    /// Sorts the remaining records and returns an iterator over all records in sort order.
+    ///
+    /// Temporary files are removed when the returned iterator is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::sort;
+    ///
+    /// let header = sam::Header::default();
+    /// let sorter = sort::Builder::default().build(&header);
+    ///
+    /// let mut records = sorter.finish()?;
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<Sorted> {
+        self.sort_buf();
+
+        let mut sources = Vec::with_capacity(self.chunks.len() + 1);
+
+        for chunk in &self.chunks {
+            sources.push(chunk::records(chunk, &self.header)?);
+        }
+
+        let buf = mem::take(&mut self.buf);
+        sources.push(Box::new(buf.into_iter().map(Ok)) as Source<'static, RecordBuf>);
+
+        let sort_order = self.sort_order;
+
+        Ok(Sorted {
+            merger: Merger::new(sources, move |a, b| sort_order.compare(a, b)),
+            _chunks: mem::take(&mut self.chunks),
+        })
+    }
+
+    fn sort_buf(&mut self) {
+        let sort_order = self.sort_order;
+        self.buf.sort_by(|a, b| sort_order.compare(a, b));
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        self.sort_buf();
+        let chunk = chunk::write(&self.temp_dir, &self.header, &self.buf)?;
+        self.chunks.push(chunk);
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+/// An iterator over sorted alignment records.
+///
+/// This is created by calling [`Sorter::finish`].
+pub struct Sorted {
+    merger: Merger<'static, RecordBuf>,
+    _chunks: Vec<Chunk>,
+}
+
+impl Iterator for Sorted {
+    type Item = io::Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.merger.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, num::NonZeroUsize};
+
+    use noodles_core::Position;
+    use noodles_sam::{
+        alignment::record::{
+            cigar::{op::Kind, Op},
+            Flags,
+        },
+        header::record::value::{
+            map::{header::tag, ReferenceSequence},
+            Map,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_finish_with_chunks() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = env::temp_dir().join(format!("noodles-util-sort-{}", std::process::id()));
+        fs::create_dir_all(&temp_dir)?;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(144)?),
+            )
+            .build();
+
+        let mut sorter = Builder::default()
+            .set_chunk_size(NonZeroUsize::try_from(3)?)
+            .set_temp_dir(&temp_dir)
+            .build(&header);
+
+        let positions = [55, 8, 89, 1, 34, 13, 21, 2, 5, 3];
+
+        for &position in &positions {
+            let record = RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(position)?)
+                .set_cigar([Op::new(Kind::Match, 1)].into_iter().collect())
+                .build();
+
+            sorter.add_record(&record)?;
+        }
+
+        let sort_order = sorter
+            .header()
+            .header()
+            .and_then(|hd| hd.other_fields().get(&tag::SORT_ORDER))
+            .cloned();
+        assert_eq!(sort_order, Some("coordinate".into()));
+
+        let records = sorter.finish()?;
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 3);
+
+        let actual: Vec<_> = records
+            .map(|result| result.map(|record| record.alignment_start().map(usize::from)))
+            .collect::<io::Result<_>>()?;
+
+        let mut expected: Vec<_> = positions.iter().copied().map(Some).collect();
+        expected.sort_unstable();
+
+        assert_eq!(actual, expected);
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 0);
+
+        fs::remove_dir(&temp_dir)?;
+
+        Ok(())
+    }
+}
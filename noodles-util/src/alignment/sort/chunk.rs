@@ -0,0 +1,54 @@
+use std::{io, iter, path::Path};
+
+use noodles_bam as bam;
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+use noodles_sam::{
+    self as sam,
+    alignment::{io::Write, RecordBuf},
+};
+
+use crate::sort::{chunk::Chunk, merger::Source};
+
+/// Writes sorted records to a temporary BAM file.
+pub(super) fn write(
+    temp_dir: &Path,
+    header: &sam::Header,
+    records: &[RecordBuf],
+) -> io::Result<Chunk> {
+    let (chunk, file) = Chunk::create(temp_dir, "bam")?;
+
+    let inner = bgzf::writer::Builder::default()
+        .set_compression_level(CompressionLevel::FAST)
+        .build_with_writer(file);
+
+    let mut writer = bam::io::Writer::from(inner);
+    writer.write_header(header)?;
+
+    for record in records {
+        writer.write_alignment_record(header, record)?;
+    }
+
+    writer.try_finish()?;
+
+    Ok(chunk)
+}
+
+/// Returns an iterator over the records in a temporary BAM file.
+pub(super) fn records(
+    chunk: &Chunk,
+    header: &sam::Header,
+) -> io::Result<Source<'static, RecordBuf>> {
+    let mut reader = chunk.open().map(bam::io::Reader::new)?;
+    reader.read_header()?;
+
+    let header = header.clone();
+    let mut record = RecordBuf::default();
+
+    Ok(Box::new(iter::from_fn(move || {
+        match reader.read_record_buf(&header, &mut record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(record.clone())),
+            Err(e) => Some(Err(e)),
+        }
+    })))
+}
@@ -0,0 +1,279 @@
+use std::cmp::Ordering;
+
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        record::{data::field::Tag, Flags},
+        record_buf::data::field::Value,
+        RecordBuf,
+    },
+    header::record::value::map::header::{sort_order, tag},
+};
+
+/// An alignment record sort order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortOrder {
+    /// Sort by reference sequence, alignment start, and strand.
+    ///
+    /// Unmapped records without a reference sequence are placed last.
+    Coordinate,
+    /// Sort by name using a natural ordering, i.e., runs of digits are compared numerically.
+    ///
+    /// Ties are broken by placing first segments before last segments.
+    QueryName,
+    /// Sort by name using a lexicographic ordering.
+    ///
+    /// Ties are broken by placing first segments before last segments.
+    LexicographicQueryName,
+    /// Sort by a data field value and then by coordinate.
+    ///
+    /// Records missing the field are placed first.
+    Tag(Tag),
+}
+
+impl SortOrder {
+    /// Compares two records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use noodles_sam::alignment::{record_buf::Name, RecordBuf};
+    /// use noodles_util::alignment::sort::SortOrder;
+    ///
+    /// let a = RecordBuf::builder().set_name(Name::from(&b"r2"[..])).build();
+    /// let b = RecordBuf::builder().set_name(Name::from(&b"r10"[..])).build();
+    ///
+    /// assert_eq!(SortOrder::QueryName.compare(&a, &b), Ordering::Less);
+    /// assert_eq!(SortOrder::LexicographicQueryName.compare(&a, &b), Ordering::Greater);
+    /// ```
+    pub fn compare(&self, a: &RecordBuf, b: &RecordBuf) -> Ordering {
+        match self {
+            Self::Coordinate => compare_coordinates(a, b),
+            Self::QueryName => compare_names(a, b, compare_natural),
+            Self::LexicographicQueryName => compare_names(a, b, |a, b| a.cmp(b)),
+            Self::Tag(tag) => compare_data_field_values(a.data().get(tag), b.data().get(tag))
+                .then_with(|| compare_coordinates(a, b)),
+        }
+    }
+
+    /// Sets the sort order (`SO`) and subsort order (`SS`) of a SAM header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::record::value::map::header::tag};
+    /// use noodles_util::alignment::sort::SortOrder;
+    ///
+    /// let mut header = sam::Header::default();
+    /// SortOrder::Coordinate.update_header(&mut header);
+    ///
+    /// let sort_order = header
+    ///     .header()
+    ///     .and_then(|hd| hd.other_fields().get(&tag::SORT_ORDER));
+    /// assert_eq!(sort_order.map(|value| value.as_ref()), Some(&b"coordinate"[..]));
+    /// ```
+    pub fn update_header(&self, header: &mut sam::Header) {
+        let (sort_order, subsort_order): (&[u8], Option<&[u8]>) = match self {
+            Self::Coordinate => (sort_order::COORDINATE, None),
+            Self::QueryName => (sort_order::QUERY_NAME, Some(b"queryname:natural")),
+            Self::LexicographicQueryName => {
+                (sort_order::QUERY_NAME, Some(b"queryname:lexicographical"))
+            }
+            Self::Tag(_) => (sort_order::UNKNOWN, None),
+        };
+
+        let other_fields = header
+            .header_mut()
+            .get_or_insert_with(Default::default)
+            .other_fields_mut();
+
+        other_fields.insert(tag::SORT_ORDER, sort_order.into());
+
+        if let Some(subsort_order) = subsort_order {
+            other_fields.insert(tag::SUBSORT_ORDER, subsort_order.into());
+        } else {
+            other_fields.shift_remove(&tag::SUBSORT_ORDER);
+        }
+    }
+}
+
+fn compare_coordinates(a: &RecordBuf, b: &RecordBuf) -> Ordering {
+    fn key(record: &RecordBuf) -> (usize, usize, bool) {
+        (
+            record.reference_sequence_id().unwrap_or(usize::MAX),
+            record.alignment_start().map(usize::from).unwrap_or(0),
+            record.flags().is_reverse_complemented(),
+        )
+    }
+
+    key(a).cmp(&key(b))
+}
+
+fn compare_names<F>(a: &RecordBuf, b: &RecordBuf, f: F) -> Ordering
+where
+    F: Fn(&[u8], &[u8]) -> Ordering,
+{
+    fn segment_key(flags: Flags) -> u16 {
+        (flags & (Flags::FIRST_SEGMENT | Flags::LAST_SEGMENT)).bits()
+    }
+
+    let a_name = a.name().map(|name| name.as_ref()).unwrap_or_default();
+    let b_name = b.name().map(|name| name.as_ref()).unwrap_or_default();
+
+    f(a_name, b_name).then_with(|| segment_key(a.flags()).cmp(&segment_key(b.flags())))
+}
+
+/// Compares two byte strings, comparing runs of digits by their numeric value.
+fn compare_natural(a: &[u8], b: &[u8]) -> Ordering {
+    fn split_digits(s: &[u8]) -> (&[u8], &[u8]) {
+        let i = s
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(s.len());
+        s.split_at(i)
+    }
+
+    fn trim_leading_zeros(s: &[u8]) -> &[u8] {
+        let i = s.iter().position(|&b| b != b'0').unwrap_or(s.len());
+        &s[i..]
+    }
+
+    let (mut a, mut b) = (a, b);
+
+    loop {
+        match (a.first(), b.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(c), Some(d)) if c.is_ascii_digit() && d.is_ascii_digit() => {
+                let (a_digits, a_rest) = split_digits(a);
+                let (b_digits, b_rest) = split_digits(b);
+
+                let a_value = trim_leading_zeros(a_digits);
+                let b_value = trim_leading_zeros(b_digits);
+
+                let ordering = a_value
+                    .len()
+                    .cmp(&b_value.len())
+                    .then_with(|| a_value.cmp(b_value))
+                    .then_with(|| a_digits.len().cmp(&b_digits.len()));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+
+                a = a_rest;
+                b = b_rest;
+            }
+            (Some(c), Some(d)) => {
+                if c != d {
+                    return c.cmp(d);
+                }
+
+                a = &a[1..];
+                b = &b[1..];
+            }
+        }
+    }
+}
+
+fn compare_data_field_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => match (a, b) {
+            (Value::Character(a), Value::Character(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) | (Value::Hex(a), Value::Hex(b)) => a.cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.total_cmp(b),
+            _ => match (a.as_int(), b.as_int()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => Ordering::Equal,
+            },
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn test_compare_coordinates() -> Result<(), noodles_core::position::TryFromIntError> {
+        let build = |reference_sequence_id: Option<usize>, alignment_start, flags| {
+            let mut record = RecordBuf::default();
+            *record.reference_sequence_id_mut() = reference_sequence_id;
+            *record.alignment_start_mut() = alignment_start;
+            *record.flags_mut() = flags;
+            record
+        };
+
+        let mut records = [
+            build(None, None, Flags::UNMAPPED),
+            build(Some(1), Some(Position::try_from(5)?), Flags::empty()),
+            build(
+                Some(0),
+                Some(Position::try_from(8)?),
+                Flags::REVERSE_COMPLEMENTED,
+            ),
+            build(Some(0), Some(Position::try_from(8)?), Flags::empty()),
+            build(Some(0), Some(Position::try_from(13)?), Flags::empty()),
+        ];
+
+        records.sort_by(|a, b| SortOrder::Coordinate.compare(a, b));
+
+        let actual: Vec<_> = records
+            .iter()
+            .map(|record| {
+                (
+                    record.reference_sequence_id(),
+                    record.alignment_start().map(usize::from),
+                    record.flags(),
+                )
+            })
+            .collect();
+
+        let expected = [
+            (Some(0), Some(8), Flags::empty()),
+            (Some(0), Some(8), Flags::REVERSE_COMPLEMENTED),
+            (Some(0), Some(13), Flags::empty()),
+            (Some(1), Some(5), Flags::empty()),
+            (None, None, Flags::UNMAPPED),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_natural() {
+        assert_eq!(compare_natural(b"r2", b"r10"), Ordering::Less);
+        assert_eq!(compare_natural(b"r10", b"r10"), Ordering::Equal);
+        assert_eq!(compare_natural(b"r10:2", b"r10:11"), Ordering::Less);
+        assert_eq!(compare_natural(b"r010", b"r10"), Ordering::Greater);
+        assert_eq!(compare_natural(b"ra", b"r1"), Ordering::Greater);
+        assert_eq!(compare_natural(b"r", b"r1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_compare_data_field_values() {
+        let a = Value::from(8);
+        let b = Value::UInt8(13);
+        assert_eq!(
+            compare_data_field_values(Some(&a), Some(&b)),
+            Ordering::Less
+        );
+        assert_eq!(compare_data_field_values(None, Some(&b)), Ordering::Less);
+
+        let a = Value::from("AC");
+        let b = Value::from("AG");
+        assert_eq!(
+            compare_data_field_values(Some(&a), Some(&b)),
+            Ordering::Less
+        );
+    }
+}
@@ -0,0 +1,3 @@
+//! Annotation format utilities.
+
+pub mod convert;
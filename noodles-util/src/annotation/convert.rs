@@ -0,0 +1,127 @@
+//! GTF and GFF3 conversion.
+//!
+//! GTF records are linked by their `gene_id` and `transcript_id` attributes, and GFF3 records, by
+//! their `ID` and `Parent` attributes. Converting a GTF record to GFF3 sets `ID` and `Parent` using
+//! the prefixes `gene:` and `transcript:` (e.g., `ID=transcript:t0;Parent=gene:g0`), and converting
+//! a GFF3 record to GTF sets `gene_id` and `transcript_id` from the feature hierarchy.
+//!
+//! `ID` and `Parent` attributes that differ from the ones derived from the gene and transcript IDs
+//! are kept as GTF attributes, as is an unknown GFF3 strand (`strand "?"`), which GTF cannot
+//! represent. All other attributes are kept, and the frame and phase are equivalent.
+
+mod gff_to_gtf;
+mod gtf_to_gff;
+
+pub use self::{
+    gff_to_gtf::{gff_to_gtf, gff_to_gtf_record},
+    gtf_to_gff::{gtf_to_gff, gtf_to_gff_record},
+};
+
+const GENE: &str = "gene";
+const TRANSCRIPT: &str = "transcript";
+
+const GENE_ID: &str = "gene_id";
+const TRANSCRIPT_ID: &str = "transcript_id";
+
+const GENE_ID_PREFIX: &str = "gene:";
+const TRANSCRIPT_ID_PREFIX: &str = "transcript:";
+
+const STRAND: &str = "strand";
+const UNKNOWN_STRAND: &str = "?";
+
+// Returns the GFF3 `ID` and `Parent` of a record derived from its type, gene ID, and transcript
+// ID.
+//
+// A record with an `ID` that is the gene ID or transcript ID is the gene or transcript,
+// respectively, regardless of its type.
+fn derived_ids(
+    ty: &str,
+    id: Option<&str>,
+    gene_id: &str,
+    transcript_id: Option<&str>,
+) -> (Option<String>, Option<String>) {
+    let gene_parent = || Some(format!("{GENE_ID_PREFIX}{gene_id}"));
+
+    match (ty, transcript_id) {
+        (GENE, _) => (Some(format!("{GENE_ID_PREFIX}{gene_id}")), None),
+        (TRANSCRIPT, Some(transcript_id)) => (
+            Some(format!("{TRANSCRIPT_ID_PREFIX}{transcript_id}")),
+            gene_parent(),
+        ),
+        _ if is_id(id, GENE_ID_PREFIX, gene_id) => (None, None),
+        (_, Some(transcript_id)) if is_id(id, TRANSCRIPT_ID_PREFIX, transcript_id) => {
+            (None, gene_parent())
+        }
+        (_, Some(transcript_id)) => (None, Some(format!("{TRANSCRIPT_ID_PREFIX}{transcript_id}"))),
+        (_, None) => (None, gene_parent()),
+    }
+}
+
+// Returns whether the `ID`, sans the given prefix, is the given ID.
+fn is_id(id: Option<&str>, prefix: &str, expected: &str) -> bool {
+    id.map(|s| s.strip_prefix(prefix).unwrap_or(s)) == Some(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use noodles_gff as gff;
+    use noodles_gtf as gtf;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> io::Result<()> {
+        let src = b"\
+sq0\tNOODLES\tgene\t8\t55\t.\t+\t.\tgene_id \"g0\"; gene_name \"ndls\";
+sq0\tNOODLES\ttranscript\t8\t55\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\"; tag \"a\"; tag \"b\";
+sq0\tNOODLES\tCDS\t8\t13\t0.5\t+\t2\tgene_id \"g0\"; transcript_id \"t0\";
+sq0\tNOODLES\texon\t21\t55\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";
+";
+
+        let mut reader = gtf::Reader::new(&src[..]);
+        let mut writer = gff::io::Writer::new(Vec::new());
+        gtf_to_gff(&mut reader, &mut writer)?;
+
+        let gff_data = writer.get_ref();
+
+        let mut reader = gff::io::Reader::new(&gff_data[..]);
+        let mut writer = gtf::Writer::new(Vec::new());
+        gff_to_gtf(&mut reader, &mut writer)?;
+
+        assert_eq!(writer.get_ref(), src);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gff_round_trip() -> io::Result<()> {
+        let src = b"\
+##gff-version 3
+sq0\tNOODLES\tgene\t8\t55\t.\t+\t.\tID=g0;gene_id=g0;gene_name=ndls
+sq0\tNOODLES\tmRNA\t8\t55\t.\t+\t.\tID=t0;Parent=g0;gene_id=g0;transcript_id=t0
+sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=t0;gene_id=g0;transcript_id=t0;tag=a,b
+###
+sq0\tNOODLES\tgene\t89\t144\t.\t?\t.\tID=gene:g1;gene_id=g1
+sq0\tNOODLES\ttranscript\t89\t144\t.\t?\t.\tID=transcript:t1;Parent=gene:g1;gene_id=g1;transcript_id=t1
+sq0\tNOODLES\tCDS\t89\t144\t0.5\t?\t2\tParent=transcript:t1;gene_id=g1;transcript_id=t1
+###
+";
+
+        let mut reader = gff::io::Reader::new(&src[..]);
+        let mut writer = gtf::Writer::new(Vec::new());
+        gff_to_gtf(&mut reader, &mut writer)?;
+
+        let gtf_data = writer.get_ref();
+
+        let mut reader = gtf::Reader::new(&gtf_data[..]);
+        let mut writer = gff::io::Writer::new(Vec::new());
+        gtf_to_gff(&mut reader, &mut writer)?;
+
+        assert_eq!(writer.get_ref(), src);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,247 @@
+use std::io::{self, BufRead, Write};
+
+use noodles_gff::{
+    self as gff,
+    feature_graph::Feature,
+    record::{attributes::field::tag, Phase},
+    FeatureGraph,
+};
+use noodles_gtf::{
+    self as gtf,
+    record::{attributes::Entry, Frame},
+};
+
+use super::{
+    derived_ids, GENE, GENE_ID, GENE_ID_PREFIX, STRAND, TRANSCRIPT_ID, TRANSCRIPT_ID_PREFIX,
+    UNKNOWN_STRAND,
+};
+
+/// Converts a GFF3 record to a GTF record.
+///
+/// The given gene ID and transcript ID are set as the first attributes. The `gene_id` and
+/// `transcript_id` attributes of the input are not copied, nor are `ID` and `Parent` attributes
+/// that can be derived from the gene ID and transcript ID (see [`super::gtf_to_gff_record`]).
+/// Attributes with a list of values are written as an entry per value. An unknown strand is
+/// written as a `strand "?"` attribute.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_gff as gff;
+/// use noodles_util::annotation::convert::gff_to_gtf_record;
+///
+/// let record: gff::Record = "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=t0;tag=a,b".parse()?;
+/// let gtf_record = gff_to_gtf_record(&record, "g0", Some("t0"));
+///
+/// assert_eq!(
+///     gtf_record.to_string(),
+///     "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\"; Parent \"t0\"; tag \"a\"; tag \"b\";"
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn gff_to_gtf_record(
+    record: &gff::Record,
+    gene_id: &str,
+    transcript_id: Option<&str>,
+) -> gtf::Record {
+    let mut entries = vec![Entry::new(GENE_ID, gene_id)];
+
+    if let Some(id) = transcript_id {
+        entries.push(Entry::new(TRANSCRIPT_ID, id));
+    }
+
+    let id = record
+        .attributes()
+        .get(tag::ID)
+        .and_then(|value| value.as_string());
+
+    let (id, parent) = derived_ids(record.ty(), id, gene_id, transcript_id);
+
+    for (key, value) in record.attributes().iter() {
+        let is_derived = match key.as_str() {
+            tag::ID => value.as_string() == id.as_deref(),
+            tag::PARENT => value.as_string() == parent.as_deref(),
+            GENE_ID | TRANSCRIPT_ID => true,
+            _ => false,
+        };
+
+        if is_derived {
+            continue;
+        }
+
+        for v in value.iter() {
+            entries.push(Entry::new(key.as_str(), v.as_str()));
+        }
+    }
+
+    let strand = match record.strand() {
+        gff::record::Strand::Forward => Some(gtf::record::Strand::Forward),
+        gff::record::Strand::Reverse => Some(gtf::record::Strand::Reverse),
+        gff::record::Strand::Unknown => {
+            entries.push(Entry::new(STRAND, UNKNOWN_STRAND));
+            None
+        }
+        gff::record::Strand::None => None,
+    };
+
+    let mut builder = gtf::Record::builder()
+        .set_reference_sequence_name(record.reference_sequence_name())
+        .set_source(record.source())
+        .set_type(record.ty())
+        .set_start(record.start())
+        .set_end(record.end())
+        .set_attributes(entries.into());
+
+    if let Some(score) = record.score() {
+        builder = builder.set_score(score);
+    }
+
+    if let Some(strand) = strand {
+        builder = builder.set_strand(strand);
+    }
+
+    if let Some(phase) = record.phase() {
+        let n = match phase {
+            Phase::Zero => 0,
+            Phase::One => 1,
+            Phase::Two => 2,
+        };
+
+        // SAFETY: `n` <= 2.
+        builder = builder.set_frame(Frame::try_from(n).unwrap());
+    }
+
+    builder.build()
+}
+
+/// Converts a GFF3 file to GTF.
+///
+/// The records are read as feature graphs (see [`gff::io::Reader::feature_graphs`]). Only
+/// gene-like top-level features (e.g., `gene`, `ncRNA_gene`, and `pseudogene`) and their
+/// descendants are written; other top-level features, e.g., `region` and `chromosome`, are
+/// skipped.
+///
+/// The gene ID of a top-level feature and the transcript ID of its children are their `gene_id`
+/// and `transcript_id` attributes, respectively, or, if missing, their `ID`s sans the `gene:` or
+/// `transcript:` prefix. Descendants inherit both IDs. A feature with multiple parents is written
+/// once per parent.
+///
+/// This returns an [`io::ErrorKind::InvalidData`] error if a gene or transcript has no ID.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_gff as gff;
+/// use noodles_gtf as gtf;
+/// use noodles_util::annotation::convert::gff_to_gtf;
+///
+/// let src = b"##gff-version 3
+/// sq0\tNOODLES\tregion\t1\t34\t.\t.\t.\tID=sq0
+/// sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=g0
+/// sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tID=t0;Parent=g0
+/// sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=t0
+/// ";
+/// let mut reader = gff::io::Reader::new(&src[..]);
+/// let mut writer = gtf::Writer::new(Vec::new());
+///
+/// gff_to_gtf(&mut reader, &mut writer)?;
+///
+/// let expected = b"sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\"; ID \"g0\";
+/// sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\"; ID \"t0\"; Parent \"g0\";
+/// sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\"; Parent \"t0\";
+/// ";
+///
+/// assert_eq!(writer.get_ref(), expected);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn gff_to_gtf<R, W>(
+    reader: &mut gff::io::Reader<R>,
+    writer: &mut gtf::Writer<W>,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    for result in reader.feature_graphs() {
+        let graph = result?;
+
+        for feature in graph.roots().filter(|feature| is_gene_like(feature.ty())) {
+            write_feature(writer, &graph, feature, None, None)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_feature<W>(
+    writer: &mut gtf::Writer<W>,
+    graph: &FeatureGraph,
+    feature: &Feature,
+    gene_id: Option<&str>,
+    transcript_id: Option<&str>,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let (gene_id, transcript_id) = match (gene_id, transcript_id) {
+        (None, _) => (resolve_id(feature, GENE_ID, GENE_ID_PREFIX)?, None),
+        (Some(gene_id), None) => (
+            gene_id,
+            Some(resolve_id(feature, TRANSCRIPT_ID, TRANSCRIPT_ID_PREFIX)?),
+        ),
+        (Some(gene_id), Some(transcript_id)) => (gene_id, Some(transcript_id)),
+    };
+
+    for record in feature.records() {
+        let record = gff_to_gtf_record(record, gene_id, transcript_id);
+        writer.write_record(&record)?;
+    }
+
+    for child in graph.children(feature) {
+        write_feature(writer, graph, child, Some(gene_id), transcript_id)?;
+    }
+
+    Ok(())
+}
+
+fn is_gene_like(ty: &str) -> bool {
+    ty.ends_with(GENE)
+}
+
+fn resolve_id<'f>(feature: &'f Feature, key: &str, prefix: &str) -> io::Result<&'f str> {
+    feature
+        .records()
+        .first()
+        .and_then(|record| record.attributes().get(key))
+        .and_then(|value| value.as_string())
+        .or_else(|| feature.id().map(|id| id.strip_prefix(prefix).unwrap_or(id)))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing {key} and ID: {}", feature.ty()),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gff_to_gtf_with_missing_ids() {
+        fn t(src: &[u8]) {
+            let mut reader = gff::io::Reader::new(src);
+            let mut writer = gtf::Writer::new(Vec::new());
+
+            assert!(matches!(
+                gff_to_gtf(&mut reader, &mut writer),
+                Err(e) if e.kind() == io::ErrorKind::InvalidData
+            ));
+        }
+
+        t(b"sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tName=ndls\n");
+        t(b"sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=g0\n\
+sq0\tNOODLES\tmRNA\t8\t13\t.\t+\t.\tParent=g0\n");
+    }
+}
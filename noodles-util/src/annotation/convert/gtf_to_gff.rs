@@ -0,0 +1,358 @@
+use std::{
+    collections::HashSet,
+    io::{self, BufRead, Write},
+};
+
+use noodles_core::Position;
+use noodles_gff::{
+    self as gff,
+    directive::GffVersion,
+    record::{
+        attributes::field::{tag, Value},
+        Attributes, Phase,
+    },
+    Directive,
+};
+use noodles_gtf::{self as gtf, record::attributes::Entry};
+
+use super::{
+    derived_ids, is_id, GENE, GENE_ID, GENE_ID_PREFIX, STRAND, TRANSCRIPT, TRANSCRIPT_ID,
+    TRANSCRIPT_ID_PREFIX, UNKNOWN_STRAND,
+};
+
+/// Converts a GTF record to a GFF3 record.
+///
+/// A `gene` record gets an `ID` of its gene ID; a `transcript` record, an `ID` of its transcript
+/// ID and a `Parent` of its gene ID; and all other records, a `Parent` of their transcript ID or,
+/// if missing, gene ID. `ID` and `Parent` attributes override these, and a `strand "?"`
+/// attribute sets an unknown strand. Attributes with the same key are combined into a single list.
+///
+/// This returns an [`io::ErrorKind::InvalidData`] error if the record is missing a gene ID or if
+/// a `transcript` record is missing a transcript ID.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_gff::record::attributes::field::{tag, Value};
+/// use noodles_gtf as gtf;
+/// use noodles_util::annotation::convert::gtf_to_gff_record;
+///
+/// let record: gtf::Record =
+///     "sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";".parse()?;
+///
+/// let gff_record = gtf_to_gff_record(&record)?;
+///
+/// assert_eq!(gff_record.ty(), "exon");
+/// assert_eq!(
+///     gff_record.attributes().get(tag::PARENT),
+///     Some(&Value::from("transcript:t0"))
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn gtf_to_gff_record(record: &gtf::Record) -> io::Result<gff::Record> {
+    let gene_id = get_attribute(record, GENE_ID)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing gene ID"))?;
+
+    let transcript_id = get_attribute(record, TRANSCRIPT_ID);
+
+    if record.ty() == TRANSCRIPT && transcript_id.is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing transcript ID",
+        ));
+    }
+
+    let id = get_attribute(record, tag::ID);
+    let (id, parent) = derived_ids(record.ty(), id, gene_id, transcript_id);
+
+    let mut attributes = Attributes::default();
+
+    if let Some(value) = get_attribute_values(record, tag::ID).or(id.map(Value::from)) {
+        attributes.insert(tag::ID.into(), value);
+    }
+
+    if let Some(value) = get_attribute_values(record, tag::PARENT).or(parent.map(Value::from)) {
+        attributes.insert(tag::PARENT.into(), value);
+    }
+
+    let mut is_strand_unknown = false;
+
+    for entry in record.attributes().iter() {
+        if entry.key() == tag::ID || entry.key() == tag::PARENT {
+            continue;
+        } else if entry.key() == STRAND && entry.value() == UNKNOWN_STRAND {
+            is_strand_unknown = true;
+            continue;
+        }
+
+        let value = entry.value().to_string();
+
+        attributes
+            .entry(entry.key().into())
+            .and_modify(|v| v.extend([value.clone()]))
+            .or_insert_with(|| value.into());
+    }
+
+    let strand = match record.strand() {
+        Some(gtf::record::Strand::Forward) => gff::record::Strand::Forward,
+        Some(gtf::record::Strand::Reverse) => gff::record::Strand::Reverse,
+        None if is_strand_unknown => gff::record::Strand::Unknown,
+        None => gff::record::Strand::None,
+    };
+
+    let mut builder = gff::Record::builder()
+        .set_reference_sequence_name(record.reference_sequence_name().into())
+        .set_source(record.source().into())
+        .set_type(record.ty().into())
+        .set_start(record.start())
+        .set_end(record.end())
+        .set_strand(strand)
+        .set_attributes(attributes);
+
+    if let Some(score) = record.score() {
+        builder = builder.set_score(score);
+    }
+
+    if let Some(frame) = record.frame() {
+        let phase = match u8::from(frame) {
+            0 => Phase::Zero,
+            1 => Phase::One,
+            _ => Phase::Two,
+        };
+
+        builder = builder.set_phase(phase);
+    }
+
+    Ok(builder.build())
+}
+
+/// Converts a GTF file to GFF3.
+///
+/// Records must be grouped by gene ID. Each gene is written with its `gene` record, followed by
+/// its records without a transcript ID, and then each of its transcripts with its `transcript`
+/// record and child records. A record with an `ID` attribute that is the gene ID or transcript ID
+/// (with or without the `gene:` or `transcript:` prefix) is used as the gene or transcript
+/// record, respectively, regardless of its type. A `gene` or `transcript` record that is missing
+/// is synthesized to span its records. Each gene is followed by a `###` directive.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_gff as gff;
+/// use noodles_gtf as gtf;
+/// use noodles_util::annotation::convert::gtf_to_gff;
+///
+/// let src = b"sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";\n";
+/// let mut reader = gtf::Reader::new(&src[..]);
+/// let mut writer = gff::io::Writer::new(Vec::new());
+///
+/// gtf_to_gff(&mut reader, &mut writer)?;
+///
+/// let expected = b"##gff-version 3
+/// sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tID=gene:g0;gene_id=g0
+/// sq0\tNOODLES\ttranscript\t8\t13\t.\t+\t.\tID=transcript:t0;Parent=gene:g0;gene_id=g0;transcript_id=t0
+/// sq0\tNOODLES\texon\t8\t13\t.\t+\t.\tParent=transcript:t0;gene_id=g0;transcript_id=t0
+/// ####
+/// ";
+///
+/// assert_eq!(writer.get_ref(), expected);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn gtf_to_gff<R, W>(
+    reader: &mut gtf::Reader<R>,
+    writer: &mut gff::io::Writer<W>,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    writer.write_directive(&Directive::GffVersion(GffVersion::default()))?;
+
+    let mut gene_ids = HashSet::new();
+    let mut group: Option<GeneGroup> = None;
+
+    for result in reader.records() {
+        let record = result?;
+
+        let gene_id = get_attribute(&record, GENE_ID)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing gene ID"))?;
+
+        if group.as_ref().map(|g| g.gene_id != gene_id).unwrap_or(true) {
+            if let Some(g) = group.take() {
+                write_gene_group(writer, g)?;
+            }
+
+            if !gene_ids.insert(gene_id.to_string()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("records are not grouped by gene ID: {gene_id}"),
+                ));
+            }
+
+            group = Some(GeneGroup::new(gene_id.into()));
+        }
+
+        if let Some(g) = group.as_mut() {
+            g.push(record);
+        }
+    }
+
+    if let Some(g) = group {
+        write_gene_group(writer, g)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Group {
+    record: Option<gtf::Record>,
+    children: Vec<gtf::Record>,
+}
+
+impl Group {
+    fn records(&self) -> impl Iterator<Item = &gtf::Record> {
+        self.record.iter().chain(&self.children)
+    }
+}
+
+struct GeneGroup {
+    gene_id: String,
+    gene: Group,
+    transcripts: Vec<(String, Group)>,
+}
+
+impl GeneGroup {
+    fn new(gene_id: String) -> Self {
+        Self {
+            gene_id,
+            gene: Group::default(),
+            transcripts: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, record: gtf::Record) {
+        let Some(transcript_id) = get_attribute(&record, TRANSCRIPT_ID) else {
+            let id = get_attribute(&record, tag::ID);
+
+            if self.gene.record.is_none()
+                && (record.ty() == GENE || is_id(id, GENE_ID_PREFIX, &self.gene_id))
+            {
+                self.gene.record = Some(record);
+            } else {
+                self.gene.children.push(record);
+            }
+
+            return;
+        };
+
+        let i = match self
+            .transcripts
+            .iter()
+            .position(|(id, _)| id == transcript_id)
+        {
+            Some(i) => i,
+            None => {
+                self.transcripts
+                    .push((transcript_id.into(), Group::default()));
+                self.transcripts.len() - 1
+            }
+        };
+
+        let id = get_attribute(&record, tag::ID);
+        let is_transcript =
+            record.ty() == TRANSCRIPT || is_id(id, TRANSCRIPT_ID_PREFIX, transcript_id);
+
+        let group = &mut self.transcripts[i].1;
+
+        if is_transcript && group.record.is_none() {
+            group.record = Some(record);
+        } else {
+            group.children.push(record);
+        }
+    }
+}
+
+fn write_gene_group<W>(writer: &mut gff::io::Writer<W>, mut group: GeneGroup) -> io::Result<()>
+where
+    W: Write,
+{
+    if group.gene.record.is_none() {
+        let records = group
+            .gene
+            .records()
+            .chain(group.transcripts.iter().flat_map(|(_, g)| g.records()));
+
+        let attributes = vec![Entry::new(GENE_ID, group.gene_id.as_str())];
+        group.gene.record = synthesize_record(records, GENE, attributes);
+    }
+
+    for record in group.gene.records() {
+        writer.write_record(&gtf_to_gff_record(record)?)?;
+    }
+
+    for (transcript_id, mut transcript) in group.transcripts {
+        if transcript.record.is_none() {
+            let attributes = vec![
+                Entry::new(GENE_ID, group.gene_id.as_str()),
+                Entry::new(TRANSCRIPT_ID, transcript_id),
+            ];
+
+            transcript.record = synthesize_record(transcript.records(), TRANSCRIPT, attributes);
+        }
+
+        for record in transcript.records() {
+            writer.write_record(&gtf_to_gff_record(record)?)?;
+        }
+    }
+
+    writer.write_directive(&Directive::ForwardReferencesAreResolved)
+}
+
+fn synthesize_record<'a, I>(records: I, ty: &str, attributes: Vec<Entry>) -> Option<gtf::Record>
+where
+    I: Iterator<Item = &'a gtf::Record>,
+{
+    let mut records = records.peekable();
+    let first = *records.peek()?;
+
+    let (start, end) = records.fold((Position::MAX, Position::MIN), |(start, end), record| {
+        (start.min(record.start()), end.max(record.end()))
+    });
+
+    let mut builder = gtf::Record::builder()
+        .set_reference_sequence_name(first.reference_sequence_name())
+        .set_source(first.source())
+        .set_type(ty)
+        .set_start(start)
+        .set_end(end)
+        .set_attributes(attributes.into());
+
+    if let Some(strand) = first.strand() {
+        builder = builder.set_strand(strand);
+    }
+
+    Some(builder.build())
+}
+
+fn get_attribute<'r>(record: &'r gtf::Record, key: &str) -> Option<&'r str> {
+    record
+        .attributes()
+        .iter()
+        .find(|entry| entry.key() == key)
+        .map(|entry| entry.value())
+}
+
+fn get_attribute_values(record: &gtf::Record, key: &str) -> Option<Value> {
+    let mut values = record
+        .attributes()
+        .iter()
+        .filter(|entry| entry.key() == key)
+        .map(|entry| entry.value().to_string());
+
+    let mut value = Value::from(values.next()?);
+    value.extend(values);
+
+    Some(value)
+}
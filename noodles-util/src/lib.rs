@@ -5,5 +5,11 @@
 #[cfg(feature = "alignment")]
 pub mod alignment;
 
+#[cfg(feature = "annotation")]
+pub mod annotation;
+
 #[cfg(feature = "variant")]
 pub mod variant;
+
+#[cfg(any(feature = "alignment", feature = "variant"))]
+mod sort;
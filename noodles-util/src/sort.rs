@@ -0,0 +1,4 @@
+//! Shared external-memory sorting support.
+
+pub(crate) mod chunk;
+pub(crate) mod merger;
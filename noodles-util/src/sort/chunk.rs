@@ -0,0 +1,62 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{self, AtomicUsize},
+};
+
+static CHUNK_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A temporary file of sorted records.
+///
+/// The file is removed when the chunk is dropped.
+pub(crate) struct Chunk {
+    path: PathBuf,
+}
+
+impl Chunk {
+    /// Creates a new temporary file in the given directory.
+    pub(crate) fn create(temp_dir: &Path, extension: &str) -> io::Result<(Self, File)> {
+        loop {
+            let n = CHUNK_COUNT.fetch_add(1, atomic::Ordering::Relaxed);
+            let path = temp_dir.join(format!("noodles-sort-{}-{n}.{extension}", process::id()));
+
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Self { path }, file)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Opens the temporary file for reading.
+    pub(crate) fn open(&self) -> io::Result<File> {
+        File::open(&self.path)
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_drop() -> io::Result<()> {
+        let (chunk, _) = Chunk::create(&env::temp_dir(), "tmp")?;
+        let path = chunk.path.clone();
+        assert!(path.exists());
+
+        drop(chunk);
+        assert!(!path.exists());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use std::{cmp::Ordering, collections::BinaryHeap, io, rc::Rc};
+
+pub(crate) type Source<'a, T> = Box<dyn Iterator<Item = io::Result<T>> + 'a>;
+
+type Compare<'a, T> = Rc<dyn Fn(&T, &T) -> Ordering + 'a>;
+
+/// A k-way merger of sorted record sources.
+///
+/// Records that compare equal are emitted in source order.
+pub(crate) struct Merger<'a, T> {
+    compare: Compare<'a, T>,
+    sources: Vec<Source<'a, T>>,
+    heap: Option<BinaryHeap<Entry<'a, T>>>,
+}
+
+impl<'a, T> Merger<'a, T> {
+    /// Creates a merger of sources that are each sorted by the given comparator.
+    pub(crate) fn new<F>(sources: Vec<Source<'a, T>>, compare: F) -> Self
+    where
+        F: Fn(&T, &T) -> Ordering + 'a,
+    {
+        Self {
+            compare: Rc::new(compare),
+            sources,
+            heap: None,
+        }
+    }
+
+    fn push_next(
+        &mut self,
+        heap: &mut BinaryHeap<Entry<'a, T>>,
+        source_id: usize,
+    ) -> io::Result<()> {
+        if let Some(record) = self.sources[source_id].next().transpose()? {
+            heap.push(Entry {
+                compare: Rc::clone(&self.compare),
+                record,
+                source_id,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn initialize(&mut self) -> io::Result<BinaryHeap<Entry<'a, T>>> {
+        let mut heap = BinaryHeap::with_capacity(self.sources.len());
+
+        for source_id in 0..self.sources.len() {
+            self.push_next(&mut heap, source_id)?;
+        }
+
+        Ok(heap)
+    }
+}
+
+impl<'a, T> Iterator for Merger<'a, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut heap = match self.heap.take() {
+            Some(heap) => heap,
+            None => match self.initialize() {
+                Ok(heap) => heap,
+                Err(e) => return Some(Err(e)),
+            },
+        };
+
+        let entry = heap.pop()?;
+        let result = self.push_next(&mut heap, entry.source_id);
+
+        self.heap = Some(heap);
+
+        Some(result.map(|_| entry.record))
+    }
+}
+
+struct Entry<'a, T> {
+    compare: Compare<'a, T>,
+    record: T,
+    source_id: usize,
+}
+
+impl<'a, T> Ord for Entry<'a, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `BinaryHeap` is a max-heap, so the ordering is reversed.
+        (self.compare)(&other.record, &self.record)
+            .then_with(|| other.source_id.cmp(&self.source_id))
+    }
+}
+
+impl<'a, T> PartialOrd for Entry<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T> PartialEq for Entry<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, T> Eq for Entry<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        fn source(values: &'static [(u32, char)]) -> Source<'static, (u32, char)> {
+            Box::new(values.iter().copied().map(Ok))
+        }
+
+        let merger = Merger::new(
+            vec![
+                source(&[(1, 'a'), (3, 'a'), (8, 'a')]),
+                source(&[]),
+                source(&[(2, 'c'), (3, 'c')]),
+            ],
+            |a: &(u32, char), b: &(u32, char)| a.0.cmp(&b.0),
+        );
+
+        let actual: Vec<_> = merger.collect::<io::Result<_>>()?;
+        let expected = [(1, 'a'), (2, 'c'), (3, 'a'), (3, 'c'), (8, 'a')];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_error() {
+        let sources: Vec<Source<'static, u32>> = vec![
+            Box::new([Ok(1)].into_iter()),
+            Box::new([Err(io::Error::from(io::ErrorKind::InvalidData))].into_iter()),
+        ];
+
+        let mut merger = Merger::new(sources, |a: &u32, b: &u32| a.cmp(b));
+
+        assert!(matches!(
+            merger.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
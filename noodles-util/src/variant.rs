@@ -1,3 +1,5 @@
 //! Variant format utilities.
 
+pub mod concat;
 pub mod io;
+pub mod sort;
@@ -0,0 +1,464 @@
+//! Variant file concatenation.
+//!
+//! This concatenates inputs that share the same samples, e.g., per-chromosome shards, in the
+//! given order. Records are not reordered.
+
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+use noodles_bcf as bcf;
+use noodles_bgzf as bgzf;
+use noodles_vcf::{self as vcf, header::StringMaps, variant::RecordBuf};
+
+use super::io::{reader::builder::detect_format, CompressionMethod, Format, Reader, Writer};
+
+/// Combines the headers of inputs to be concatenated.
+///
+/// The inputs must have the same sample names in the same order. The output header is the first
+/// header with any contig, filter, info, format, and alternative allele records only defined in
+/// later headers appended. Contigs with the same ID must have the same length, if present.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_util::variant::concat;
+/// use noodles_vcf::{self as vcf, header::record::value::{map::Contig, Map}};
+///
+/// let header_a = vcf::Header::builder()
+///     .add_contig("sq0", Map::<Contig>::new())
+///     .build();
+///
+/// let header_b = vcf::Header::builder()
+///     .add_contig("sq1", Map::<Contig>::new())
+///     .build();
+///
+/// let header = concat::merge_headers(&[&header_a, &header_b])?;
+/// assert_eq!(header.contigs().len(), 2);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn merge_headers(headers: &[&vcf::Header]) -> io::Result<vcf::Header> {
+    let Some((first, rest)) = headers.split_first() else {
+        return Ok(vcf::Header::default());
+    };
+
+    let mut header = (*first).clone();
+
+    for src in rest {
+        if src.sample_names() != header.sample_names() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "sample names differ between inputs",
+            ));
+        }
+
+        for (id, contig) in src.contigs() {
+            match header.contigs().get(id) {
+                Some(dst) => {
+                    if let (Some(a), Some(b)) = (dst.length(), contig.length()) {
+                        if a != b {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                format!("contig length mismatch: {id}"),
+                            ));
+                        }
+                    }
+                }
+                None => {
+                    header.contigs_mut().insert(id.clone(), contig.clone());
+                }
+            }
+        }
+
+        for (id, filter) in src.filters() {
+            if !header.filters().contains_key(id) {
+                header.filters_mut().insert(id.clone(), filter.clone());
+            }
+        }
+
+        for (id, info) in src.infos() {
+            if !header.infos().contains_key(id) {
+                header.infos_mut().insert(id.clone(), info.clone());
+            }
+        }
+
+        for (id, format) in src.formats() {
+            if !header.formats().contains_key(id) {
+                header.formats_mut().insert(id.clone(), format.clone());
+            }
+        }
+
+        for (id, alternative_allele) in src.alternative_alleles() {
+            if !header.alternative_alleles().contains_key(id) {
+                header
+                    .alternative_alleles_mut()
+                    .insert(id.clone(), alternative_allele.clone());
+            }
+        }
+    }
+
+    *header.string_maps_mut() = StringMaps::try_from(&header)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    Ok(header)
+}
+
+/// Concatenates variant inputs by decoding and reencoding their records.
+///
+/// The output header is built using [`merge_headers`] and written before the records. Records
+/// are read from each input in order. The inputs can be any combination of formats.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_util::variant::{self, concat};
+///
+/// let src = b"##fileformat=VCFv4.4\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n";
+///
+/// let readers = vec![
+///     variant::io::reader::Builder::default().build_from_reader(&src[..])?,
+///     variant::io::reader::Builder::default().build_from_reader(&src[..])?,
+/// ];
+///
+/// let mut writer = variant::io::writer::Builder::default().build_from_writer(io::sink());
+///
+/// let header = concat::concat(&mut writer, readers)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn concat<R>(writer: &mut Writer, readers: Vec<Reader<R>>) -> io::Result<vcf::Header>
+where
+    R: BufRead,
+{
+    let mut readers = readers;
+
+    let headers: Vec<_> = readers
+        .iter_mut()
+        .map(|reader| reader.read_header())
+        .collect::<io::Result<_>>()?;
+
+    let header_refs: Vec<_> = headers.iter().collect();
+    let header = merge_headers(&header_refs)?;
+
+    writer.write_header(&header)?;
+
+    for (reader, src_header) in readers.iter_mut().zip(&headers) {
+        for result in reader.records(src_header) {
+            let record = result?;
+            let record = RecordBuf::try_from_variant_record(src_header, record.as_ref())?;
+            writer.write_record(&header, &record)?;
+        }
+    }
+
+    Ok(header)
+}
+
+/// Concatenates BGZF-compressed variant inputs by copying compressed blocks.
+///
+/// All inputs must be BGZF-compressed, have the same format, and have identical headers. The
+/// first input is copied whole. For the remaining inputs, the header is skipped: the block that
+/// contains the end of the header is recompressed from the first record, and all following blocks
+/// are copied without being decompressed. Empty blocks, including end-of-file markers, are
+/// dropped, and a single end-of-file marker is written at the end of the output.
+///
+/// This returns the output writer.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor};
+/// use noodles_bgzf as bgzf;
+/// use noodles_util::variant::concat;
+/// use noodles_vcf as vcf;
+///
+/// let mut writer = vcf::io::Writer::new(bgzf::Writer::new(Vec::new()));
+/// writer.write_header(&vcf::Header::default())?;
+/// let src = writer.into_inner().finish()?;
+///
+/// let inputs = vec![Cursor::new(src.clone()), Cursor::new(src)];
+/// let dst = concat::concat_bgzf(inputs, Vec::new())?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn concat_bgzf<R, W>(inputs: Vec<R>, mut writer: W) -> io::Result<W>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    let mut first_header = None;
+    let mut buf = Vec::new();
+
+    for (i, mut input) in inputs.into_iter().enumerate() {
+        let (format, header, position) = read_header(&mut input)?;
+
+        match &first_header {
+            None => first_header = Some((format, header)),
+            Some((first_format, first_header)) => {
+                if format != *first_format || header != *first_header {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "input headers differ",
+                    ));
+                }
+            }
+        }
+
+        if i == 0 {
+            input.seek(SeekFrom::Start(0))?;
+        } else {
+            input.seek(SeekFrom::Start(position.compressed()))?;
+
+            if read_block(&mut input, &mut buf)? {
+                let mut data = Vec::new();
+                bgzf::Reader::new(&buf[..]).read_to_end(&mut data)?;
+
+                let start = usize::from(position.uncompressed());
+                let rest = data.get(start..).unwrap_or_default();
+
+                if !rest.is_empty() {
+                    let mut block_writer = bgzf::Writer::new(&mut writer);
+                    block_writer.write_all(rest)?;
+                    block_writer.flush()?;
+                    // The end-of-file marker is not written until all inputs are copied.
+                    block_writer.into_inner();
+                }
+            }
+        }
+
+        while read_block(&mut input, &mut buf)? {
+            if !is_empty_block(&buf) {
+                writer.write_all(&buf)?;
+            }
+        }
+    }
+
+    bgzf::Writer::new(&mut writer).finish()?;
+
+    Ok(writer)
+}
+
+fn read_header<R>(reader: &mut R) -> io::Result<(Format, vcf::Header, bgzf::VirtualPosition)>
+where
+    R: Read,
+{
+    let mut reader = io::BufReader::new(reader);
+
+    let format = detect_format(&mut reader, Some(CompressionMethod::Bgzf))?;
+
+    if reader.fill_buf()?.get(..2) != Some(&[0x1f, 0x8b][..]) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "input is not BGZF-compressed",
+        ));
+    }
+
+    let bgzf_reader = bgzf::Reader::new(reader);
+
+    let (header, position) = match format {
+        Format::Vcf => {
+            let mut reader = vcf::io::Reader::new(bgzf_reader);
+            let header = reader.read_header()?;
+            (header, reader.get_ref().virtual_position())
+        }
+        Format::Bcf => {
+            let mut reader = bcf::io::Reader::from(bgzf_reader);
+            let header = reader.read_header()?;
+            (header, reader.get_ref().virtual_position())
+        }
+    };
+
+    Ok((format, header, position))
+}
+
+// Reads a raw BGZF block into `buf`. This returns `false` at the end of the stream.
+fn read_block<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<bool>
+where
+    R: Read,
+{
+    // gzip header (10 bytes) + XLEN (2 bytes)
+    const HEADER_SIZE: usize = 12;
+    const FEXTRA: u8 = 0x04;
+
+    buf.resize(HEADER_SIZE, 0);
+
+    let mut n = 0;
+
+    while n < HEADER_SIZE {
+        match reader.read(&mut buf[n..])? {
+            0 if n == 0 => return Ok(false),
+            0 => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            m => n += m,
+        }
+    }
+
+    if buf[..2] != [0x1f, 0x8b] || buf[3] & FEXTRA == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid BGZF header",
+        ));
+    }
+
+    let xlen = usize::from(u16::from_le_bytes([buf[10], buf[11]]));
+    buf.resize(HEADER_SIZE + xlen, 0);
+    reader.read_exact(&mut buf[HEADER_SIZE..])?;
+
+    let block_size = find_block_size(&buf[HEADER_SIZE..])
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing BGZF block size"))?;
+
+    if block_size < buf.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid BGZF block size",
+        ));
+    }
+
+    let start = buf.len();
+    buf.resize(block_size, 0);
+    reader.read_exact(&mut buf[start..])?;
+
+    Ok(true)
+}
+
+// Finds the total block size (BSIZE + 1) in the gzip extra subfields.
+fn find_block_size(mut extra: &[u8]) -> Option<usize> {
+    while extra.len() >= 4 {
+        let (si1, si2) = (extra[0], extra[1]);
+        let slen = usize::from(u16::from_le_bytes([extra[2], extra[3]]));
+        let data = extra.get(4..4 + slen)?;
+
+        if (si1, si2) == (b'B', b'C') && slen == 2 {
+            return Some(usize::from(u16::from_le_bytes([data[0], data[1]])) + 1);
+        }
+
+        extra = &extra[4 + slen..];
+    }
+
+    None
+}
+
+fn is_empty_block(buf: &[u8]) -> bool {
+    // The last 4 bytes are ISIZE, the uncompressed data length.
+    buf.len() >= 4 && buf[buf.len() - 4..] == [0, 0, 0, 0]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_core::Position;
+    use noodles_vcf::{
+        header::record::value::{map::Contig, Map},
+        variant::io::Write as _,
+    };
+
+    use super::*;
+
+    fn build_bgzf_vcf(
+        header: &vcf::Header,
+        records: &[(&str, usize)],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut writer = vcf::io::Writer::new(bgzf::Writer::new(Vec::new()));
+        writer.write_header(header)?;
+
+        for &(name, position) in records {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::try_from(position)?)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(header, &record)?;
+        }
+
+        Ok(writer.into_inner().finish()?)
+    }
+
+    fn read_positions(src: Vec<u8>) -> Result<Vec<(String, usize)>, Box<dyn std::error::Error>> {
+        let mut reader = vcf::io::Reader::new(bgzf::Reader::new(&src[..]));
+        let header = reader.read_header()?;
+
+        let mut positions = Vec::new();
+
+        for result in reader.record_bufs(&header) {
+            let record = result?;
+
+            positions.push((
+                record.reference_sequence_name().into(),
+                record.variant_start().map(usize::from).unwrap_or_default(),
+            ));
+        }
+
+        Ok(positions)
+    }
+
+    #[test]
+    fn test_concat_bgzf() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .add_contig("sq1", Map::<Contig>::new())
+            .build();
+
+        let src_a = build_bgzf_vcf(&header, &[("sq0", 8), ("sq0", 13)])?;
+        let src_b = build_bgzf_vcf(&header, &[("sq1", 5)])?;
+
+        let dst = concat_bgzf(vec![Cursor::new(src_a), Cursor::new(src_b)], Vec::new())?;
+
+        let eof = bgzf::Writer::new(Vec::new()).finish()?;
+        assert!(dst.ends_with(&eof));
+        assert!(!dst[..dst.len() - eof.len()].ends_with(&eof));
+
+        assert_eq!(
+            read_positions(dst)?,
+            [
+                (String::from("sq0"), 8),
+                (String::from("sq0"), 13),
+                (String::from("sq1"), 5)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_concat_bgzf_with_different_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let header_a = vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .build();
+        let header_b = vcf::Header::builder()
+            .add_contig("sq1", Map::<Contig>::new())
+            .build();
+
+        let src_a = build_bgzf_vcf(&header_a, &[])?;
+        let src_b = build_bgzf_vcf(&header_b, &[])?;
+
+        assert!(matches!(
+            concat_bgzf(vec![Cursor::new(src_a), Cursor::new(src_b)], Vec::new()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_headers() -> io::Result<()> {
+        let header_a = vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .add_sample_name("sample0")
+            .build();
+
+        let header_b = vcf::Header::builder()
+            .add_contig("sq1", Map::<Contig>::new())
+            .add_sample_name("sample0")
+            .build();
+
+        let header = merge_headers(&[&header_a, &header_b])?;
+        let ids: Vec<_> = header.contigs().keys().collect();
+        assert_eq!(ids, ["sq0", "sq1"]);
+
+        let header_c = vcf::Header::builder().add_sample_name("sample1").build();
+
+        assert!(matches!(
+            merge_headers(&[&header_a, &header_c]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
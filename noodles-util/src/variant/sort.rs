@@ -0,0 +1,257 @@
+//! External-memory variant record sorting.
+//!
+//! Records are sorted by the order of the contigs in the header and then by position. Records
+//! are buffered in memory up to a chunk size. Each full buffer is sorted and written to a
+//! temporary VCF file, and the sorted chunks are then merged.
+
+mod builder;
+mod chunk;
+mod key;
+
+pub use self::builder::Builder;
+
+use std::{io, mem, path::PathBuf};
+
+use noodles_vcf::{
+    self as vcf,
+    variant::{Record, RecordBuf},
+};
+
+use self::key::Key;
+use crate::sort::{
+    chunk::Chunk,
+    merger::{Merger, Source},
+};
+
+/// A variant record sorter.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_util::variant::sort;
+/// use noodles_vcf::{self as vcf, variant::RecordBuf};
+///
+/// let header = vcf::Header::default();
+/// let mut sorter = sort::Builder::default().build(&header);
+///
+/// sorter.add_record_buf(RecordBuf::default())?;
+///
+/// for result in sorter.finish()? {
+///     let record = result?;
+///     // ...
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Sorter {
+    header: vcf::Header,
+    chunk_size: usize,
+    temp_dir: PathBuf,
+    buf: Vec<RecordBuf>,
+    chunks: Vec<Chunk>,
+}
+
+impl Sorter {
+    /// Returns the header used to order records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::sort;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::default();
+    /// let sorter = sort::Builder::default().build(&header);
+    ///
+    /// assert_eq!(sorter.header(), &header);
+    /// ```
+    pub fn header(&self) -> &vcf::Header {
+        &self.header
+    }
+
+    /// Adds a variant record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::sort;
+    /// use noodles_vcf::{self as vcf, variant::RecordBuf};
+    ///
+    /// let header = vcf::Header::default();
+    /// let mut sorter = sort::Builder::default().build(&header);
+    ///
+    /// let record = RecordBuf::default();
+    /// sorter.add_record(&record)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn add_record<R>(&mut self, record: &R) -> io::Result<()>
+    where
+        R: Record,
+    {
+        let record = RecordBuf::try_from_variant_record(&self.header, record)?;
+        self.add_record_buf(record)
+    }
+
+    /// Adds a variant record buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::sort;
+    /// use noodles_vcf::{self as vcf, variant::RecordBuf};
+    ///
+    /// let header = vcf::Header::default();
+    /// let mut sorter = sort::Builder::default().build(&header);
+    /// sorter.add_record_buf(RecordBuf::default())?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn add_record_buf(&mut self, record: RecordBuf) -> io::Result<()> {
+        self.buf.push(record);
+
+        if self.buf.len() >= self.chunk_size {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    /// Sorts the remaining records and returns an iterator over all records in sort order.
+    ///
+    /// Temporary files are removed when the returned iterator is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::sort;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::default();
+    /// let sorter = sort::Builder::default().build(&header);
+    ///
+    /// let mut records = sorter.finish()?;
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<Sorted> {
+        self.sort_buf();
+
+        let mut sources = Vec::with_capacity(self.chunks.len() + 1);
+
+        for chunk in &self.chunks {
+            sources.push(chunk::records(chunk, &self.header)?);
+        }
+
+        let buf = mem::take(&mut self.buf);
+        sources.push(Box::new(buf.into_iter().map(Ok)) as Source<'static, RecordBuf>);
+
+        let header = self.header.clone();
+
+        Ok(Sorted {
+            merger: Merger::new(sources, move |a, b| {
+                Key::new(&header, a).cmp(&Key::new(&header, b))
+            }),
+            _chunks: mem::take(&mut self.chunks),
+        })
+    }
+
+    fn sort_buf(&mut self) {
+        let header = &self.header;
+        self.buf
+            .sort_by_cached_key(|record| Key::new(header, record));
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        self.sort_buf();
+        let chunk = chunk::write(&self.temp_dir, &self.header, &self.buf)?;
+        self.chunks.push(chunk);
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+/// An iterator over sorted variant records.
+///
+/// This is created by calling [`Sorter::finish`].
+pub struct Sorted {
+    merger: Merger<'static, RecordBuf>,
+    _chunks: Vec<Chunk>,
+}
+
+impl Iterator for Sorted {
+    type Item = io::Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.merger.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, num::NonZeroUsize};
+
+    use noodles_core::Position;
+    use noodles_vcf::header::record::value::{map::Contig, Map};
+
+    use super::*;
+
+    #[test]
+    fn test_finish_with_chunks() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir =
+            env::temp_dir().join(format!("noodles-util-variant-sort-{}", std::process::id()));
+        fs::create_dir_all(&temp_dir)?;
+
+        let header = vcf::Header::builder()
+            .add_contig("sq1", Map::<Contig>::new())
+            .add_contig("sq0", Map::<Contig>::new())
+            .build();
+
+        let mut sorter = Builder::default()
+            .set_chunk_size(NonZeroUsize::try_from(2)?)
+            .set_temp_dir(&temp_dir)
+            .build(&header);
+
+        let records = [("sq0", 8), ("sq1", 13), ("sq0", 5), ("sq1", 1), ("sq0", 3)];
+
+        for (name, position) in records {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::try_from(position)?)
+                .set_reference_bases("A")
+                .build();
+
+            sorter.add_record(&record)?;
+        }
+
+        let records = sorter.finish()?;
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 2);
+
+        let actual: Vec<_> = records
+            .map(|result| {
+                result.map(|record| {
+                    (
+                        record.reference_sequence_name().to_string(),
+                        record.variant_start().map(usize::from),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (String::from("sq1"), Some(1)),
+            (String::from("sq1"), Some(13)),
+            (String::from("sq0"), Some(3)),
+            (String::from("sq0"), Some(5)),
+            (String::from("sq0"), Some(8)),
+        ];
+
+        assert_eq!(actual, expected);
+        assert_eq!(fs::read_dir(&temp_dir)?.count(), 0);
+
+        fs::remove_dir(&temp_dir)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::{env, num::NonZeroUsize, path::PathBuf};
+
+use noodles_vcf as vcf;
+
+use super::Sorter;
+
+// The default number of records held in memory before they are written to a temporary file.
+const DEFAULT_CHUNK_SIZE: NonZeroUsize = match NonZeroUsize::new(1 << 19) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
+/// A variant record sorter builder.
+#[derive(Debug)]
+pub struct Builder {
+    chunk_size: NonZeroUsize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Sets the maximum number of records held in memory.
+    ///
+    /// When this many records are buffered, they are sorted and written to a temporary VCF file.
+    /// By default, this is 524288 (2^19).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_util::variant::sort;
+    /// let builder = sort::Builder::default().set_chunk_size(NonZeroUsize::MIN);
+    /// ```
+    pub fn set_chunk_size(mut self, chunk_size: NonZeroUsize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets the directory where temporary files are written.
+    ///
+    /// By default, this is the system temporary directory ([`std::env::temp_dir`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::sort;
+    /// let builder = sort::Builder::default().set_temp_dir("tmp");
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.temp_dir = Some(temp_dir.into());
+        self
+    }
+
+    /// Builds a sorter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::sort;
+    /// use noodles_vcf as vcf;
+    /// let header = vcf::Header::default();
+    /// let sorter = sort::Builder::default().build(&header);
+    /// ```
+    pub fn build(self, header: &vcf::Header) -> Sorter {
+        Sorter {
+            header: header.clone(),
+            chunk_size: self.chunk_size.get(),
+            temp_dir: self.temp_dir.unwrap_or_else(env::temp_dir),
+            buf: Vec::new(),
+            chunks: Vec::new(),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            temp_dir: None,
+        }
+    }
+}
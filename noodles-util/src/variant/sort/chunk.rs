@@ -0,0 +1,52 @@
+use std::{
+    io::{self, BufReader, BufWriter, Write},
+    iter,
+    path::Path,
+};
+
+use noodles_vcf::{
+    self as vcf,
+    variant::{io::Write as _, RecordBuf},
+};
+
+use crate::sort::{chunk::Chunk, merger::Source};
+
+/// Writes sorted records to a temporary VCF file.
+pub(super) fn write(
+    temp_dir: &Path,
+    header: &vcf::Header,
+    records: &[RecordBuf],
+) -> io::Result<Chunk> {
+    let (chunk, file) = Chunk::create(temp_dir, "vcf")?;
+
+    let mut writer = vcf::io::Writer::new(BufWriter::new(file));
+    writer.write_header(header)?;
+
+    for record in records {
+        writer.write_variant_record(header, record)?;
+    }
+
+    writer.get_mut().flush()?;
+
+    Ok(chunk)
+}
+
+/// Returns an iterator over the records in a temporary VCF file.
+pub(super) fn records(
+    chunk: &Chunk,
+    header: &vcf::Header,
+) -> io::Result<Source<'static, RecordBuf>> {
+    let mut reader = chunk.open().map(BufReader::new).map(vcf::io::Reader::new)?;
+    reader.read_header()?;
+
+    let header = header.clone();
+    let mut record = RecordBuf::default();
+
+    Ok(Box::new(iter::from_fn(move || {
+        match reader.read_record_buf(&header, &mut record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(record.clone())),
+            Err(e) => Some(Err(e)),
+        }
+    })))
+}
@@ -0,0 +1,83 @@
+use noodles_vcf::{self as vcf, variant::RecordBuf};
+
+/// A record sort key.
+///
+/// Records are ordered by the position of their reference sequence name in the header contigs and
+/// then by their start position. Reference sequences not in the header are placed last, ordered
+/// by name.
+#[derive(Eq, Ord, PartialEq, PartialOrd)]
+pub(super) struct Key {
+    contig_index: usize,
+    contig_name: Option<String>,
+    position: usize,
+}
+
+impl Key {
+    pub(super) fn new(header: &vcf::Header, record: &RecordBuf) -> Self {
+        let name = record.reference_sequence_name();
+
+        let (contig_index, contig_name) = match header.contigs().get_index_of(name) {
+            Some(i) => (i, None),
+            None => (usize::MAX, Some(name.into())),
+        };
+
+        Self {
+            contig_index,
+            contig_name,
+            position: record.variant_start().map(usize::from).unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_vcf::header::record::value::{map::Contig, Map};
+
+    use super::*;
+
+    #[test]
+    fn test_key_cmp() -> Result<(), noodles_core::position::TryFromIntError> {
+        let header = vcf::Header::builder()
+            .add_contig("sq1", Map::<Contig>::new())
+            .add_contig("sq0", Map::<Contig>::new())
+            .build();
+
+        let build = |name: &str, position| {
+            RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(position)
+                .build()
+        };
+
+        let mut records = [
+            build("sq2", Position::try_from(1)?),
+            build("sq0", Position::try_from(1)?),
+            build("sq1", Position::try_from(8)?),
+            build("sq1", Position::try_from(5)?),
+        ];
+
+        records.sort_by_key(|record| Key::new(&header, record));
+
+        let actual: Vec<_> = records
+            .iter()
+            .map(|record| {
+                (
+                    record.reference_sequence_name(),
+                    record.variant_start().map(usize::from),
+                )
+            })
+            .collect();
+
+        let expected = [
+            ("sq1", Some(5)),
+            ("sq1", Some(8)),
+            ("sq0", Some(1)),
+            ("sq2", Some(1)),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
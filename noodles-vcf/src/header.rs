@@ -14,6 +14,7 @@ pub use self::{
 use std::{hash::Hash, str::FromStr};
 
 use indexmap::{IndexMap, IndexSet};
+use noodles_core::ops;
 
 use self::record::value::{
     map::{AlternativeAllele, Contig, Filter, Format, Info},
@@ -516,6 +517,42 @@ impl Default for Header {
     }
 }
 
+impl From<&Header> for ops::ReferenceSequences {
+    /// Converts the contigs of a VCF header to interval operation reference sequences.
+    ///
+    /// Contigs without a length have a length of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::ops::ReferenceSequences;
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::record::value::{map::Contig, Map},
+    /// };
+    ///
+    /// let mut contig = Map::<Contig>::new();
+    /// *contig.length_mut() = Some(13);
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig("sq0", Map::<Contig>::new())
+    ///     .add_contig("sq1", contig)
+    ///     .build();
+    ///
+    /// let reference_sequences = ReferenceSequences::from(&header);
+    ///
+    /// assert_eq!(reference_sequences.get_index(0), Some((b"sq0".as_ref().into(), 0)));
+    /// assert_eq!(reference_sequences.get_index(1), Some((b"sq1".as_ref().into(), 13)));
+    /// ```
+    fn from(header: &Header) -> Self {
+        header
+            .contigs()
+            .iter()
+            .map(|(name, contig)| (name.clone(), contig.length().unwrap_or_default()))
+            .collect()
+    }
+}
+
 impl FromStr for Header {
     type Err = ParseError;
 
@@ -1,6 +1,7 @@
 //! Variant format.
 
 pub mod io;
+pub mod normalize;
 pub mod record;
 pub mod record_buf;
 
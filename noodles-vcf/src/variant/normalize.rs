@@ -0,0 +1,266 @@
+//! Variant record normalization.
+//!
+//! This includes left-aligning and trimming alleles ([`left_align`]), splitting multiallelic
+//! records into biallelic records ([`split`]), and joining biallelic records into multiallelic
+//! records ([`join`]).
+
+mod fields;
+mod join;
+mod split;
+mod values;
+
+pub use self::{join::join, split::split};
+
+use std::io;
+
+use noodles_core::Position;
+use noodles_fasta as fasta;
+
+use super::RecordBuf;
+
+/// Left-aligns and trims the alleles of a record.
+///
+/// Common trailing bases are removed from all alleles, and while any allele is empty, the
+/// preceding reference base is prepended to all alleles. This shifts indels in repeats to their
+/// leftmost position. Then, common leading bases are removed while all alleles have at least two
+/// bases. The start position is updated accordingly.
+///
+/// The reference bases of the record are first checked against the reference sequence.
+///
+/// Records with symbolic alleles, breakends, or missing or overlapping deletion (`*`) alleles
+/// and records where an alternate allele is the same as the reference allele are left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_vcf::variant::{normalize, record_buf::AlternateBases, RecordBuf};
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"GCACACAT".to_vec()),
+/// )]);
+///
+/// let mut record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::try_from(4)?)
+///     .set_reference_bases("CAC")
+///     .set_alternate_bases(AlternateBases::from(vec![String::from("C")]))
+///     .build();
+///
+/// normalize::left_align(&repository, &mut record)?;
+///
+/// assert_eq!(record.variant_start(), Some(Position::try_from(1)?));
+/// assert_eq!(record.reference_bases(), "GCA");
+/// assert_eq!(record.alternate_bases().as_ref(), [String::from("G")]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn left_align(repository: &fasta::Repository, record: &mut RecordBuf) -> io::Result<()> {
+    let reference_bases = record.reference_bases().as_bytes();
+    let alternate_bases = record.alternate_bases().as_ref();
+
+    if reference_bases.is_empty()
+        || alternate_bases.is_empty()
+        || !is_sequence(reference_bases)
+        || alternate_bases
+            .iter()
+            .any(|bases| !is_sequence(bases.as_bytes()))
+        || alternate_bases
+            .iter()
+            .any(|bases| bases.as_bytes().eq_ignore_ascii_case(reference_bases))
+    {
+        return Ok(());
+    }
+
+    let name = record.reference_sequence_name();
+
+    let sequence = repository
+        .get(name.as_bytes())
+        .transpose()?
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("missing reference sequence: {name}"),
+            )
+        })?;
+
+    let start = record
+        .variant_start()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing variant start"))?;
+
+    let end = start
+        .checked_add(reference_bases.len() - 1)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid variant end"))?;
+
+    match sequence.get(start..=end) {
+        Some(bases) if bases.eq_ignore_ascii_case(reference_bases) => {}
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "reference bases do not match the reference sequence",
+            ))
+        }
+    }
+
+    let mut alleles: Vec<Vec<u8>> = Some(reference_bases)
+        .into_iter()
+        .chain(alternate_bases.iter().map(|bases| bases.as_bytes()))
+        .map(|bases| bases.to_vec())
+        .collect();
+
+    let mut start = usize::from(start);
+
+    while has_common_last_base(&alleles) {
+        if alleles.iter().any(|allele| allele.len() == 1) {
+            let Some(prev_base) = start
+                .checked_sub(1)
+                .and_then(Position::new)
+                .and_then(|position| sequence.get(position).copied())
+            else {
+                break;
+            };
+
+            for allele in &mut alleles {
+                allele.insert(0, prev_base.to_ascii_uppercase());
+            }
+
+            start -= 1;
+        }
+
+        for allele in &mut alleles {
+            allele.pop();
+        }
+    }
+
+    while alleles.iter().all(|allele| allele.len() >= 2) && has_common_first_base(&alleles) {
+        for allele in &mut alleles {
+            allele.remove(0);
+        }
+
+        start += 1;
+    }
+
+    let mut alleles = alleles.into_iter().map(|allele| {
+        // SAFETY: Alleles only contain ASCII alphabetic characters.
+        String::from_utf8(allele).unwrap()
+    });
+
+    // SAFETY: `alleles` is nonempty.
+    *record.reference_bases_mut() = alleles.next().unwrap();
+    *record.alternate_bases_mut() = alleles.collect::<Vec<_>>().into();
+    *record.variant_start_mut() = Position::new(start);
+
+    Ok(())
+}
+
+fn is_sequence(bases: &[u8]) -> bool {
+    !bases.is_empty() && bases.iter().all(|b| b.is_ascii_alphabetic())
+}
+
+fn has_common_last_base(alleles: &[Vec<u8>]) -> bool {
+    has_common_base(alleles, |allele| allele.last())
+}
+
+fn has_common_first_base(alleles: &[Vec<u8>]) -> bool {
+    has_common_base(alleles, |allele| allele.first())
+}
+
+fn has_common_base<F>(alleles: &[Vec<u8>], f: F) -> bool
+where
+    F: Fn(&[u8]) -> Option<&u8>,
+{
+    let Some((first, rest)) = alleles.split_first() else {
+        return false;
+    };
+
+    let Some(base) = f(first).map(u8::to_ascii_uppercase) else {
+        return false;
+    };
+
+    rest.iter()
+        .all(|allele| f(allele).map(u8::to_ascii_uppercase) == Some(base))
+}
+
+#[cfg(test)]
+mod tests {
+    use fasta::record::{Definition, Sequence};
+
+    use super::*;
+    use crate::variant::record_buf::AlternateBases;
+
+    fn build_repository() -> fasta::Repository {
+        let sequence = Sequence::from(b"TGCACACAT".to_vec());
+        fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            sequence,
+        )])
+    }
+
+    fn build_record(
+        start: usize,
+        reference_bases: &str,
+        alternate_bases: &[&str],
+    ) -> Result<RecordBuf, noodles_core::position::TryFromIntError> {
+        Ok(RecordBuf::builder()
+            .set_reference_sequence_name("sq0")
+            .set_variant_start(Position::try_from(start)?)
+            .set_reference_bases(reference_bases)
+            .set_alternate_bases(AlternateBases::from(
+                alternate_bases
+                    .iter()
+                    .map(|s| String::from(*s))
+                    .collect::<Vec<_>>(),
+            ))
+            .build())
+    }
+
+    #[test]
+    fn test_left_align() -> Result<(), Box<dyn std::error::Error>> {
+        fn t(
+            repository: &fasta::Repository,
+            (start, reference_bases, alternate_bases): (usize, &str, &[&str]),
+            expected: (usize, &str, &[&str]),
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            let mut record = build_record(start, reference_bases, alternate_bases)?;
+            left_align(repository, &mut record)?;
+            assert_eq!(record, build_record(expected.0, expected.1, expected.2)?);
+            Ok(())
+        }
+
+        let repository = build_repository();
+
+        // deletion in a repeat
+        t(&repository, (5, "CAC", &["C"]), (2, "GCA", &["G"]))?;
+        // insertion in a repeat
+        t(&repository, (8, "A", &["ACA"]), (2, "G", &["GCA"]))?;
+        // padded SNV
+        t(&repository, (3, "CAC", &["CTC"]), (4, "A", &["T"]))?;
+        // multiallelic
+        t(
+            &repository,
+            (5, "CAC", &["C", "CACAC"]),
+            (2, "GCA", &["G", "GCACA"]),
+        )?;
+        // already normalized
+        t(&repository, (2, "GCA", &["G"]), (2, "GCA", &["G"]))?;
+        // symbolic allele
+        t(&repository, (5, "C", &["<DEL>"]), (5, "C", &["<DEL>"]))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_left_align_with_reference_bases_mismatch(
+    ) -> Result<(), noodles_core::position::TryFromIntError> {
+        let repository = build_repository();
+        let mut record = build_record(1, "A", &["C"])?;
+
+        assert!(matches!(
+            left_align(&repository, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
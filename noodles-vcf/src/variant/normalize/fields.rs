@@ -0,0 +1,220 @@
+//! Info and samples field remapping.
+
+use std::io;
+
+use super::values::{build_slots, AlleleMap, Cardinality, Values};
+use crate::{
+    header::record::value::map::{format, info},
+    variant::{
+        record::samples::keys::key,
+        record_buf::{
+            samples::{
+                sample::{value::Genotype, Value as SampleValue},
+                Keys,
+            },
+            Info, Samples,
+        },
+    },
+    Header,
+};
+
+fn info_cardinality(header: &Header, key: &str) -> Cardinality {
+    header
+        .infos()
+        .get(key)
+        .map(|info| info.number())
+        .or_else(|| {
+            info::definition::definition(header.file_format(), key).map(|(number, _, _)| number)
+        })
+        .map(Cardinality::from)
+        .unwrap_or(Cardinality::Other)
+}
+
+fn format_cardinality(header: &Header, key: &str) -> Cardinality {
+    header
+        .formats()
+        .get(key)
+        .map(|format| format.number())
+        .or_else(|| {
+            format::definition::definition(header.file_format(), key).map(|(number, _, _)| number)
+        })
+        .map(Cardinality::from)
+        .unwrap_or(Cardinality::Other)
+}
+
+/// Builds the info fields of an output record from the info fields of its sources.
+///
+/// Allele-indexed fields (`Number=A`, `R`, or `G`) are remapped using the allele maps. Other
+/// fields are taken from the first source that has them.
+pub(super) fn remap_info(
+    header: &Header,
+    infos: &[&Info],
+    allele_count: usize,
+    allele_maps: &[AlleleMap],
+) -> Info {
+    let mut dst = Info::default();
+
+    for (i, src) in infos.iter().enumerate() {
+        for (key, value) in src.as_ref() {
+            if dst.as_ref().contains_key(key) {
+                continue;
+            }
+
+            let cardinality = info_cardinality(header, key);
+
+            let sources: Vec<_> = infos
+                .iter()
+                .map(|info| info.get(key).flatten().and_then(Values::from_info_value))
+                .collect();
+
+            let ploidy_source = sources
+                .iter()
+                .enumerate()
+                .find_map(|(j, values)| values.as_ref().map(|values| (j, values.len())));
+
+            let value = match build_slots(cardinality, allele_count, allele_maps, ploidy_source) {
+                Some(slots) if sources[i].is_some() => {
+                    Values::gather(&sources, &slots).map(Values::into_info_value)
+                }
+                _ => value.clone(),
+            };
+
+            dst.insert(key.clone(), value);
+        }
+    }
+
+    dst
+}
+
+/// Builds the samples of an output record from the samples of its sources.
+///
+/// Allele-indexed fields (`Number=A`, `R`, or `G`) are remapped using the allele maps. Genotypes
+/// (`GT`) are remapped allele by allele; alleles that are not in the output are set to the
+/// reference allele. When there is more than one source, each allele of a genotype is taken from
+/// the first source where it is not the reference allele. Other fields are taken from the first
+/// source that has them.
+pub(super) fn remap_samples(
+    header: &Header,
+    samples: &[&Samples],
+    allele_count: usize,
+    allele_maps: &[AlleleMap],
+) -> io::Result<Samples> {
+    let sample_count = samples.first().map(|s| s.values.len()).unwrap_or_default();
+
+    if samples.iter().any(|s| s.values.len() != sample_count) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "sample counts differ",
+        ));
+    }
+
+    let keys: Keys = samples
+        .iter()
+        .flat_map(|s| s.keys().as_ref().iter().cloned())
+        .collect();
+
+    let mut values = Vec::with_capacity(sample_count);
+
+    for sample_index in 0..sample_count {
+        let mut sample_values = Vec::with_capacity(keys.as_ref().len());
+
+        for key in keys.as_ref() {
+            let raw_values: Vec<_> = samples
+                .iter()
+                .map(|s| {
+                    s.keys()
+                        .as_ref()
+                        .get_index_of(key)
+                        .and_then(|i| s.values[sample_index].get(i))
+                        .and_then(|value| value.as_ref())
+                })
+                .collect();
+
+            let value = if key == key::GENOTYPE {
+                remap_genotypes(&raw_values, allele_maps).map(SampleValue::Genotype)
+            } else {
+                remap_sample_value(
+                    format_cardinality(header, key),
+                    &raw_values,
+                    allele_count,
+                    allele_maps,
+                )
+            };
+
+            sample_values.push(value);
+        }
+
+        values.push(sample_values);
+    }
+
+    Ok(Samples::new(keys, values))
+}
+
+fn remap_sample_value(
+    cardinality: Cardinality,
+    raw_values: &[Option<&SampleValue>],
+    allele_count: usize,
+    allele_maps: &[AlleleMap],
+) -> Option<SampleValue> {
+    let first = raw_values.iter().flatten().next().copied()?;
+
+    let sources: Vec<_> = raw_values
+        .iter()
+        .map(|value| value.and_then(Values::from_sample_value))
+        .collect();
+
+    let ploidy_source = sources
+        .iter()
+        .enumerate()
+        .find_map(|(i, values)| values.as_ref().map(|values| (i, values.len())));
+
+    match build_slots(cardinality, allele_count, allele_maps, ploidy_source) {
+        Some(slots) if ploidy_source.is_some() => {
+            Values::gather(&sources, &slots).map(Values::into_sample_value)
+        }
+        _ => Some(first.clone()),
+    }
+}
+
+fn remap_genotypes(
+    raw_values: &[Option<&SampleValue>],
+    allele_maps: &[AlleleMap],
+) -> Option<Genotype> {
+    let mut dst: Option<Genotype> = None;
+
+    for (value, allele_map) in raw_values.iter().zip(allele_maps) {
+        let Some(SampleValue::Genotype(genotype)) = value else {
+            continue;
+        };
+
+        let mut genotype = genotype.clone();
+
+        for allele in genotype.as_mut() {
+            if let Some(position) = allele.position() {
+                *allele.position_mut() = allele_map
+                    .get(position)
+                    .map(|dst_position| dst_position.unwrap_or(0));
+            }
+        }
+
+        match dst.as_mut() {
+            None => dst = Some(genotype),
+            Some(dst) => {
+                for (i, allele) in genotype.as_ref().iter().enumerate() {
+                    match dst.as_mut().get_mut(i) {
+                        Some(dst_allele) => {
+                            if matches!(dst_allele.position(), None | Some(0))
+                                && matches!(allele.position(), Some(n) if n > 0)
+                            {
+                                *dst_allele.position_mut() = allele.position();
+                            }
+                        }
+                        None => dst.as_mut().push(allele.clone()),
+                    }
+                }
+            }
+        }
+    }
+
+    dst
+}
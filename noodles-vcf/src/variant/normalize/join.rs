@@ -0,0 +1,214 @@
+use std::io;
+
+use super::fields::{remap_info, remap_samples};
+use crate::{variant::RecordBuf, Header};
+
+/// Joins records at the same position into a single multiallelic record.
+///
+/// This is the inverse of [`super::split`]. All records must have the same reference sequence
+/// name and start position, and each reference allele must be a prefix of the longest reference
+/// allele. Shorter reference alleles are extended with the remaining reference bases, and their
+/// alternate alleles are extended by the same bases. Duplicate alternate alleles are only listed
+/// once.
+///
+/// Allele-indexed info and samples fields are joined, and genotype likelihoods with alleles from
+/// different input records are set to missing. Other fields are taken from the first record that
+/// has them. The IDs of all records are combined, and the quality score and filters are taken
+/// from the first record.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     variant::{normalize, record_buf::AlternateBases, RecordBuf},
+/// };
+///
+/// let header = vcf::Header::default();
+///
+/// let records = [
+///     RecordBuf::builder()
+///         .set_reference_bases("A")
+///         .set_alternate_bases(AlternateBases::from(vec![String::from("C")]))
+///         .build(),
+///     RecordBuf::builder()
+///         .set_reference_bases("AT")
+///         .set_alternate_bases(AlternateBases::from(vec![String::from("A")]))
+///         .build(),
+/// ];
+///
+/// let record = normalize::join(&header, &records)?;
+///
+/// assert_eq!(record.reference_bases(), "AT");
+/// assert_eq!(
+///     record.alternate_bases().as_ref(),
+///     [String::from("CT"), String::from("A")],
+/// );
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn join(header: &Header, records: &[RecordBuf]) -> io::Result<RecordBuf> {
+    let (first, rest) = records
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records"))?;
+
+    for record in rest {
+        if record.reference_sequence_name() != first.reference_sequence_name()
+            || record.variant_start() != first.variant_start()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "records are not at the same position",
+            ));
+        }
+    }
+
+    let reference_bases = records
+        .iter()
+        .map(|record| record.reference_bases())
+        .max_by_key(|bases| bases.len())
+        .unwrap_or_default()
+        .to_string();
+
+    let mut alternate_bases: Vec<String> = Vec::new();
+    let mut allele_maps = Vec::with_capacity(records.len());
+
+    for record in records {
+        let src_reference_bases = record.reference_bases();
+
+        let suffix = reference_bases
+            .get(..src_reference_bases.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(src_reference_bases))
+            .map(|_| &reference_bases[src_reference_bases.len()..])
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "incompatible reference bases")
+            })?;
+
+        let mut allele_map = vec![Some(0)];
+
+        for alternate_base in record.alternate_bases().as_ref() {
+            let alternate_base = extend_allele(alternate_base, suffix);
+
+            let i = match alternate_bases.iter().position(|b| *b == alternate_base) {
+                Some(i) => i,
+                None => {
+                    alternate_bases.push(alternate_base);
+                    alternate_bases.len() - 1
+                }
+            };
+
+            allele_map.push(Some(i + 1));
+        }
+
+        allele_maps.push(allele_map);
+    }
+
+    let allele_count = alternate_bases.len() + 1;
+
+    let infos: Vec<_> = records.iter().map(|record| record.info()).collect();
+    let samples: Vec<_> = records.iter().map(|record| record.samples()).collect();
+
+    let mut dst = first.clone();
+
+    *dst.reference_bases_mut() = reference_bases;
+    *dst.alternate_bases_mut() = alternate_bases.into();
+
+    for record in rest {
+        dst.ids_mut()
+            .as_mut()
+            .extend(record.ids().as_ref().iter().cloned());
+    }
+
+    *dst.info_mut() = remap_info(header, &infos, allele_count, &allele_maps);
+    *dst.samples_mut() = remap_samples(header, &samples, allele_count, &allele_maps)?;
+
+    Ok(dst)
+}
+
+fn extend_allele(allele: &str, suffix: &str) -> String {
+    // Symbolic alleles, breakends, and the missing and overlapping deletion alleles are not
+    // sequences.
+    if suffix.is_empty() || !allele.bytes().all(|b| b.is_ascii_alphabetic()) {
+        allele.into()
+    } else {
+        format!("{allele}{suffix}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::{
+            map::{info, Info},
+            Map,
+        },
+        variant::{
+            normalize::split,
+            record_buf::{
+                info::field::{value::Array as InfoArray, Value as InfoValue},
+                samples::{sample::Value as SampleValue, Keys},
+                AlternateBases, Samples,
+            },
+        },
+    };
+
+    #[test]
+    fn test_join() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_info(
+                "AF",
+                Map::<Info>::new(info::Number::A, info::Type::Float, ""),
+            )
+            .add_sample_name("sample0")
+            .build();
+
+        let record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(AlternateBases::from(vec![
+                String::from("C"),
+                String::from("G"),
+            ]))
+            .set_info(
+                [(
+                    String::from("AF"),
+                    Some(InfoValue::Array(InfoArray::Float(vec![
+                        Some(0.25),
+                        Some(0.5),
+                    ]))),
+                )]
+                .into_iter()
+                .collect(),
+            )
+            .set_samples(Samples::new(
+                vec![String::from("GT")].into_iter().collect::<Keys>(),
+                vec![vec![Some(SampleValue::Genotype("1|2".parse()?))]],
+            ))
+            .build();
+
+        let records = split(&header, &record)?;
+        let actual = join(&header, &records)?;
+        assert_eq!(actual, record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_incompatible_records() {
+        let header = Header::default();
+
+        let records = [
+            RecordBuf::builder().set_reference_bases("A").build(),
+            RecordBuf::builder().set_reference_bases("CT").build(),
+        ];
+
+        assert!(matches!(
+            join(&header, &records),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            join(&header, &[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
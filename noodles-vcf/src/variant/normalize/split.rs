@@ -0,0 +1,181 @@
+use std::io;
+
+use super::fields::{remap_info, remap_samples};
+use crate::{variant::RecordBuf, Header};
+
+/// Splits a multiallelic record into biallelic records.
+///
+/// One record is returned for each alternate allele. Info and samples fields with a cardinality
+/// of `Number=A`, `Number=R`, or `Number=G` are subset to the reference allele and the alternate
+/// allele of each output record, using the definitions in the header or, if missing, the
+/// reserved definitions of the file format. In genotypes (`GT`), the other alternate alleles are
+/// set to the reference allele.
+///
+/// Records with at most one alternate allele are returned unchanged.
+///
+/// Split records may have common leading or trailing bases, e.g., `REF=CA, ALT=C,CAA` is split
+/// into `REF=CA, ALT=C` and `REF=CA, ALT=CAA`. Use [`super::left_align`] to normalize them.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     variant::{normalize, record_buf::AlternateBases, RecordBuf},
+/// };
+///
+/// let header = vcf::Header::default();
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_bases("A")
+///     .set_alternate_bases(AlternateBases::from(vec![String::from("C"), String::from("G")]))
+///     .build();
+///
+/// let records = normalize::split(&header, &record)?;
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(records[1].alternate_bases().as_ref(), [String::from("G")]);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn split(header: &Header, record: &RecordBuf) -> io::Result<Vec<RecordBuf>> {
+    let alternate_bases = record.alternate_bases().as_ref();
+
+    if alternate_bases.len() <= 1 {
+        return Ok(vec![record.clone()]);
+    }
+
+    let allele_count = alternate_bases.len() + 1;
+    let mut records = Vec::with_capacity(alternate_bases.len());
+
+    for (i, alternate_base) in alternate_bases.iter().enumerate() {
+        let mut allele_map = vec![None; allele_count];
+        allele_map[0] = Some(0);
+        allele_map[i + 1] = Some(1);
+
+        let allele_maps = [allele_map];
+
+        let mut dst = record.clone();
+        *dst.alternate_bases_mut() = vec![alternate_base.clone()].into();
+        *dst.info_mut() = remap_info(header, &[record.info()], 2, &allele_maps);
+        *dst.samples_mut() = remap_samples(header, &[record.samples()], 2, &allele_maps)?;
+
+        records.push(dst);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::{
+            map::{format, info, Format, Info},
+            Map,
+        },
+        variant::record_buf::{
+            info::field::{value::Array as InfoArray, Value as InfoValue},
+            samples::{
+                sample::{value::Array as SampleArray, Value as SampleValue},
+                Keys,
+            },
+            AlternateBases, Samples,
+        },
+    };
+
+    #[test]
+    fn test_split() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_info(
+                "AC",
+                Map::<Info>::new(info::Number::A, info::Type::Integer, ""),
+            )
+            .add_info(
+                "DP",
+                Map::<Info>::new(info::Number::Count(1), info::Type::Integer, ""),
+            )
+            .add_format(
+                "AD",
+                Map::<Format>::new(
+                    format::Number::ReferenceAlternateBases,
+                    format::Type::Integer,
+                    "",
+                ),
+            )
+            .add_format(
+                "PL",
+                Map::<Format>::new(format::Number::Samples, format::Type::Integer, ""),
+            )
+            .add_sample_name("sample0")
+            .build();
+
+        let record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(AlternateBases::from(vec![
+                String::from("C"),
+                String::from("G"),
+            ]))
+            .set_info(
+                [
+                    (
+                        String::from("AC"),
+                        Some(InfoValue::Array(InfoArray::Integer(vec![Some(3), Some(5)]))),
+                    ),
+                    (String::from("DP"), Some(InfoValue::Integer(13))),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .set_samples(Samples::new(
+                vec![String::from("GT"), String::from("AD"), String::from("PL")]
+                    .into_iter()
+                    .collect::<Keys>(),
+                vec![vec![
+                    Some(SampleValue::Genotype("1/2".parse()?)),
+                    Some(SampleValue::Array(SampleArray::Integer(vec![
+                        Some(1),
+                        Some(2),
+                        Some(3),
+                    ]))),
+                    Some(SampleValue::Array(SampleArray::Integer(
+                        (0..6).map(Some).collect(),
+                    ))),
+                ]],
+            ))
+            .build();
+
+        let records = split(&header, &record)?;
+        assert_eq!(records.len(), 2);
+
+        let record = &records[1];
+        assert_eq!(record.alternate_bases().as_ref(), [String::from("G")]);
+
+        assert_eq!(
+            record.info().get("AC"),
+            Some(Some(&InfoValue::Array(InfoArray::Integer(vec![Some(5)]))))
+        );
+        assert_eq!(record.info().get("DP"), Some(Some(&InfoValue::Integer(13))));
+
+        let sample = record.samples().get_index(0).unwrap();
+        assert_eq!(
+            sample.get("GT").flatten(),
+            Some(&SampleValue::Genotype("0/1".parse()?))
+        );
+        assert_eq!(
+            sample.get("AD").flatten(),
+            Some(&SampleValue::Array(SampleArray::Integer(vec![
+                Some(1),
+                Some(3)
+            ])))
+        );
+        assert_eq!(
+            sample.get("PL").flatten(),
+            Some(&SampleValue::Array(SampleArray::Integer(vec![
+                Some(0),
+                Some(3),
+                Some(5)
+            ])))
+        );
+
+        Ok(())
+    }
+}
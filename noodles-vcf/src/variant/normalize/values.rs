@@ -0,0 +1,300 @@
+//! Allele-indexed field value remapping.
+
+use crate::{
+    header::record::value::map::{format, info},
+    variant::record_buf::{info::field::Value as InfoValue, samples::sample::Value as SampleValue},
+};
+
+/// The allele cardinality of a field.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Cardinality {
+    /// One value per alternate allele (`Number=A`).
+    AlternateBases,
+    /// One value per allele (`Number=R`).
+    ReferenceAlternateBases,
+    /// One value per genotype (`Number=G`).
+    Genotypes,
+    /// Any other number.
+    Other,
+}
+
+impl From<info::Number> for Cardinality {
+    fn from(number: info::Number) -> Self {
+        match number {
+            info::Number::AlternateBases => Self::AlternateBases,
+            info::Number::ReferenceAlternateBases => Self::ReferenceAlternateBases,
+            info::Number::Samples => Self::Genotypes,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl From<format::Number> for Cardinality {
+    fn from(number: format::Number) -> Self {
+        match number {
+            format::Number::AlternateBases => Self::AlternateBases,
+            format::Number::ReferenceAlternateBases => Self::ReferenceAlternateBases,
+            format::Number::Samples => Self::Genotypes,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// A source value index, i.e., a (source index, value index) pair.
+pub(super) type Slot = Option<(usize, usize)>;
+
+/// Maps allele indices of each source to allele indices in the output.
+///
+/// Alleles of a source that are not in the output map to `None`.
+pub(super) type AlleleMap = Vec<Option<usize>>;
+
+/// Builds the list of source values for each output value of a field.
+///
+/// `allele_count` is the number of alleles in the output, including the reference allele.
+/// `ploidy_source` is the source index and value count used to determine whether a `Number=G`
+/// field is haploid or diploid.
+pub(super) fn build_slots(
+    cardinality: Cardinality,
+    allele_count: usize,
+    allele_maps: &[AlleleMap],
+    ploidy_source: Option<(usize, usize)>,
+) -> Option<Vec<Slot>> {
+    let find = |allele: usize, skip_reference: bool| {
+        allele_maps.iter().enumerate().find_map(|(i, map)| {
+            map.iter()
+                .enumerate()
+                .skip(usize::from(skip_reference))
+                .find(|(_, &dst)| dst == Some(allele))
+                .map(|(j, _)| (i, j))
+        })
+    };
+
+    match cardinality {
+        Cardinality::AlternateBases => Some(
+            (1..allele_count)
+                .map(|allele| find(allele, true).map(|(i, j)| (i, j - 1)))
+                .collect(),
+        ),
+        Cardinality::ReferenceAlternateBases => Some(
+            (0..allele_count)
+                .map(|allele| find(allele, false))
+                .collect(),
+        ),
+        Cardinality::Genotypes => {
+            let (i, len) = ploidy_source?;
+
+            if len == allele_maps[i].len() {
+                Some(
+                    (0..allele_count)
+                        .map(|allele| find(allele, false))
+                        .collect(),
+                )
+            } else {
+                Some(build_diploid_genotype_slots(allele_count, allele_maps))
+            }
+        }
+        Cardinality::Other => None,
+    }
+}
+
+fn build_diploid_genotype_slots(allele_count: usize, allele_maps: &[AlleleMap]) -> Vec<Slot> {
+    let mut slots = Vec::new();
+
+    for k in 0..allele_count {
+        for j in 0..=k {
+            let slot = allele_maps.iter().enumerate().find_map(|(i, map)| {
+                for b in 0..map.len() {
+                    for a in 0..=b {
+                        let (Some(c), Some(d)) = (map[a], map[b]) else {
+                            continue;
+                        };
+
+                        if (c.min(d), c.max(d)) == (j, k) {
+                            return Some((i, genotype_index(a, b)));
+                        }
+                    }
+                }
+
+                None
+            });
+
+            slots.push(slot);
+        }
+    }
+
+    slots
+}
+
+// § 1.6.2 "Genotype fields" (2023-08-23): "the ordering of genotypes for the likelihoods is
+// given by: F(j/k) = (k*(k+1)/2)+j."
+fn genotype_index(j: usize, k: usize) -> usize {
+    k * (k + 1) / 2 + j
+}
+
+/// A list of optional values of the same type.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Values {
+    Integer(Vec<Option<i32>>),
+    Float(Vec<Option<f32>>),
+    Character(Vec<Option<char>>),
+    String(Vec<Option<String>>),
+}
+
+impl Values {
+    pub(super) fn len(&self) -> usize {
+        match self {
+            Self::Integer(values) => values.len(),
+            Self::Float(values) => values.len(),
+            Self::Character(values) => values.len(),
+            Self::String(values) => values.len(),
+        }
+    }
+
+    pub(super) fn from_info_value(value: &InfoValue) -> Option<Self> {
+        use crate::variant::record_buf::info::field::value::Array;
+
+        match value {
+            InfoValue::Integer(n) => Some(Self::Integer(vec![Some(*n)])),
+            InfoValue::Float(n) => Some(Self::Float(vec![Some(*n)])),
+            InfoValue::Flag => None,
+            InfoValue::Character(c) => Some(Self::Character(vec![Some(*c)])),
+            InfoValue::String(s) => Some(Self::String(vec![Some(s.clone())])),
+            InfoValue::Array(Array::Integer(values)) => Some(Self::Integer(values.clone())),
+            InfoValue::Array(Array::Float(values)) => Some(Self::Float(values.clone())),
+            InfoValue::Array(Array::Character(values)) => Some(Self::Character(values.clone())),
+            InfoValue::Array(Array::String(values)) => Some(Self::String(values.clone())),
+        }
+    }
+
+    pub(super) fn into_info_value(self) -> InfoValue {
+        use crate::variant::record_buf::info::field::value::Array;
+
+        match self {
+            Self::Integer(values) => InfoValue::Array(Array::Integer(values)),
+            Self::Float(values) => InfoValue::Array(Array::Float(values)),
+            Self::Character(values) => InfoValue::Array(Array::Character(values)),
+            Self::String(values) => InfoValue::Array(Array::String(values)),
+        }
+    }
+
+    pub(super) fn from_sample_value(value: &SampleValue) -> Option<Self> {
+        use crate::variant::record_buf::samples::sample::value::Array;
+
+        match value {
+            SampleValue::Integer(n) => Some(Self::Integer(vec![Some(*n)])),
+            SampleValue::Float(n) => Some(Self::Float(vec![Some(*n)])),
+            SampleValue::Character(c) => Some(Self::Character(vec![Some(*c)])),
+            SampleValue::String(s) => Some(Self::String(vec![Some(s.clone())])),
+            SampleValue::Genotype(_) => None,
+            SampleValue::Array(Array::Integer(values)) => Some(Self::Integer(values.clone())),
+            SampleValue::Array(Array::Float(values)) => Some(Self::Float(values.clone())),
+            SampleValue::Array(Array::Character(values)) => Some(Self::Character(values.clone())),
+            SampleValue::Array(Array::String(values)) => Some(Self::String(values.clone())),
+        }
+    }
+
+    pub(super) fn into_sample_value(self) -> SampleValue {
+        use crate::variant::record_buf::samples::sample::value::Array;
+
+        match self {
+            Self::Integer(values) => SampleValue::Array(Array::Integer(values)),
+            Self::Float(values) => SampleValue::Array(Array::Float(values)),
+            Self::Character(values) => SampleValue::Array(Array::Character(values)),
+            Self::String(values) => SampleValue::Array(Array::String(values)),
+        }
+    }
+
+    /// Builds a list of values by taking each slot from the sources.
+    ///
+    /// The type of the output is the type of the first source. Slots that are missing or refer to
+    /// sources of other types are set to missing.
+    pub(super) fn gather(sources: &[Option<Values>], slots: &[Slot]) -> Option<Self> {
+        fn gather_as<T, F>(sources: &[Option<Values>], slots: &[Slot], f: F) -> Vec<Option<T>>
+        where
+            T: Clone,
+            F: Fn(&Values) -> Option<&Vec<Option<T>>>,
+        {
+            slots
+                .iter()
+                .map(|slot| {
+                    let (i, j) = (*slot)?;
+                    let values = sources.get(i)?.as_ref().and_then(&f)?;
+                    values.get(j).cloned().flatten()
+                })
+                .collect()
+        }
+
+        let values = match sources.iter().flatten().next()? {
+            Self::Integer(_) => Self::Integer(gather_as(sources, slots, |values| match values {
+                Self::Integer(values) => Some(values),
+                _ => None,
+            })),
+            Self::Float(_) => Self::Float(gather_as(sources, slots, |values| match values {
+                Self::Float(values) => Some(values),
+                _ => None,
+            })),
+            Self::Character(_) => {
+                Self::Character(gather_as(sources, slots, |values| match values {
+                    Self::Character(values) => Some(values),
+                    _ => None,
+                }))
+            }
+            Self::String(_) => Self::String(gather_as(sources, slots, |values| match values {
+                Self::String(values) => Some(values),
+                _ => None,
+            })),
+        };
+
+        Some(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_slots() {
+        // REF=A, ALT=C,G -> REF=A, ALT=G
+        let allele_maps = [vec![Some(0), None, Some(1)]];
+
+        assert_eq!(
+            build_slots(Cardinality::AlternateBases, 2, &allele_maps, None),
+            Some(vec![Some((0, 1))])
+        );
+
+        assert_eq!(
+            build_slots(Cardinality::ReferenceAlternateBases, 2, &allele_maps, None),
+            Some(vec![Some((0, 0)), Some((0, 2))])
+        );
+
+        assert_eq!(
+            build_slots(Cardinality::Genotypes, 2, &allele_maps, Some((0, 6))),
+            Some(vec![Some((0, 0)), Some((0, 3)), Some((0, 5))])
+        );
+
+        assert_eq!(
+            build_slots(Cardinality::Genotypes, 2, &allele_maps, Some((0, 3))),
+            Some(vec![Some((0, 0)), Some((0, 2))])
+        );
+
+        assert_eq!(build_slots(Cardinality::Other, 2, &allele_maps, None), None);
+    }
+
+    #[test]
+    fn test_gather() {
+        let sources = [
+            Some(Values::Integer(vec![Some(8), Some(13)])),
+            Some(Values::Float(vec![Some(0.5)])),
+        ];
+
+        let slots = [Some((0, 1)), None, Some((1, 0)), Some((0, 2))];
+
+        assert_eq!(
+            Values::gather(&sources, &slots),
+            Some(Values::Integer(vec![Some(13), None, None, None]))
+        );
+
+        assert_eq!(Values::gather(&[None], &slots), None);
+    }
+}
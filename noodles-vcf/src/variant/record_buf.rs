@@ -10,7 +10,8 @@ pub mod samples;
 
 use std::io;
 
-use noodles_core::Position;
+use bstr::{BStr, ByteSlice};
+use noodles_core::{ops, region::Interval, Position};
 
 pub use self::{
     alternate_bases::AlternateBases, builder::Builder, filters::Filters, ids::Ids, info::Info,
@@ -519,6 +520,26 @@ impl super::Record for RecordBuf {
     }
 }
 
+impl ops::Feature for RecordBuf {
+    fn reference_sequence_name(&self) -> &BStr {
+        self.reference_sequence_name().as_bytes().as_bstr()
+    }
+
+    fn interval(&self) -> Interval {
+        use self::info::field::Value;
+        use super::record::info::field::key;
+
+        let start = self.variant_start().unwrap_or(Position::MIN);
+
+        let end = match self.info().get(key::END_POSITION) {
+            Some(Some(Value::Integer(n))) => usize::try_from(*n).ok().and_then(Position::new),
+            _ => start.checked_add(self.reference_bases().len().saturating_sub(1)),
+        };
+
+        Interval::from(start..=end.unwrap_or(start).max(start))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -537,4 +558,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_interval() -> Result<(), Box<dyn std::error::Error>> {
+        use ops::Feature;
+
+        use self::info::field::Value;
+        use super::super::record::info::field::key;
+
+        let record = RecordBuf::builder()
+            .set_reference_sequence_name("sq0")
+            .set_variant_start(Position::try_from(8)?)
+            .set_reference_bases("ACGT")
+            .build();
+
+        assert_eq!(Feature::reference_sequence_name(&record), "sq0");
+        assert_eq!(
+            Feature::interval(&record),
+            Interval::from(Position::try_from(8)?..=Position::try_from(11)?)
+        );
+
+        let record = RecordBuf::builder()
+            .set_variant_start(Position::try_from(8)?)
+            .set_reference_bases("N")
+            .set_info(
+                [(String::from(key::END_POSITION), Some(Value::from(13)))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        assert_eq!(
+            Feature::interval(&record),
+            Interval::from(Position::try_from(8)?..=Position::try_from(13)?)
+        );
+
+        Ok(())
+    }
 }